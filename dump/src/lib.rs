@@ -1,6 +1,8 @@
 #![allow(clippy::type_complexity)]
 #![allow(clippy::wrong_self_convention)]
 
+use std::collections::BTreeMap;
+
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::keys::Key;
 use meilisearch_types::milli::update::IndexDocumentsMethod;
@@ -97,12 +99,23 @@ pub enum KindDump {
         documents_count: u64,
         allow_index_creation: bool,
     },
+    DocumentEdition {
+        documents_count: u64,
+    },
     DocumentDeletion {
         documents_ids: Vec<String>,
     },
     DocumentClear,
     DocumentDeletionByFilter {
         filter: serde_json::Value,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        filter_params: Option<BTreeMap<String, serde_json::Value>>,
+    },
+    DocumentEditionByFilter {
+        filter: serde_json::Value,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        filter_params: Option<BTreeMap<String, serde_json::Value>>,
+        edits: serde_json::Map<String, serde_json::Value>,
     },
     Settings {
         settings: Box<meilisearch_types::settings::Settings<Unchecked>>,
@@ -116,6 +129,7 @@ pub enum KindDump {
     IndexUpdate {
         primary_key: Option<String>,
     },
+    IndexCompaction,
     IndexSwap {
         swaps: Vec<IndexSwap>,
     },
@@ -166,12 +180,18 @@ impl From<KindWithContent> for KindDump {
                 documents_count,
                 allow_index_creation,
             },
+            KindWithContent::DocumentEdition { documents_count, .. } => {
+                KindDump::DocumentEdition { documents_count }
+            }
             KindWithContent::DocumentDeletion { documents_ids, .. } => {
                 KindDump::DocumentDeletion { documents_ids }
             }
-            KindWithContent::DocumentDeletionByFilter { filter_expr, .. } => {
-                KindDump::DocumentDeletionByFilter { filter: filter_expr }
+            KindWithContent::DocumentDeletionByFilter { filter_expr, filter_params, .. } => {
+                KindDump::DocumentDeletionByFilter { filter: filter_expr, filter_params }
             }
+            KindWithContent::DocumentEditionByFilter {
+                filter_expr, filter_params, edits, ..
+            } => KindDump::DocumentEditionByFilter { filter: filter_expr, filter_params, edits },
             KindWithContent::DocumentClear { .. } => KindDump::DocumentClear,
             KindWithContent::SettingsUpdate {
                 new_settings,
@@ -186,6 +206,7 @@ impl From<KindWithContent> for KindDump {
             KindWithContent::IndexUpdate { primary_key, .. } => {
                 KindDump::IndexUpdate { primary_key }
             }
+            KindWithContent::IndexCompaction { .. } => KindDump::IndexCompaction,
             KindWithContent::IndexSwap { swaps } => KindDump::IndexSwap { swaps },
             KindWithContent::TaskCancelation { query, tasks } => {
                 KindDump::TaskCancelation { query, tasks }
@@ -260,8 +281,19 @@ pub(crate) mod test {
             sortable_attributes: Setting::Set(btreeset! { S("age") }),
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
+            non_separator_tokens: Setting::NotSet,
+            separator_tokens: Setting::NotSet,
+            dictionary: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            stemming: Setting::NotSet,
+            infix_attributes: Setting::NotSet,
+            phonetic_attributes: Setting::NotSet,
             synonyms: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
+            version_attribute: Setting::NotSet,
+            expiry_attribute: Setting::NotSet,
+            ingestion_rules: Setting::NotSet,
+            document_schema: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
@@ -287,6 +319,8 @@ pub(crate) mod test {
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 12,
                         indexed_documents: Some(10),
+                        skipped_documents: None,
+                        invalid_documents: Vec::new(),
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
@@ -310,6 +344,8 @@ pub(crate) mod test {
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 2,
                         indexed_documents: None,
+                        skipped_documents: None,
+                        invalid_documents: Vec::new(),
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
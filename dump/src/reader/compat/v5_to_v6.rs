@@ -132,6 +132,8 @@ impl CompatV5ToV6 {
                             v6::Details::DocumentAdditionOrUpdate {
                                 received_documents: received_documents as u64,
                                 indexed_documents,
+                                skipped_documents: None,
+                                invalid_documents: Vec::new(),
                             }
                         }
                         v5::Details::Settings { settings } => {
@@ -336,8 +338,19 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                 }
             },
             stop_words: settings.stop_words.into(),
+            non_separator_tokens: v6::Setting::NotSet,
+            separator_tokens: v6::Setting::NotSet,
+            dictionary: v6::Setting::NotSet,
+            localized_attributes: v6::Setting::NotSet,
+            stemming: v6::Setting::NotSet,
+            infix_attributes: v6::Setting::NotSet,
+            phonetic_attributes: v6::Setting::NotSet,
             synonyms: settings.synonyms.into(),
             distinct_attribute: settings.distinct_attribute.into(),
+            version_attribute: v6::Setting::NotSet,
+            expiry_attribute: v6::Setting::NotSet,
+            ingestion_rules: v6::Setting::NotSet,
+            document_schema: v6::Setting::NotSet,
             typo_tolerance: match settings.typo_tolerance {
                 v5::Setting::Set(typo) => v6::Setting::Set(v6::TypoTolerance {
                     enabled: typo.enabled.into(),
@@ -351,6 +364,7 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                     },
                     disable_on_words: typo.disable_on_words.into(),
                     disable_on_attributes: typo.disable_on_attributes.into(),
+                    disable_on_numbers: v6::Setting::NotSet,
                 }),
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
@@ -358,6 +372,8 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             faceting: match settings.faceting {
                 v5::Setting::Set(faceting) => v6::Setting::Set(v6::FacetingSettings {
                     max_values_per_facet: faceting.max_values_per_facet.into(),
+                    sort_facet_values_by: v6::Setting::NotSet,
+                    hierarchical_facets: v6::Setting::NotSet,
                 }),
                 v5::Setting::Reset => v6::Setting::Reset,
                 v5::Setting::NotSet => v6::Setting::NotSet,
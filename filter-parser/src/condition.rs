@@ -3,6 +3,9 @@
 //! ```text
 //! condition      = value ("==" | ">" ...) value
 //! to             = value value TO value
+//! contains       = value "CONTAINS" WS+ value
+//! starts_with    = value "STARTS" WS+ "WITH" WS+ value
+//! is_type        = value "IS" WS+ ("NOT" WS+)? ("NUMBER" | "STRING")
 //! ```
 
 use nom::branch::alt;
@@ -12,6 +15,7 @@ use nom::combinator::cut;
 use nom::sequence::{terminated, tuple};
 use Condition::*;
 
+use crate::value::word_exact;
 use crate::{parse_value, FilterCondition, IResult, Span, Token};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -26,6 +30,28 @@ pub enum Condition<'a> {
     LowerThan(Token<'a>),
     LowerThanOrEqual(Token<'a>),
     Between { from: Token<'a>, to: Token<'a> },
+    Contains(Token<'a>),
+    StartsWith(Token<'a>),
+    IsNumber,
+    IsString,
+}
+
+impl<'a> Condition<'a> {
+    /// Returns the values used by the condition.
+    pub fn tokens_mut(&mut self) -> Vec<&mut Token<'a>> {
+        match self {
+            GreaterThan(token)
+            | GreaterThanOrEqual(token)
+            | Equal(token)
+            | NotEqual(token)
+            | LowerThan(token)
+            | LowerThanOrEqual(token)
+            | Contains(token)
+            | StartsWith(token) => vec![token],
+            Between { from, to } => vec![from, to],
+            Null | Empty | Exists | IsNumber | IsString => vec![],
+        }
+    }
 }
 
 /// condition      = value ("==" | ">" ...) value
@@ -78,6 +104,31 @@ pub fn parse_is_not_empty(input: Span) -> IResult<FilterCondition> {
     Ok((input, FilterCondition::Not(Box::new(FilterCondition::Condition { fid: key, op: Empty }))))
 }
 
+/// is_type        = value "IS" WS+ ("NUMBER" | "STRING")
+pub fn parse_is_type(input: Span) -> IResult<FilterCondition> {
+    let (input, key) = parse_value(input)?;
+
+    let (input, (_, _, value_type)) =
+        tuple((tag("IS"), multispace1, alt((tag("NUMBER"), tag("STRING")))))(input)?;
+    let op = if *value_type.fragment() == "NUMBER" { IsNumber } else { IsString };
+    Ok((input, FilterCondition::Condition { fid: key, op }))
+}
+
+/// is_type        = value "IS" WS+ "NOT" WS+ ("NUMBER" | "STRING")
+pub fn parse_is_not_type(input: Span) -> IResult<FilterCondition> {
+    let (input, key) = parse_value(input)?;
+
+    let (input, (_, _, _, _, value_type)) = tuple((
+        tag("IS"),
+        multispace1,
+        tag("NOT"),
+        multispace1,
+        alt((tag("NUMBER"), tag("STRING"))),
+    ))(input)?;
+    let op = if *value_type.fragment() == "NUMBER" { IsNumber } else { IsString };
+    Ok((input, FilterCondition::Not(Box::new(FilterCondition::Condition { fid: key, op }))))
+}
+
 /// exist          = value "EXISTS"
 pub fn parse_exists(input: Span) -> IResult<FilterCondition> {
     let (input, key) = terminated(parse_value, tag("EXISTS"))(input)?;
@@ -99,3 +150,64 @@ pub fn parse_to(input: Span) -> IResult<FilterCondition> {
 
     Ok((input, FilterCondition::Condition { fid: key, op: Between { from, to } }))
 }
+
+/// contains       = value "CONTAINS" WS+ value
+pub fn parse_contains(input: Span) -> IResult<FilterCondition> {
+    let (input, (key, _, value)) =
+        tuple((parse_value, word_exact("CONTAINS"), cut(parse_value)))(input)?;
+
+    Ok((input, FilterCondition::Condition { fid: key, op: Contains(value) }))
+}
+
+/// not_contains   = value "NOT" WS+ "CONTAINS" WS+ value
+pub fn parse_not_contains(input: Span) -> IResult<FilterCondition> {
+    let (input, (key, _, _, _, value)) = tuple((
+        parse_value,
+        word_exact("NOT"),
+        multispace1,
+        word_exact("CONTAINS"),
+        cut(parse_value),
+    ))(input)?;
+
+    Ok((
+        input,
+        FilterCondition::Not(Box::new(FilterCondition::Condition {
+            fid: key,
+            op: Contains(value),
+        })),
+    ))
+}
+
+/// starts_with    = value "STARTS" WS+ "WITH" WS+ value
+pub fn parse_starts_with(input: Span) -> IResult<FilterCondition> {
+    let (input, (key, _, _, _, value)) = tuple((
+        parse_value,
+        word_exact("STARTS"),
+        multispace1,
+        word_exact("WITH"),
+        cut(parse_value),
+    ))(input)?;
+
+    Ok((input, FilterCondition::Condition { fid: key, op: StartsWith(value) }))
+}
+
+/// not_starts_with = value "NOT" WS+ "STARTS" WS+ "WITH" WS+ value
+pub fn parse_not_starts_with(input: Span) -> IResult<FilterCondition> {
+    let (input, (key, _, _, _, _, _, value)) = tuple((
+        parse_value,
+        word_exact("NOT"),
+        multispace1,
+        word_exact("STARTS"),
+        multispace1,
+        word_exact("WITH"),
+        cut(parse_value),
+    ))(input)?;
+
+    Ok((
+        input,
+        FilterCondition::Not(Box::new(FilterCondition::Condition {
+            fid: key,
+            op: StartsWith(value),
+        })),
+    ))
+}
@@ -46,6 +46,8 @@ pub fn cut_with_err<'a, O>(
 pub struct Error<'a> {
     context: Span<'a>,
     kind: ErrorKind<'a>,
+    /// The JSON path of the invalid value when the filter was written with the JSON syntax.
+    location: Option<String>,
 }
 
 #[derive(Debug)]
@@ -67,6 +69,8 @@ pub enum ErrorKind<'a> {
     MalformedValue,
     InOpeningBracket,
     InClosingBracket,
+    MatchesOpeningParenthesis,
+    UnboundPlaceholder(String),
     NonFiniteFloat,
     InExpectedValue(ExpectedValueKind),
     ReservedKeyword(String),
@@ -87,13 +91,17 @@ impl<'a> Error<'a> {
     }
 
     pub fn new_from_kind(context: Span<'a>, kind: ErrorKind<'a>) -> Self {
-        Self { context, kind }
+        Self { context, kind, location: None }
     }
 
     pub fn new_from_external(context: Span<'a>, error: impl std::error::Error) -> Self {
         Self::new_from_kind(context, ErrorKind::External(error.to_string()))
     }
 
+    pub fn with_location(self, location: Option<String>) -> Self {
+        Self { location, ..self }
+    }
+
     pub fn char(self) -> char {
         match self.kind {
             ErrorKind::Char(c) => c,
@@ -108,7 +116,7 @@ impl<'a> ParseError<Span<'a>> for Error<'a> {
             error::ErrorKind::Eof => ErrorKind::ExpectedEof,
             kind => ErrorKind::InternalError(kind),
         };
-        Self { context: input, kind }
+        Self { context: input, kind, location: None }
     }
 
     fn append(_input: Span<'a>, _kind: error::ErrorKind, other: Self) -> Self {
@@ -116,7 +124,7 @@ impl<'a> ParseError<Span<'a>> for Error<'a> {
     }
 
     fn from_char(input: Span<'a>, c: char) -> Self {
-        Self { context: input, kind: ErrorKind::Char(c) }
+        Self { context: input, kind: ErrorKind::Char(c), location: None }
     }
 }
 
@@ -145,7 +153,7 @@ impl<'a> Display for Error<'a> {
             }
             ErrorKind::InvalidPrimary => {
                 let text = if input.trim().is_empty() { "but instead got nothing.".to_string() } else { format!("at `{}`.", escaped_input) };
-                writeln!(f, "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` {}", text)?
+                writeln!(f, "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS NUMBER`, `IS NOT NUMBER`, `IS STRING`, `IS NOT STRING`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `MATCHES`, `_geoRadius`, or `_geoBoundingBox` {}", text)?
             }
             ErrorKind::ExpectedEof => {
                 writeln!(f, "Found unexpected characters at the end of the filter: `{}`. You probably forgot an `OR` or an `AND` rule.", escaped_input)?
@@ -174,6 +182,12 @@ impl<'a> Display for Error<'a> {
             ErrorKind::InClosingBracket => {
                 writeln!(f, "Expected matching `]` after the list of field names given to `IN[`")?
             }
+            ErrorKind::MatchesOpeningParenthesis => {
+                writeln!(f, "Expected `(` after `MATCHES` keyword.")?
+            }
+            ErrorKind::UnboundPlaceholder(name) => {
+                writeln!(f, "The `${name}` placeholder has no value.")?
+            }
             ErrorKind::NonFiniteFloat => {
                 writeln!(f, "Non finite floats are not supported")?
             }
@@ -196,6 +210,9 @@ impl<'a> Display for Error<'a> {
             )?,
             ErrorKind::External(ref error) => writeln!(f, "{}", error)?,
         }
+        if let Some(location) = &self.location {
+            return write!(f, "at `{location}`");
+        }
         let base_column = self.context.get_utf8_column();
         let size = self.context.fragment().chars().count();
 
@@ -6,17 +6,21 @@
 //! or             = and ("OR" WS+ and)*
 //! and            = not ("AND" WS+ not)*
 //! not            = ("NOT" WS+ not) | primary
-//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | in | condition | exists | not_exists | to
+//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | matches | in | condition | exists | not_exists | to | contains | starts_with
+//! matches        = value "MATCHES" WS* "(" WS* expression WS* ")"
 //! in             = value "IN" WS* "[" value_list "]"
 //! condition      = value ("=" | "!=" | ">" | ">=" | "<" | "<=") value
 //! exists         = value "EXISTS"
 //! not_exists     = value "NOT" WS+ "EXISTS"
 //! to             = value value "TO" WS+ value
-//! value          = WS* ( word | singleQuoted | doubleQuoted) WS+
+//! contains       = value ("NOT" WS+)? "CONTAINS" WS+ value
+//! starts_with    = value ("NOT" WS+)? "STARTS" WS+ "WITH" WS+ value
+//! value          = WS* ( word | singleQuoted | doubleQuoted | placeholder) WS+
 //! value_list     = (value ("," value)* ","?)?
 //! singleQuoted   = "'" .* all but quotes "'"
 //! doubleQuoted   = "\"" .* all but double quotes "\""
 //! word           = (alphanumeric | _ | - | .)+
+//! placeholder    = "$" (alphanumeric | _)+
 //! geoRadius      = "_geoRadius(" WS* float WS* "," WS* float WS* "," float WS* ")"
 //! geoBoundingBox = "_geoBoundingBox([" WS * float WS* "," WS* float WS* "], [" WS* float WS* "," WS* float WS* "]")
 //! ```
@@ -48,8 +52,9 @@ use std::fmt::Debug;
 
 pub use condition::{parse_condition, parse_to, Condition};
 use condition::{
-    parse_exists, parse_is_empty, parse_is_not_empty, parse_is_not_null, parse_is_null,
-    parse_not_exists,
+    parse_contains, parse_exists, parse_is_empty, parse_is_not_empty, parse_is_not_null,
+    parse_is_not_type, parse_is_null, parse_is_type, parse_not_contains, parse_not_exists,
+    parse_not_starts_with, parse_starts_with,
 };
 use error::{cut_with_err, ExpectedValueKind, NomErrorExt};
 pub use error::{Error, ErrorKind};
@@ -78,6 +83,11 @@ pub struct Token<'a> {
     /// If you need to modify the original input you can use the `value` field
     /// to store your modified input.
     value: Option<String>,
+    /// Whether the token is a `$name` placeholder that must be bound to a value.
+    placeholder: bool,
+    /// The path of the token in the filter written with the structured JSON syntax,
+    /// the errors point to it instead of the empty span.
+    location: Option<String>,
 }
 
 impl<'a> PartialEq for Token<'a> {
@@ -88,7 +98,46 @@ impl<'a> PartialEq for Token<'a> {
 
 impl<'a> Token<'a> {
     pub fn new(span: Span<'a>, value: Option<String>) -> Self {
-        Self { span, value }
+        Self { span, value, placeholder: false, location: None }
+    }
+
+    pub(crate) fn new_placeholder(span: Span<'a>) -> Self {
+        Self { span, value: None, placeholder: true, location: None }
+    }
+
+    /// Creates a token that is not part of a string expression but of a filter written
+    /// with the structured JSON syntax, `location` is the JSON path of the value.
+    pub fn new_located(value: String, location: String) -> Self {
+        Self {
+            span: Span::new_extra("", ""),
+            value: Some(value),
+            placeholder: false,
+            location: Some(location),
+        }
+    }
+
+    /// Returns the name of the placeholder, without the `$`, if this token is a placeholder.
+    pub fn placeholder(&self) -> Option<&str> {
+        self.placeholder.then(|| &self.span.fragment()[1..])
+    }
+
+    /// Replaces the value of the token by the one returned by `bind` if this token is a placeholder.
+    pub fn bind_placeholder(
+        &mut self,
+        bind: &impl Fn(&str) -> Option<String>,
+    ) -> Result<(), Error<'a>> {
+        if let Some(name) = self.placeholder() {
+            match bind(name) {
+                Some(value) => self.value = Some(value),
+                None => {
+                    return Err(Error::new_from_kind(
+                        self.span,
+                        ErrorKind::UnboundPlaceholder(name.to_string()),
+                    ))
+                }
+            }
+        }
+        Ok(())
     }
 
     /// Returns the string contained in the span of the `Token`.
@@ -105,7 +154,7 @@ impl<'a> Token<'a> {
     }
 
     pub fn as_external_error(&self, error: impl std::error::Error) -> Error<'a> {
-        Error::new_from_external(self.span, error)
+        Error::new_from_external(self.span, error).with_location(self.location.clone())
     }
 
     /// Returns a copy of the span this token was created with.
@@ -118,14 +167,15 @@ impl<'a> Token<'a> {
         if value.is_finite() {
             Ok(value)
         } else {
-            Err(Error::new_from_kind(self.span, ErrorKind::NonFiniteFloat))
+            Err(Error::new_from_kind(self.span, ErrorKind::NonFiniteFloat)
+                .with_location(self.location.clone()))
         }
     }
 }
 
 impl<'a> From<Span<'a>> for Token<'a> {
     fn from(span: Span<'a>) -> Self {
-        Self { span, value: None }
+        Self { span, value: None, placeholder: false, location: None }
     }
 }
 
@@ -145,6 +195,8 @@ pub enum FilterCondition<'a> {
     And(Vec<Self>),
     GeoLowerThan { point: [Token<'a>; 2], radius: Token<'a> },
     GeoBoundingBox { top_right_point: [Token<'a>; 2], bottom_left_point: [Token<'a>; 2] },
+    /// The sub-filter must be matched by a single element of the `fid` array of objects.
+    Matches { fid: Token<'a>, filter: Box<Self> },
 }
 
 impl<'a> FilterCondition<'a> {
@@ -170,11 +222,47 @@ impl<'a> FilterCondition<'a> {
                 }
                 None
             }
+            FilterCondition::Matches { fid, .. } if depth == 0 => Some(fid),
+            FilterCondition::Matches { filter, .. } => filter.token_at_depth(depth - 1),
             FilterCondition::GeoLowerThan { point: [point, _], .. } if depth == 0 => Some(point),
             _ => None,
         }
     }
 
+    /// Replaces the value of all the `$name` placeholders of the filter by the one
+    /// returned by `bind`, it fails on the first placeholder that `bind` can't resolve.
+    pub fn bind_placeholders(
+        &mut self,
+        bind: &impl Fn(&str) -> Option<String>,
+    ) -> Result<(), Error<'a>> {
+        match self {
+            FilterCondition::Not(filter) => filter.bind_placeholders(bind),
+            FilterCondition::Condition { fid, op } => {
+                fid.bind_placeholder(bind)?;
+                op.tokens_mut().into_iter().try_for_each(|token| token.bind_placeholder(bind))
+            }
+            FilterCondition::In { fid, els } => {
+                fid.bind_placeholder(bind)?;
+                els.iter_mut().try_for_each(|token| token.bind_placeholder(bind))
+            }
+            FilterCondition::Or(filters) | FilterCondition::And(filters) => {
+                filters.iter_mut().try_for_each(|filter| filter.bind_placeholders(bind))
+            }
+            FilterCondition::Matches { fid, filter } => {
+                fid.bind_placeholder(bind)?;
+                filter.bind_placeholders(bind)
+            }
+            FilterCondition::GeoLowerThan { point, radius } => {
+                point.iter_mut().try_for_each(|token| token.bind_placeholder(bind))?;
+                radius.bind_placeholder(bind)
+            }
+            FilterCondition::GeoBoundingBox { top_right_point, bottom_left_point } => top_right_point
+                .iter_mut()
+                .chain(bottom_left_point.iter_mut())
+                .try_for_each(|token| token.bind_placeholder(bind)),
+        }
+    }
+
     pub fn parse(input: &'a str) -> Result<Option<Self>, Error> {
         if input.trim().is_empty() {
             return Ok(None);
@@ -257,6 +345,29 @@ fn parse_not_in(input: Span) -> IResult<FilterCondition> {
     Ok((input, filter))
 }
 
+/// matches        = value "MATCHES" WS* "(" WS* expression WS* ")"
+/// The field names used in the expression are relative to the elements of the array.
+fn parse_matches(input: Span, depth: usize) -> IResult<FilterCondition> {
+    if depth > MAX_FILTER_DEPTH {
+        return Err(nom::Err::Error(Error::new_from_kind(input, ErrorKind::DepthLimitReached)));
+    }
+    let (input, fid) = parse_value(input)?;
+    let (input, _) = terminated(word_exact("MATCHES"), multispace0)(input)?;
+
+    // everything after `MATCHES` can be a failure
+    let (input, filter) = delimited(
+        cut_with_err(ws(char('(')), |_| {
+            Error::new_from_kind(input, ErrorKind::MatchesOpeningParenthesis)
+        }),
+        cut(|input| parse_expression(input, depth + 1)),
+        cut_with_err(ws(char(')')), |c| {
+            Error::new_from_kind(input, ErrorKind::MissingClosingDelimiter(c.char()))
+        }),
+    )(input)?;
+
+    Ok((input, FilterCondition::Matches { fid, filter: Box::new(filter) }))
+}
+
 /// or             = and ("OR" and)
 fn parse_or(input: Span, depth: usize) -> IResult<FilterCondition> {
     if depth > MAX_FILTER_DEPTH {
@@ -426,7 +537,7 @@ fn parse_error_reserved_keyword(input: Span) -> IResult<FilterCondition> {
     }
 }
 
-/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | condition | exists | not_exists | to
+/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | matches | condition | exists | not_exists | to | contains | starts_with
 fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
     if depth > MAX_FILTER_DEPTH {
         return Err(nom::Err::Error(Error::new_from_kind(input, ErrorKind::DepthLimitReached)));
@@ -442,16 +553,22 @@ fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
         ),
         parse_geo_radius,
         parse_geo_bounding_box,
+        |input| parse_matches(input, depth + 1),
         parse_in,
         parse_not_in,
         parse_condition,
-        parse_is_null,
-        parse_is_not_null,
-        parse_is_empty,
-        parse_is_not_empty,
+        alt((
+            parse_is_null,
+            parse_is_not_null,
+            parse_is_empty,
+            parse_is_not_empty,
+            parse_is_type,
+            parse_is_not_type,
+        )),
         parse_exists,
         parse_not_exists,
         parse_to,
+        alt((parse_contains, parse_not_contains, parse_starts_with, parse_not_starts_with)),
         // the next lines are only for error handling and are written at the end to have the less possible performance impact
         parse_geo,
         parse_geo_distance,
@@ -551,6 +668,14 @@ pub mod tests {
         insta::assert_display_snapshot!(p("NOT subscribers IS NOT EMPTY"), @"{subscribers} IS EMPTY");
         insta::assert_display_snapshot!(p("subscribers  IS   NOT   EMPTY"), @"NOT ({subscribers} IS EMPTY)");
 
+        // Test NUMBER + NOT NUMBER + STRING + NOT STRING
+        insta::assert_display_snapshot!(p("price IS NUMBER"), @"{price} IS NUMBER");
+        insta::assert_display_snapshot!(p("NOT price IS NUMBER"), @"NOT ({price} IS NUMBER)");
+        insta::assert_display_snapshot!(p("price IS NOT NUMBER"), @"NOT ({price} IS NUMBER)");
+        insta::assert_display_snapshot!(p("name IS STRING"), @"{name} IS STRING");
+        insta::assert_display_snapshot!(p("name  IS   NOT   STRING"), @"NOT ({name} IS STRING)");
+        insta::assert_display_snapshot!(p("_size.tags > 3"), @"{_size.tags} > {3}");
+
         // Test EXISTS + NOT EXITS
         insta::assert_display_snapshot!(p("subscribers EXISTS"), @"{subscribers} EXISTS");
         insta::assert_display_snapshot!(p("NOT subscribers EXISTS"), @"NOT ({subscribers} EXISTS)");
@@ -558,6 +683,31 @@ pub mod tests {
         insta::assert_display_snapshot!(p("NOT subscribers NOT EXISTS"), @"{subscribers} EXISTS");
         insta::assert_display_snapshot!(p("subscribers NOT   EXISTS"), @"NOT ({subscribers} EXISTS)");
 
+        // Test CONTAINS + NOT CONTAINS
+        insta::assert_display_snapshot!(p("sku CONTAINS 4521"), @"{sku} CONTAINS {4521}");
+        insta::assert_display_snapshot!(p("sku CONTAINS 'AB-45'"), @"{sku} CONTAINS {AB-45}");
+        insta::assert_display_snapshot!(p("NOT sku CONTAINS 4521"), @"NOT ({sku} CONTAINS {4521})");
+        insta::assert_display_snapshot!(p("sku NOT CONTAINS 4521"), @"NOT ({sku} CONTAINS {4521})");
+        insta::assert_display_snapshot!(p("sku  NOT   CONTAINS   4521"), @"NOT ({sku} CONTAINS {4521})");
+
+        // Test STARTS WITH + NOT STARTS WITH
+        insta::assert_display_snapshot!(p("sku STARTS WITH AB"), @"{sku} STARTS WITH {AB}");
+        insta::assert_display_snapshot!(p("sku STARTS WITH \"AB 12\""), @"{sku} STARTS WITH {AB 12}");
+        insta::assert_display_snapshot!(p("NOT sku STARTS WITH AB"), @"NOT ({sku} STARTS WITH {AB})");
+        insta::assert_display_snapshot!(p("sku NOT STARTS WITH AB"), @"NOT ({sku} STARTS WITH {AB})");
+        insta::assert_display_snapshot!(p("sku  STARTS   WITH   AB"), @"{sku} STARTS WITH {AB}");
+
+        // Test MATCHES
+        insta::assert_display_snapshot!(p("variants MATCHES (color = red AND size = M)"), @"{variants} MATCHES (AND[{color} = {red}, {size} = {M}, ])");
+        insta::assert_display_snapshot!(p("variants MATCHES(color = red)"), @"{variants} MATCHES ({color} = {red})");
+        insta::assert_display_snapshot!(p("NOT variants MATCHES (color = red OR NOT size IN [M, L])"), @"NOT ({variants} MATCHES (OR[{color} = {red}, NOT ({size} IN[{M}, {L}, ]), ]))");
+        insta::assert_display_snapshot!(p("'my variants' MATCHES ( price 10 TO 20 ) AND brand = apple"), @"AND[{my variants} MATCHES ({price} {10} TO {20}), {brand} = {apple}, ]");
+
+        // Test placeholders
+        insta::assert_display_snapshot!(p("brand = $brand"), @"{brand} = {$brand}");
+        insta::assert_display_snapshot!(p("price $min TO $max_price"), @"{price} {$min} TO {$max_price}");
+        insta::assert_display_snapshot!(p("brand IN [$first, apple, '$second']"), @"{brand} IN[{$first}, {apple}, {$second}, ]");
+
         // Test nested NOT
         insta::assert_display_snapshot!(p("NOT NOT NOT NOT x = 5"), @"{x} = {5}");
         insta::assert_display_snapshot!(p("NOT NOT (NOT NOT x = 5)"), @"{x} = {5}");
@@ -629,7 +779,7 @@ pub mod tests {
         "###);
 
         insta::assert_display_snapshot!(p("'OR'"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `\'OR\'`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS NUMBER`, `IS NOT NUMBER`, `IS STRING`, `IS NOT STRING`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `MATCHES`, `_geoRadius`, or `_geoBoundingBox` at `\'OR\'`.
         1:5 'OR'
         "###);
 
@@ -639,12 +789,12 @@ pub mod tests {
         "###);
 
         insta::assert_display_snapshot!(p("channel Ponce"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `channel Ponce`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS NUMBER`, `IS NOT NUMBER`, `IS STRING`, `IS NOT STRING`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `MATCHES`, `_geoRadius`, or `_geoBoundingBox` at `channel Ponce`.
         1:14 channel Ponce
         "###);
 
         insta::assert_display_snapshot!(p("channel = Ponce OR"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` but instead got nothing.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS NUMBER`, `IS NOT NUMBER`, `IS STRING`, `IS NOT STRING`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `MATCHES`, `_geoRadius`, or `_geoBoundingBox` but instead got nothing.
         19:19 channel = Ponce OR
         "###);
 
@@ -729,12 +879,12 @@ pub mod tests {
         "###);
 
         insta::assert_display_snapshot!(p("colour NOT EXIST"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `colour NOT EXIST`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS NUMBER`, `IS NOT NUMBER`, `IS STRING`, `IS NOT STRING`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `MATCHES`, `_geoRadius`, or `_geoBoundingBox` at `colour NOT EXIST`.
         1:17 colour NOT EXIST
         "###);
 
         insta::assert_display_snapshot!(p("subscribers 100 TO1000"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `subscribers 100 TO1000`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS NUMBER`, `IS NOT NUMBER`, `IS STRING`, `IS NOT STRING`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `MATCHES`, `_geoRadius`, or `_geoBoundingBox` at `subscribers 100 TO1000`.
         1:23 subscribers 100 TO1000
         "###);
 
@@ -797,35 +947,63 @@ pub mod tests {
         "###);
 
         insta::assert_display_snapshot!(p(r#"value NULL"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value NULL`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS NUMBER`, `IS NOT NUMBER`, `IS STRING`, `IS NOT STRING`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `MATCHES`, `_geoRadius`, or `_geoBoundingBox` at `value NULL`.
         1:11 value NULL
         "###);
         insta::assert_display_snapshot!(p(r#"value NOT NULL"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value NOT NULL`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS NUMBER`, `IS NOT NUMBER`, `IS STRING`, `IS NOT STRING`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `MATCHES`, `_geoRadius`, or `_geoBoundingBox` at `value NOT NULL`.
         1:15 value NOT NULL
         "###);
         insta::assert_display_snapshot!(p(r#"value EMPTY"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value EMPTY`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS NUMBER`, `IS NOT NUMBER`, `IS STRING`, `IS NOT STRING`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `MATCHES`, `_geoRadius`, or `_geoBoundingBox` at `value EMPTY`.
         1:12 value EMPTY
         "###);
         insta::assert_display_snapshot!(p(r#"value NOT EMPTY"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value NOT EMPTY`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS NUMBER`, `IS NOT NUMBER`, `IS STRING`, `IS NOT STRING`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `MATCHES`, `_geoRadius`, or `_geoBoundingBox` at `value NOT EMPTY`.
         1:16 value NOT EMPTY
         "###);
         insta::assert_display_snapshot!(p(r#"value IS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value IS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS NUMBER`, `IS NOT NUMBER`, `IS STRING`, `IS NOT STRING`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `MATCHES`, `_geoRadius`, or `_geoBoundingBox` at `value IS`.
         1:9 value IS
         "###);
         insta::assert_display_snapshot!(p(r#"value IS NOT"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value IS NOT`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS NUMBER`, `IS NOT NUMBER`, `IS STRING`, `IS NOT STRING`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `MATCHES`, `_geoRadius`, or `_geoBoundingBox` at `value IS NOT`.
         1:13 value IS NOT
         "###);
         insta::assert_display_snapshot!(p(r#"value IS EXISTS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value IS EXISTS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS NUMBER`, `IS NOT NUMBER`, `IS STRING`, `IS NOT STRING`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `MATCHES`, `_geoRadius`, or `_geoBoundingBox` at `value IS EXISTS`.
         1:16 value IS EXISTS
         "###);
+        insta::assert_display_snapshot!(p(r#"value CONTAINS"#), @r###"
+        Was expecting a value but instead got nothing.
+        15:15 value CONTAINS
+        "###);
+        insta::assert_display_snapshot!(p(r#"value STARTS WITH"#), @r###"
+        Was expecting a value but instead got nothing.
+        18:18 value STARTS WITH
+        "###);
+        insta::assert_display_snapshot!(p(r#"value STARTS"#), @r###"
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS NUMBER`, `IS NOT NUMBER`, `IS STRING`, `IS NOT STRING`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `MATCHES`, `_geoRadius`, or `_geoBoundingBox` at `value STARTS`.
+        1:13 value STARTS
+        "###);
+        insta::assert_display_snapshot!(p(r#"brand = $"#), @r###"
+        Was expecting a value but instead got `$`.
+        9:10 brand = $
+        "###);
+        insta::assert_display_snapshot!(p(r#"variants MATCHES color = red"#), @r###"
+        Expected `(` after `MATCHES` keyword.
+        18:29 variants MATCHES color = red
+        "###);
+        insta::assert_display_snapshot!(p(r#"variants MATCHES (color = red"#), @r###"
+        Expression `(color = red` is missing the following closing delimiter: `)`.
+        18:30 variants MATCHES (color = red
+        "###);
+        insta::assert_display_snapshot!(p(r#"variants MATCHES ()"#), @r###"
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS NUMBER`, `IS NOT NUMBER`, `IS STRING`, `IS NOT STRING`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `MATCHES`, `_geoRadius`, or `_geoBoundingBox` at `)`.
+        19:20 variants MATCHES ()
+        "###);
         insta::assert_display_snapshot!(p(r#"value IS NOT EXISTS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `_geoRadius`, or `_geoBoundingBox` at `value IS NOT EXISTS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `IS NUMBER`, `IS NOT NUMBER`, `IS STRING`, `IS NOT STRING`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `MATCHES`, `_geoRadius`, or `_geoBoundingBox` at `value IS NOT EXISTS`.
         1:20 value IS NOT EXISTS
         "###);
     }
@@ -845,6 +1023,38 @@ pub mod tests {
         assert!(filter.token_at_depth(3).is_none());
     }
 
+    #[test]
+    fn bind_placeholders() {
+        let bind = |name: &str| match name {
+            "brand" => Some(String::from("O'Reilly")),
+            "min" => Some(String::from("10")),
+            _ => None,
+        };
+
+        let mut filter =
+            FilterCondition::parse("brand = $brand AND price $min TO 20").unwrap().unwrap();
+        filter.bind_placeholders(&bind).unwrap();
+        insta::assert_display_snapshot!(filter, @"AND[{brand} = {O'Reilly}, {price} {10} TO {20}, ]");
+
+        let mut filter =
+            FilterCondition::parse("brand = $brand OR NOT tag IN [a, $tag]").unwrap().unwrap();
+        let error = filter.bind_placeholders(&bind).unwrap_err();
+        insta::assert_display_snapshot!(error, @r###"
+        The `$tag` placeholder has no value.
+        34:38 brand = $brand OR NOT tag IN [a, $tag]
+        "###);
+    }
+
+    #[test]
+    fn located_token() {
+        let token = Token::new_located(String::from("ten"), String::from(".and[0].value"));
+        let error = token.parse_finite_float().unwrap_err();
+        insta::assert_display_snapshot!(error, @r###"
+        invalid float literal
+        at `.and[0].value`
+        "###);
+    }
+
     #[test]
     fn token_from_str() {
         let s = "test string that should not be parsed";
@@ -886,6 +1096,9 @@ impl<'a> std::fmt::Display for FilterCondition<'a> {
             FilterCondition::GeoLowerThan { point, radius } => {
                 write!(f, "_geoRadius({}, {}, {})", point[0], point[1], radius)
             }
+            FilterCondition::Matches { fid, filter } => {
+                write!(f, "{fid} MATCHES ({filter})")
+            }
             FilterCondition::GeoBoundingBox {
                 top_right_point: top_left_point,
                 bottom_left_point: bottom_right_point,
@@ -915,6 +1128,10 @@ impl<'a> std::fmt::Display for Condition<'a> {
             Condition::LowerThan(token) => write!(f, "< {token}"),
             Condition::LowerThanOrEqual(token) => write!(f, "<= {token}"),
             Condition::Between { from, to } => write!(f, "{from} TO {to}"),
+            Condition::Contains(token) => write!(f, "CONTAINS {token}"),
+            Condition::StartsWith(token) => write!(f, "STARTS WITH {token}"),
+            Condition::IsNumber => write!(f, "IS NUMBER"),
+            Condition::IsString => write!(f, "IS STRING"),
         }
     }
 }
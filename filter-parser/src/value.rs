@@ -1,8 +1,8 @@
 use nom::branch::alt;
 use nom::bytes::complete::{take_till, take_while, take_while1};
 use nom::character::complete::{char, multispace0};
-use nom::combinator::cut;
-use nom::sequence::{delimited, terminated};
+use nom::combinator::{cut, recognize};
+use nom::sequence::{delimited, preceded, terminated};
 use nom::{InputIter, InputLength, InputTake, Slice};
 
 use crate::error::{ExpectedValueKind, NomErrorExt};
@@ -80,7 +80,14 @@ pub fn word_exact<'a, 'b: 'a>(tag: &'b str) -> impl Fn(Span<'a>) -> IResult<'a,
     }
 }
 
-/// value          = WS* ( word | singleQuoted | doubleQuoted) WS+
+/// placeholder    = "$" (alphanumeric | _)+
+fn parse_placeholder(input: Span) -> IResult<Token> {
+    let (input, placeholder) =
+        recognize(preceded(char('$'), take_while1(is_placeholder_component)))(input)?;
+    Ok((input, Token::new_placeholder(placeholder)))
+}
+
+/// value          = WS* ( word | singleQuoted | doubleQuoted | placeholder) WS+
 pub fn parse_value(input: Span) -> IResult<Token> {
     // to get better diagnostic message we are going to strip the left whitespaces from the input right now
     let (input, _) = take_while(char::is_whitespace)(input)?;
@@ -141,6 +148,7 @@ pub fn parse_value(input: Span) -> IResult<Token> {
         alt((
             delimited(char('\''), cut(|input| quoted_by('\'', input)), cut(char('\''))),
             delimited(char('"'), cut(|input| quoted_by('"', input)), cut(char('"'))),
+            parse_placeholder,
             word_not_keyword,
         )),
         multispace0,
@@ -178,6 +186,10 @@ fn is_value_component(c: char) -> bool {
     c.is_alphanumeric() || ['_', '-', '.'].contains(&c)
 }
 
+fn is_placeholder_component(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
 fn is_syntax_component(c: char) -> bool {
     c.is_whitespace() || ['(', ')', '=', '<', '>', '!'].contains(&c)
 }
@@ -8,7 +8,7 @@ The main function of the autobatcher is [`next_autobatch`].
 use std::ops::ControlFlow::{self, Break, Continue};
 
 use meilisearch_types::milli::update::IndexDocumentsMethod::{
-    self, ReplaceDocuments, UpdateDocuments,
+    self, EditDocuments, ReplaceDocuments, UpdateDocuments,
 };
 use meilisearch_types::tasks::TaskId;
 
@@ -26,6 +26,7 @@ enum AutobatchKind {
     },
     DocumentDeletion,
     DocumentDeletionByFilter,
+    DocumentEditionByFilter,
     DocumentClear,
     Settings {
         allow_index_creation: bool,
@@ -33,6 +34,7 @@ enum AutobatchKind {
     IndexCreation,
     IndexDeletion,
     IndexUpdate,
+    IndexCompaction,
     IndexSwap,
 }
 
@@ -63,11 +65,20 @@ impl From<KindWithContent> for AutobatchKind {
                 primary_key,
                 ..
             } => AutobatchKind::DocumentImport { method, allow_index_creation, primary_key },
+            // an edition only applies on existing documents and thus never creates the index.
+            KindWithContent::DocumentEdition { .. } => AutobatchKind::DocumentImport {
+                method: EditDocuments,
+                allow_index_creation: false,
+                primary_key: None,
+            },
             KindWithContent::DocumentDeletion { .. } => AutobatchKind::DocumentDeletion,
             KindWithContent::DocumentClear { .. } => AutobatchKind::DocumentClear,
             KindWithContent::DocumentDeletionByFilter { .. } => {
                 AutobatchKind::DocumentDeletionByFilter
             }
+            KindWithContent::DocumentEditionByFilter { .. } => {
+                AutobatchKind::DocumentEditionByFilter
+            }
             KindWithContent::SettingsUpdate { allow_index_creation, is_deletion, .. } => {
                 AutobatchKind::Settings {
                     allow_index_creation: allow_index_creation && !is_deletion,
@@ -76,6 +87,7 @@ impl From<KindWithContent> for AutobatchKind {
             KindWithContent::IndexDeletion { .. } => AutobatchKind::IndexDeletion,
             KindWithContent::IndexCreation { .. } => AutobatchKind::IndexCreation,
             KindWithContent::IndexUpdate { .. } => AutobatchKind::IndexUpdate,
+            KindWithContent::IndexCompaction { .. } => AutobatchKind::IndexCompaction,
             KindWithContent::IndexSwap { .. } => AutobatchKind::IndexSwap,
             KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
@@ -104,6 +116,9 @@ pub enum BatchKind {
     DocumentDeletionByFilter {
         id: TaskId,
     },
+    DocumentEditionByFilter {
+        id: TaskId,
+    },
     ClearAndSettings {
         other: Vec<TaskId>,
         allow_index_creation: bool,
@@ -129,6 +144,9 @@ pub enum BatchKind {
     IndexUpdate {
         id: TaskId,
     },
+    IndexCompaction {
+        id: TaskId,
+    },
     IndexSwap {
         id: TaskId,
     },
@@ -174,6 +192,7 @@ impl BatchKind {
             K::IndexCreation => (Break(BatchKind::IndexCreation { id: task_id }), true),
             K::IndexDeletion => (Break(BatchKind::IndexDeletion { ids: vec![task_id] }), false),
             K::IndexUpdate => (Break(BatchKind::IndexUpdate { id: task_id }), false),
+            K::IndexCompaction => (Break(BatchKind::IndexCompaction { id: task_id }), false),
             K::IndexSwap => (Break(BatchKind::IndexSwap { id: task_id }), false),
             K::DocumentClear => (Continue(BatchKind::DocumentClear { ids: vec![task_id] }), false),
             K::DocumentImport { method, allow_index_creation, primary_key: pk }
@@ -205,6 +224,9 @@ impl BatchKind {
             K::DocumentDeletionByFilter => {
                 (Break(BatchKind::DocumentDeletionByFilter { id: task_id }), false)
             }
+            K::DocumentEditionByFilter => {
+                (Break(BatchKind::DocumentEditionByFilter { id: task_id }), false)
+            }
             K::Settings { allow_index_creation } => (
                 Continue(BatchKind::Settings { allow_index_creation, settings_ids: vec![task_id] }),
                 allow_index_creation,
@@ -222,7 +244,7 @@ impl BatchKind {
 
         match (self, kind) {
             // We don't batch any of these operations
-            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::DocumentDeletionByFilter) => Break(this),
+            (this, K::IndexCreation | K::IndexUpdate | K::IndexCompaction | K::IndexSwap | K::DocumentDeletionByFilter | K::DocumentEditionByFilter) => Break(this),
             // We must not batch tasks that don't have the same index creation rights if the index doesn't already exists.
             (this, kind) if !index_already_exists && this.allow_index_creation() == Some(false) && kind.allow_index_creation() == Some(true) => {
                 Break(this)
@@ -320,6 +342,18 @@ impl BatchKind {
                     operation_ids,
                 })
             }
+            (
+                BatchKind::DocumentOperation { method: EditDocuments, allow_index_creation, primary_key, mut operation_ids },
+                K::DocumentImport { method: EditDocuments, .. },
+            ) => {
+                operation_ids.push(id);
+                Continue(BatchKind::DocumentOperation {
+                    method: EditDocuments,
+                    allow_index_creation,
+                    primary_key,
+                    operation_ids,
+                })
+            }
             (
                 this @ BatchKind::DocumentOperation { .. },
                 K::DocumentDeletion,
@@ -458,6 +492,19 @@ impl BatchKind {
                     operation_ids,
                 })
             }
+            (
+                BatchKind::SettingsAndDocumentOperation { settings_ids, method: EditDocuments, allow_index_creation, primary_key, mut operation_ids },
+                K::DocumentImport { method: EditDocuments, .. },
+            ) => {
+                operation_ids.push(id);
+                Continue(BatchKind::SettingsAndDocumentOperation {
+                    settings_ids,
+                    method: EditDocuments,
+                    allow_index_creation,
+                    primary_key,
+                    operation_ids,
+                })
+            }
             // But we can't batch a settings and a doc op with another doc op
             // this MUST be AFTER the two previous branch
             (
@@ -481,8 +528,10 @@ impl BatchKind {
                 BatchKind::IndexCreation { .. }
                 | BatchKind::IndexDeletion { .. }
                 | BatchKind::IndexUpdate { .. }
+                | BatchKind::IndexCompaction { .. }
                 | BatchKind::IndexSwap { .. }
-                | BatchKind::DocumentDeletionByFilter { .. },
+                | BatchKind::DocumentDeletionByFilter { .. }
+                | BatchKind::DocumentEditionByFilter { .. },
                 _,
             ) => {
                 unreachable!()
@@ -565,6 +614,14 @@ mod tests {
         }
     }
 
+    fn doc_edit() -> KindWithContent {
+        KindWithContent::DocumentEdition {
+            index_uid: String::from("doggo"),
+            content_file: Uuid::new_v4(),
+            documents_count: 0,
+        }
+    }
+
     fn doc_del() -> KindWithContent {
         KindWithContent::DocumentDeletion {
             index_uid: String::from("doggo"),
@@ -572,6 +629,15 @@ mod tests {
         }
     }
 
+    fn doc_edit_fil() -> KindWithContent {
+        KindWithContent::DocumentEditionByFilter {
+            index_uid: String::from("doggo"),
+            filter_expr: serde_json::json!("cuteness > 100"),
+            filter_params: None,
+            edits: serde_json::Map::new(),
+        }
+    }
+
     fn doc_clr() -> KindWithContent {
         KindWithContent::DocumentClear { index_uid: String::from("doggo") }
     }
@@ -597,6 +663,10 @@ mod tests {
         KindWithContent::IndexDeletion { index_uid: String::from("doggo") }
     }
 
+    fn idx_compact() -> KindWithContent {
+        KindWithContent::IndexCompaction { index_uid: String::from("doggo") }
+    }
+
     fn idx_swap() -> KindWithContent {
         KindWithContent::IndexSwap {
             swaps: vec![IndexSwap { indexes: (String::from("doggo"), String::from("catto")) }],
@@ -631,6 +701,11 @@ mod tests {
         debug_snapshot!(autobatch_from(false,None,  [doc_imp(UpdateDocuments, false, None)]), @"Some((DocumentOperation { method: UpdateDocuments, allow_index_creation: false, primary_key: None, operation_ids: [0] }, false))");
         debug_snapshot!(autobatch_from(false,None,  [doc_imp(UpdateDocuments, false, None), doc_imp(UpdateDocuments, false, None), doc_imp(UpdateDocuments, false, None)]), @"Some((DocumentOperation { method: UpdateDocuments, allow_index_creation: false, primary_key: None, operation_ids: [0, 1, 2] }, false))");
 
+        // we can autobatch one or multiple document editions together, they never create the index.
+        debug_snapshot!(autobatch_from(true, None, [doc_edit()]), @"Some((DocumentOperation { method: EditDocuments, allow_index_creation: false, primary_key: None, operation_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(true, None, [doc_edit(), doc_edit(), doc_edit()]), @"Some((DocumentOperation { method: EditDocuments, allow_index_creation: false, primary_key: None, operation_ids: [0, 1, 2] }, false))");
+        debug_snapshot!(autobatch_from(false,None,  [doc_edit(), doc_edit(), doc_edit()]), @"Some((DocumentOperation { method: EditDocuments, allow_index_creation: false, primary_key: None, operation_ids: [0, 1, 2] }, false))");
+
         // we can autobatch one or multiple DocumentDeletion together
         debug_snapshot!(autobatch_from(true, None, [doc_del()]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
         debug_snapshot!(autobatch_from(true, None, [doc_del(), doc_del(), doc_del()]), @"Some((DocumentDeletion { deletion_ids: [0, 1, 2] }, false))");
@@ -685,6 +760,8 @@ mod tests {
         // addition, updates and deletion can't batch together
         debug_snapshot!(autobatch_from(true, None, [doc_imp(ReplaceDocuments, true, None), doc_imp(UpdateDocuments, true, None)]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
         debug_snapshot!(autobatch_from(true, None, [doc_imp(UpdateDocuments, true, None), doc_imp(ReplaceDocuments, true, None)]), @"Some((DocumentOperation { method: UpdateDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(UpdateDocuments, true, None), doc_edit()]), @"Some((DocumentOperation { method: UpdateDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_edit(), doc_imp(UpdateDocuments, true, None)]), @"Some((DocumentOperation { method: EditDocuments, allow_index_creation: false, primary_key: None, operation_ids: [0] }, false))");
 
         debug_snapshot!(autobatch_from(true, None, [doc_imp(ReplaceDocuments, true, None), idx_create()]), @"Some((DocumentOperation { method: ReplaceDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
         debug_snapshot!(autobatch_from(true, None, [doc_imp(UpdateDocuments, true, None), idx_create()]), @"Some((DocumentOperation { method: UpdateDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
@@ -699,6 +776,25 @@ mod tests {
         debug_snapshot!(autobatch_from(true, None, [doc_del(), idx_swap()]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
     }
 
+    #[test]
+    fn document_edition_by_filter_is_never_batched() {
+        debug_snapshot!(autobatch_from(true, None, [doc_edit_fil()]), @"Some((DocumentEditionByFilter { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, None, [doc_edit_fil(), doc_edit_fil()]), @"Some((DocumentEditionByFilter { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, None, [doc_edit_fil(), doc_edit()]), @"Some((DocumentEditionByFilter { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, None, [doc_edit(), doc_edit_fil()]), @"Some((DocumentOperation { method: EditDocuments, allow_index_creation: false, primary_key: None, operation_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(true, None, [doc_del(), doc_edit_fil()]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(true, None, [settings(true), doc_edit_fil()]), @"Some((Settings { allow_index_creation: true, settings_ids: [0] }, true))");
+    }
+
+    #[test]
+    fn index_compaction_is_never_batched() {
+        debug_snapshot!(autobatch_from(true, None, [idx_compact()]), @"Some((IndexCompaction { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, None, [idx_compact(), idx_compact()]), @"Some((IndexCompaction { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, None, [idx_compact(), doc_del()]), @"Some((IndexCompaction { id: 0 }, false))");
+        debug_snapshot!(autobatch_from(true, None, [doc_del(), idx_compact()]), @"Some((DocumentDeletion { deletion_ids: [0] }, false))");
+        debug_snapshot!(autobatch_from(true, None, [settings(true), idx_compact()]), @"Some((Settings { allow_index_creation: true, settings_ids: [0] }, true))");
+    }
+
     #[test]
     fn document_addition_batch_with_settings() {
         // simple case
@@ -17,23 +17,27 @@ tasks individally, but should be much faster since we are only performing
 one indexing operation.
 */
 
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::ffi::OsStr;
 use std::fs::{self, File};
-use std::io::BufWriter;
+use std::io::{BufReader, BufWriter};
 
 use dump::IndexMetadata;
 use log::{debug, error, info};
 use meilisearch_types::heed::{RoTxn, RwTxn};
-use meilisearch_types::milli::documents::{obkv_to_object, DocumentsBatchReader};
+use meilisearch_types::milli::documents::{
+    obkv_to_object, DocumentsBatchBuilder, DocumentsBatchReader,
+};
 use meilisearch_types::milli::heed::CompactionOption;
 use meilisearch_types::milli::update::{
-    DeleteDocuments, DocumentDeletionResult, IndexDocumentsConfig, IndexDocumentsMethod,
-    Settings as MilliSettings,
+    patch_to_operations, DeleteDocuments, DeletionStrategy, DocumentDeletionResult,
+    IndexDocumentsConfig, IndexDocumentsMethod, IndexerConfig, Settings as MilliSettings,
 };
 use meilisearch_types::milli::{self, Filter, BEU32};
 use meilisearch_types::settings::{apply_settings_to_builder, Settings, Unchecked};
-use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{
+    Details, IndexSwap, Kind, KindWithContent, Status, Task, MAX_REPORTED_INVALID_DOCUMENTS,
+};
 use meilisearch_types::{compression, Index, VERSION_FILE_NAME};
 use roaring::RoaringBitmap;
 use time::macros::format_description;
@@ -42,7 +46,7 @@ use uuid::Uuid;
 
 use crate::autobatcher::{self, BatchKind};
 use crate::utils::{self, swap_index_uid_in_task};
-use crate::{Error, IndexScheduler, ProcessingTasks, Result, TaskId};
+use crate::{Error, IndexScheduler, MustStopProcessing, ProcessingTasks, Result, TaskId};
 
 /// Represents a combination of tasks that can all be processed at the same time.
 ///
@@ -70,6 +74,10 @@ pub(crate) enum Batch {
         index_uid: String,
         task: Task,
     },
+    IndexDocumentEditionByFilter {
+        index_uid: String,
+        task: Task,
+    },
     IndexCreation {
         index_uid: String,
         primary_key: Option<String>,
@@ -80,6 +88,10 @@ pub(crate) enum Batch {
         primary_key: Option<String>,
         task: Task,
     },
+    IndexCompaction {
+        index_uid: String,
+        task: Task,
+    },
     IndexDeletion {
         index_uid: String,
         tasks: Vec<Task>,
@@ -155,7 +167,9 @@ impl Batch {
             | Batch::Dump(task)
             | Batch::IndexCreation { task, .. }
             | Batch::IndexDocumentDeletionByFilter { task, .. }
-            | Batch::IndexUpdate { task, .. } => vec![task.uid],
+            | Batch::IndexDocumentEditionByFilter { task, .. }
+            | Batch::IndexUpdate { task, .. }
+            | Batch::IndexCompaction { task, .. } => vec![task.uid],
             Batch::SnapshotCreation(tasks) | Batch::IndexDeletion { tasks, .. } => {
                 tasks.iter().map(|task| task.uid).collect()
             }
@@ -181,6 +195,18 @@ impl Batch {
         }
     }
 
+    /// Return the number of times the batch is expected to index the documents, each
+    /// indexation goes through the indexing steps reported in the progress of the tasks.
+    pub fn indexations(&self) -> usize {
+        match self {
+            Batch::IndexOperation {
+                op: IndexOperation::SettingsAndDocumentOperation { .. },
+                ..
+            } => 2,
+            _ => 1,
+        }
+    }
+
     /// Return the index UID associated with this batch
     pub fn index_uid(&self) -> Option<&str> {
         use Batch::*;
@@ -193,8 +219,10 @@ impl Batch {
             IndexOperation { op, .. } => Some(op.index_uid()),
             IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
+            | IndexCompaction { index_uid, .. }
             | IndexDeletion { index_uid, .. }
-            | IndexDocumentDeletionByFilter { index_uid, .. } => Some(index_uid),
+            | IndexDocumentDeletionByFilter { index_uid, .. }
+            | IndexDocumentEditionByFilter { index_uid, .. } => Some(index_uid),
         }
     }
 }
@@ -246,6 +274,18 @@ impl IndexScheduler {
                     _ => unreachable!(),
                 }
             }
+            BatchKind::DocumentEditionByFilter { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                match &task.kind {
+                    KindWithContent::DocumentEditionByFilter { index_uid, .. } => {
+                        Ok(Some(Batch::IndexDocumentEditionByFilter {
+                            index_uid: index_uid.clone(),
+                            task,
+                        }))
+                    }
+                    _ => unreachable!(),
+                }
+            }
             BatchKind::DocumentOperation { method, operation_ids, .. } => {
                 let tasks = self.get_existing_tasks(rtxn, operation_ids)?;
                 let primary_key = tasks
@@ -255,7 +295,8 @@ impl IndexScheduler {
                             // we want to stop on the first document addition
                             Some(primary_key.clone())
                         }
-                        KindWithContent::DocumentDeletion { .. } => None,
+                        KindWithContent::DocumentEdition { .. }
+                        | KindWithContent::DocumentDeletion { .. } => None,
                         _ => unreachable!(),
                     })
                     .flatten();
@@ -269,6 +310,9 @@ impl IndexScheduler {
                             content_file,
                             documents_count,
                             ..
+                        }
+                        | KindWithContent::DocumentEdition {
+                            content_file, documents_count, ..
                         } => {
                             documents_counts.push(documents_count);
                             operations.push(DocumentOperation::Add(content_file));
@@ -448,6 +492,10 @@ impl IndexScheduler {
                 };
                 Ok(Some(Batch::IndexUpdate { index_uid, primary_key, task }))
             }
+            BatchKind::IndexCompaction { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                Ok(Some(Batch::IndexCompaction { index_uid, task }))
+            }
             BatchKind::IndexDeletion { ids } => Ok(Some(Batch::IndexDeletion {
                 index_uid,
                 index_has_been_created: must_create_index,
@@ -887,11 +935,14 @@ impl IndexScheduler {
                 Ok(tasks)
             }
             Batch::IndexDocumentDeletionByFilter { mut task, index_uid: _ } => {
-                let (index_uid, filter) =
-                    if let KindWithContent::DocumentDeletionByFilter { index_uid, filter_expr } =
-                        &task.kind
+                let (index_uid, filter, filter_params) =
+                    if let KindWithContent::DocumentDeletionByFilter {
+                        index_uid,
+                        filter_expr,
+                        filter_params,
+                    } = &task.kind
                     {
-                        (index_uid, filter_expr)
+                        (index_uid, filter_expr, filter_params)
                     } else {
                         unreachable!()
                     };
@@ -899,7 +950,8 @@ impl IndexScheduler {
                     let rtxn = self.env.read_txn()?;
                     self.index_mapper.index(&rtxn, index_uid)?
                 };
-                let deleted_documents = delete_document_by_filter(filter, index);
+                let deleted_documents =
+                    delete_document_by_filter(filter, filter_params.as_ref(), index);
                 let original_filter = if let Some(Details::DocumentDeletionByFilter {
                     original_filter,
                     deleted_documents: _,
@@ -931,6 +983,80 @@ impl IndexScheduler {
 
                 Ok(vec![task])
             }
+            Batch::IndexDocumentEditionByFilter { mut task, index_uid: _ } => {
+                let (index_uid, filter, filter_params, edits) =
+                    if let KindWithContent::DocumentEditionByFilter {
+                        index_uid,
+                        filter_expr,
+                        filter_params,
+                        edits,
+                    } = &task.kind
+                    {
+                        (index_uid, filter_expr, filter_params, edits)
+                    } else {
+                        unreachable!()
+                    };
+                let index = {
+                    let rtxn = self.env.read_txn()?;
+                    self.index_mapper.index(&rtxn, index_uid)?
+                };
+                let edited_documents = edit_documents_by_filter(
+                    filter,
+                    filter_params.as_ref(),
+                    edits,
+                    &index,
+                    self.index_mapper.indexer_config(),
+                    self.indexing_step_callback(),
+                    self.must_stop_processing.clone(),
+                );
+                let original_filter = if let Some(Details::DocumentEditionByFilter {
+                    original_filter,
+                    edited_documents: _,
+                }) = task.details
+                {
+                    original_filter
+                } else {
+                    // In the case of a `documentEditionByFilter` the details MUST be set
+                    unreachable!();
+                };
+
+                match edited_documents {
+                    Ok(edited_documents) => {
+                        task.status = Status::Succeeded;
+                        task.details = Some(Details::DocumentEditionByFilter {
+                            original_filter,
+                            edited_documents: Some(edited_documents),
+                        });
+
+                        // the edited documents may have changed the stats of the index. Since the
+                        // task has already been processed and this is a non-critical operation,
+                        // if it fails, we should not fail the task.
+                        let res = || -> Result<()> {
+                            let mut wtxn = self.env.write_txn()?;
+                            let index_rtxn = index.read_txn()?;
+                            let stats = crate::index_mapper::IndexStats::new(&index, &index_rtxn)?;
+                            self.index_mapper.store_stats_of(&mut wtxn, index_uid, &stats)?;
+                            wtxn.commit()?;
+                            Ok(())
+                        }();
+
+                        match res {
+                            Ok(_) => (),
+                            Err(e) => error!("Could not write the stats of the index {}", e),
+                        }
+                    }
+                    Err(e) => {
+                        task.status = Status::Failed;
+                        task.details = Some(Details::DocumentEditionByFilter {
+                            original_filter,
+                            edited_documents: Some(0),
+                        });
+                        task.error = Some(e.into());
+                    }
+                }
+
+                Ok(vec![task])
+            }
             Batch::IndexCreation { index_uid, primary_key, task } => {
                 let wtxn = self.env.write_txn()?;
                 if self.index_mapper.exists(&wtxn, &index_uid)? {
@@ -953,10 +1079,8 @@ impl IndexScheduler {
                     );
                     builder.set_primary_key(primary_key);
                     let must_stop_processing = self.must_stop_processing.clone();
-                    builder.execute(
-                        |indexing_step| debug!("update: {:?}", indexing_step),
-                        || must_stop_processing.get(),
-                    )?;
+                    builder
+                        .execute(self.indexing_step_callback(), || must_stop_processing.get())?;
                     index_wtxn.commit()?;
                 }
 
@@ -986,6 +1110,49 @@ impl IndexScheduler {
 
                 Ok(vec![task])
             }
+            Batch::IndexCompaction { index_uid, mut task } => {
+                let rtxn = self.env.read_txn()?;
+                let index = self.index_mapper.index(&rtxn, &index_uid)?;
+
+                // We first definitely remove the soft-deleted documents so that the
+                // compaction can reclaim the space they were using.
+                let mut index_wtxn = index.write_txn()?;
+                let mut builder = DeleteDocuments::new(&mut index_wtxn, &index)?;
+                builder.strategy(DeletionStrategy::AlwaysHard);
+                builder.execute()?;
+                index_wtxn.commit()?;
+                // the index must not be used anymore while its environment is being swapped.
+                drop(index);
+
+                let reclaimed_bytes = self.index_mapper.compact_index(&rtxn, &index_uid)?;
+
+                // drop rtxn before starting a new wtxn on the same db
+                rtxn.commit()?;
+
+                task.status = Status::Succeeded;
+                task.details =
+                    Some(Details::IndexCompaction { reclaimed_bytes: Some(reclaimed_bytes) });
+
+                // The size of the index changed, we're going to store its new stats.
+                // Since the task has already been processed and this is a non-critical
+                // operation, if it fails, we should not fail the task.
+                let res = || -> Result<()> {
+                    let mut wtxn = self.env.write_txn()?;
+                    let index = self.index_mapper.index(&wtxn, &index_uid)?;
+                    let index_rtxn = index.read_txn()?;
+                    let stats = crate::index_mapper::IndexStats::new(&index, &index_rtxn)?;
+                    self.index_mapper.store_stats_of(&mut wtxn, &index_uid, &stats)?;
+                    wtxn.commit()?;
+                    Ok(())
+                }();
+
+                match res {
+                    Ok(_) => (),
+                    Err(e) => error!("Could not write the stats of the index {}", e),
+                }
+
+                Ok(vec![task])
+            }
             Batch::IndexDeletion { index_uid, index_has_been_created, mut tasks } => {
                 let wtxn = self.env.write_txn()?;
 
@@ -1157,34 +1324,54 @@ impl IndexScheduler {
                             let mut builder =
                                 milli::update::Settings::new(index_wtxn, index, indexer_config);
                             builder.set_primary_key(primary_key);
-                            builder.execute(
-                                |indexing_step| debug!("update: {:?}", indexing_step),
-                                || must_stop_processing.clone().get(),
-                            )?;
+                            builder.execute(self.indexing_step_callback(), || {
+                                must_stop_processing.clone().get()
+                            })?;
                             primary_key_has_been_set = true;
                         }
                     }
                 }
 
                 let config = IndexDocumentsConfig { update_method: method, ..Default::default() };
+                // the skipped documents are only reported when the index compares the versions
+                // or skips the documents not matching its schema.
+                let reports_skipped_documents = index.version_field(index_wtxn)?.is_some() || {
+                    let schema = index.document_schema(index_wtxn)?;
+                    !schema.is_empty()
+                        && schema.on_invalid_document == milli::InvalidDocumentPolicy::Skip
+                };
 
                 let mut builder = milli::update::IndexDocuments::new(
                     index_wtxn,
                     index,
                     indexer_config,
                     config,
-                    |indexing_step| debug!("update: {:?}", indexing_step),
+                    self.indexing_step_callback(),
                     || must_stop_processing.get(),
                 )?;
 
-                for (operation, task) in operations.into_iter().zip(tasks.iter_mut()) {
+                // The positions of the tasks of the documents batches, in the order they are added.
+                let mut added_tasks = Vec::new();
+                for (position, (operation, task)) in
+                    operations.into_iter().zip(tasks.iter_mut()).enumerate()
+                {
                     match operation {
                         DocumentOperation::Add(content_uuid) => {
+                            added_tasks.push(position);
                             let content_file = self.file_store.get_update(content_uuid)?;
                             let reader = DocumentsBatchReader::from_reader(content_file)
                                 .map_err(milli::Error::from)?;
+                            let skipped_before = builder.skipped_documents();
+                            let invalid_before = builder.invalid_documents().len();
                             let (new_builder, user_result) = builder.add_documents(reader)?;
                             builder = new_builder;
+                            let skipped_documents = reports_skipped_documents
+                                .then(|| builder.skipped_documents() - skipped_before);
+                            let invalid_documents = builder.invalid_documents()[invalid_before..]
+                                .iter()
+                                .take(MAX_REPORTED_INVALID_DOCUMENTS)
+                                .cloned()
+                                .collect();
 
                             let received_documents =
                                 if let Some(Details::DocumentAdditionOrUpdate {
@@ -1204,6 +1391,8 @@ impl IndexScheduler {
                                     task.details = Some(Details::DocumentAdditionOrUpdate {
                                         received_documents,
                                         indexed_documents: Some(count),
+                                        skipped_documents,
+                                        invalid_documents,
                                     })
                                 }
                                 Err(e) => {
@@ -1211,6 +1400,8 @@ impl IndexScheduler {
                                     task.details = Some(Details::DocumentAdditionOrUpdate {
                                         received_documents,
                                         indexed_documents: Some(0),
+                                        skipped_documents: skipped_documents.map(|_| 0),
+                                        invalid_documents: Vec::new(),
                                     });
                                     task.error = Some(milli::Error::from(e).into());
                                 }
@@ -1255,16 +1446,39 @@ impl IndexScheduler {
                 if !tasks.iter().all(|res| res.error.is_some()) {
                     let addition = builder.execute()?;
                     info!("document addition done: {:?}", addition);
+
+                    // The edited documents not matching the schema are only known once edited,
+                    // they are reported by the task that last edited them.
+                    for (batch, invalid_edited_documents) in addition.invalid_edited_documents {
+                        let details = added_tasks
+                            .get(batch)
+                            .and_then(|&position| tasks[position].details.as_mut());
+                        if let Some(Details::DocumentAdditionOrUpdate {
+                            indexed_documents,
+                            skipped_documents,
+                            invalid_documents,
+                            ..
+                        }) = details
+                        {
+                            let skipped = invalid_edited_documents.len() as u64;
+                            *indexed_documents =
+                                indexed_documents.map(|n| n.saturating_sub(skipped));
+                            *skipped_documents = skipped_documents.map(|n| n + skipped);
+                            let remaining = MAX_REPORTED_INVALID_DOCUMENTS
+                                .saturating_sub(invalid_documents.len());
+                            invalid_documents
+                                .extend(invalid_edited_documents.into_iter().take(remaining));
+                        }
+                    }
                 } else if primary_key_has_been_set {
                     // Everything failed but we've set a primary key.
                     // We need to remove it.
                     let mut builder =
                         milli::update::Settings::new(index_wtxn, index, indexer_config);
                     builder.reset_primary_key();
-                    builder.execute(
-                        |indexing_step| debug!("update: {:?}", indexing_step),
-                        || must_stop_processing.clone().get(),
-                    )?;
+                    builder.execute(self.indexing_step_callback(), || {
+                        must_stop_processing.clone().get()
+                    })?;
                 }
 
                 Ok(tasks)
@@ -1302,10 +1516,7 @@ impl IndexScheduler {
                 }
 
                 let must_stop_processing = self.must_stop_processing.clone();
-                builder.execute(
-                    |indexing_step| debug!("update: {:?}", indexing_step),
-                    || must_stop_processing.get(),
-                )?;
+                builder.execute(self.indexing_step_callback(), || must_stop_processing.get())?;
 
                 Ok(tasks)
             }
@@ -1374,6 +1585,17 @@ impl IndexScheduler {
         }
     }
 
+    /// Returns the callback given to milli to report its indexing steps.
+    ///
+    /// Every step is logged and stored as the progress of the processing tasks.
+    fn indexing_step_callback(&self) -> impl Fn(UpdateIndexingStep) + Sync {
+        let processing_tasks = self.processing_tasks.clone();
+        move |indexing_step| {
+            debug!("update: {:?}", indexing_step);
+            processing_tasks.write().unwrap().update_progress(indexing_step);
+        }
+    }
+
     /// Delete each given task from all the databases (if it is deleteable).
     ///
     /// Return the number of tasks that were actually deleted.
@@ -1486,9 +1708,14 @@ impl IndexScheduler {
     }
 }
 
-fn delete_document_by_filter(filter: &serde_json::Value, index: Index) -> Result<u64> {
+fn delete_document_by_filter(
+    filter: &serde_json::Value,
+    filter_params: Option<&BTreeMap<String, serde_json::Value>>,
+    index: Index,
+) -> Result<u64> {
     let filter = Filter::from_json(filter)?;
-    Ok(if let Some(filter) = filter {
+    Ok(if let Some(mut filter) = filter {
+        filter.bind_params(filter_params.unwrap_or(&BTreeMap::new()))?;
         let mut wtxn = index.write_txn()?;
 
         let candidates = filter.evaluate(&wtxn, &index)?;
@@ -1502,3 +1729,88 @@ fn delete_document_by_filter(filter: &serde_json::Value, index: Index) -> Result
         0
     })
 }
+
+/// Applies the same edits on all the documents matching the filter, the matching documents
+/// are re-indexed as an edition of their fields identified by their external id.
+fn edit_documents_by_filter(
+    filter: &serde_json::Value,
+    filter_params: Option<&BTreeMap<String, serde_json::Value>>,
+    edits: &serde_json::Map<String, serde_json::Value>,
+    index: &Index,
+    indexer_config: &IndexerConfig,
+    indexing_step_callback: impl Fn(UpdateIndexingStep) + Sync,
+    must_stop_processing: MustStopProcessing,
+) -> Result<u64> {
+    let mut filter = match Filter::from_json(filter)? {
+        Some(filter) => filter,
+        None => return Ok(0),
+    };
+    filter.bind_params(filter_params.unwrap_or(&BTreeMap::new()))?;
+    let mut wtxn = index.write_txn()?;
+
+    let candidates = filter.evaluate(&wtxn, index)?;
+    if candidates.is_empty() {
+        return Ok(0);
+    }
+
+    let file = {
+        // an index containing documents always has a primary key.
+        let primary_key = match index.primary_key(&wtxn)? {
+            Some(primary_key) => primary_key.to_string(),
+            None => return Ok(0),
+        };
+
+        // A nested primary key is not a top-level field of the stored documents,
+        // the external ids are read from their mapping instead.
+        let external_ids: HashMap<u32, String> = index
+            .external_documents_ids(&wtxn)?
+            .to_hash_map()
+            .into_iter()
+            .filter(|(_, docid)| candidates.contains(*docid))
+            .map(|(external_id, docid)| (docid, external_id))
+            .collect();
+
+        let mut operations = patch_to_operations(edits);
+        // the primary key identifies the documents, it can't be edited.
+        operations.remove(&primary_key);
+
+        let file = tempfile::tempfile()?;
+        let mut builder = DocumentsBatchBuilder::new(BufWriter::new(file));
+        for docid in &candidates {
+            let external_id = external_ids.get(&docid).ok_or_else(|| {
+                milli::Error::from(milli::InternalError::DatabaseMissingEntry {
+                    db_name: milli::index::db_name::MAIN,
+                    key: Some(milli::index::main_key::HARD_EXTERNAL_DOCUMENTS_IDS_KEY),
+                })
+            })?;
+
+            let mut document = operations.clone();
+            document.insert(primary_key.clone(), serde_json::Value::String(external_id.clone()));
+            builder.append_json_object(&document)?;
+        }
+        builder.into_inner()?.into_inner().map_err(|e| e.into_error())?
+    };
+
+    let reader =
+        DocumentsBatchReader::from_reader(BufReader::new(file)).map_err(milli::Error::from)?;
+    let config = IndexDocumentsConfig {
+        update_method: IndexDocumentsMethod::EditDocuments,
+        ..Default::default()
+    };
+    let builder = milli::update::IndexDocuments::new(
+        &mut wtxn,
+        index,
+        indexer_config,
+        config,
+        indexing_step_callback,
+        || must_stop_processing.get(),
+    )?;
+    let (builder, user_result) = builder.add_documents(reader)?;
+    let edited_documents = user_result.map_err(milli::Error::from)?;
+    let addition = builder.execute()?;
+    wtxn.commit()?;
+
+    // the edited documents that don't match the schema of the index are not edited.
+    let skipped_documents: usize = addition.invalid_edited_documents.values().map(Vec::len).sum();
+    Ok(edited_documents.saturating_sub(skipped_documents as u64))
+}
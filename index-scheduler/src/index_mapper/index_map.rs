@@ -2,6 +2,7 @@
 const DEFAULT_MAP_SIZE: usize = 10 * 1024 * 1024 * 1024; // 10 GiB
 
 use std::collections::BTreeMap;
+use std::fs;
 use std::path::Path;
 use std::time::Duration;
 
@@ -108,6 +109,39 @@ impl ReopenableIndex {
         Ok(())
     }
 
+    /// Attempts to replace the data file of the index with the one at `data_path`, then reopens the index.
+    ///
+    /// The replacement only happens if the index is still closed with the same generation, so that no other
+    /// thread can have reopened the environment in between.
+    ///
+    /// Returns whether the data file was replaced.
+    ///
+    /// # Status table
+    ///
+    /// | Previous Status | New Status                                   | Return value                 |
+    /// |-----------------|----------------------------------------------|------------------------------|
+    /// | Missing         | Missing                                      | false                        |
+    /// | BeingDeleted    | BeingDeleted                                 | false                        |
+    /// | Closing         | Available or Closing depending on generation | true if the index was opened |
+    /// | Available       | Available                                    | false                        |
+    pub fn replace_and_reopen(
+        self,
+        map: &mut IndexMap,
+        path: &Path,
+        data_path: &Path,
+    ) -> Result<bool> {
+        if let Closing(reopen) = map.get(&self.uuid) {
+            if reopen.generation != self.generation {
+                return Ok(false);
+            }
+            fs::rename(data_path, path.join("data.mdb"))?;
+            map.unavailable.remove(&self.uuid);
+            map.create(&self.uuid, path, None, self.map_size)?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
     /// Attempts to close the index, which may or may not result in the index being closed
     /// (e.g. if another thread already reopened the index again).
     ///
@@ -5,7 +5,7 @@ use std::{fs, thread};
 
 use log::error;
 use meilisearch_types::heed::types::{SerdeJson, Str};
-use meilisearch_types::heed::{Database, Env, RoTxn, RwTxn};
+use meilisearch_types::heed::{CompactionOption, Database, Env, RoTxn, RwTxn};
 use meilisearch_types::milli::update::IndexerConfig;
 use meilisearch_types::milli::{FieldDistribution, Index};
 use serde::{Deserialize, Serialize};
@@ -278,6 +278,83 @@ impl IndexMapper {
         Ok(())
     }
 
+    /// Compacts the environment of the specified index and returns the number of bytes reclaimed on disk.
+    ///
+    /// A compacted copy of the environment is first written next to the original one, then the index
+    /// is closed and its data file atomically replaced by the compacted copy before being reopened.
+    ///
+    /// # Panics
+    ///
+    /// - If the index is kept open by another user for too long, see `IndexMapper::index`.
+    pub fn compact_index(&self, rtxn: &RoTxn, name: &str) -> Result<u64> {
+        let uuid = self
+            .index_mapping
+            .get(rtxn, name)?
+            .ok_or_else(|| Error::IndexNotFound(name.to_string()))?;
+
+        let index_path = self.base_path.join(uuid.to_string());
+        let data_path = index_path.join("data.mdb");
+        let compacted_path = index_path.join("data.mdb.compacted");
+
+        // A previous compaction may have been interrupted before the swap.
+        if compacted_path.exists() {
+            fs::remove_file(&compacted_path)?;
+        }
+
+        let index = self.index(rtxn, name)?;
+        index.copy_to_path(&compacted_path, CompactionOption::Enabled)?;
+        drop(index);
+
+        let size_before = fs::metadata(&data_path)?.len();
+        let size_after = fs::metadata(&compacted_path)?.len();
+
+        let mut tries = 0;
+        // Attempts to close the index and swap its data file in a loop.
+        //
+        // If another thread reopens the index before we could swap the data file, we close it again
+        // and retry in a subsequent loop iteration. We make 100 attempts before giving up, see `IndexMapper::index`.
+        loop {
+            tries += 1;
+            if tries > 100 {
+                panic!("Too many attempts to close index {name} prior to compaction.");
+            }
+
+            // we get the index here to drop the lock before entering the match
+            let index = self.index_map.read().unwrap().get(&uuid);
+
+            match index {
+                Available(_) => {
+                    self.index_map.write().unwrap().close_for_resize(&uuid, 0);
+                    continue;
+                }
+                Closing(reopen) => {
+                    // Avoiding deadlocks: no lock taken while doing this operation.
+                    let reopen = if let Some(reopen) = reopen.wait_timeout(Duration::from_secs(6)) {
+                        reopen
+                    } else {
+                        continue;
+                    };
+                    // take the lock to swap the data file and reopen the environment.
+                    let mut index_map = self.index_map.write().unwrap();
+                    if reopen.replace_and_reopen(&mut index_map, &index_path, &compacted_path)? {
+                        break;
+                    }
+                }
+                BeingDeleted => return Err(Error::IndexNotFound(name.to_string())),
+                Missing => {
+                    let index_map = self.index_map.write().unwrap();
+                    // nobody can open the index while we hold the lock, we can swap the data file safely.
+                    if let Missing = index_map.get(&uuid) {
+                        fs::rename(&compacted_path, &data_path)?;
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(size_before.saturating_sub(size_after))
+    }
+
     /// Return an index, may open it if it wasn't already opened.
     pub fn index(&self, rtxn: &RoTxn, name: &str) -> Result<Index> {
         let uuid = self
@@ -171,15 +171,28 @@ fn snapshot_details(d: &Details) -> String {
         Details::DocumentAdditionOrUpdate {
             received_documents,
             indexed_documents,
+            skipped_documents: None,
+            ..
         } => {
             format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?} }}")
         }
+        Details::DocumentAdditionOrUpdate {
+            received_documents,
+            indexed_documents,
+            skipped_documents: Some(skipped_documents),
+            ..
+        } => {
+            format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?}, skipped_documents: {skipped_documents} }}")
+        }
         Details::SettingsUpdate { settings } => {
             format!("{{ settings: {settings:?} }}")
         }
         Details::IndexInfo { primary_key } => {
             format!("{{ primary_key: {primary_key:?} }}")
         }
+        Details::IndexCompaction { reclaimed_bytes } => {
+            format!("{{ reclaimed_bytes: {reclaimed_bytes:?} }}")
+        }
         Details::DocumentDeletion {
             provided_ids: received_document_ids,
             deleted_documents,
@@ -187,6 +200,9 @@ fn snapshot_details(d: &Details) -> String {
         Details::DocumentDeletionByFilter { original_filter, deleted_documents } => format!(
            "{{ original_filter: {original_filter}, deleted_documents: {deleted_documents:?} }}"
         ),
+        Details::DocumentEditionByFilter { original_filter, edited_documents } => format!(
+           "{{ original_filter: {original_filter}, edited_documents: {edited_documents:?} }}"
+        ),
         Details::ClearAll { deleted_documents } => {
             format!("{{ deleted_documents: {deleted_documents:?} }}")
         },
@@ -31,13 +31,13 @@ mod uuid_codec;
 pub type Result<T> = std::result::Result<T, Error>;
 pub type TaskId = u32;
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::ops::{Bound, RangeBounds};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering::Relaxed;
 use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use dump::{KindDump, TaskDump, UpdateFile};
 pub use error::Error;
@@ -46,7 +46,7 @@ use meilisearch_types::error::ResponseError;
 use meilisearch_types::heed::types::{OwnedType, SerdeBincode, SerdeJson, Str};
 use meilisearch_types::heed::{self, Database, Env, RoTxn, RwTxn};
 use meilisearch_types::milli::documents::DocumentsBatchBuilder;
-use meilisearch_types::milli::update::IndexerConfig;
+use meilisearch_types::milli::update::{IndexerConfig, UpdateIndexingStep};
 use meilisearch_types::milli::{self, CboRoaringBitmapCodec, Index, RoaringBitmapCodec, BEU32};
 use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
 use roaring::RoaringBitmap;
@@ -56,6 +56,7 @@ use time::OffsetDateTime;
 use utils::{filter_out_references_to_newer_tasks, keep_tasks_within_datetimes, map_bound};
 use uuid::Uuid;
 
+use crate::batch::Batch;
 use crate::index_mapper::IndexMapper;
 use crate::utils::{check_index_swap_validity, clamp_to_page_size};
 
@@ -143,23 +144,43 @@ struct ProcessingTasks {
     started_at: OffsetDateTime,
     /// The list of tasks ids that are currently running.
     processing: RoaringBitmap,
+    /// The progress of the indexation, as reported by milli.
+    progress: TaskProgress,
 }
 
 impl ProcessingTasks {
     /// Creates an empty `ProcessingAt` struct.
     fn new() -> ProcessingTasks {
-        ProcessingTasks { started_at: OffsetDateTime::now_utc(), processing: RoaringBitmap::new() }
+        ProcessingTasks {
+            started_at: OffsetDateTime::now_utc(),
+            processing: RoaringBitmap::new(),
+            progress: TaskProgress::default(),
+        }
     }
 
     /// Stores the currently processing tasks, and the date time at which it started.
-    fn start_processing_at(&mut self, started_at: OffsetDateTime, processing: RoaringBitmap) {
+    ///
+    /// The progress of the tasks is split between the indexations the batch goes through.
+    fn start_processing_at(
+        &mut self,
+        started_at: OffsetDateTime,
+        processing: RoaringBitmap,
+        indexations: usize,
+    ) {
         self.started_at = started_at;
         self.processing = processing;
+        self.progress = TaskProgress::new(indexations);
+    }
+
+    /// Stores the last indexing step reported by milli for the currently processing tasks.
+    fn update_progress(&mut self, indexing_step: UpdateIndexingStep) {
+        self.progress.update(indexing_step);
     }
 
     /// Set the processing tasks to an empty list
     fn stop_processing(&mut self) {
         self.processing = RoaringBitmap::new();
+        self.progress = TaskProgress::default();
     }
 
     /// Returns `true` if there, at least, is one task that is currently processing that we must stop.
@@ -168,6 +189,78 @@ impl ProcessingTasks {
     }
 }
 
+/// The progress of the batch currently being processed, made of the steps reported by milli.
+#[derive(Debug, Clone, Default)]
+pub struct TaskProgress {
+    /// The steps milli went through, with the date and time at which each of them started.
+    ///
+    /// The last one is the step currently running, along with its latest reported advancement.
+    pub steps: Vec<(UpdateIndexingStep, OffsetDateTime)>,
+    /// The number of indexations the batch is expected to go through.
+    ///
+    /// A batch can index several times, e.g. a settings update followed by a document
+    /// addition, each indexation goes through the steps again and weighs the same
+    /// share of the percentage.
+    indexations: usize,
+    /// The number of indexations of the batch that are done.
+    finished_indexations: usize,
+    /// The percentage reached when the current indexation started.
+    started_at_percentage: f32,
+}
+
+impl TaskProgress {
+    fn new(indexations: usize) -> TaskProgress {
+        TaskProgress { indexations, ..Default::default() }
+    }
+
+    /// Records the indexing step, starting a new step if it differs from the current one.
+    fn update(&mut self, indexing_step: UpdateIndexingStep) {
+        match self.steps.last_mut() {
+            Some((step, _)) if step.step() == indexing_step.step() => *step = indexing_step,
+            Some((step, _)) if step.step() > indexing_step.step() => {
+                // a new indexation starts where the previous one stopped.
+                self.started_at_percentage = self.percentage().unwrap_or_default();
+                self.finished_indexations += 1;
+                self.steps.push((indexing_step, OffsetDateTime::now_utc()));
+            }
+            _ => self.steps.push((indexing_step, OffsetDateTime::now_utc())),
+        }
+    }
+
+    /// Returns the step currently running, if any was reported.
+    pub fn current_step(&self) -> Option<UpdateIndexingStep> {
+        self.steps.last().map(|(step, _)| *step)
+    }
+
+    /// Returns the percentage of the batch that is done, according to the current step.
+    ///
+    /// The percentage only reaches 100 at the end of the last indexation of the batch and never
+    /// decreases, even when the batch indexes more times than expected.
+    pub fn percentage(&self) -> Option<f32> {
+        use UpdateIndexingStep::*;
+
+        let step = self.current_step()?;
+        let (seen, total) = match step {
+            RemapDocumentAddition { .. } => (0, 0),
+            ComputeIdsAndMergeDocuments { documents_seen, total_documents }
+            | IndexDocuments { documents_seen, total_documents } => {
+                (documents_seen, total_documents)
+            }
+            MergeDataIntoFinalDatabase { databases_seen, total_databases } => {
+                (databases_seen, total_databases)
+            }
+        };
+        let step_ratio = if total == 0 { 0.0 } else { seen.min(total) as f32 / total as f32 };
+        let indexation_ratio = (step.step() as f32 + step_ratio) / step.number_of_steps() as f32;
+        // the unexpected indexations share the last part of the percentage.
+        let indexations = self.indexations.max(1);
+        let finished_indexations = self.finished_indexations.min(indexations - 1);
+        let percentage =
+            (finished_indexations as f32 + indexation_ratio) / indexations as f32 * 100.0;
+        Some(percentage.max(self.started_at_percentage))
+    }
+}
+
 #[derive(Default, Clone, Debug)]
 struct MustStopProcessing(Arc<AtomicBool>);
 
@@ -245,6 +338,9 @@ pub struct IndexSchedulerOptions {
     /// The maximum number of tasks stored in the task queue before starting
     /// to auto schedule task deletions.
     pub max_number_of_tasks: usize,
+    /// The interval at which the indexes are checked for expired documents,
+    /// `None` disables the automatic deletion of the expired documents.
+    pub expired_documents_check_interval: Option<Duration>,
 }
 
 /// Structure which holds meilisearch's indexes and schedules the tasks
@@ -298,6 +394,15 @@ pub struct IndexScheduler {
     /// the finished tasks automatically.
     pub(crate) max_number_of_tasks: usize,
 
+    /// The interval at which the indexes are checked for expired documents.
+    pub(crate) expired_documents_check_interval: Option<Duration>,
+
+    /// When the indexes were last checked for expired documents.
+    pub(crate) last_expired_documents_check: Arc<RwLock<Option<Instant>>>,
+
+    /// The indexes with an expiry attribute, `None` until the indexes are first checked.
+    pub(crate) indexes_with_expiry_field: Arc<RwLock<Option<BTreeSet<String>>>>,
+
     /// The path used to create the dumps.
     pub(crate) dumps_path: PathBuf,
 
@@ -348,6 +453,9 @@ impl IndexScheduler {
             wake_up: self.wake_up.clone(),
             autobatching_enabled: self.autobatching_enabled,
             max_number_of_tasks: self.max_number_of_tasks,
+            expired_documents_check_interval: self.expired_documents_check_interval,
+            last_expired_documents_check: self.last_expired_documents_check.clone(),
+            indexes_with_expiry_field: self.indexes_with_expiry_field.clone(),
             snapshots_path: self.snapshots_path.clone(),
             dumps_path: self.dumps_path.clone(),
             auth_path: self.auth_path.clone(),
@@ -422,6 +530,9 @@ impl IndexScheduler {
             wake_up: Arc::new(SignalEvent::auto(true)),
             autobatching_enabled: options.autobatching_enabled,
             max_number_of_tasks: options.max_number_of_tasks,
+            expired_documents_check_interval: options.expired_documents_check_interval,
+            last_expired_documents_check: Arc::new(RwLock::new(None)),
+            indexes_with_expiry_field: Arc::new(RwLock::new(None)),
             dumps_path: options.dumps_path,
             snapshots_path: options.snapshots_path,
             auth_path: options.auth_path,
@@ -536,7 +647,15 @@ impl IndexScheduler {
                 loop {
                     match run.tick() {
                         Ok(TickOutcome::TickAgain(_)) => (),
-                        Ok(TickOutcome::WaitForSignal) => run.wake_up.wait(),
+                        Ok(TickOutcome::WaitForSignal) => {
+                            // wake up regularly to look for expired documents
+                            match run.expired_documents_check_interval {
+                                Some(interval) => {
+                                    run.wake_up.wait_timeout(interval);
+                                }
+                                None => run.wake_up.wait(),
+                            }
+                        }
                         Err(e) => {
                             log::error!("{}", e);
                             // Wait one second when an irrecoverable error occurs.
@@ -747,6 +866,19 @@ impl IndexScheduler {
         Ok(nbr_index_processing_tasks > 0)
     }
 
+    /// Returns the progress of the given task if it is currently being processed
+    /// and milli already reported some indexing steps.
+    pub fn task_progress(&self, task_id: TaskId) -> Option<TaskProgress> {
+        let processing_tasks = self.processing_tasks.read().unwrap();
+        if processing_tasks.processing.contains(task_id)
+            && !processing_tasks.progress.steps.is_empty()
+        {
+            Some(processing_tasks.progress.clone())
+        } else {
+            None
+        }
+    }
+
     /// Return the task ids matching the query from the user's point of view.
     ///
     /// There are two differences between an internal query and a query executed by
@@ -963,6 +1095,7 @@ impl IndexScheduler {
         }
 
         self.cleanup_task_queue()?;
+        self.register_expired_documents_deletions()?;
 
         let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
         let batch =
@@ -971,6 +1104,11 @@ impl IndexScheduler {
                 None => return Ok(TickOutcome::WaitForSignal),
             };
         let index_uid = batch.index_uid().map(ToOwned::to_owned);
+        // the indexes whose expiry attribute may be changed by the batch
+        let updated_indexes: Vec<String> = match &batch {
+            Batch::IndexSwap { task } => task.indexes().into_iter().map(String::from).collect(),
+            _ => index_uid.iter().cloned().collect(),
+        };
         drop(rtxn);
 
         // 1. store the starting date with the bitmap of processing tasks.
@@ -982,7 +1120,12 @@ impl IndexScheduler {
 
         // We reset the must_stop flag to be sure that we don't stop processing tasks
         self.must_stop_processing.reset();
-        self.processing_tasks.write().unwrap().start_processing_at(started_at, processing_tasks);
+        let indexations = batch.indexations();
+        self.processing_tasks.write().unwrap().start_processing_at(
+            started_at,
+            processing_tasks,
+            indexations,
+        );
 
         #[cfg(test)]
         self.breakpoint(Breakpoint::BatchCreated);
@@ -1027,6 +1170,7 @@ impl IndexScheduler {
                     }
                 }
                 log::info!("A batch of tasks was successfully completed.");
+                self.update_indexes_with_expiry_field(&updated_indexes)?;
             }
             // If we have an abortion error we must stop the tick here and re-schedule tasks.
             Err(Error::Milli(milli::Error::InternalError(
@@ -1149,6 +1293,96 @@ impl IndexScheduler {
         Ok(())
     }
 
+    /// Register a task deleting the expired documents of every index with an expiry attribute.
+    ///
+    /// The indexes are only checked once per `expired_documents_check_interval` and only the
+    /// indexes without any enqueued task are checked, so that the expired documents can't be
+    /// updated between the registration and the processing of the deletion.
+    fn register_expired_documents_deletions(&self) -> Result<()> {
+        let interval = match self.expired_documents_check_interval {
+            Some(interval) => interval,
+            None => return Ok(()),
+        };
+        {
+            let mut last_check = self.last_expired_documents_check.write().unwrap();
+            if last_check.map_or(false, |last_check| last_check.elapsed() < interval) {
+                return Ok(());
+            }
+            *last_check = Some(Instant::now());
+        }
+
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let mut to_register = Vec::new();
+        {
+            let mut indexes = self.indexes_with_expiry_field.write().unwrap();
+            let indexes = match &mut *indexes {
+                Some(indexes) => indexes,
+                None => {
+                    // the indexes are only all opened once, the list is then maintained
+                    // by `update_indexes_with_expiry_field` after every batch.
+                    let uids: Vec<Option<String>> = self.try_for_each_index(|uid, index| {
+                        let rtxn = index.read_txn()?;
+                        Ok(index.expiry_field(&rtxn)?.map(|_| uid.to_owned()))
+                    })?;
+                    indexes.insert(uids.into_iter().flatten().collect())
+                }
+            };
+
+            let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
+            let enqueued = self.get_status(&rtxn, Status::Enqueued)?;
+            for index_uid in indexes.iter() {
+                if !self.index_mapper.exists(&rtxn, index_uid)?
+                    || !self.index_tasks(&rtxn, index_uid)?.is_disjoint(&enqueued)
+                {
+                    continue;
+                }
+                let index = self.index_mapper.index(&rtxn, index_uid)?;
+                let index_rtxn = index.read_txn()?;
+                let documents_ids = index.expired_documents_external_ids(&index_rtxn, now)?;
+                if !documents_ids.is_empty() {
+                    to_register.push((index_uid.clone(), documents_ids));
+                }
+            }
+        }
+
+        for (index_uid, documents_ids) in to_register {
+            log::info!("Registering the deletion of the expired documents of `{index_uid}`.");
+            self.register(KindWithContent::DocumentDeletion { index_uid, documents_ids })?;
+        }
+
+        Ok(())
+    }
+
+    /// Keeps the list of the indexes with an expiry attribute up to date
+    /// after a batch was processed on the given indexes.
+    fn update_indexes_with_expiry_field(&self, index_uids: &[String]) -> Result<()> {
+        let mut indexes = self.indexes_with_expiry_field.write().unwrap();
+        // the list is built when the indexes are first checked
+        let indexes = match &mut *indexes {
+            Some(indexes) => indexes,
+            None => return Ok(()),
+        };
+
+        let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
+        for index_uid in index_uids {
+            let has_expiry_field = if self.index_mapper.exists(&rtxn, index_uid)? {
+                let index = self.index_mapper.index(&rtxn, index_uid)?;
+                let index_rtxn = index.read_txn()?;
+                index.expiry_field(&index_rtxn)?.is_some()
+            } else {
+                false
+            };
+
+            if has_expiry_field {
+                indexes.insert(index_uid.clone());
+            } else {
+                indexes.remove(index_uid);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn index_stats(&self, index_uid: &str) -> Result<IndexStats> {
         let is_indexing = self.is_index_processing(index_uid)?;
         let rtxn = self.read_txn()?;
@@ -1260,13 +1494,27 @@ impl<'a> Dump<'a> {
                     documents_count,
                     allow_index_creation,
                 },
+                KindDump::DocumentEdition { documents_count } => KindWithContent::DocumentEdition {
+                    index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                    content_file: content_uuid.ok_or(Error::CorruptedDump)?,
+                    documents_count,
+                },
                 KindDump::DocumentDeletion { documents_ids } => KindWithContent::DocumentDeletion {
                     documents_ids,
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                 },
-                KindDump::DocumentDeletionByFilter { filter } => {
+                KindDump::DocumentDeletionByFilter { filter, filter_params } => {
                     KindWithContent::DocumentDeletionByFilter {
                         filter_expr: filter,
+                        filter_params,
+                        index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                    }
+                }
+                KindDump::DocumentEditionByFilter { filter, filter_params, edits } => {
+                    KindWithContent::DocumentEditionByFilter {
+                        filter_expr: filter,
+                        filter_params,
+                        edits,
                         index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                     }
                 }
@@ -1292,6 +1540,9 @@ impl<'a> Dump<'a> {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                     primary_key,
                 },
+                KindDump::IndexCompaction => KindWithContent::IndexCompaction {
+                    index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                },
                 KindDump::IndexSwap { swaps } => KindWithContent::IndexSwap { swaps },
                 KindDump::TaskCancelation { query, tasks } => {
                     KindWithContent::TaskCancelation { query, tasks }
@@ -1421,7 +1672,8 @@ mod tests {
     use meilisearch_types::milli::update::IndexDocumentsMethod::{
         ReplaceDocuments, UpdateDocuments,
     };
-    use meilisearch_types::tasks::IndexSwap;
+    use meilisearch_types::settings::{Setting, Settings};
+    use meilisearch_types::tasks::{Details, IndexSwap};
     use meilisearch_types::VERSION_FILE_NAME;
     use tempfile::{NamedTempFile, TempDir};
     use time::Duration;
@@ -1476,6 +1728,7 @@ mod tests {
                 indexer_config,
                 autobatching_enabled: true,
                 max_number_of_tasks: 1_000_000,
+                expired_documents_check_interval: None,
             };
             configuration(&mut options);
 
@@ -3915,4 +4168,216 @@ mod tests {
         snapshot!(json_string!(tasks, { "[].enqueuedAt" => "[date]", "[].startedAt" => "[date]", "[].finishedAt" => "[date]", ".**.original_filter" => "[filter]", ".**.query" => "[query]" }), name: "everything_has_been_processed");
         drop(rtxn);
     }
+
+    #[test]
+    fn delete_expired_documents() {
+        let (index_scheduler, mut handle) =
+            IndexScheduler::test_with_custom_config(vec![], |config| {
+                config.expired_documents_check_interval = Some(std::time::Duration::ZERO);
+            });
+
+        index_scheduler
+            .register(KindWithContent::SettingsUpdate {
+                index_uid: S("doggos"),
+                new_settings: Box::new(Settings {
+                    expiry_attribute: Setting::Set(S("expiresAt")),
+                    ..Default::default()
+                }),
+                is_deletion: false,
+                allow_index_creation: true,
+            })
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        let now = OffsetDateTime::now_utc().unix_timestamp();
+        let content = format!(
+            r#"[
+                {{ "id": 1, "doggo": "jean", "expiresAt": {} }},
+                {{ "id": 2, "doggo": "bob", "expiresAt": {} }},
+                {{ "id": 3, "doggo": "bernard" }},
+                {{ "id": 4, "doggo": "billy", "expiresAt": "2001-01-01T00:00:00Z" }}
+            ]"#,
+            now - 60,
+            now + 3600
+        );
+        let (uuid, mut file) = index_scheduler.create_update_file_with_uuid(0).unwrap();
+        let documents_count = read_json(content.as_bytes(), file.as_file_mut()).unwrap();
+        file.persist().unwrap();
+        index_scheduler
+            .register(KindWithContent::DocumentAdditionOrUpdate {
+                index_uid: S("doggos"),
+                primary_key: Some(S("id")),
+                method: ReplaceDocuments,
+                content_file: uuid,
+                documents_count,
+                allow_index_creation: true,
+            })
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        // the scheduler registers and processes the deletion of the expired document by itself
+        handle.advance_one_successful_batch();
+
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let task = index_scheduler.get_task(&rtxn, 2).unwrap().unwrap();
+        assert_eq!(task.status, Status::Succeeded);
+        assert!(matches!(
+            task.kind,
+            KindWithContent::DocumentDeletion { ref index_uid, ref documents_ids }
+                if index_uid == "doggos" && documents_ids == &[S("1"), S("4")]
+        ));
+        drop(rtxn);
+
+        let index = index_scheduler.index("doggos").unwrap();
+        let rtxn = index.read_txn().unwrap();
+        let external_documents_ids = index.external_documents_ids(&rtxn).unwrap();
+        assert_eq!(external_documents_ids.get("1"), None);
+        assert_eq!(external_documents_ids.get("4"), None);
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 2);
+    }
+
+    #[test]
+    fn task_progress_follows_indexing_steps() {
+        use UpdateIndexingStep::*;
+
+        let mut progress = TaskProgress::new(1);
+        assert_eq!(progress.percentage(), None);
+
+        progress.update(RemapDocumentAddition { documents_seen: 10 });
+        assert_eq!(progress.percentage(), Some(0.0));
+
+        progress.update(IndexDocuments { documents_seen: 50, total_documents: 100 });
+        assert_eq!(progress.percentage(), Some(62.5));
+        progress.update(IndexDocuments { documents_seen: 100, total_documents: 100 });
+        assert_eq!(progress.percentage(), Some(75.0));
+        // the same step reported twice is only recorded once
+        assert_eq!(progress.steps.len(), 2);
+
+        progress.update(MergeDataIntoFinalDatabase { databases_seen: 5, total_databases: 10 });
+        assert_eq!(progress.percentage(), Some(87.5));
+        assert_eq!(progress.steps.len(), 3);
+        assert!(matches!(progress.current_step(), Some(MergeDataIntoFinalDatabase { .. })));
+
+        // an unexpected indexation never makes the percentage decrease
+        progress.update(RemapDocumentAddition { documents_seen: 10 });
+        assert_eq!(progress.percentage(), Some(87.5));
+        assert_eq!(progress.steps.len(), 4);
+        progress.update(MergeDataIntoFinalDatabase { databases_seen: 10, total_databases: 10 });
+        assert_eq!(progress.percentage(), Some(100.0));
+    }
+
+    #[test]
+    fn task_progress_splits_the_indexations_of_a_batch() {
+        use UpdateIndexingStep::*;
+
+        // e.g. a settings update followed by a document addition
+        let mut progress = TaskProgress::new(2);
+        progress.update(IndexDocuments { documents_seen: 50, total_documents: 100 });
+        assert_eq!(progress.percentage(), Some(31.25));
+        progress.update(MergeDataIntoFinalDatabase { databases_seen: 10, total_databases: 10 });
+        // the first indexation is done but not the batch
+        assert_eq!(progress.percentage(), Some(50.0));
+
+        progress.update(RemapDocumentAddition { documents_seen: 10 });
+        assert_eq!(progress.percentage(), Some(50.0));
+        progress.update(IndexDocuments { documents_seen: 100, total_documents: 100 });
+        assert_eq!(progress.percentage(), Some(87.5));
+        progress.update(MergeDataIntoFinalDatabase { databases_seen: 10, total_databases: 10 });
+        assert_eq!(progress.percentage(), Some(100.0));
+    }
+
+    #[test]
+    fn task_progress_is_only_reported_while_processing() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let (uuid, mut file) = index_scheduler.create_update_file_with_uuid(0).unwrap();
+        let documents_count =
+            read_json(r#"[{ "id": 1, "doggo": "bob" }]"#.as_bytes(), file.as_file_mut()).unwrap();
+        file.persist().unwrap();
+        index_scheduler
+            .register(KindWithContent::DocumentAdditionOrUpdate {
+                index_uid: S("doggos"),
+                primary_key: Some(S("id")),
+                method: ReplaceDocuments,
+                content_file: uuid,
+                documents_count,
+                allow_index_creation: true,
+            })
+            .unwrap();
+        assert!(index_scheduler.task_progress(0).is_none());
+
+        handle.advance_till([Start, BatchCreated, InsideProcessBatch]);
+        index_scheduler.processing_tasks.write().unwrap().update_progress(
+            UpdateIndexingStep::IndexDocuments { documents_seen: 0, total_documents: 1 },
+        );
+        let progress = index_scheduler.task_progress(0).unwrap();
+        assert_eq!(progress.percentage(), Some(50.0));
+        assert!(index_scheduler.task_progress(1).is_none());
+
+        // the process_batch function can call itself recursively
+        while handle.advance() != ProcessBatchSucceeded {}
+        handle.advance_till([AfterProcessing]);
+        assert!(index_scheduler.task_progress(0).is_none());
+    }
+
+    #[test]
+    fn compact_index_purges_soft_deleted_documents() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let content = r#"[
+            { "id": 1, "doggo": "jean" },
+            { "id": 2, "doggo": "bob" },
+            { "id": 3, "doggo": "bernard" }
+        ]"#;
+        let (uuid, mut file) = index_scheduler.create_update_file_with_uuid(0).unwrap();
+        let documents_count = read_json(content.as_bytes(), file.as_file_mut()).unwrap();
+        file.persist().unwrap();
+        index_scheduler
+            .register(KindWithContent::DocumentAdditionOrUpdate {
+                index_uid: S("doggos"),
+                primary_key: Some(S("id")),
+                method: ReplaceDocuments,
+                content_file: uuid,
+                documents_count,
+                allow_index_creation: true,
+            })
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        index_scheduler
+            .register(KindWithContent::DocumentDeletion {
+                index_uid: S("doggos"),
+                documents_ids: vec![S("1")],
+            })
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        let index = index_scheduler.index("doggos").unwrap();
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.soft_deleted_documents_ids(&rtxn).unwrap().len(), 1);
+        drop(rtxn);
+        drop(index);
+
+        index_scheduler
+            .register(KindWithContent::IndexCompaction { index_uid: S("doggos") })
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let task = index_scheduler.get_task(&rtxn, 2).unwrap().unwrap();
+        assert_eq!(task.status, Status::Succeeded);
+        assert!(matches!(
+            task.details,
+            Some(Details::IndexCompaction { reclaimed_bytes: Some(_) })
+        ));
+        drop(rtxn);
+
+        let index = index_scheduler.index("doggos").unwrap();
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.soft_deleted_documents_ids(&rtxn).unwrap().is_empty());
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 2);
+        let external_documents_ids = index.external_documents_ids(&rtxn).unwrap();
+        assert_eq!(external_documents_ids.get("1"), None);
+        assert!(external_documents_ids.get("2").is_some());
+    }
 }
@@ -238,13 +238,16 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
     let mut index_uids = vec![];
     match &mut task.kind {
         K::DocumentAdditionOrUpdate { index_uid, .. } => index_uids.push(index_uid),
+        K::DocumentEdition { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentDeletion { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentDeletionByFilter { index_uid, .. } => index_uids.push(index_uid),
+        K::DocumentEditionByFilter { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentClear { index_uid } => index_uids.push(index_uid),
         K::SettingsUpdate { index_uid, .. } => index_uids.push(index_uid),
         K::IndexDeletion { index_uid } => index_uids.push(index_uid),
         K::IndexCreation { index_uid, .. } => index_uids.push(index_uid),
         K::IndexUpdate { index_uid, .. } => index_uids.push(index_uid),
+        K::IndexCompaction { index_uid } => index_uids.push(index_uid),
         K::IndexSwap { swaps } => {
             for IndexSwap { indexes: (lhs, rhs) } in swaps.iter_mut() {
                 if lhs == swap.0 || lhs == swap.1 {
@@ -402,8 +405,15 @@ impl IndexScheduler {
                             assert_eq!(&sw1, sw2);
                         }
                     }
-                    Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
-                        assert_eq!(kind.as_kind(), Kind::DocumentAdditionOrUpdate);
+                    Details::DocumentAdditionOrUpdate {
+                        received_documents,
+                        indexed_documents,
+                        ..
+                    } => {
+                        assert!(matches!(
+                            kind.as_kind(),
+                            Kind::DocumentAdditionOrUpdate | Kind::DocumentEdition
+                        ));
                         match indexed_documents {
                             Some(indexed_documents) => {
                                 assert!(matches!(
@@ -436,6 +446,18 @@ impl IndexScheduler {
                         }
                         _ => panic!(),
                     },
+                    Details::IndexCompaction { reclaimed_bytes } => {
+                        assert_eq!(kind.as_kind(), Kind::IndexCompaction);
+                        match status {
+                            Status::Enqueued | Status::Processing => {
+                                assert!(reclaimed_bytes.is_none())
+                            }
+                            Status::Succeeded => assert!(reclaimed_bytes.is_some()),
+                            Status::Failed | Status::Canceled => {
+                                assert_eq!(reclaimed_bytes, Some(0))
+                            }
+                        }
+                    }
                     Details::DocumentDeletion {
                         provided_ids: received_document_ids,
                         deleted_documents,
@@ -470,6 +492,7 @@ impl IndexScheduler {
                         let (index_uid, _) = if let KindWithContent::DocumentDeletionByFilter {
                             ref index_uid,
                             ref filter_expr,
+                            ..
                         } = kind
                         {
                             (index_uid, filter_expr)
@@ -488,6 +511,29 @@ impl IndexScheduler {
                             }
                         }
                     }
+                    Details::DocumentEditionByFilter { edited_documents, original_filter: _ } => {
+                        assert_eq!(kind.as_kind(), Kind::DocumentEdition);
+                        let index_uid = if let KindWithContent::DocumentEditionByFilter {
+                            ref index_uid,
+                            ..
+                        } = kind
+                        {
+                            index_uid
+                        } else {
+                            unreachable!()
+                        };
+                        assert_eq!(&task_index_uid.unwrap(), index_uid);
+
+                        match status {
+                            Status::Enqueued | Status::Processing => (),
+                            Status::Succeeded => {
+                                assert!(edited_documents.is_some());
+                            }
+                            Status::Failed | Status::Canceled => {
+                                assert!(edited_documents == Some(0));
+                            }
+                        }
+                    }
                     Details::ClearAll { deleted_documents } => {
                         assert!(matches!(
                             kind.as_kind(),
@@ -538,7 +584,9 @@ impl IndexScheduler {
             assert!(self.get_status(&rtxn, status).unwrap().contains(uid));
             assert!(self.get_kind(&rtxn, kind.as_kind()).unwrap().contains(uid));
 
-            if let KindWithContent::DocumentAdditionOrUpdate { content_file, .. } = kind {
+            if let KindWithContent::DocumentAdditionOrUpdate { content_file, .. }
+            | KindWithContent::DocumentEdition { content_file, .. } = kind
+            {
                 match status {
                     Status::Enqueued | Status::Processing => {
                         assert!(self
@@ -9,7 +9,7 @@ use std::str::FromStr;
 use deserr::{DeserializeError, Deserr, ErrorKind, MergeWithError, ValuePointerRef};
 use fst::IntoStreamer;
 use milli::update::Setting;
-use milli::{Criterion, CriterionError, Index, DEFAULT_VALUES_PER_FACET};
+use milli::{Criterion, CriterionError, Index, OrderBy, DEFAULT_VALUES_PER_FACET};
 use serde::{Deserialize, Serialize, Serializer};
 
 use crate::deserr::DeserrJsonError;
@@ -93,6 +93,9 @@ pub struct TypoSettings {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
     pub disable_on_attributes: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub disable_on_numbers: Setting<bool>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
@@ -102,6 +105,43 @@ pub struct FacetingSettings {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
     pub max_values_per_facet: Setting<usize>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub sort_facet_values_by: Setting<BTreeMap<String, FacetValuesSort>>,
+    /// The separator of the levels of the hierarchical facets, by facet name.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub hierarchical_facets: Setting<BTreeMap<String, String>>,
+}
+
+/// The order of the values of a facet in the facet distribution.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum FacetValuesSort {
+    /// The facet values are sorted in alphabetical order.
+    #[default]
+    Alpha,
+    /// The facet values are sorted by decreasing number of documents.
+    Count,
+}
+
+impl From<FacetValuesSort> for OrderBy {
+    fn from(sort: FacetValuesSort) -> Self {
+        match sort {
+            FacetValuesSort::Alpha => OrderBy::Lexicographic,
+            FacetValuesSort::Count => OrderBy::Count,
+        }
+    }
+}
+
+impl From<OrderBy> for FacetValuesSort {
+    fn from(order_by: OrderBy) -> Self {
+        match order_by {
+            OrderBy::Lexicographic => FacetValuesSort::Alpha,
+            OrderBy::Count => FacetValuesSort::Count,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
@@ -127,6 +167,101 @@ impl MergeWithError<milli::CriterionError> for DeserrJsonError<InvalidSettingsRa
     }
 }
 
+/// The rules applied to the documents before they are indexed. The version is bumped
+/// automatically when the rules change, unless it is given, e.g. by a dump.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidSettingsIngestionRules>, rename_all = camelCase, deny_unknown_fields)]
+pub struct IngestionRulesView {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub version: Option<u32>,
+    #[serde(default)]
+    #[deserr(default)]
+    pub rules: Vec<milli::IngestionRule>,
+}
+
+/// The types and the required fields the documents sent to the index must match.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidSettingsDocumentSchema>, rename_all = camelCase, deny_unknown_fields)]
+pub struct DocumentSchemaView {
+    #[serde(default)]
+    #[deserr(default)]
+    pub fields: BTreeMap<String, FieldTypeView>,
+    #[serde(default)]
+    #[deserr(default)]
+    pub required: BTreeSet<String>,
+    #[serde(default)]
+    #[deserr(default)]
+    pub on_invalid_document: InvalidDocumentPolicyView,
+}
+
+/// The type of a field of the document schema, e.g. `number` or `array<string>`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(transparent)]
+#[deserr(try_from(&String) = FromStr::from_str -> milli::InvalidFieldType)]
+pub struct FieldTypeView(pub milli::FieldType);
+
+impl FromStr for FieldTypeView {
+    type Err = milli::InvalidFieldType;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(FieldTypeView)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(error = DeserrJsonError<InvalidSettingsDocumentSchema>, rename_all = camelCase)]
+pub enum InvalidDocumentPolicyView {
+    #[default]
+    Reject,
+    Skip,
+}
+
+impl MergeWithError<milli::InvalidFieldType> for DeserrJsonError<InvalidSettingsDocumentSchema> {
+    fn merge(
+        _self_: Option<Self>,
+        other: milli::InvalidFieldType,
+        merge_location: ValuePointerRef,
+    ) -> ControlFlow<Self, Self> {
+        Self::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected { msg: other.to_string() },
+            merge_location,
+        )
+    }
+}
+
+impl From<DocumentSchemaView> for milli::DocumentSchema {
+    fn from(view: DocumentSchemaView) -> Self {
+        let DocumentSchemaView { fields, required, on_invalid_document } = view;
+        milli::DocumentSchema {
+            fields: fields.into_iter().map(|(name, FieldTypeView(ty))| (name, ty)).collect(),
+            required,
+            on_invalid_document: match on_invalid_document {
+                InvalidDocumentPolicyView::Reject => milli::InvalidDocumentPolicy::Reject,
+                InvalidDocumentPolicyView::Skip => milli::InvalidDocumentPolicy::Skip,
+            },
+        }
+    }
+}
+
+impl From<milli::DocumentSchema> for DocumentSchemaView {
+    fn from(schema: milli::DocumentSchema) -> Self {
+        let milli::DocumentSchema { fields, required, on_invalid_document } = schema;
+        DocumentSchemaView {
+            fields: fields.into_iter().map(|(name, ty)| (name, FieldTypeView(ty))).collect(),
+            required,
+            on_invalid_document: match on_invalid_document {
+                milli::InvalidDocumentPolicy::Reject => InvalidDocumentPolicyView::Reject,
+                milli::InvalidDocumentPolicy::Skip => InvalidDocumentPolicyView::Skip,
+            },
+        }
+    }
+}
+
 /// Holds all the settings for an index. `T` can either be `Checked` if they represents settings
 /// whose validity is guaranteed, or `Unchecked` if they need to be validated. In the later case, a
 /// call to `check` will return a `Settings<Checked>` from a `Settings<Unchecked>`.
@@ -167,12 +302,45 @@ pub struct Settings<T> {
     #[deserr(default, error = DeserrJsonError<InvalidSettingsStopWords>)]
     pub stop_words: Setting<BTreeSet<String>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsNonSeparatorTokens>)]
+    pub non_separator_tokens: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsSeparatorTokens>)]
+    pub separator_tokens: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsDictionary>)]
+    pub dictionary: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsLocalizedAttributes>)]
+    pub localized_attributes: Setting<BTreeMap<String, Vec<String>>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsStemming>)]
+    pub stemming: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsInfixAttributes>)]
+    pub infix_attributes: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsPhoneticAttributes>)]
+    pub phonetic_attributes: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsSynonyms>)]
     pub synonyms: Setting<BTreeMap<String, Vec<String>>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsDistinctAttribute>)]
     pub distinct_attribute: Setting<String>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsVersionAttribute>)]
+    pub version_attribute: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsExpiryAttribute>)]
+    pub expiry_attribute: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsIngestionRules>)]
+    pub ingestion_rules: Setting<IngestionRulesView>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsDocumentSchema>)]
+    pub document_schema: Setting<DocumentSchemaView>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsTypoTolerance>)]
     pub typo_tolerance: Setting<TypoSettings>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
@@ -196,8 +364,19 @@ impl Settings<Checked> {
             sortable_attributes: Setting::Reset,
             ranking_rules: Setting::Reset,
             stop_words: Setting::Reset,
+            non_separator_tokens: Setting::Reset,
+            separator_tokens: Setting::Reset,
+            dictionary: Setting::Reset,
+            localized_attributes: Setting::Reset,
+            stemming: Setting::Reset,
+            infix_attributes: Setting::Reset,
+            phonetic_attributes: Setting::Reset,
             synonyms: Setting::Reset,
             distinct_attribute: Setting::Reset,
+            version_attribute: Setting::Reset,
+            expiry_attribute: Setting::Reset,
+            ingestion_rules: Setting::Reset,
+            document_schema: Setting::Reset,
             typo_tolerance: Setting::Reset,
             faceting: Setting::Reset,
             pagination: Setting::Reset,
@@ -213,8 +392,19 @@ impl Settings<Checked> {
             sortable_attributes,
             ranking_rules,
             stop_words,
+            non_separator_tokens,
+            separator_tokens,
+            dictionary,
+            localized_attributes,
+            stemming,
+            infix_attributes,
+            phonetic_attributes,
             synonyms,
             distinct_attribute,
+            version_attribute,
+            expiry_attribute,
+            ingestion_rules,
+            document_schema,
             typo_tolerance,
             faceting,
             pagination,
@@ -228,8 +418,19 @@ impl Settings<Checked> {
             sortable_attributes,
             ranking_rules,
             stop_words,
+            non_separator_tokens,
+            separator_tokens,
+            dictionary,
+            localized_attributes,
+            stemming,
+            infix_attributes,
+            phonetic_attributes,
             synonyms,
             distinct_attribute,
+            version_attribute,
+            expiry_attribute,
+            ingestion_rules,
+            document_schema,
             typo_tolerance,
             faceting,
             pagination,
@@ -269,8 +470,19 @@ impl Settings<Unchecked> {
             sortable_attributes: self.sortable_attributes,
             ranking_rules: self.ranking_rules,
             stop_words: self.stop_words,
+            non_separator_tokens: self.non_separator_tokens,
+            separator_tokens: self.separator_tokens,
+            dictionary: self.dictionary,
+            localized_attributes: self.localized_attributes,
+            stemming: self.stemming,
+            infix_attributes: self.infix_attributes,
+            phonetic_attributes: self.phonetic_attributes,
             synonyms: self.synonyms,
             distinct_attribute: self.distinct_attribute,
+            version_attribute: self.version_attribute,
+            expiry_attribute: self.expiry_attribute,
+            ingestion_rules: self.ingestion_rules,
+            document_schema: self.document_schema,
             typo_tolerance: self.typo_tolerance,
             faceting: self.faceting,
             pagination: self.pagination,
@@ -331,6 +543,54 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match settings.non_separator_tokens {
+        Setting::Set(ref non_separator_tokens) => {
+            builder.set_non_separator_tokens(non_separator_tokens.clone())
+        }
+        Setting::Reset => builder.reset_non_separator_tokens(),
+        Setting::NotSet => (),
+    }
+
+    match settings.separator_tokens {
+        Setting::Set(ref separator_tokens) => {
+            builder.set_separator_tokens(separator_tokens.clone())
+        }
+        Setting::Reset => builder.reset_separator_tokens(),
+        Setting::NotSet => (),
+    }
+
+    match settings.dictionary {
+        Setting::Set(ref dictionary) => builder.set_dictionary(dictionary.clone()),
+        Setting::Reset => builder.reset_dictionary(),
+        Setting::NotSet => (),
+    }
+
+    match settings.localized_attributes {
+        Setting::Set(ref localized_attributes) => {
+            builder.set_localized_attributes(localized_attributes.clone())
+        }
+        Setting::Reset => builder.reset_localized_attributes(),
+        Setting::NotSet => (),
+    }
+
+    match settings.stemming {
+        Setting::Set(ref stemming) => builder.set_stemming(stemming.clone()),
+        Setting::Reset => builder.reset_stemming(),
+        Setting::NotSet => (),
+    }
+
+    match settings.infix_attributes {
+        Setting::Set(ref attrs) => builder.set_infix_attributes(attrs.iter().cloned().collect()),
+        Setting::Reset => builder.reset_infix_attributes(),
+        Setting::NotSet => (),
+    }
+
+    match settings.phonetic_attributes {
+        Setting::Set(ref attrs) => builder.set_phonetic_attributes(attrs.iter().cloned().collect()),
+        Setting::Reset => builder.reset_phonetic_attributes(),
+        Setting::NotSet => (),
+    }
+
     match settings.synonyms {
         Setting::Set(ref synonyms) => builder.set_synonyms(synonyms.clone().into_iter().collect()),
         Setting::Reset => builder.reset_synonyms(),
@@ -343,6 +603,35 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match settings.version_attribute {
+        Setting::Set(ref attr) => builder.set_version_field(attr.clone()),
+        Setting::Reset => builder.reset_version_field(),
+        Setting::NotSet => (),
+    }
+
+    match settings.expiry_attribute {
+        Setting::Set(ref attr) => builder.set_expiry_field(attr.clone()),
+        Setting::Reset => builder.reset_expiry_field(),
+        Setting::NotSet => (),
+    }
+
+    match settings.ingestion_rules {
+        Setting::Set(ref value) => {
+            builder.set_ingestion_rules(value.rules.clone());
+            if let Some(version) = value.version {
+                builder.set_ingestion_rules_version(version);
+            }
+        }
+        Setting::Reset => builder.reset_ingestion_rules(),
+        Setting::NotSet => (),
+    }
+
+    match settings.document_schema {
+        Setting::Set(ref schema) => builder.set_document_schema(schema.clone().into()),
+        Setting::Reset => builder.reset_document_schema(),
+        Setting::NotSet => (),
+    }
+
     match settings.typo_tolerance {
         Setting::Set(ref value) => {
             match value.enabled {
@@ -386,6 +675,12 @@ pub fn apply_settings_to_builder(
                 Setting::Reset => builder.reset_exact_attributes(),
                 Setting::NotSet => (),
             }
+
+            match value.disable_on_numbers {
+                Setting::Set(val) => builder.set_disable_typos_on_numbers(val),
+                Setting::Reset => builder.reset_disable_typos_on_numbers(),
+                Setting::NotSet => (),
+            }
         }
         Setting::Reset => {
             // all typo settings need to be reset here.
@@ -394,17 +689,36 @@ pub fn apply_settings_to_builder(
             builder.reset_min_word_len_two_typos();
             builder.reset_exact_words();
             builder.reset_exact_attributes();
+            builder.reset_disable_typos_on_numbers();
         }
         Setting::NotSet => (),
     }
 
     match settings.faceting {
-        Setting::Set(ref value) => match value.max_values_per_facet {
-            Setting::Set(val) => builder.set_max_values_per_facet(val),
-            Setting::Reset => builder.reset_max_values_per_facet(),
-            Setting::NotSet => (),
-        },
-        Setting::Reset => builder.reset_max_values_per_facet(),
+        Setting::Set(ref value) => {
+            match value.max_values_per_facet {
+                Setting::Set(val) => builder.set_max_values_per_facet(val),
+                Setting::Reset => builder.reset_max_values_per_facet(),
+                Setting::NotSet => (),
+            }
+            match value.sort_facet_values_by {
+                Setting::Set(ref val) => builder.set_sort_facet_values_by(
+                    val.iter().map(|(name, sort)| (name.clone(), (*sort).into())).collect(),
+                ),
+                Setting::Reset => builder.reset_sort_facet_values_by(),
+                Setting::NotSet => (),
+            }
+            match value.hierarchical_facets {
+                Setting::Set(ref val) => builder.set_hierarchical_facets(val.clone()),
+                Setting::Reset => builder.reset_hierarchical_facets(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_max_values_per_facet();
+            builder.reset_sort_facet_values_by();
+            builder.reset_hierarchical_facets();
+        }
         Setting::NotSet => (),
     }
 
@@ -443,7 +757,19 @@ pub fn settings(
         })
         .transpose()?
         .unwrap_or_default();
+    let non_separator_tokens = index.non_separator_tokens(rtxn)?.unwrap_or_default();
+    let separator_tokens = index.separator_tokens(rtxn)?.unwrap_or_default();
+    let dictionary = index.dictionary(rtxn)?.unwrap_or_default();
+    let localized_attributes = index.localized_attributes(rtxn)?;
+    let stemming = index.stemming(rtxn)?;
+    let infix_attributes = index.infix_attributes(rtxn)?.into_iter().map(String::from).collect();
+    let phonetic_attributes =
+        index.phonetic_attributes(rtxn)?.into_iter().map(String::from).collect();
     let distinct_field = index.distinct_field(rtxn)?.map(String::from);
+    let version_field = index.version_field(rtxn)?.map(String::from);
+    let expiry_field = index.expiry_field(rtxn)?.map(String::from);
+    let ingestion_rules = index.ingestion_rules(rtxn)?;
+    let document_schema = index.document_schema(rtxn)?;
 
     // in milli each word in the synonyms map were split on their separator. Since we lost
     // this information we are going to put space between words.
@@ -470,12 +796,22 @@ pub fn settings(
         min_word_size_for_typos: Setting::Set(min_typo_word_len),
         disable_on_words: Setting::Set(disabled_words),
         disable_on_attributes: Setting::Set(disabled_attributes),
+        disable_on_numbers: Setting::Set(index.disable_typos_on_numbers(rtxn)?),
     };
 
+    let mut sort_facet_values_by: BTreeMap<String, FacetValuesSort> = index
+        .sort_facet_values_by(rtxn)?
+        .into_iter()
+        .map(|(name, order_by)| (name, order_by.into()))
+        .collect();
+    sort_facet_values_by.entry("*".to_string()).or_default();
+
     let faceting = FacetingSettings {
         max_values_per_facet: Setting::Set(
             index.max_values_per_facet(rtxn)?.unwrap_or(DEFAULT_VALUES_PER_FACET),
         ),
+        sort_facet_values_by: Setting::Set(sort_facet_values_by),
+        hierarchical_facets: Setting::Set(index.hierarchical_facets(rtxn)?),
     };
 
     let pagination = PaginationSettings {
@@ -497,10 +833,30 @@ pub fn settings(
         sortable_attributes: Setting::Set(sortable_attributes),
         ranking_rules: Setting::Set(criteria.iter().map(|c| c.clone().into()).collect()),
         stop_words: Setting::Set(stop_words),
+        non_separator_tokens: Setting::Set(non_separator_tokens),
+        separator_tokens: Setting::Set(separator_tokens),
+        dictionary: Setting::Set(dictionary),
+        localized_attributes: Setting::Set(localized_attributes),
+        stemming: Setting::Set(stemming),
+        infix_attributes: Setting::Set(infix_attributes),
+        phonetic_attributes: Setting::Set(phonetic_attributes),
         distinct_attribute: match distinct_field {
             Some(field) => Setting::Set(field),
             None => Setting::Reset,
         },
+        version_attribute: match version_field {
+            Some(field) => Setting::Set(field),
+            None => Setting::Reset,
+        },
+        expiry_attribute: match expiry_field {
+            Some(field) => Setting::Set(field),
+            None => Setting::Reset,
+        },
+        ingestion_rules: Setting::Set(IngestionRulesView {
+            version: Some(ingestion_rules.version),
+            rules: ingestion_rules.rules,
+        }),
+        document_schema: Setting::Set(document_schema.into()),
         synonyms: Setting::Set(synonyms),
         typo_tolerance: Setting::Set(typo_tolerance),
         faceting: Setting::Set(faceting),
@@ -619,8 +975,19 @@ pub(crate) mod test {
             sortable_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
+            non_separator_tokens: Setting::NotSet,
+            separator_tokens: Setting::NotSet,
+            dictionary: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            stemming: Setting::NotSet,
+            infix_attributes: Setting::NotSet,
+            phonetic_attributes: Setting::NotSet,
             synonyms: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
+            version_attribute: Setting::NotSet,
+            expiry_attribute: Setting::NotSet,
+            ingestion_rules: Setting::NotSet,
+            document_schema: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
@@ -640,8 +1007,19 @@ pub(crate) mod test {
             sortable_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
+            non_separator_tokens: Setting::NotSet,
+            separator_tokens: Setting::NotSet,
+            dictionary: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            stemming: Setting::NotSet,
+            infix_attributes: Setting::NotSet,
+            phonetic_attributes: Setting::NotSet,
             synonyms: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
+            version_attribute: Setting::NotSet,
+            expiry_attribute: Setting::NotSet,
+            ingestion_rules: Setting::NotSet,
+            document_schema: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
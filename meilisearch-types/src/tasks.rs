@@ -1,10 +1,11 @@
 use core::fmt;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::{Display, Write};
 use std::str::FromStr;
 
 use enum_iterator::Sequence;
 use milli::update::IndexDocumentsMethod;
+use milli::InvalidDocument;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize, Serializer};
 use time::{Duration, OffsetDateTime};
@@ -15,6 +16,10 @@ use crate::keys::Key;
 use crate::settings::{Settings, Unchecked};
 use crate::InstanceUid;
 
+/// The maximum number of invalid documents listed in the details of a task,
+/// the other ones are only counted in the skipped documents.
+pub const MAX_REPORTED_INVALID_DOCUMENTS: usize = 100;
+
 pub type TaskId = u32;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -48,12 +53,15 @@ impl Task {
             | TaskDeletion { .. }
             | IndexSwap { .. } => None,
             DocumentAdditionOrUpdate { index_uid, .. }
+            | DocumentEdition { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
             | DocumentDeletionByFilter { index_uid, .. }
+            | DocumentEditionByFilter { index_uid, .. }
             | DocumentClear { index_uid }
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
+            | IndexCompaction { index_uid }
             | IndexDeletion { index_uid } => Some(index_uid),
         }
     }
@@ -66,14 +74,17 @@ impl Task {
     /// Return the content-uuid if there is one
     pub fn content_uuid(&self) -> Option<Uuid> {
         match self.kind {
-            KindWithContent::DocumentAdditionOrUpdate { content_file, .. } => Some(content_file),
+            KindWithContent::DocumentAdditionOrUpdate { content_file, .. }
+            | KindWithContent::DocumentEdition { content_file, .. } => Some(content_file),
             KindWithContent::DocumentDeletion { .. }
             | KindWithContent::DocumentDeletionByFilter { .. }
+            | KindWithContent::DocumentEditionByFilter { .. }
             | KindWithContent::DocumentClear { .. }
             | KindWithContent::SettingsUpdate { .. }
             | KindWithContent::IndexDeletion { .. }
             | KindWithContent::IndexCreation { .. }
             | KindWithContent::IndexUpdate { .. }
+            | KindWithContent::IndexCompaction { .. }
             | KindWithContent::IndexSwap { .. }
             | KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
@@ -94,6 +105,11 @@ pub enum KindWithContent {
         documents_count: u64,
         allow_index_creation: bool,
     },
+    DocumentEdition {
+        index_uid: String,
+        content_file: Uuid,
+        documents_count: u64,
+    },
     DocumentDeletion {
         index_uid: String,
         documents_ids: Vec<String>,
@@ -101,6 +117,15 @@ pub enum KindWithContent {
     DocumentDeletionByFilter {
         index_uid: String,
         filter_expr: serde_json::Value,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        filter_params: Option<BTreeMap<String, serde_json::Value>>,
+    },
+    DocumentEditionByFilter {
+        index_uid: String,
+        filter_expr: serde_json::Value,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        filter_params: Option<BTreeMap<String, serde_json::Value>>,
+        edits: serde_json::Map<String, serde_json::Value>,
     },
     DocumentClear {
         index_uid: String,
@@ -122,6 +147,9 @@ pub enum KindWithContent {
         index_uid: String,
         primary_key: Option<String>,
     },
+    IndexCompaction {
+        index_uid: String,
+    },
     IndexSwap {
         swaps: Vec<IndexSwap>,
     },
@@ -150,13 +178,16 @@ impl KindWithContent {
     pub fn as_kind(&self) -> Kind {
         match self {
             KindWithContent::DocumentAdditionOrUpdate { .. } => Kind::DocumentAdditionOrUpdate,
+            KindWithContent::DocumentEdition { .. } => Kind::DocumentEdition,
             KindWithContent::DocumentDeletion { .. } => Kind::DocumentDeletion,
             KindWithContent::DocumentDeletionByFilter { .. } => Kind::DocumentDeletion,
+            KindWithContent::DocumentEditionByFilter { .. } => Kind::DocumentEdition,
             KindWithContent::DocumentClear { .. } => Kind::DocumentDeletion,
             KindWithContent::SettingsUpdate { .. } => Kind::SettingsUpdate,
             KindWithContent::IndexCreation { .. } => Kind::IndexCreation,
             KindWithContent::IndexDeletion { .. } => Kind::IndexDeletion,
             KindWithContent::IndexUpdate { .. } => Kind::IndexUpdate,
+            KindWithContent::IndexCompaction { .. } => Kind::IndexCompaction,
             KindWithContent::IndexSwap { .. } => Kind::IndexSwap,
             KindWithContent::TaskCancelation { .. } => Kind::TaskCancelation,
             KindWithContent::TaskDeletion { .. } => Kind::TaskDeletion,
@@ -174,12 +205,15 @@ impl KindWithContent {
             | TaskCancelation { .. }
             | TaskDeletion { .. } => vec![],
             DocumentAdditionOrUpdate { index_uid, .. }
+            | DocumentEdition { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
             | DocumentDeletionByFilter { index_uid, .. }
+            | DocumentEditionByFilter { index_uid, .. }
             | DocumentClear { index_uid }
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
+            | IndexCompaction { index_uid }
             | IndexDeletion { index_uid } => vec![index_uid],
             IndexSwap { swaps } => {
                 let mut indexes = HashSet::<&str>::default();
@@ -196,10 +230,13 @@ impl KindWithContent {
     /// `None` if it cannot be generated.
     pub fn default_details(&self) -> Option<Details> {
         match self {
-            KindWithContent::DocumentAdditionOrUpdate { documents_count, .. } => {
+            KindWithContent::DocumentAdditionOrUpdate { documents_count, .. }
+            | KindWithContent::DocumentEdition { documents_count, .. } => {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    skipped_documents: None,
+                    invalid_documents: Vec::new(),
                 })
             }
             KindWithContent::DocumentDeletion { index_uid: _, documents_ids } => {
@@ -208,12 +245,18 @@ impl KindWithContent {
                     deleted_documents: None,
                 })
             }
-            KindWithContent::DocumentDeletionByFilter { index_uid: _, filter_expr } => {
+            KindWithContent::DocumentDeletionByFilter { filter_expr, .. } => {
                 Some(Details::DocumentDeletionByFilter {
                     original_filter: filter_expr.to_string(),
                     deleted_documents: None,
                 })
             }
+            KindWithContent::DocumentEditionByFilter { filter_expr, .. } => {
+                Some(Details::DocumentEditionByFilter {
+                    original_filter: filter_expr.to_string(),
+                    edited_documents: None,
+                })
+            }
             KindWithContent::DocumentClear { .. } | KindWithContent::IndexDeletion { .. } => {
                 Some(Details::ClearAll { deleted_documents: None })
             }
@@ -224,6 +267,9 @@ impl KindWithContent {
             | KindWithContent::IndexUpdate { primary_key, .. } => {
                 Some(Details::IndexInfo { primary_key: primary_key.clone() })
             }
+            KindWithContent::IndexCompaction { .. } => {
+                Some(Details::IndexCompaction { reclaimed_bytes: None })
+            }
             KindWithContent::IndexSwap { swaps } => {
                 Some(Details::IndexSwap { swaps: swaps.clone() })
             }
@@ -244,10 +290,13 @@ impl KindWithContent {
 
     pub fn default_finished_details(&self) -> Option<Details> {
         match self {
-            KindWithContent::DocumentAdditionOrUpdate { documents_count, .. } => {
+            KindWithContent::DocumentAdditionOrUpdate { documents_count, .. }
+            | KindWithContent::DocumentEdition { documents_count, .. } => {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: Some(0),
+                    skipped_documents: None,
+                    invalid_documents: Vec::new(),
                 })
             }
             KindWithContent::DocumentDeletion { index_uid: _, documents_ids } => {
@@ -256,12 +305,18 @@ impl KindWithContent {
                     deleted_documents: Some(0),
                 })
             }
-            KindWithContent::DocumentDeletionByFilter { index_uid: _, filter_expr } => {
+            KindWithContent::DocumentDeletionByFilter { filter_expr, .. } => {
                 Some(Details::DocumentDeletionByFilter {
                     original_filter: filter_expr.to_string(),
                     deleted_documents: Some(0),
                 })
             }
+            KindWithContent::DocumentEditionByFilter { filter_expr, .. } => {
+                Some(Details::DocumentEditionByFilter {
+                    original_filter: filter_expr.to_string(),
+                    edited_documents: Some(0),
+                })
+            }
             KindWithContent::DocumentClear { .. } => {
                 Some(Details::ClearAll { deleted_documents: None })
             }
@@ -273,6 +328,9 @@ impl KindWithContent {
             | KindWithContent::IndexUpdate { primary_key, .. } => {
                 Some(Details::IndexInfo { primary_key: primary_key.clone() })
             }
+            KindWithContent::IndexCompaction { .. } => {
+                Some(Details::IndexCompaction { reclaimed_bytes: Some(0) })
+            }
             KindWithContent::IndexSwap { .. } => {
                 todo!()
             }
@@ -295,14 +353,18 @@ impl KindWithContent {
 impl From<&KindWithContent> for Option<Details> {
     fn from(kind: &KindWithContent) -> Self {
         match kind {
-            KindWithContent::DocumentAdditionOrUpdate { documents_count, .. } => {
+            KindWithContent::DocumentAdditionOrUpdate { documents_count, .. }
+            | KindWithContent::DocumentEdition { documents_count, .. } => {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    skipped_documents: None,
+                    invalid_documents: Vec::new(),
                 })
             }
             KindWithContent::DocumentDeletion { .. } => None,
             KindWithContent::DocumentDeletionByFilter { .. } => None,
+            KindWithContent::DocumentEditionByFilter { .. } => None,
             KindWithContent::DocumentClear { .. } => None,
             KindWithContent::SettingsUpdate { new_settings, .. } => {
                 Some(Details::SettingsUpdate { settings: new_settings.clone() })
@@ -314,6 +376,7 @@ impl From<&KindWithContent> for Option<Details> {
             KindWithContent::IndexUpdate { primary_key, .. } => {
                 Some(Details::IndexInfo { primary_key: primary_key.clone() })
             }
+            KindWithContent::IndexCompaction { .. } => None,
             KindWithContent::IndexSwap { .. } => None,
             KindWithContent::TaskCancelation { query, tasks } => Some(Details::TaskCancelation {
                 matched_tasks: tasks.len(),
@@ -396,10 +459,12 @@ pub enum Kind {
     DocumentAdditionOrUpdate,
     DocumentDeletion,
     DocumentDeletionByFilter,
+    DocumentEdition,
     SettingsUpdate,
     IndexCreation,
     IndexDeletion,
     IndexUpdate,
+    IndexCompaction,
     IndexSwap,
     TaskCancelation,
     TaskDeletion,
@@ -413,10 +478,12 @@ impl Kind {
             Kind::DocumentAdditionOrUpdate
             | Kind::DocumentDeletion
             | Kind::DocumentDeletionByFilter
+            | Kind::DocumentEdition
             | Kind::SettingsUpdate
             | Kind::IndexCreation
             | Kind::IndexDeletion
-            | Kind::IndexUpdate => true,
+            | Kind::IndexUpdate
+            | Kind::IndexCompaction => true,
             Kind::IndexSwap
             | Kind::TaskCancelation
             | Kind::TaskDeletion
@@ -431,10 +498,12 @@ impl Display for Kind {
             Kind::DocumentAdditionOrUpdate => write!(f, "documentAdditionOrUpdate"),
             Kind::DocumentDeletion => write!(f, "documentDeletion"),
             Kind::DocumentDeletionByFilter => write!(f, "documentDeletionByFilter"),
+            Kind::DocumentEdition => write!(f, "documentEdition"),
             Kind::SettingsUpdate => write!(f, "settingsUpdate"),
             Kind::IndexCreation => write!(f, "indexCreation"),
             Kind::IndexDeletion => write!(f, "indexDeletion"),
             Kind::IndexUpdate => write!(f, "indexUpdate"),
+            Kind::IndexCompaction => write!(f, "indexCompaction"),
             Kind::IndexSwap => write!(f, "indexSwap"),
             Kind::TaskCancelation => write!(f, "taskCancelation"),
             Kind::TaskDeletion => write!(f, "taskDeletion"),
@@ -451,6 +520,8 @@ impl FromStr for Kind {
             Ok(Kind::IndexCreation)
         } else if kind.eq_ignore_ascii_case("indexUpdate") {
             Ok(Kind::IndexUpdate)
+        } else if kind.eq_ignore_ascii_case("indexCompaction") {
+            Ok(Kind::IndexCompaction)
         } else if kind.eq_ignore_ascii_case("indexSwap") {
             Ok(Kind::IndexSwap)
         } else if kind.eq_ignore_ascii_case("indexDeletion") {
@@ -459,6 +530,8 @@ impl FromStr for Kind {
             Ok(Kind::DocumentAdditionOrUpdate)
         } else if kind.eq_ignore_ascii_case("documentDeletion") {
             Ok(Kind::DocumentDeletion)
+        } else if kind.eq_ignore_ascii_case("documentEdition") {
+            Ok(Kind::DocumentEdition)
         } else if kind.eq_ignore_ascii_case("settingsUpdate") {
             Ok(Kind::SettingsUpdate)
         } else if kind.eq_ignore_ascii_case("taskCancelation") {
@@ -498,32 +571,82 @@ impl std::error::Error for ParseTaskKindError {}
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum Details {
-    DocumentAdditionOrUpdate { received_documents: u64, indexed_documents: Option<u64> },
-    SettingsUpdate { settings: Box<Settings<Unchecked>> },
-    IndexInfo { primary_key: Option<String> },
-    DocumentDeletion { provided_ids: usize, deleted_documents: Option<u64> },
-    DocumentDeletionByFilter { original_filter: String, deleted_documents: Option<u64> },
-    ClearAll { deleted_documents: Option<u64> },
-    TaskCancelation { matched_tasks: u64, canceled_tasks: Option<u64>, original_filter: String },
-    TaskDeletion { matched_tasks: u64, deleted_tasks: Option<u64>, original_filter: String },
-    Dump { dump_uid: Option<String> },
-    IndexSwap { swaps: Vec<IndexSwap> },
+    DocumentAdditionOrUpdate {
+        received_documents: u64,
+        indexed_documents: Option<u64>,
+        /// Only reported when the index has a version attribute.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        skipped_documents: Option<u64>,
+        /// The documents skipped because they did not match the schema of the index,
+        /// at most [`MAX_REPORTED_INVALID_DOCUMENTS`] of them.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        invalid_documents: Vec<InvalidDocument>,
+    },
+    SettingsUpdate {
+        settings: Box<Settings<Unchecked>>,
+    },
+    IndexInfo {
+        primary_key: Option<String>,
+    },
+    IndexCompaction {
+        reclaimed_bytes: Option<u64>,
+    },
+    DocumentDeletion {
+        provided_ids: usize,
+        deleted_documents: Option<u64>,
+    },
+    DocumentDeletionByFilter {
+        original_filter: String,
+        deleted_documents: Option<u64>,
+    },
+    DocumentEditionByFilter {
+        original_filter: String,
+        edited_documents: Option<u64>,
+    },
+    ClearAll {
+        deleted_documents: Option<u64>,
+    },
+    TaskCancelation {
+        matched_tasks: u64,
+        canceled_tasks: Option<u64>,
+        original_filter: String,
+    },
+    TaskDeletion {
+        matched_tasks: u64,
+        deleted_tasks: Option<u64>,
+        original_filter: String,
+    },
+    Dump {
+        dump_uid: Option<String>,
+    },
+    IndexSwap {
+        swaps: Vec<IndexSwap>,
+    },
 }
 
 impl Details {
     pub fn to_failed(&self) -> Self {
         let mut details = self.clone();
         match &mut details {
-            Self::DocumentAdditionOrUpdate { indexed_documents, .. } => {
-                *indexed_documents = Some(0)
+            Self::DocumentAdditionOrUpdate {
+                indexed_documents,
+                skipped_documents,
+                invalid_documents,
+                ..
+            } => {
+                *indexed_documents = Some(0);
+                *skipped_documents = skipped_documents.map(|_| 0);
+                invalid_documents.clear();
             }
             Self::DocumentDeletion { deleted_documents, .. } => *deleted_documents = Some(0),
             Self::DocumentDeletionByFilter { deleted_documents, .. } => {
                 *deleted_documents = Some(0)
             }
+            Self::DocumentEditionByFilter { edited_documents, .. } => *edited_documents = Some(0),
             Self::ClearAll { deleted_documents } => *deleted_documents = Some(0),
             Self::TaskCancelation { canceled_tasks, .. } => *canceled_tasks = Some(0),
             Self::TaskDeletion { deleted_tasks, .. } => *deleted_tasks = Some(0),
+            Self::IndexCompaction { reclaimed_bytes } => *reclaimed_bytes = Some(0),
             Self::SettingsUpdate { .. }
             | Self::IndexInfo { .. }
             | Self::Dump { .. }
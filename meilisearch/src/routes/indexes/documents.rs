@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::io::ErrorKind;
 
 use actix_web::http::header::CONTENT_TYPE;
@@ -16,7 +17,7 @@ use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
-use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::milli::update::{patch_to_operations, FieldEdit, IndexDocumentsMethod};
 use meilisearch_types::milli::DocumentId;
 use meilisearch_types::star_or::OptionStarOrList;
 use meilisearch_types::tasks::KindWithContent;
@@ -24,7 +25,7 @@ use meilisearch_types::{milli, Document, Index};
 use mime::Mime;
 use once_cell::sync::Lazy;
 use serde::Deserialize;
-use serde_json::Value;
+use serde_json::{json, Value};
 use tempfile::tempfile;
 use tokio::fs::File;
 use tokio::io::{AsyncSeekExt, AsyncWriteExt, BufWriter};
@@ -79,6 +80,11 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
     )
     .service(web::resource("/delete").route(web::post().to(SeqHandler(delete_documents_by_filter))))
     .service(web::resource("/fetch").route(web::post().to(SeqHandler(documents_by_query_post))))
+    .service(web::resource("/edit").route(web::post().to(SeqHandler(edit_documents))))
+    .service(
+        web::resource("/edit-by-filter")
+            .route(web::post().to(SeqHandler(edit_documents_by_filter))),
+    )
     .service(
         web::resource("/{document_id}")
             .route(web::get().to(SeqHandler(get_document)))
@@ -155,6 +161,8 @@ pub struct BrowseQuery {
     fields: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidDocumentFilter>)]
     filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentFilter>)]
+    filter_params: Option<BTreeMap<String, Value>>,
 }
 
 pub async fn documents_by_query_post(
@@ -189,6 +197,7 @@ pub async fn get_documents(
         limit: limit.0,
         fields: fields.merge_star_and_none(),
         filter,
+        filter_params: None,
     };
 
     documents_by_query(&index_scheduler, index_uid, query)
@@ -200,10 +209,11 @@ fn documents_by_query(
     query: BrowseQuery,
 ) -> Result<HttpResponse, ResponseError> {
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
-    let BrowseQuery { offset, limit, fields, filter } = query;
+    let BrowseQuery { offset, limit, fields, filter, filter_params } = query;
 
     let index = index_scheduler.index(&index_uid)?;
-    let (total, documents) = retrieve_documents(&index, offset, limit, filter, fields)?;
+    let (total, documents) =
+        retrieve_documents(&index, offset, limit, filter, filter_params, fields)?;
 
     let ret = PaginationView::new(offset, limit, total as usize, documents);
 
@@ -295,6 +305,33 @@ pub async fn update_documents(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+pub async fn edit_documents(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: Payload,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    analytics.publish("Documents Edited".to_string(), json!({}), Some(&req));
+
+    // an edition only applies on the existing documents, it never creates the index.
+    let task = document_addition(
+        extract_mime_type(&req)?,
+        index_scheduler,
+        index_uid,
+        None,
+        None,
+        body,
+        IndexDocumentsMethod::EditDocuments,
+        false,
+    )
+    .await?;
+
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn document_addition(
     mime_type: Option<Mime>,
@@ -407,13 +444,20 @@ async fn document_addition(
         }
     };
 
-    let task = KindWithContent::DocumentAdditionOrUpdate {
-        method,
-        content_file: uuid,
-        documents_count,
-        primary_key,
-        allow_index_creation,
-        index_uid: index_uid.to_string(),
+    let task = match method {
+        IndexDocumentsMethod::EditDocuments => KindWithContent::DocumentEdition {
+            content_file: uuid,
+            documents_count,
+            index_uid: index_uid.to_string(),
+        },
+        method => KindWithContent::DocumentAdditionOrUpdate {
+            method,
+            content_file: uuid,
+            documents_count,
+            primary_key,
+            allow_index_creation,
+            index_uid: index_uid.to_string(),
+        },
     };
 
     let scheduler = index_scheduler.clone();
@@ -460,6 +504,8 @@ pub async fn delete_documents_batch(
 pub struct DocumentDeletionByFilter {
     #[deserr(error = DeserrJsonError<InvalidDocumentDeleteFilter>)]
     filter: Value,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentDeleteFilter>)]
+    filter_params: Option<BTreeMap<String, Value>>,
 }
 
 pub async fn delete_documents_by_filter(
@@ -472,17 +518,74 @@ pub async fn delete_documents_by_filter(
     debug!("called with params: {:?}", body);
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
     let index_uid = index_uid.into_inner();
-    let filter = body.into_inner().filter;
+    let DocumentDeletionByFilter { filter, filter_params } = body.into_inner();
 
     analytics.delete_documents(DocumentDeletionKind::PerFilter, &req);
 
     // we ensure the filter is well formed before enqueuing it
     || -> Result<_, ResponseError> {
-        Ok(crate::search::parse_filter(&filter)?.ok_or(MeilisearchHttpError::EmptyFilter)?)
+        Ok(crate::search::parse_filter(&filter, filter_params.as_ref())?
+            .ok_or(MeilisearchHttpError::EmptyFilter)?)
     }()
     // and whatever was the error, the error code should always be an InvalidDocumentDeleteFilter
     .map_err(|err| ResponseError::from_msg(err.message, Code::InvalidDocumentDeleteFilter))?;
-    let task = KindWithContent::DocumentDeletionByFilter { index_uid, filter_expr: filter };
+    let task =
+        KindWithContent::DocumentDeletionByFilter { index_uid, filter_expr: filter, filter_params };
+
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+
+    debug!("returns: {:?}", task);
+    Ok(HttpResponse::Accepted().json(task))
+}
+
+#[derive(Debug, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct DocumentEditionByFilter {
+    #[deserr(error = DeserrJsonError<InvalidDocumentEditFilter>)]
+    filter: Value,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentEditFilter>)]
+    filter_params: Option<BTreeMap<String, Value>>,
+    #[deserr(error = DeserrJsonError<InvalidDocumentEditOperation>)]
+    edits: BTreeMap<String, Value>,
+}
+
+pub async fn edit_documents_by_filter(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<DocumentEditionByFilter, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!("called with params: {:?}", body);
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid = index_uid.into_inner();
+    let DocumentEditionByFilter { filter, filter_params, edits } = body.into_inner();
+    let edits: serde_json::Map<String, Value> = edits.into_iter().collect();
+
+    analytics.publish("Documents Edited By Filter".to_string(), json!({}), Some(&req));
+
+    // we ensure the filter is well formed before enqueuing it
+    || -> Result<_, ResponseError> {
+        Ok(crate::search::parse_filter(&filter, filter_params.as_ref())?
+            .ok_or(MeilisearchHttpError::EmptyFilter)?)
+    }()
+    // and whatever was the error, the error code should always be an InvalidDocumentEditFilter
+    .map_err(|err| ResponseError::from_msg(err.message, Code::InvalidDocumentEditFilter))?;
+
+    // the edits are applied on every matching document, they must all be valid operations
+    for (field, operation) in patch_to_operations(&edits) {
+        FieldEdit::from_json(&field, operation).map_err(|err| {
+            ResponseError::from_msg(err.to_string(), Code::InvalidDocumentEditOperation)
+        })?;
+    }
+
+    let task = KindWithContent::DocumentEditionByFilter {
+        index_uid,
+        filter_expr: filter,
+        filter_params,
+        edits,
+    };
 
     let task: SummarizedTaskView =
         tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
@@ -528,12 +631,13 @@ fn retrieve_documents<S: AsRef<str>>(
     offset: usize,
     limit: usize,
     filter: Option<Value>,
+    filter_params: Option<BTreeMap<String, Value>>,
     attributes_to_retrieve: Option<Vec<S>>,
 ) -> Result<(u64, Vec<Document>), ResponseError> {
     let rtxn = index.read_txn()?;
     let filter = &filter;
     let filter = if let Some(filter) = filter {
-        parse_filter(filter)
+        parse_filter(filter, filter_params.as_ref())
             .map_err(|err| ResponseError::from_msg(err.to_string(), Code::InvalidDocumentFilter))?
     } else {
         None
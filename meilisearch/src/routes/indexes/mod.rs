@@ -42,6 +42,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                     .route(web::delete().to(SeqHandler(delete_index))),
             )
             .service(web::resource("/stats").route(web::get().to(SeqHandler(get_index_stats))))
+            .service(web::resource("/compact").route(web::post().to(SeqHandler(compact_index))))
             .service(web::scope("/documents").configure(documents::configure))
             .service(web::scope("/search").configure(search::configure))
             .service(web::scope("/settings").configure(settings::configure)),
@@ -220,6 +221,23 @@ pub async fn delete_index(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+pub async fn compact_index(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::INDEXES_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    analytics.publish("Index Compacted".to_string(), json!({}), Some(&req));
+
+    let task = KindWithContent::IndexCompaction { index_uid: index_uid.into_inner() };
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+
+    debug!("returns: {:?}", task);
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 /// Stats of an `Index`, as known to the `stats` route.
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -66,6 +66,8 @@ pub struct SearchQueryGet {
     crop_marker: String,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchMatchingStrategy>)]
     matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchLocales>)]
+    locales: Option<CS<String>>,
 }
 
 impl From<SearchQueryGet> for SearchQuery {
@@ -89,13 +91,19 @@ impl From<SearchQueryGet> for SearchQuery {
             crop_length: other.crop_length.0,
             attributes_to_highlight: other.attributes_to_highlight.map(|o| o.into_iter().collect()),
             filter,
+            filter_params: None,
             sort: other.sort.map(|attr| fix_sort_query_parameters(&attr)),
             show_matches_position: other.show_matches_position.0,
             facets: other.facets.map(|o| o.into_iter().collect()),
+            sort_facet_values_by: None,
+            facet_ranges: None,
+            facet_histogram: None,
+            facet_paths: None,
             highlight_pre_tag: other.highlight_pre_tag,
             highlight_post_tag: other.highlight_post_tag,
             crop_marker: other.crop_marker,
             matching_strategy: other.matching_strategy,
+            locales: other.locales.map(|o| o.into_iter().collect()),
         }
     }
 }
@@ -6,7 +6,9 @@ use log::debug;
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
-use meilisearch_types::settings::{settings, RankingRuleView, Settings, Unchecked};
+use meilisearch_types::settings::{
+    settings, FacetValuesSort, RankingRuleView, Settings, Unchecked,
+};
 use meilisearch_types::tasks::KindWithContent;
 use serde_json::json;
 
@@ -237,6 +239,9 @@ make_setting_route!(
                     "disable_on_words": setting
                         .as_ref()
                         .and_then(|s| s.disable_on_words.as_ref().set().map(|m| !m.is_empty())),
+                    "disable_on_numbers": setting
+                        .as_ref()
+                        .and_then(|s| s.disable_on_numbers.as_ref().set().copied()),
                     "min_word_size_for_one_typo": setting
                         .as_ref()
                         .and_then(|s| s.min_word_size_for_typos
@@ -309,6 +314,181 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/non-separator-tokens",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsNonSeparatorTokens,
+    >,
+    non_separator_tokens,
+    "nonSeparatorTokens",
+    analytics,
+    |non_separator_tokens: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "NonSeparatorTokens Updated".to_string(),
+            json!({
+                "non_separator_tokens": {
+                    "total": non_separator_tokens.as_ref().map(|non_separator_tokens| non_separator_tokens.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/separator-tokens",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsSeparatorTokens,
+    >,
+    separator_tokens,
+    "separatorTokens",
+    analytics,
+    |separator_tokens: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "SeparatorTokens Updated".to_string(),
+            json!({
+                "separator_tokens": {
+                    "total": separator_tokens.as_ref().map(|separator_tokens| separator_tokens.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/dictionary",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsDictionary,
+    >,
+    dictionary,
+    "dictionary",
+    analytics,
+    |dictionary: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Dictionary Updated".to_string(),
+            json!({
+                "dictionary": {
+                    "total": dictionary.as_ref().map(|dictionary| dictionary.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/localized-attributes",
+    put,
+    std::collections::BTreeMap<String, Vec<String>>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsLocalizedAttributes,
+    >,
+    localized_attributes,
+    "localizedAttributes",
+    analytics,
+    |localized_attributes: &Option<std::collections::BTreeMap<String, Vec<String>>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "LocalizedAttributes Updated".to_string(),
+            json!({
+                "localized_attributes": {
+                    "total": localized_attributes.as_ref().map(|localized_attributes| localized_attributes.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/stemming",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsStemming,
+    >,
+    stemming,
+    "stemming",
+    analytics,
+    |stemming: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Stemming Updated".to_string(),
+            json!({
+                "stemming": {
+                    "locales": stemming,
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/infix-attributes",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsInfixAttributes,
+    >,
+    infix_attributes,
+    "infixAttributes",
+    analytics,
+    |infix_attributes: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "InfixAttributes Updated".to_string(),
+            json!({
+                "infix_attributes": {
+                    "total": infix_attributes.as_ref().map(|infix_attributes| infix_attributes.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/phonetic-attributes",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsPhoneticAttributes,
+    >,
+    phonetic_attributes,
+    "phoneticAttributes",
+    analytics,
+    |phonetic_attributes: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "PhoneticAttributes Updated".to_string(),
+            json!({
+                "phonetic_attributes": {
+                    "total": phonetic_attributes.as_ref().map(|phonetic_attributes| phonetic_attributes.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/synonyms",
     put,
@@ -358,6 +538,103 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/version-attribute",
+    put,
+    String,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsVersionAttribute,
+    >,
+    version_attribute,
+    "versionAttribute",
+    analytics,
+    |version: &Option<String>, req: &HttpRequest| {
+        use serde_json::json;
+        analytics.publish(
+            "VersionAttribute Updated".to_string(),
+            json!({
+                "version_attribute": {
+                    "set": version.is_some(),
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/expiry-attribute",
+    put,
+    String,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsExpiryAttribute,
+    >,
+    expiry_attribute,
+    "expiryAttribute",
+    analytics,
+    |expiry: &Option<String>, req: &HttpRequest| {
+        use serde_json::json;
+        analytics.publish(
+            "ExpiryAttribute Updated".to_string(),
+            json!({
+                "expiry_attribute": {
+                    "set": expiry.is_some(),
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/ingestion-rules",
+    put,
+    meilisearch_types::settings::IngestionRulesView,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsIngestionRules,
+    >,
+    ingestion_rules,
+    "ingestionRules",
+    analytics,
+    |setting: &Option<meilisearch_types::settings::IngestionRulesView>, req: &HttpRequest| {
+        use serde_json::json;
+        analytics.publish(
+            "IngestionRules Updated".to_string(),
+            json!({
+                "ingestion_rules": {
+                    "total": setting.as_ref().map(|s| s.rules.len()),
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/document-schema",
+    put,
+    meilisearch_types::settings::DocumentSchemaView,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsDocumentSchema,
+    >,
+    document_schema,
+    "documentSchema",
+    analytics,
+    |setting: &Option<meilisearch_types::settings::DocumentSchemaView>, req: &HttpRequest| {
+        use serde_json::json;
+        analytics.publish(
+            "DocumentSchema Updated".to_string(),
+            json!({
+                "document_schema": {
+                    "total_fields": setting.as_ref().map(|s| s.fields.len()),
+                    "total_required": setting.as_ref().map(|s| s.required.len()),
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/ranking-rules",
     put,
@@ -407,6 +684,11 @@ make_setting_route!(
             json!({
                 "faceting": {
                     "max_values_per_facet": setting.as_ref().and_then(|s| s.max_values_per_facet.set()),
+                    "sort_facet_values_by_star_count": setting.as_ref().and_then(|s| {
+                        s.sort_facet_values_by.as_ref().set().map(|s| s.iter().any(|(k, v)| k == "*" && v == &meilisearch_types::settings::FacetValuesSort::Count))
+                    }),
+                    "sort_facet_values_by_total": setting.as_ref().and_then(|s| s.sort_facet_values_by.as_ref().set().map(|s| s.len())),
+                    "hierarchical_facets_total": setting.as_ref().and_then(|s| s.hierarchical_facets.as_ref().set().map(|s| s.len())),
                 },
             }),
             Some(req),
@@ -459,7 +741,18 @@ generate_configure!(
     displayed_attributes,
     searchable_attributes,
     distinct_attribute,
+    version_attribute,
+    expiry_attribute,
+    ingestion_rules,
+    document_schema,
     stop_words,
+    non_separator_tokens,
+    separator_tokens,
+    dictionary,
+    localized_attributes,
+    stemming,
+    infix_attributes,
+    phonetic_attributes,
     synonyms,
     ranking_rules,
     typo_tolerance,
@@ -509,6 +802,19 @@ pub async fn update_all(
             "distinct_attribute": {
                 "set": new_settings.distinct_attribute.as_ref().set().is_some()
             },
+            "version_attribute": {
+                "set": new_settings.version_attribute.as_ref().set().is_some()
+            },
+            "expiry_attribute": {
+                "set": new_settings.expiry_attribute.as_ref().set().is_some()
+            },
+            "ingestion_rules": {
+                "total": new_settings.ingestion_rules.as_ref().set().map(|s| s.rules.len()),
+            },
+            "document_schema": {
+                "total_fields": new_settings.document_schema.as_ref().set().map(|s| s.fields.len()),
+                "total_required": new_settings.document_schema.as_ref().set().map(|s| s.required.len()),
+            },
             "typo_tolerance": {
                 "enabled": new_settings.typo_tolerance
                     .as_ref()
@@ -523,6 +829,10 @@ pub async fn update_all(
                     .as_ref()
                     .set()
                     .and_then(|s| s.disable_on_words.as_ref().set().map(|m| !m.is_empty())),
+                "disable_on_numbers": new_settings.typo_tolerance
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.disable_on_numbers.as_ref().set().copied()),
                 "min_word_size_for_one_typo": new_settings.typo_tolerance
                     .as_ref()
                     .set()
@@ -545,6 +855,20 @@ pub async fn update_all(
                     .as_ref()
                     .set()
                     .and_then(|s| s.max_values_per_facet.as_ref().set()),
+                "sort_facet_values_by_star_count": new_settings.faceting
+                    .as_ref()
+                    .set()
+                    .and_then(|s| {
+                        s.sort_facet_values_by.as_ref().set().map(|s| s.iter().any(|(k, v)| k == "*" && v == &FacetValuesSort::Count))
+                    }),
+                "sort_facet_values_by_total": new_settings.faceting
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.sort_facet_values_by.as_ref().set().map(|s| s.len())),
+                "hierarchical_facets_total": new_settings.faceting
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.hierarchical_facets.as_ref().set().map(|s| s.len())),
             },
             "pagination": {
                 "max_total_hits": new_settings.pagination
@@ -555,6 +879,27 @@ pub async fn update_all(
             "stop_words": {
                 "total": new_settings.stop_words.as_ref().set().map(|stop_words| stop_words.len()),
             },
+            "non_separator_tokens": {
+                "total": new_settings.non_separator_tokens.as_ref().set().map(|non_separator_tokens| non_separator_tokens.len()),
+            },
+            "separator_tokens": {
+                "total": new_settings.separator_tokens.as_ref().set().map(|separator_tokens| separator_tokens.len()),
+            },
+            "dictionary": {
+                "total": new_settings.dictionary.as_ref().set().map(|dictionary| dictionary.len()),
+            },
+            "localized_attributes": {
+                "total": new_settings.localized_attributes.as_ref().set().map(|localized_attributes| localized_attributes.len()),
+            },
+            "stemming": {
+                "locales": new_settings.stemming.as_ref().set(),
+            },
+            "infix_attributes": {
+                "total": new_settings.infix_attributes.as_ref().set().map(|infix_attributes| infix_attributes.len()),
+            },
+            "phonetic_attributes": {
+                "total": new_settings.phonetic_attributes.as_ref().set().map(|phonetic_attributes| phonetic_attributes.len()),
+            },
             "synonyms": {
                 "total": new_settings.synonyms.as_ref().set().map(|synonyms| synonyms.len()),
             },
@@ -2,12 +2,14 @@ use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::actix_web::AwebQueryParameter;
 use deserr::Deserr;
-use index_scheduler::{IndexScheduler, Query, TaskId};
+use index_scheduler::{IndexScheduler, Query, TaskId, TaskProgress};
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::DeserrQueryParamError;
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{InvalidTaskDateError, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::update::UpdateIndexingStep;
+use meilisearch_types::milli::InvalidDocument;
 use meilisearch_types::settings::{Settings, Unchecked};
 use meilisearch_types::star_or::{OptionStarOr, OptionStarOrList};
 use meilisearch_types::tasks::{
@@ -38,7 +40,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
     .service(web::resource("/{task_id}").route(web::get().to(SeqHandler(get_task))));
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskView {
     pub uid: TaskId,
@@ -53,6 +55,8 @@ pub struct TaskView {
     pub error: Option<ResponseError>,
     #[serde(serialize_with = "serialize_duration", default)]
     pub duration: Option<Duration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<TaskProgressView>,
     #[serde(with = "time::serde::rfc3339")]
     pub enqueued_at: OffsetDateTime,
     #[serde(with = "time::serde::rfc3339::option", default)]
@@ -72,6 +76,7 @@ impl TaskView {
             details: task.details.clone().map(DetailsView::from),
             error: task.error.clone(),
             duration: task.started_at.zip(task.finished_at).map(|(start, end)| end - start),
+            progress: None,
             enqueued_at: task.enqueued_at,
             started_at: task.started_at,
             finished_at: task.finished_at,
@@ -79,6 +84,55 @@ impl TaskView {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskProgressView {
+    pub current_step: &'static str,
+    pub percentage: f32,
+    pub steps: Vec<TaskStepView>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskStepView {
+    pub name: &'static str,
+    #[serde(serialize_with = "serialize_duration")]
+    pub duration: Option<Duration>,
+}
+
+impl From<TaskProgress> for TaskProgressView {
+    fn from(progress: TaskProgress) -> Self {
+        // a step lasts until the next one starts, the last one is still running
+        let now = OffsetDateTime::now_utc();
+        let finished_at =
+            progress.steps.iter().skip(1).map(|(_, started_at)| *started_at).chain(Some(now));
+        let steps = progress
+            .steps
+            .iter()
+            .zip(finished_at)
+            .map(|((step, started_at), finished_at)| TaskStepView {
+                name: indexing_step_name(*step),
+                duration: Some(finished_at - *started_at),
+            })
+            .collect();
+
+        TaskProgressView {
+            current_step: progress.current_step().map(indexing_step_name).unwrap_or_default(),
+            percentage: progress.percentage().unwrap_or_default(),
+            steps,
+        }
+    }
+}
+
+fn indexing_step_name(step: UpdateIndexingStep) -> &'static str {
+    match step {
+        UpdateIndexingStep::RemapDocumentAddition { .. } => "remapDocumentAddition",
+        UpdateIndexingStep::ComputeIdsAndMergeDocuments { .. } => "computeIdsAndMergeDocuments",
+        UpdateIndexingStep::IndexDocuments { .. } => "indexDocuments",
+        UpdateIndexingStep::MergeDataIntoFinalDatabase { .. } => "mergeDataIntoFinalDatabase",
+    }
+}
+
 #[derive(Default, Debug, PartialEq, Eq, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DetailsView {
@@ -87,12 +141,20 @@ pub struct DetailsView {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub indexed_documents: Option<Option<u64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped_documents: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid_documents: Option<Vec<InvalidDocument>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub primary_key: Option<Option<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub reclaimed_bytes: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub provided_ids: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deleted_documents: Option<Option<u64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub edited_documents: Option<Option<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub matched_tasks: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub canceled_tasks: Option<Option<u64>>,
@@ -112,19 +174,27 @@ pub struct DetailsView {
 impl From<Details> for DetailsView {
     fn from(details: Details) -> Self {
         match details {
-            Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
-                DetailsView {
-                    received_documents: Some(received_documents),
-                    indexed_documents: Some(indexed_documents),
-                    ..DetailsView::default()
-                }
-            }
+            Details::DocumentAdditionOrUpdate {
+                received_documents,
+                indexed_documents,
+                skipped_documents,
+                invalid_documents,
+            } => DetailsView {
+                received_documents: Some(received_documents),
+                indexed_documents: Some(indexed_documents),
+                skipped_documents,
+                invalid_documents: (!invalid_documents.is_empty()).then_some(invalid_documents),
+                ..DetailsView::default()
+            },
             Details::SettingsUpdate { settings } => {
                 DetailsView { settings: Some(settings), ..DetailsView::default() }
             }
             Details::IndexInfo { primary_key } => {
                 DetailsView { primary_key: Some(primary_key), ..DetailsView::default() }
             }
+            Details::IndexCompaction { reclaimed_bytes } => {
+                DetailsView { reclaimed_bytes: Some(reclaimed_bytes), ..DetailsView::default() }
+            }
             Details::DocumentDeletion {
                 provided_ids: received_document_ids,
                 deleted_documents,
@@ -141,6 +211,11 @@ impl From<Details> for DetailsView {
                     ..DetailsView::default()
                 }
             }
+            Details::DocumentEditionByFilter { original_filter, edited_documents } => DetailsView {
+                original_filter: Some(original_filter),
+                edited_documents: Some(edited_documents),
+                ..DetailsView::default()
+            },
             Details::ClearAll { deleted_documents } => {
                 DetailsView { deleted_documents: Some(deleted_documents), ..DetailsView::default() }
             }
@@ -447,7 +522,8 @@ async fn get_task(
     if let Some(task) =
         index_scheduler.get_tasks_from_authorized_indexes(query, index_scheduler.filters())?.first()
     {
-        let task_view = TaskView::from_task(task);
+        let mut task_view = TaskView::from_task(task);
+        task_view.progress = index_scheduler.task_progress(task.uid).map(TaskProgressView::from);
         Ok(HttpResponse::Ok().json(task_view))
     } else {
         Err(index_scheduler::Error::TaskNotFound(task_uid).into())
@@ -729,7 +805,7 @@ mod tests {
             let err = deserr_query_params::<TaskDeletionOrCancelationQuery>(params).unwrap_err();
             snapshot!(meili_snap::json_string!(err), @r###"
             {
-              "message": "Invalid value in parameter `types`: `createIndex` is not a valid task type. Available types are `documentAdditionOrUpdate`, `documentDeletion`, `documentDeletionByFilter`, `settingsUpdate`, `indexCreation`, `indexDeletion`, `indexUpdate`, `indexSwap`, `taskCancelation`, `taskDeletion`, `dumpCreation`, `snapshotCreation`.",
+              "message": "Invalid value in parameter `types`: `createIndex` is not a valid task type. Available types are `documentAdditionOrUpdate`, `documentDeletion`, `documentDeletionByFilter`, `documentEdition`, `settingsUpdate`, `indexCreation`, `indexDeletion`, `indexUpdate`, `indexCompaction`, `indexSwap`, `taskCancelation`, `taskDeletion`, `dumpCreation`, `snapshotCreation`.",
               "code": "invalid_task_types",
               "type": "invalid_request",
               "link": "https://docs.meilisearch.com/errors#invalid_task_types"
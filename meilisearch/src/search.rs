@@ -4,17 +4,18 @@ use std::str::FromStr;
 use std::time::Instant;
 
 use deserr::Deserr;
-use either::Either;
+use indexmap::IndexMap;
 use meilisearch_auth::IndexSearchRules;
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::index_uid::IndexUid;
-use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
+use meilisearch_types::settings::{FacetValuesSort, DEFAULT_PAGINATION_MAX_TOTAL_HITS};
 use meilisearch_types::{milli, Document};
-use milli::tokenizer::TokenizerBuilder;
+use milli::tokenizer::{Language, TokenizerBuilder};
 use milli::{
-    AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, MatchBounds, MatcherBuilder,
-    SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    AscDesc, FacetHierarchy, FacetNumberStats, FacetRange, FieldId, FieldsIdsMap, Filter,
+    FormatOptions, Index, MatchBounds, MatcherBuilder, SortError, TermsMatchingStrategy,
+    DEFAULT_VALUES_PER_FACET,
 };
 use regex::Regex;
 use serde::Serialize;
@@ -56,10 +57,20 @@ pub struct SearchQuery {
     pub show_matches_position: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
     pub filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
+    pub filter_params: Option<BTreeMap<String, Value>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSortFacetValuesBy>)]
+    pub sort_facet_values_by: Option<BTreeMap<String, FacetValuesSort>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetRanges>)]
+    pub facet_ranges: Option<BTreeMap<String, Vec<FacetRangeQuery>>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetHistogram>)]
+    pub facet_histogram: Option<BTreeMap<String, FacetHistogramQuery>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetPaths>)]
+    pub facet_paths: Option<BTreeMap<String, String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -68,6 +79,8 @@ pub struct SearchQuery {
     pub crop_marker: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
+    pub locales: Option<Vec<String>>,
 }
 
 impl SearchQuery {
@@ -107,10 +120,20 @@ pub struct SearchQueryWithIndex {
     pub show_matches_position: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
     pub filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
+    pub filter_params: Option<BTreeMap<String, Value>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSortFacetValuesBy>)]
+    pub sort_facet_values_by: Option<BTreeMap<String, FacetValuesSort>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetRanges>)]
+    pub facet_ranges: Option<BTreeMap<String, Vec<FacetRangeQuery>>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetHistogram>)]
+    pub facet_histogram: Option<BTreeMap<String, FacetHistogramQuery>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetPaths>)]
+    pub facet_paths: Option<BTreeMap<String, String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -119,6 +142,8 @@ pub struct SearchQueryWithIndex {
     pub crop_marker: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
+    pub locales: Option<Vec<String>>,
 }
 
 impl SearchQueryWithIndex {
@@ -136,12 +161,18 @@ impl SearchQueryWithIndex {
             attributes_to_highlight,
             show_matches_position,
             filter,
+            filter_params,
             sort,
             facets,
+            sort_facet_values_by,
+            facet_ranges,
+            facet_histogram,
+            facet_paths,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
+            locales,
         } = self;
         (
             index_uid,
@@ -157,12 +188,18 @@ impl SearchQueryWithIndex {
                 attributes_to_highlight,
                 show_matches_position,
                 filter,
+                filter_params,
                 sort,
                 facets,
+                sort_facet_values_by,
+                facet_ranges,
+                facet_histogram,
+                facet_paths,
                 highlight_pre_tag,
                 highlight_post_tag,
                 crop_marker,
                 matching_strategy,
+                locales,
                 // do not use ..Default::default() here,
                 // rather add any missing field from `SearchQuery` to `SearchQueryWithIndex`
             },
@@ -170,6 +207,34 @@ impl SearchQueryWithIndex {
     }
 }
 
+/// A range of numeric facet values, `from` is inclusive and `to` is exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Deserr)]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct FacetRangeQuery {
+    #[deserr(default)]
+    pub from: Option<f64>,
+    #[deserr(default)]
+    pub to: Option<f64>,
+}
+
+// The bounds are deserialized from JSON numbers and thus are never NaN.
+impl Eq for FacetRangeQuery {}
+
+impl From<FacetRangeQuery> for FacetRange {
+    fn from(FacetRangeQuery { from, to }: FacetRangeQuery) -> Self {
+        FacetRange { from, to }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserr)]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct FacetHistogramQuery {
+    pub interval: f64,
+}
+
+// The interval is deserialized from a JSON number and thus is never NaN.
+impl Eq for FacetHistogramQuery {}
+
 #[derive(Debug, Clone, PartialEq, Eq, Deserr)]
 #[deserr(rename_all = camelCase)]
 pub enum MatchingStrategy {
@@ -213,9 +278,15 @@ pub struct SearchResult {
     #[serde(flatten)]
     pub hits_info: HitsInfo,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub facet_distribution: Option<BTreeMap<String, BTreeMap<String, u64>>>,
+    pub facet_distribution: Option<BTreeMap<String, IndexMap<String, u64>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_stats: Option<BTreeMap<String, FacetStats>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_ranges: Option<BTreeMap<String, Vec<FacetRangeCount>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_histogram: Option<BTreeMap<String, Vec<FacetRangeCount>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_hierarchy: Option<BTreeMap<String, FacetHierarchyResult>>,
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
@@ -236,9 +307,80 @@ pub enum HitsInfo {
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct FacetStats {
     pub min: f64,
     pub max: f64,
+    pub count: u64,
+    pub sum: f64,
+    pub avg: f64,
+    pub distinct_count: u64,
+    pub percentiles: BTreeMap<String, f64>,
+}
+
+impl From<FacetNumberStats> for FacetStats {
+    fn from(stats: FacetNumberStats) -> Self {
+        let FacetNumberStats { min, max, count, sum, avg, distinct_count, percentiles } = stats;
+        FacetStats {
+            min,
+            max,
+            count,
+            sum,
+            avg,
+            distinct_count,
+            percentiles: percentiles
+                .into_iter()
+                .map(|(percentile, value)| (format!("p{percentile}"), value))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FacetRangeCount {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<f64>,
+    pub count: u64,
+}
+
+impl FacetRangeCount {
+    fn from_ranges(
+        ranges: BTreeMap<String, Vec<(FacetRange, u64)>>,
+    ) -> BTreeMap<String, Vec<FacetRangeCount>> {
+        ranges
+            .into_iter()
+            .map(|(name, ranges)| {
+                let ranges = ranges
+                    .into_iter()
+                    .map(|(FacetRange { from, to }, count)| FacetRangeCount { from, to, count })
+                    .collect();
+                (name, ranges)
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FacetHierarchyResult {
+    pub path: Vec<FacetValueCount>,
+    pub children: Vec<FacetValueCount>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FacetValueCount {
+    pub value: String,
+    pub count: u64,
+}
+
+impl From<FacetHierarchy> for FacetHierarchyResult {
+    fn from(FacetHierarchy { path, children }: FacetHierarchy) -> Self {
+        let values = |values: Vec<(String, u64)>| {
+            values.into_iter().map(|(value, count)| FacetValueCount { value, count }).collect()
+        };
+        FacetHierarchyResult { path: values(path), children: values(children) }
+    }
 }
 
 /// Incorporate search rules in search query
@@ -277,6 +419,14 @@ pub fn perform_search(
     let is_finite_pagination = query.is_finite_pagination();
     search.terms_matching_strategy(query.matching_strategy.into());
 
+    let locales = match query.locales {
+        Some(ref locales) => Some(parse_locales(locales)?),
+        None => None,
+    };
+    if let Some(ref locales) = locales {
+        search.locales(locales.clone());
+    }
+
     let max_total_hits = index
         .pagination_max_total_hits(&rtxn)
         .map_err(milli::Error::from)?
@@ -304,7 +454,7 @@ pub fn perform_search(
     search.limit(limit);
 
     if let Some(ref filter) = query.filter {
-        if let Some(facets) = parse_filter(filter)? {
+        if let Some(facets) = parse_filter(filter, query.filter_params.as_ref())? {
             search.filter(facets);
         }
     }
@@ -378,12 +528,32 @@ pub fn perform_search(
     let mut tokenizer_builder = TokenizerBuilder::default();
     tokenizer_builder.create_char_map(true);
 
-    let script_lang_map = index.script_language(&rtxn)?;
+    let separators = index.allowed_separators(&rtxn)?;
+    let separators: Option<Vec<_>> =
+        separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+    if let Some(ref separators) = separators {
+        tokenizer_builder.separators(separators);
+    }
+
+    let dictionary = index.dictionary(&rtxn)?;
+    let dictionary: Option<Vec<_>> =
+        dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+    if let Some(ref dictionary) = dictionary {
+        tokenizer_builder.words_dict(dictionary);
+    }
+
+    // the formatter must tokenize the documents with the languages used to tokenize the query.
+    let script_lang_map = match locales {
+        Some(ref locales) => milli::locales_allow_list(locales),
+        None => index.query_script_language(&rtxn)?,
+    };
     if !script_lang_map.is_empty() {
         tokenizer_builder.allow_list(&script_lang_map);
     }
 
     let mut formatter_builder = MatcherBuilder::new(matching_words, tokenizer_builder.build());
+    // the words containing the query words are only highlighted in the infix attributes.
+    let infix_attributes = index.infix_attributes(&rtxn)?;
     formatter_builder.crop_marker(query.crop_marker);
     formatter_builder.highlight_prefix(query.highlight_pre_tag);
     formatter_builder.highlight_suffix(query.highlight_post_tag);
@@ -410,6 +580,7 @@ pub fn perform_search(
             &formatted_options,
             query.show_matches_position,
             &displayed_ids,
+            &infix_attributes,
         )?;
 
         if let Some(sort) = query.sort.as_ref() {
@@ -438,7 +609,39 @@ pub fn perform_search(
         HitsInfo::OffsetLimit { limit: query.limit, offset, estimated_total_hits: number_of_hits }
     };
 
-    let (facet_distribution, facet_stats) = match query.facets {
+    let facet_ranges = match query.facet_ranges {
+        Some(ref ranges) => {
+            let ranges = ranges
+                .iter()
+                .map(|(name, ranges)| {
+                    (name.clone(), ranges.iter().copied().map(FacetRange::from).collect())
+                })
+                .collect();
+            let ranges = index
+                .facets_distribution(&rtxn)
+                .candidates(candidates.clone())
+                .compute_ranges(&ranges)?;
+            Some(FacetRangeCount::from_ranges(ranges))
+        }
+        None => None,
+    };
+
+    let facet_histogram = match query.facet_histogram {
+        Some(ref histogram) => {
+            let intervals = histogram
+                .iter()
+                .map(|(name, FacetHistogramQuery { interval })| (name.clone(), *interval))
+                .collect();
+            let histogram = index
+                .facets_distribution(&rtxn)
+                .candidates(candidates.clone())
+                .compute_histogram(&intervals)?;
+            Some(FacetRangeCount::from_ranges(histogram))
+        }
+        None => None,
+    };
+
+    let (facet_distribution, facet_stats, facet_hierarchy) = match query.facets {
         Some(ref fields) => {
             let mut facet_distribution = index.facets_distribution(&rtxn);
 
@@ -448,19 +651,32 @@ pub fn perform_search(
                 .unwrap_or(DEFAULT_VALUES_PER_FACET);
             facet_distribution.max_values_per_facet(max_values_by_facet);
 
+            // The order given in the query takes precedence over the one of the settings.
+            let mut sort_facet_values_by =
+                index.sort_facet_values_by(&rtxn).map_err(milli::Error::from)?;
+            if let Some(ref sort) = query.sort_facet_values_by {
+                sort_facet_values_by
+                    .extend(sort.iter().map(|(name, sort)| (name.clone(), (*sort).into())));
+            }
+            facet_distribution.order_by(sort_facet_values_by);
+
             if fields.iter().all(|f| f != "*") {
                 facet_distribution.facets(fields);
             }
             let distribution = facet_distribution.candidates(candidates).execute()?;
-            let stats = facet_distribution.compute_stats()?;
-            (Some(distribution), Some(stats))
+            let stats = facet_distribution.compute_detailed_stats()?;
+            let hierarchy = facet_distribution
+                .compute_hierarchies(query.facet_paths.as_ref().unwrap_or(&BTreeMap::new()))?;
+            let hierarchy = (!hierarchy.is_empty()).then(|| {
+                hierarchy.into_iter().map(|(k, h)| (k, FacetHierarchyResult::from(h))).collect()
+            });
+            (Some(distribution), Some(stats), hierarchy)
         }
-        None => (None, None),
+        None => (None, None, None),
     };
 
-    let facet_stats = facet_stats.map(|stats| {
-        stats.into_iter().map(|(k, (min, max))| (k, FacetStats { min, max })).collect()
-    });
+    let facet_stats = facet_stats
+        .map(|stats| stats.into_iter().map(|(k, stats)| (k, FacetStats::from(stats))).collect());
 
     let result = SearchResult {
         hits: documents,
@@ -469,6 +685,9 @@ pub fn perform_search(
         processing_time_ms: before_search.elapsed().as_millis(),
         facet_distribution,
         facet_stats,
+        facet_ranges,
+        facet_histogram,
+        facet_hierarchy,
     };
     Ok(result)
 }
@@ -616,13 +835,14 @@ fn make_document(
     Ok(document)
 }
 
-fn format_fields<A: AsRef<[u8]>>(
+fn format_fields(
     document: &Document,
     field_ids_map: &FieldsIdsMap,
-    builder: &MatcherBuilder<'_, A>,
+    builder: &MatcherBuilder<'_>,
     formatted_options: &BTreeMap<FieldId, FormatOptions>,
     compute_matches: bool,
     displayable_ids: &BTreeSet<FieldId>,
+    infix_attributes: &[&str],
 ) -> Result<(Option<MatchesPosition>, Document), MeilisearchHttpError> {
     let mut matches_position = compute_matches.then(BTreeMap::new);
     let mut document = document.clone();
@@ -643,9 +863,17 @@ fn format_fields<A: AsRef<[u8]>>(
             })
             .map(|(_, option)| *option)
             .reduce(|acc, option| acc.merge(option));
+        let infix = infix_attributes.iter().any(|attribute| milli::is_faceted_by(key, attribute));
         let mut infos = Vec::new();
 
-        *value = format_value(std::mem::take(value), builder, format, &mut infos, compute_matches);
+        *value = format_value(
+            std::mem::take(value),
+            builder,
+            format,
+            &mut infos,
+            compute_matches,
+            infix,
+        );
 
         if let Some(matches) = matches_position.as_mut() {
             if !infos.is_empty() {
@@ -664,16 +892,18 @@ fn format_fields<A: AsRef<[u8]>>(
     Ok((matches_position, document))
 }
 
-fn format_value<A: AsRef<[u8]>>(
+fn format_value(
     value: Value,
-    builder: &MatcherBuilder<'_, A>,
+    builder: &MatcherBuilder<'_>,
     format_options: Option<FormatOptions>,
     infos: &mut Vec<MatchBounds>,
     compute_matches: bool,
+    infix: bool,
 ) -> Value {
     match value {
         Value::String(old_string) => {
-            let mut matcher = builder.build(&old_string);
+            let mut matcher =
+                if infix { builder.build_infix(&old_string) } else { builder.build(&old_string) };
             if compute_matches {
                 let matches = matcher.matches();
                 infos.extend_from_slice(&matches[..]);
@@ -700,6 +930,7 @@ fn format_value<A: AsRef<[u8]>>(
                         }),
                         infos,
                         compute_matches,
+                        infix,
                     )
                 })
                 .collect(),
@@ -719,6 +950,7 @@ fn format_value<A: AsRef<[u8]>>(
                             }),
                             infos,
                             compute_matches,
+                            infix,
                         ),
                     )
                 })
@@ -727,7 +959,7 @@ fn format_value<A: AsRef<[u8]>>(
         Value::Number(number) => {
             let s = number.to_string();
 
-            let mut matcher = builder.build(&s);
+            let mut matcher = if infix { builder.build_infix(&s) } else { builder.build(&s) };
             if compute_matches {
                 let matches = matcher.matches();
                 infos.extend_from_slice(&matches[..]);
@@ -745,47 +977,42 @@ fn format_value<A: AsRef<[u8]>>(
     }
 }
 
-pub(crate) fn parse_filter(facets: &Value) -> Result<Option<Filter>, MeilisearchHttpError> {
-    match facets {
-        Value::String(expr) => {
-            let condition = Filter::from_str(expr)?;
-            Ok(condition)
-        }
-        Value::Array(arr) => parse_filter_array(arr),
-        v => Err(MeilisearchHttpError::InvalidExpression(&["String", "Array"], v.clone())),
-    }
+fn parse_locales(locales: &[String]) -> Result<Vec<Language>, MeilisearchHttpError> {
+    locales
+        .iter()
+        .map(|locale| {
+            milli::parse_locale(locale).ok_or_else(|| {
+                milli::Error::from(milli::UserError::InvalidSearchLocale { locale: locale.clone() })
+                    .into()
+            })
+        })
+        .collect()
 }
 
-fn parse_filter_array(arr: &[Value]) -> Result<Option<Filter>, MeilisearchHttpError> {
-    let mut ands = Vec::new();
-    for value in arr {
-        match value {
-            Value::String(s) => ands.push(Either::Right(s.as_str())),
-            Value::Array(arr) => {
-                let mut ors = Vec::new();
-                for value in arr {
-                    match value {
-                        Value::String(s) => ors.push(s.as_str()),
-                        v => {
-                            return Err(MeilisearchHttpError::InvalidExpression(
-                                &["String"],
-                                v.clone(),
-                            ))
-                        }
-                    }
-                }
-                ands.push(Either::Left(ors));
-            }
-            v => {
-                return Err(MeilisearchHttpError::InvalidExpression(
-                    &["String", "[String]"],
-                    v.clone(),
-                ))
-            }
+pub(crate) fn parse_filter<'a>(
+    facets: &'a Value,
+    params: Option<&BTreeMap<String, Value>>,
+) -> Result<Option<Filter<'a>>, MeilisearchHttpError> {
+    let filter = match facets {
+        Value::String(expr) => Filter::from_str(expr)?,
+        // the arrays can mix expressions and filters written with the JSON syntax
+        Value::Array(_) | Value::Object(_) => Filter::from_json(facets)?,
+        v => {
+            return Err(MeilisearchHttpError::InvalidExpression(
+                &["String", "Array", "Object"],
+                v.clone(),
+            ))
         }
-    }
+    };
 
-    Ok(Filter::from_array(ands)?)
+    match filter {
+        Some(mut filter) => {
+            // We always bind the parameters to make sure that no placeholder is left without a value.
+            filter.bind_params(params.unwrap_or(&BTreeMap::new()))?;
+            Ok(Some(filter))
+        }
+        None => Ok(None),
+    }
 }
 
 #[cfg(test)]
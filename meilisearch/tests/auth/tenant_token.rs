@@ -377,6 +377,26 @@ async fn filter_search_authorized_filter_token() {
     compute_authorized_search!(tenant_tokens, "color = yellow", 1);
 }
 
+#[actix_rt::test]
+async fn json_filter_search_authorized_filter_token() {
+    let tenant_tokens = vec![
+        hashmap! {
+            "searchRules" => json!({"*": {"filter": "color = blue"}}),
+            "exp" => json!((OffsetDateTime::now_utc() + Duration::hours(1)).unix_timestamp())
+        },
+        hashmap! {
+            "searchRules" => json!({"sales": {"filter": ["color = blue"]}}),
+            "exp" => json!((OffsetDateTime::now_utc() + Duration::hours(1)).unix_timestamp())
+        },
+    ];
+
+    compute_authorized_search!(
+        tenant_tokens,
+        json!({ "field": "color", "op": "=", "value": "yellow" }),
+        1
+    );
+}
+
 /// Tests that those Tenant Token are incompatible with the REFUSED_KEYS defined above.
 #[actix_rt::test]
 async fn error_search_token_forbidden_parent_key() {
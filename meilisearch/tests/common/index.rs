@@ -82,6 +82,11 @@ impl Index<'_> {
         self.service.delete(url).await
     }
 
+    pub async fn compact(&self) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/compact", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, json!({}), self.encoder).await
+    }
+
     pub async fn add_documents(
         &self,
         documents: Value,
@@ -125,6 +130,16 @@ impl Index<'_> {
         self.service.put_encoded(url, documents, self.encoder).await
     }
 
+    pub async fn edit_documents(&self, documents: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/documents/edit", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, documents, self.encoder).await
+    }
+
+    pub async fn edit_documents_by_filter(&self, body: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/documents/edit-by-filter", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, body, self.encoder).await
+    }
+
     pub async fn raw_update_documents(
         &self,
         payload: &str,
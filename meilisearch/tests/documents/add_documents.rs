@@ -1982,3 +1982,92 @@ async fn batch_several_documents_addition() {
     assert_eq!(code, 200, "failed with `{}`", response);
     assert_eq!(response["results"].as_array().unwrap().len(), 120);
 }
+
+#[actix_rt::test]
+async fn add_documents_with_version_attribute() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.update_settings(json!({ "versionAttribute": "version" })).await;
+
+    let documents = json!([
+        { "id": 1, "title": "Dune", "version": 2 },
+        { "id": 2, "title": "Emma", "version": 1 },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    // the stale update of the document 1 must not overwrite the stored one.
+    let documents = json!([
+        { "id": 1, "title": "Dune Messiah", "version": 1 },
+        { "id": 2, "title": "Persuasion", "version": 3 },
+    ]);
+    index.add_documents(documents, None).await;
+    let response = index.wait_task(2).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["details"]["receivedDocuments"], 2);
+    assert_eq!(response["details"]["indexedDocuments"], 1);
+    assert_eq!(response["details"]["skippedDocuments"], 1);
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(response, json!({ "id": 1, "title": "Dune", "version": 2 }));
+
+    let (response, code) = index.get_document(2, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(response, json!({ "id": 2, "title": "Persuasion", "version": 3 }));
+}
+
+#[actix_rt::test]
+async fn add_documents_not_matching_the_schema() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let schema =
+        json!({ "fields": { "price": "number", "tags": "array<string>" }, "required": ["price"] });
+    index.update_settings(json!({ "documentSchema": schema })).await;
+    index.wait_task(0).await;
+
+    let documents = json!([
+        { "id": 1, "price": 10, "tags": ["book"] },
+        { "id": 2, "price": "12,50" },
+    ]);
+    index.add_documents(documents.clone(), None).await;
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "failed", "{}", response);
+    assert_eq!(response["error"]["code"], "invalid_document_schema");
+    assert_eq!(
+        response["error"]["message"],
+        "Document `2` does not match the schema of the index: the field `price` must be of type `number`, found `\"12,50\"`."
+    );
+
+    // only the invalid documents are skipped when asked to.
+    let mut schema = schema;
+    schema["onInvalidDocument"] = json!("skip");
+    index.update_settings(json!({ "documentSchema": schema })).await;
+    index.wait_task(2).await;
+
+    index.add_documents(documents, None).await;
+    let response = index.wait_task(3).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["details"]["receivedDocuments"], 2);
+    assert_eq!(response["details"]["indexedDocuments"], 1);
+    assert_eq!(response["details"]["skippedDocuments"], 1);
+    assert_eq!(
+        response["details"]["invalidDocuments"],
+        json!([{
+            "documentId": "2",
+            "reason": "the field `price` must be of type `number`, found `\"12,50\"`"
+        }])
+    );
+
+    let (_, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    let (_, code) = index.get_document(2, None).await;
+    assert_eq!(code, 404);
+
+    // the partial updates don't have to contain the required fields.
+    index.update_documents(json!([{ "id": 1, "tags": ["novel"] }]), None).await;
+    let response = index.wait_task(4).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["details"]["skippedDocuments"], 0);
+    assert!(response["details"].get("invalidDocuments").is_none(), "{}", response);
+}
@@ -0,0 +1,253 @@
+use serde_json::json;
+
+use crate::common::Server;
+
+#[actix_rt::test]
+async fn edit_documents() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "title": "Dune", "stock": 3, "tags": ["scifi"], "author": { "name": "Frank", "born": 1920 } },
+        { "id": 2, "title": "Emma", "stock": 1 },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let edits = json!([
+        {
+            "id": 1,
+            "stock": { "_increment": 2 },
+            "tags": { "_append": "classic" },
+            "author.name": { "_set": "Frank Herbert" },
+            "author.born": { "_unset": null },
+        },
+        { "id": 2, "title": { "_unset": null } },
+        { "id": 3, "title": { "_set": "Ghost" } },
+    ]);
+    let (response, code) = index.edit_documents(edits).await;
+    assert_eq!(code, 202, "{}", response);
+
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["type"], "documentEdition");
+    assert_eq!(response["details"]["receivedDocuments"], 3);
+    // the document 3 doesn't exist and is not created by the edition.
+    assert_eq!(response["details"]["indexedDocuments"], 2);
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(
+        response,
+        json!({ "id": 1, "title": "Dune", "stock": 5, "tags": ["scifi", "classic"], "author": { "name": "Frank Herbert" } })
+    );
+
+    let (response, code) = index.get_document(2, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(response, json!({ "id": 2, "stock": 1 }));
+
+    let (_response, code) = index.get_document(3, None).await;
+    assert_eq!(code, 404);
+}
+
+#[actix_rt::test]
+async fn error_edit_documents_invalid_operation() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.add_documents(json!([{ "id": 1, "stock": 3 }]), None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.edit_documents(json!([{ "id": 1, "stock": { "_add": 1 } }])).await;
+    assert_eq!(code, 202, "{}", response);
+
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "failed");
+    assert_eq!(
+        response["error"],
+        json!({
+            "message": "Invalid edit of the `stock` field: unknown operation `_add`, expected one of `_set`, `_unset`, `_increment`, `_decrement`, `_append`, `_remove`.",
+            "code": "invalid_document_edit_operation",
+            "type": "invalid_request",
+            "link": "https://docs.meilisearch.com/errors#invalid_document_edit_operation"
+        })
+    );
+}
+
+#[actix_rt::test]
+async fn edit_documents_not_matching_the_schema() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let schema = json!({ "fields": { "price": "number" }, "required": ["price"] });
+    index.update_settings(json!({ "documentSchema": schema })).await;
+    index.wait_task(0).await;
+    index.add_documents(json!([{ "id": 1, "price": 10 }, { "id": 2, "price": 5 }]), None).await;
+    index.wait_task(1).await;
+
+    // the operations are not validated, only the edited documents are.
+    index.edit_documents(json!([{ "id": 1, "price": { "_increment": 2 } }])).await;
+    let response = index.wait_task(2).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+
+    index.edit_documents(json!([{ "id": 1, "price": { "_unset": null } }])).await;
+    let response = index.wait_task(3).await;
+    assert_eq!(response["status"], "failed", "{}", response);
+    assert_eq!(response["error"]["code"], "invalid_document_schema");
+
+    let mut schema = schema;
+    schema["onInvalidDocument"] = json!("skip");
+    index.update_settings(json!({ "documentSchema": schema })).await;
+    index.wait_task(4).await;
+
+    let edits = json!([
+        { "id": 1, "price": { "_set": "free" } },
+        { "id": 2, "price": { "_increment": 1 } },
+    ]);
+    index.edit_documents(edits).await;
+    let response = index.wait_task(5).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["details"]["indexedDocuments"], 1);
+    assert_eq!(response["details"]["skippedDocuments"], 1);
+    assert_eq!(
+        response["details"]["invalidDocuments"],
+        json!([{
+            "documentId": "1",
+            "reason": "the field `price` must be of type `number`, found `\"free\"`"
+        }])
+    );
+
+    // the skipped document keeps its stored version.
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(response, json!({ "id": 1, "price": 12 }));
+    let (response, code) = index.get_document(2, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(response, json!({ "id": 2, "price": 6 }));
+}
+
+#[actix_rt::test]
+async fn error_edit_documents_missing_index() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) =
+        index.edit_documents(json!([{ "id": 1, "title": { "_set": "Dune" } }])).await;
+    assert_eq!(code, 202, "{}", response);
+
+    let response = index.wait_task(0).await;
+    assert_eq!(response["status"], "failed");
+    assert_eq!(response["error"]["code"], "index_not_found");
+
+    let (_response, code) = index.get().await;
+    assert_eq!(code, 404);
+}
+
+#[actix_rt::test]
+async fn edit_documents_by_filter() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.update_settings(json!({ "filterableAttributes": ["genre"] })).await;
+
+    let documents = json!([
+        { "id": 1, "title": "Dune", "genre": "scifi", "stock": 3 },
+        { "id": 2, "title": "Emma", "genre": "romance", "stock": 1 },
+        { "id": 3, "title": "Hyperion", "genre": "scifi" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .edit_documents_by_filter(json!({
+            "filter": "genre = scifi",
+            "edits": { "stock": { "_increment": 1 }, "onSale": true, "id": 42 },
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+
+    let response = index.wait_task(2).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["type"], "documentEdition");
+    assert_eq!(response["details"]["editedDocuments"], 2);
+    assert_eq!(response["details"]["originalFilter"], "\"genre = scifi\"");
+
+    let (response, code) = index.get_all_documents(Default::default()).await;
+    assert_eq!(code, 200);
+    assert_eq!(
+        response["results"],
+        json!([
+            { "id": 1, "title": "Dune", "genre": "scifi", "stock": 4, "onSale": true },
+            { "id": 2, "title": "Emma", "genre": "romance", "stock": 1 },
+            { "id": 3, "title": "Hyperion", "genre": "scifi", "stock": 1, "onSale": true },
+        ])
+    );
+}
+
+#[actix_rt::test]
+async fn edit_documents_by_filter_with_nested_primary_key() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.update_settings(json!({ "filterableAttributes": ["genre"] })).await;
+
+    let documents = json!([
+        { "book": { "id": 1 }, "genre": "scifi", "tags": { "_private": false } },
+        { "book": { "id": 2 }, "genre": "romance" },
+    ]);
+    index.add_documents(documents, Some("book.id")).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .edit_documents_by_filter(json!({
+            "filter": "genre = scifi",
+            "edits": { "tags": { "_private": true } },
+        }))
+        .await;
+    assert_eq!(code, 202, "{}", response);
+
+    let response = index.wait_task(2).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+    assert_eq!(response["details"]["editedDocuments"], 1);
+
+    let (response, code) = index.get_all_documents(Default::default()).await;
+    assert_eq!(code, 200);
+    assert_eq!(
+        response["results"],
+        json!([
+            { "book": { "id": 1 }, "genre": "scifi", "tags": { "_private": true } },
+            { "book": { "id": 2 }, "genre": "romance" },
+        ])
+    );
+}
+
+#[actix_rt::test]
+async fn error_edit_documents_by_filter_invalid_request() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .edit_documents_by_filter(json!({ "filter": "genre = ", "edits": { "stock": 1 } }))
+        .await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_document_edit_filter");
+
+    let (response, code) = index
+        .edit_documents_by_filter(
+            json!({ "filter": "genre = scifi", "edits": { "stock": { "_add": 1 } } }),
+        )
+        .await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(
+        response,
+        json!({
+            "message": "Invalid edit of the `stock` field: unknown operation `_add`, expected one of `_set`, `_unset`, `_increment`, `_decrement`, `_append`, `_remove`.",
+            "code": "invalid_document_edit_operation",
+            "type": "invalid_request",
+            "link": "https://docs.meilisearch.com/errors#invalid_document_edit_operation"
+        })
+    );
+
+    let (response, code) =
+        index.edit_documents_by_filter(json!({ "filter": "genre = scifi" })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["message"], "Missing field `edits`");
+}
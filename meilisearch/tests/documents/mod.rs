@@ -1,5 +1,6 @@
 mod add_documents;
 mod delete_documents;
+mod edit_documents;
 mod errors;
 mod get_documents;
 mod update_documents;
@@ -0,0 +1,61 @@
+use serde_json::json;
+
+use crate::common::{GetAllDocumentsOptions, Server};
+
+#[actix_rt::test]
+async fn compact_index() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "name": "Alexey" },
+        { "id": 2, "name": "Tamo" },
+        { "id": 3, "name": "Clément" },
+    ]);
+    let (_, code) = index.add_documents(documents, Some("id")).await;
+    assert_eq!(code, 202);
+    index.wait_task(0).await;
+
+    let (_, code) = index.delete_document(2).await;
+    assert_eq!(code, 202);
+    index.wait_task(1).await;
+
+    let (response, code) = index.compact().await;
+    assert_eq!(code, 202);
+    assert_eq!(response["taskUid"], 2);
+    assert_eq!(response["type"], "indexCompaction");
+
+    let response = index.wait_task(2).await;
+    assert_eq!(response["status"], "succeeded");
+    assert!(response["details"]["reclaimedBytes"].is_u64());
+
+    // the index is still usable after its environment was swapped
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    assert_eq!(response["total"], 2);
+
+    let (response, code) = index.stats().await;
+    assert_eq!(code, 200);
+    assert_eq!(response["numberOfDocuments"], 2);
+}
+
+#[actix_rt::test]
+async fn error_compact_unexisting_index() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (_, code) = index.compact().await;
+    assert_eq!(code, 202);
+
+    let response = index.wait_task(0).await;
+
+    let expected_response = json!({
+        "message": "Index `test` not found.",
+        "code": "index_not_found",
+        "type": "invalid_request",
+        "link": "https://docs.meilisearch.com/errors#index_not_found"
+    });
+
+    assert_eq!(response["status"], "failed");
+    assert_eq!(response["error"], expected_response);
+}
@@ -358,6 +358,107 @@ async fn search_with_filter_array_notation() {
     assert_eq!(response["hits"].as_array().unwrap().len(), 3);
 }
 
+#[actix_rt::test]
+async fn search_with_filter_params() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"filterableAttributes": ["title"]})).await;
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "title = $title",
+            "filterParams": { "title": "Gläss" }
+        }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": [["title = $first", "title = $second"]],
+            "filterParams": { "first": "Shazam!", "second": "Gläss" }
+        }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"].as_array().unwrap().len(), 2);
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "title = '$title'",
+            "filterParams": { "title": "Gläss" }
+        }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"].as_array().unwrap().len(), 0);
+
+    let (response, code) = index.search_post(json!({ "filter": "title = $title" })).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "The `$title` placeholder has no value.\n9:15 title = $title",
+      "code": "invalid_search_filter",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_with_json_filter() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"filterableAttributes": ["title"]})).await;
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": { "field": "title", "op": "=", "value": "Gläss" }
+        }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"].as_array().unwrap().len(), 1);
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": {
+                "and": [
+                    { "field": "title", "op": "EXISTS" },
+                    { "not": { "in": { "field": "title", "values": ["Shazam!", "Gläss"] } } }
+                ]
+            }
+        }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"].as_array().unwrap().len(), 3);
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": { "and": [{ "field": "title", "value": "Gläss" }] }
+        }))
+        .await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_search_filter");
+    assert!(response["message"].as_str().unwrap().contains(".and[0]"), "{}", response);
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": { "or": [{ "field": "id", "op": "=", "value": 1 }] }
+        }))
+        .await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_search_filter");
+    assert!(response["message"].as_str().unwrap().ends_with("at `.or[0].field`"), "{}", response);
+}
+
 #[actix_rt::test]
 async fn search_with_sort_on_numbers() {
     let server = Server::new().await;
@@ -750,3 +851,470 @@ async fn faceting_max_values_per_facet() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn faceting_sort_facet_values_by() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index
+        .update_settings(json!({
+            "filterableAttributes": ["color"],
+            "faceting": { "maxValuesPerFacet": 2 }
+        }))
+        .await;
+
+    let documents = json!([
+        { "id": 0, "color": "azure" },
+        { "id": 1, "color": "blue" },
+        { "id": 2, "color": "blue" },
+        { "id": 3, "color": "blue" },
+        { "id": 4, "color": "green" },
+        { "id": 5, "color": "red" },
+        { "id": 6, "color": "red" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let colors = |response: &Value| -> Vec<(String, u64)> {
+        let colors = response["facetDistribution"]["color"].as_object().unwrap();
+        colors.iter().map(|(color, count)| (color.clone(), count.as_u64().unwrap())).collect()
+    };
+
+    let (response, code) = index.search_post(json!({ "facets": ["color"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(colors(&response), [("azure".to_string(), 1), ("blue".to_string(), 3)]);
+
+    let (response, code) = index
+        .search_post(json!({ "facets": ["color"], "sortFacetValuesBy": { "color": "count" } }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(colors(&response), [("blue".to_string(), 3), ("red".to_string(), 2)]);
+
+    index.update_settings(json!({ "faceting": { "sortFacetValuesBy": { "*": "count" } } })).await;
+    index.wait_task(2).await;
+
+    let (response, code) = index.search_post(json!({ "facets": ["color"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(colors(&response), [("blue".to_string(), 3), ("red".to_string(), 2)]);
+
+    let (response, code) = index
+        .search_post(json!({ "facets": ["color"], "sortFacetValuesBy": { "color": "alpha" } }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(colors(&response), [("azure".to_string(), 1), ("blue".to_string(), 3)]);
+}
+
+#[actix_rt::test]
+async fn tokenizer_customization() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "name": "wi-fi router" },
+        { "id": 1, "name": "fi fo fum" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let ids = |response: &Value| -> Vec<u64> {
+        response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].as_u64().unwrap()).collect()
+    };
+
+    let (response, code) = index.search_post(json!({ "q": "fi" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(ids(&response), [0, 1]);
+
+    index
+        .update_settings(json!({
+            "nonSeparatorTokens": ["-"],
+            "separatorTokens": ["§"],
+            "dictionary": ["c++"]
+        }))
+        .await;
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["nonSeparatorTokens"], json!(["-"]));
+    assert_eq!(response["separatorTokens"], json!(["§"]));
+    assert_eq!(response["dictionary"], json!(["c++"]));
+
+    // the documents are reindexed with the new separators
+    let (response, code) = index.search_post(json!({ "q": "fi" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(ids(&response), [1]);
+
+    let (response, code) = index.search_post(json!({ "q": "wi-fi" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(ids(&response), [0]);
+}
+
+#[actix_rt::test]
+async fn localized_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "localizedAttributes": { "title": ["jpn"] } })).await;
+    let response = index.wait_task(0).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["localizedAttributes"], json!({ "title": ["jpn"] }));
+
+    let documents = json!([
+        { "id": 0, "title": "東京のお寿司" },
+        { "id": 1, "title": "北京烤鸭" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({ "q": "東京", "locales": ["jpn"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    let hits = response["hits"].as_array().unwrap();
+    assert_eq!(hits.len(), 1, "{}", response);
+    assert_eq!(hits[0]["id"], json!(0));
+
+    let (response, code) = index.search_post(json!({ "q": "東京", "locales": ["klingon"] })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_search_locales");
+
+    index.update_settings(json!({ "localizedAttributes": { "title": ["japanese"] } })).await;
+    let response = index.wait_task(2).await;
+    assert_eq!(response["status"], "failed", "{}", response);
+    assert_eq!(response["error"]["code"], "invalid_settings_localized_attributes");
+}
+
+#[actix_rt::test]
+async fn stemming() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "title": "She runs every morning" },
+        { "id": 1, "title": "The running shoes" },
+        { "id": 2, "title": "A long run" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let ids = |response: &Value| -> Vec<u64> {
+        response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].as_u64().unwrap()).collect()
+    };
+
+    let (response, code) = index.search_post(json!({ "q": "running" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(ids(&response), [1]);
+
+    index.update_settings(json!({ "stemming": ["eng"] })).await;
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+
+    // the exact form is ranked before the stemmed ones
+    let (response, code) = index.search_post(json!({ "q": "running" })).await;
+    assert_eq!(code, 200, "{}", response);
+    let hits = ids(&response);
+    assert_eq!(hits.len(), 3, "{}", response);
+    assert_eq!(hits[0], 1);
+
+    index.update_settings(json!({ "stemming": ["jpn"] })).await;
+    let response = index.wait_task(2).await;
+    assert_eq!(response["status"], "failed", "{}", response);
+    assert_eq!(response["error"]["code"], "invalid_settings_stemming");
+}
+
+#[actix_rt::test]
+async fn infix_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "reference": "AB-45219-X", "title": "Gaming laptop 45219" },
+        { "id": 1, "reference": "CD-10000-Y", "title": "Office 45219" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({ "q": "5219" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"], json!([]));
+
+    index.update_settings(json!({ "infixAttributes": ["reference"] })).await;
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+
+    let (response, code) = index
+        .search_post(json!({ "q": "5219", "attributesToHighlight": ["reference", "title"] }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    let hits = response["hits"].as_array().unwrap();
+    assert_eq!(hits.len(), 1, "{}", response);
+    assert_eq!(hits[0]["id"], 0);
+    assert_eq!(hits[0]["_formatted"]["reference"], "AB-4<em>5219</em>-X");
+    // the title is not an infix attribute, its words containing the query word don't match.
+    assert_eq!(hits[0]["_formatted"]["title"], "Gaming laptop 45219");
+}
+
+#[actix_rt::test]
+async fn phonetic_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "name": "Schmidt" },
+        { "id": 1, "name": "Smith" },
+        { "id": 2, "name": "Jones" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.search_post(json!({ "q": "smith" })).await;
+    assert_eq!(code, 200, "{}", response);
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    assert_eq!(ids, [&json!(1)], "{}", response);
+
+    index.update_settings(json!({ "phoneticAttributes": ["name"] })).await;
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+
+    let (response, code) = index.search_post(json!({ "q": "smith" })).await;
+    assert_eq!(code, 200, "{}", response);
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    assert_eq!(ids, [&json!(1), &json!(0)], "{}", response);
+}
+
+#[actix_rt::test]
+async fn typos_disabled_on_numbers() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "title": "Model 10245" },
+        { "id": 1, "title": "Model 10246" },
+        { "id": 2, "title": "Wireless mouse" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let ids = |response: &Value| -> Vec<u64> {
+        response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].as_u64().unwrap()).collect()
+    };
+
+    let (response, code) = index.search_post(json!({ "q": "10245" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(ids(&response), [0, 1]);
+
+    index.update_settings(json!({ "typoTolerance": { "disableOnNumbers": true } })).await;
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+
+    let (response, code) = index.search_post(json!({ "q": "10245" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(ids(&response), [0]);
+
+    // words that are not only made of digits are still typo tolerant
+    let (response, code) = index.search_post(json!({ "q": "wirelss" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(ids(&response), [2]);
+}
+
+#[actix_rt::test]
+async fn hierarchical_facets() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index
+        .update_settings(json!({
+            "filterableAttributes": ["category"],
+            "faceting": { "hierarchicalFacets": { "category": " > " } }
+        }))
+        .await;
+
+    let documents = json!([
+        { "id": 0, "category": "Electronics > Laptops > Gaming" },
+        { "id": 1, "category": "Electronics > Laptops > Office" },
+        { "id": 2, "category": "Electronics > Phones" },
+        { "id": 3, "category": ["Books > Novels", "Electronics > Phones"] },
+        { "id": 4, "category": "Books" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({ "facets": ["category"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["facetHierarchy"],
+        json!({ "category": {
+            "path": [],
+            "children": [
+                { "value": "Books", "count": 2 },
+                { "value": "Electronics", "count": 4 },
+            ],
+        } })
+    );
+
+    let (response, code) = index
+        .search_post(json!({
+            "facets": ["category"],
+            "facetPaths": { "category": "Electronics > Laptops" }
+        }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["facetHierarchy"],
+        json!({ "category": {
+            "path": [
+                { "value": "Electronics", "count": 4 },
+                { "value": "Electronics > Laptops", "count": 2 },
+            ],
+            "children": [
+                { "value": "Electronics > Laptops > Gaming", "count": 1 },
+                { "value": "Electronics > Laptops > Office", "count": 1 },
+            ],
+        } })
+    );
+
+    // the levels are also regular facet values
+    let (response, code) = index.search_post(json!({ "filter": "category = Electronics" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"].as_array().unwrap().len(), 4, "{}", response);
+    let (response, code) =
+        index.search_post(json!({ "filter": "category = 'Electronics > Laptops'" })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(response["hits"].as_array().unwrap().len(), 2, "{}", response);
+
+    let (response, code) =
+        index.search_post(json!({ "facets": ["category"], "facetPaths": { "id": "1" } })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_search_facet_paths");
+}
+
+#[actix_rt::test]
+async fn facet_ranges_and_histogram() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "filterableAttributes": ["price"] })).await;
+
+    let documents = json!([
+        { "id": 0, "price": 3 },
+        { "id": 1, "price": 9.5 },
+        { "id": 2, "price": 10 },
+        { "id": 3, "price": 48 },
+        { "id": 4, "price": 50 },
+        { "id": 5, "price": 120 },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "facetRanges": { "price": [{ "to": 10 }, { "from": 10, "to": 50 }, { "from": 50 }] },
+            "facetHistogram": { "price": { "interval": 50 } }
+        }))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["facetRanges"],
+        json!({ "price": [
+            { "to": 10.0, "count": 2 },
+            { "from": 10.0, "to": 50.0, "count": 2 },
+            { "from": 50.0, "count": 2 },
+        ] })
+    );
+    assert_eq!(
+        response["facetHistogram"],
+        json!({ "price": [
+            { "from": 0.0, "to": 50.0, "count": 4 },
+            { "from": 50.0, "to": 100.0, "count": 1 },
+            { "from": 100.0, "to": 150.0, "count": 1 },
+        ] })
+    );
+    assert!(response.get("facetDistribution").is_none(), "{}", response);
+
+    let (response, code) =
+        index.search_post(json!({ "facetHistogram": { "price": { "interval": 0 } } })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_search_facet_histogram");
+
+    let (response, code) =
+        index.search_post(json!({ "facetHistogram": { "id": { "interval": 10 } } })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_search_facet_histogram");
+
+    let (response, code) =
+        index.search_post(json!({ "facetRanges": { "id": [{ "to": 10 }] } })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_search_facet_ranges");
+}
+
+#[actix_rt::test]
+async fn facet_stats() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "filterableAttributes": ["price"] })).await;
+
+    let documents = json!([
+        { "id": 0, "price": 10 },
+        { "id": 1, "price": 20 },
+        { "id": 2, "price": 20 },
+        { "id": 3, "price": 50 },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({ "facets": ["price"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(
+        response["facetStats"],
+        json!({ "price": {
+            "min": 10.0,
+            "max": 50.0,
+            "count": 4,
+            "sum": 100.0,
+            "avg": 25.0,
+            "distinctCount": 3,
+            "percentiles": { "p25": 10.0, "p50": 20.0, "p75": 20.0, "p90": 50.0, "p99": 50.0 },
+        } })
+    );
+}
+
+#[actix_rt::test]
+async fn expired_documents_are_not_returned() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index
+        .update_settings(json!({
+            "expiryAttribute": "expiresAt",
+            "filterableAttributes": ["expiresAt"],
+        }))
+        .await;
+    index.wait_task(0).await;
+
+    let now = time::OffsetDateTime::now_utc().unix_timestamp();
+    let documents = json!([
+        { "id": 0, "title": "Shazam!", "expiresAt": now - 60 },
+        { "id": 1, "title": "Shazam! Fury of the Gods", "expiresAt": now + 3600 },
+        { "id": 2, "title": "Shazam! The Animated Series" },
+        { "id": 3, "title": "Shazam! The Movie", "expiresAt": "2001-01-01T00:00:00Z" },
+    ]);
+    index.add_documents(documents, None).await;
+    let response = index.wait_task(1).await;
+    assert_eq!(response["status"], "succeeded", "{}", response);
+
+    let (response, code) = index.search_post(json!({ "q": "shazam" })).await;
+    assert_eq!(code, 200, "{}", response);
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    assert_eq!(ids, [&json!(1), &json!(2)], "{}", response);
+    assert_eq!(response["estimatedTotalHits"], 2, "{}", response);
+
+    let (response, code) =
+        index.search_post(json!({ "filter": format!("expiresAt > {}", now + 60) })).await;
+    assert_eq!(code, 200, "{}", response);
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    assert_eq!(ids, [&json!(1)], "{}", response);
+}
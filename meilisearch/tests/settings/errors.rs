@@ -274,7 +274,7 @@ async fn settings_bad_typo_tolerance() {
     snapshot!(code, @"400 Bad Request");
     snapshot!(json_string!(response), @r###"
     {
-      "message": "Unknown field `typoTolerance`: expected one of `enabled`, `minWordSizeForTypos`, `disableOnWords`, `disableOnAttributes`",
+      "message": "Unknown field `typoTolerance`: expected one of `enabled`, `minWordSizeForTypos`, `disableOnWords`, `disableOnAttributes`, `disableOnNumbers`",
       "code": "invalid_settings_typo_tolerance",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_settings_typo_tolerance"
@@ -337,3 +337,68 @@ async fn settings_bad_pagination() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn settings_bad_ingestion_rules() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(
+            json!({ "ingestionRules": { "rules": [{ "type": "uppercase", "field": "name" }] } }),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `uppercase` at `.ingestionRules.rules[0].type`: expected one of `concat`, `lowercase`, `trim`, `rename`, `drop`, `default`, `geo`, `split`",
+      "code": "invalid_settings_ingestion_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_ingestion_rules"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({ "ingestionRules": { "rules": [{ "type": "lowercase" }] } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `field` inside `.ingestionRules.rules[0]`",
+      "code": "invalid_settings_ingestion_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_ingestion_rules"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_document_schema() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(json!({ "documentSchema": { "fields": { "price": "integer" } } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.documentSchema.fields.price`: `integer` is not a valid field type. Available field types are `number`, `string`, `boolean`, `date`, `geo` and `array<type>`.",
+      "code": "invalid_settings_document_schema",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_document_schema"
+    }
+    "###);
+
+    let (response, code) =
+        index.update_settings(json!({ "documentSchema": { "onInvalidDocument": "drop" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `drop` at `.documentSchema.onInvalidDocument`: expected one of `reject`, `skip`",
+      "code": "invalid_settings_document_schema",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_document_schema"
+    }
+    "###);
+}
@@ -11,16 +11,34 @@ static DEFAULT_SETTINGS_VALUES: Lazy<HashMap<&'static str, Value>> = Lazy::new(|
     map.insert("searchable_attributes", json!(["*"]));
     map.insert("filterable_attributes", json!([]));
     map.insert("distinct_attribute", json!(Value::Null));
+    map.insert("version_attribute", json!(Value::Null));
+    map.insert("expiry_attribute", json!(Value::Null));
+    map.insert("ingestion_rules", json!({ "version": 0, "rules": [] }));
+    map.insert(
+        "document_schema",
+        json!({ "fields": {}, "required": [], "onInvalidDocument": "reject" }),
+    );
     map.insert(
         "ranking_rules",
         json!(["words", "typo", "proximity", "attribute", "sort", "exactness"]),
     );
     map.insert("stop_words", json!([]));
+    map.insert("non_separator_tokens", json!([]));
+    map.insert("separator_tokens", json!([]));
+    map.insert("dictionary", json!([]));
+    map.insert("localized_attributes", json!({}));
+    map.insert("stemming", json!([]));
+    map.insert("infix_attributes", json!([]));
+    map.insert("phonetic_attributes", json!([]));
     map.insert("synonyms", json!({}));
     map.insert(
         "faceting",
         json!({
             "maxValuesPerFacet": json!(100),
+            "sortFacetValuesBy": {
+                "*": "alpha"
+            },
+            "hierarchicalFacets": {}
         }),
     );
     map.insert(
@@ -48,21 +66,39 @@ async fn get_settings() {
     let (response, code) = index.settings().await;
     assert_eq!(code, 200);
     let settings = response.as_object().unwrap();
-    assert_eq!(settings.keys().len(), 11);
+    assert_eq!(settings.keys().len(), 22);
     assert_eq!(settings["displayedAttributes"], json!(["*"]));
     assert_eq!(settings["searchableAttributes"], json!(["*"]));
     assert_eq!(settings["filterableAttributes"], json!([]));
     assert_eq!(settings["sortableAttributes"], json!([]));
     assert_eq!(settings["distinctAttribute"], json!(null));
+    assert_eq!(settings["versionAttribute"], json!(null));
+    assert_eq!(settings["expiryAttribute"], json!(null));
+    assert_eq!(settings["ingestionRules"], json!({ "version": 0, "rules": [] }));
+    assert_eq!(
+        settings["documentSchema"],
+        json!({ "fields": {}, "required": [], "onInvalidDocument": "reject" })
+    );
     assert_eq!(
         settings["rankingRules"],
         json!(["words", "typo", "proximity", "attribute", "sort", "exactness"])
     );
     assert_eq!(settings["stopWords"], json!([]));
+    assert_eq!(settings["nonSeparatorTokens"], json!([]));
+    assert_eq!(settings["separatorTokens"], json!([]));
+    assert_eq!(settings["dictionary"], json!([]));
+    assert_eq!(settings["localizedAttributes"], json!({}));
+    assert_eq!(settings["stemming"], json!([]));
+    assert_eq!(settings["infixAttributes"], json!([]));
+    assert_eq!(settings["phoneticAttributes"], json!([]));
     assert_eq!(
         settings["faceting"],
         json!({
             "maxValuesPerFacet": 100,
+            "sortFacetValuesBy": {
+                "*": "alpha"
+            },
+            "hierarchicalFacets": {}
         })
     );
     assert_eq!(
@@ -265,7 +301,18 @@ test_setting_routes!(
     displayed_attributes put,
     searchable_attributes put,
     distinct_attribute put,
+    version_attribute put,
+    expiry_attribute put,
+    ingestion_rules put,
+    document_schema put,
     stop_words put,
+    non_separator_tokens put,
+    separator_tokens put,
+    dictionary put,
+    localized_attributes put,
+    stemming put,
+    infix_attributes put,
+    phonetic_attributes put,
     ranking_rules put,
     synonyms put,
     pagination patch,
@@ -310,3 +357,32 @@ async fn set_and_reset_distinct_attribute_with_dedicated_route() {
 
     assert_eq!(response, json!(null));
 }
+
+#[actix_rt::test]
+async fn set_ingestion_rules_bumps_their_version() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let rules = json!([
+        { "type": "concat", "fields": ["first", "last"], "separator": " ", "into": "name" },
+        { "type": "drop", "field": "first" }
+    ]);
+    index.update_settings(json!({ "ingestionRules": { "rules": rules } })).await;
+    index.wait_task(0).await;
+    index.update_settings(json!({ "ingestionRules": { "rules": rules } })).await;
+    index.wait_task(1).await;
+
+    let (response, _) = index.settings().await;
+    assert_eq!(response["ingestionRules"], json!({ "version": 1, "rules": rules }));
+
+    index.add_documents(json!([{ "id": 1, "first": "Jane", "last": "Doe" }]), None).await;
+    index.wait_task(2).await;
+    let (response, _) = index.get_document(1, None).await;
+    assert_eq!(response, json!({ "id": 1, "last": "Doe", "name": "Jane Doe" }));
+
+    index.update_settings(json!({ "ingestionRules": null })).await;
+    index.wait_task(3).await;
+
+    let (response, _) = index.settings().await;
+    assert_eq!(response["ingestionRules"], json!({ "version": 2, "rules": [] }));
+}
@@ -46,6 +46,48 @@ async fn get_task_status() {
     // TODO check response format, as per #48
 }
 
+#[actix_rt::test]
+async fn get_task_progress() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    // a settings update followed by a document addition are processed in the same batch,
+    // the progress must never go back when the documents are indexed after the settings.
+    index.update_settings(json!({ "filterableAttributes": ["n"] })).await;
+    let documents: Vec<_> = (0..20_000)
+        .map(|id| json!({ "id": id, "n": id % 100, "content": format!("document number {id}") }))
+        .collect();
+    index.add_documents(json!(documents), None).await;
+
+    let mut previous_percentage = 0.0;
+    loop {
+        let (response, code) = index.get_task(1).await;
+        assert_eq!(code, 200, "{}", response);
+
+        match response["status"].as_str().unwrap() {
+            "enqueued" => assert!(response.get("progress").is_none(), "{}", response),
+            "processing" => {
+                // the progress is only known once milli reported its first step
+                if let Some(progress) = response.get("progress") {
+                    let percentage = progress["percentage"].as_f64().unwrap();
+                    assert!((0.0..=100.0).contains(&percentage), "{}", response);
+                    assert!(percentage >= previous_percentage, "{}", response);
+                    previous_percentage = percentage;
+                    assert!(progress["currentStep"].is_string(), "{}", response);
+                    let steps = progress["steps"].as_array().unwrap();
+                    assert!(!steps.is_empty(), "{}", response);
+                    assert_eq!(steps.last().unwrap()["name"], progress["currentStep"]);
+                }
+            }
+            _ => {
+                assert_eq!(response["status"], "succeeded", "{}", response);
+                assert!(response.get("progress").is_none(), "{}", response);
+                break;
+            }
+        }
+    }
+}
+
 #[actix_rt::test]
 async fn list_tasks() {
     let server = Server::new().await;
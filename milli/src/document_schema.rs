@@ -0,0 +1,242 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+use crate::Object;
+
+/// The types of the fields and the fields that the documents sent to an index must have.
+///
+/// Only the top-level fields of the documents are checked, the fields
+/// that are not declared in the schema can hold any value.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct DocumentSchema {
+    #[serde(default)]
+    pub fields: BTreeMap<String, FieldType>,
+    #[serde(default)]
+    pub required: BTreeSet<String>,
+    #[serde(default)]
+    pub on_invalid_document: InvalidDocumentPolicy,
+}
+
+/// What to do with a batch of documents containing documents not matching the schema.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InvalidDocumentPolicy {
+    /// Rejects the whole batch of documents.
+    #[default]
+    Reject,
+    /// Only skips the invalid documents and indexes the other ones.
+    Skip,
+}
+
+/// The type of the value of a field, `null` is never a valid value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum FieldType {
+    Number,
+    String,
+    Boolean,
+    /// An RFC 3339 string or a Unix timestamp in seconds.
+    Date,
+    /// An object with a numeric `lat` and `lng`.
+    Geo,
+    Array(Box<FieldType>),
+}
+
+/// A document that was skipped because it does not match the schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvalidDocument {
+    pub document_id: String,
+    pub reason: String,
+}
+
+#[derive(Error, Debug)]
+#[error(
+    "`{0}` is not a valid field type. Available field types are `number`, `string`, `boolean`, \
+    `date`, `geo` and `array<type>`."
+)]
+pub struct InvalidFieldType(String);
+
+impl DocumentSchema {
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty() && self.required.is_empty()
+    }
+
+    /// Returns the reason why the document doesn't match the schema, if it doesn't.
+    ///
+    /// A partial document only contains the fields to update, the other fields keep their
+    /// stored values. The required fields are therefore not checked on partial documents.
+    pub fn validate(&self, document: &Object, partial: bool) -> Option<String> {
+        if !partial {
+            for field in &self.required {
+                if document.get(field).map_or(true, Value::is_null) {
+                    return Some(format!("the required field `{field}` is missing"));
+                }
+            }
+        }
+
+        for (field, field_type) in &self.fields {
+            match document.get(field) {
+                Some(value) if !field_type.matches(value) => {
+                    return Some(format!(
+                        "the field `{field}` must be of type `{field_type}`, found `{value}`"
+                    ));
+                }
+                _ => (),
+            }
+        }
+
+        None
+    }
+}
+
+impl FieldType {
+    pub fn matches(&self, value: &Value) -> bool {
+        match (self, value) {
+            (FieldType::Number, Value::Number(_)) => true,
+            (FieldType::String, Value::String(_)) => true,
+            (FieldType::Boolean, Value::Bool(_)) => true,
+            (FieldType::Date, Value::Number(number)) => number.is_i64(),
+            (FieldType::Date, Value::String(date)) => OffsetDateTime::parse(date, &Rfc3339).is_ok(),
+            (FieldType::Geo, Value::Object(object)) => {
+                object.len() == 2
+                    && object.get("lat").map_or(false, Value::is_number)
+                    && object.get("lng").map_or(false, Value::is_number)
+            }
+            (FieldType::Array(field_type), Value::Array(values)) => {
+                values.iter().all(|value| field_type.matches(value))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for FieldType {
+    type Err = InvalidFieldType;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "number" => Ok(FieldType::Number),
+            "string" => Ok(FieldType::String),
+            "boolean" => Ok(FieldType::Boolean),
+            "date" => Ok(FieldType::Date),
+            "geo" => Ok(FieldType::Geo),
+            text => match text.strip_prefix("array<").and_then(|t| t.strip_suffix('>')) {
+                Some(inner) => match inner.parse() {
+                    Ok(field_type) => Ok(FieldType::Array(Box::new(field_type))),
+                    Err(_) => Err(InvalidFieldType(s.to_string())),
+                },
+                None => Err(InvalidFieldType(s.to_string())),
+            },
+        }
+    }
+}
+
+impl TryFrom<String> for FieldType {
+    type Error = InvalidFieldType;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<FieldType> for String {
+    fn from(field_type: FieldType) -> String {
+        field_type.to_string()
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldType::Number => f.write_str("number"),
+            FieldType::String => f.write_str("string"),
+            FieldType::Boolean => f.write_str("boolean"),
+            FieldType::Date => f.write_str("date"),
+            FieldType::Geo => f.write_str("geo"),
+            FieldType::Array(field_type) => write!(f, "array<{field_type}>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parse_field_types() {
+        let field_type: FieldType = "array<array<date>>".parse().unwrap();
+        assert_eq!(
+            field_type,
+            FieldType::Array(Box::new(FieldType::Array(Box::new(FieldType::Date))))
+        );
+        assert_eq!(field_type.to_string(), "array<array<date>>");
+        assert!("array<>".parse::<FieldType>().is_err());
+        assert!("integer".parse::<FieldType>().is_err());
+    }
+
+    #[test]
+    fn validate_documents() {
+        let schema: DocumentSchema = serde_json::from_value(json!({
+            "fields": {
+                "price": "number",
+                "tags": "array<string>",
+                "published": "date",
+                "_geo": "geo",
+            },
+            "required": ["price"],
+        }))
+        .unwrap();
+
+        let document = |value: Value| match value {
+            Value::Object(object) => object,
+            _ => panic!("documents must be objects"),
+        };
+
+        let valid = document(json!({
+            "price": 12.5,
+            "tags": ["a", "b"],
+            "published": "2023-06-01T10:00:00Z",
+            "_geo": { "lat": 45.3, "lng": 2.5 },
+            "other": null,
+        }));
+        assert_eq!(schema.validate(&valid, false), None);
+        assert_eq!(
+            schema.validate(&document(json!({ "price": 1, "published": 1685613600 })), false),
+            None
+        );
+
+        assert_eq!(
+            schema.validate(&document(json!({ "price": "12,50" })), false).unwrap(),
+            "the field `price` must be of type `number`, found `\"12,50\"`"
+        );
+        assert_eq!(
+            schema.validate(&document(json!({ "price": 1, "tags": null })), false).unwrap(),
+            "the field `tags` must be of type `array<string>`, found `null`"
+        );
+        assert_eq!(
+            schema.validate(&document(json!({ "price": null })), false).unwrap(),
+            "the required field `price` is missing"
+        );
+        assert!(schema
+            .validate(&document(json!({ "price": 1, "published": "yesterday" })), false)
+            .is_some());
+        assert!(schema
+            .validate(&document(json!({ "price": 1, "_geo": { "lat": "45" } })), false)
+            .is_some());
+
+        // The partial documents don't have to contain the required fields.
+        assert_eq!(schema.validate(&document(json!({ "tags": ["a"] })), true), None);
+        assert!(schema.validate(&document(json!({ "price": "12,50" })), true).is_some());
+    }
+}
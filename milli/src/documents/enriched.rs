@@ -2,13 +2,14 @@ use std::fs::File;
 use std::{io, str};
 
 use obkv::KvReader;
+use roaring::RoaringBitmap;
 
 use super::{
     DocumentsBatchCursor, DocumentsBatchCursorError, DocumentsBatchIndex, DocumentsBatchReader,
     Error,
 };
 use crate::update::DocumentId;
-use crate::FieldId;
+use crate::{FieldId, InvalidDocument};
 
 /// The `EnrichedDocumentsBatchReader` provides a way to iterate over documents that have
 /// been created with a `DocumentsBatchWriter` and, for the enriched data,
@@ -20,6 +21,10 @@ pub struct EnrichedDocumentsBatchReader<R> {
     documents: DocumentsBatchReader<R>,
     primary_key: String,
     external_ids: grenad::ReaderCursor<File>,
+    /// The positions of the documents that must not be returned by the cursor.
+    skipped_documents: RoaringBitmap,
+    /// The skipped documents along with the reason why they don't match the schema.
+    invalid_documents: Vec<InvalidDocument>,
 }
 
 impl<R: io::Read + io::Seek> EnrichedDocumentsBatchReader<R> {
@@ -27,12 +32,16 @@ impl<R: io::Read + io::Seek> EnrichedDocumentsBatchReader<R> {
         documents: DocumentsBatchReader<R>,
         primary_key: String,
         external_ids: grenad::Reader<File>,
+        skipped_documents: RoaringBitmap,
+        invalid_documents: Vec<InvalidDocument>,
     ) -> Result<Self, Error> {
         if documents.documents_count() as u64 == external_ids.len() {
             Ok(EnrichedDocumentsBatchReader {
                 documents,
                 primary_key,
                 external_ids: external_ids.into_cursor()?,
+                skipped_documents,
+                invalid_documents,
             })
         } else {
             Err(Error::InvalidEnrichedData)
@@ -47,6 +56,15 @@ impl<R: io::Read + io::Seek> EnrichedDocumentsBatchReader<R> {
         &self.primary_key
     }
 
+    /// The number of documents of the batch that are skipped by the cursor.
+    pub fn skipped_documents_count(&self) -> u64 {
+        self.skipped_documents.len()
+    }
+
+    pub fn invalid_documents(&self) -> &[InvalidDocument] {
+        &self.invalid_documents
+    }
+
     pub fn is_empty(&self) -> bool {
         self.documents.is_empty()
     }
@@ -59,10 +77,23 @@ impl<R: io::Read + io::Seek> EnrichedDocumentsBatchReader<R> {
     pub fn into_cursor_and_fields_index(
         self,
     ) -> (EnrichedDocumentsBatchCursor<R>, DocumentsBatchIndex) {
-        let EnrichedDocumentsBatchReader { documents, primary_key, mut external_ids } = self;
+        let EnrichedDocumentsBatchReader {
+            documents,
+            primary_key,
+            mut external_ids,
+            skipped_documents,
+            invalid_documents: _,
+        } = self;
         let (documents, fields_index) = documents.into_cursor_and_fields_index();
         external_ids.reset();
-        (EnrichedDocumentsBatchCursor { documents, primary_key, external_ids }, fields_index)
+        let cursor = EnrichedDocumentsBatchCursor {
+            documents,
+            primary_key,
+            external_ids,
+            skipped_documents,
+            position: 0,
+        };
+        (cursor, fields_index)
     }
 }
 
@@ -76,6 +107,8 @@ pub struct EnrichedDocumentsBatchCursor<R> {
     documents: DocumentsBatchCursor<R>,
     primary_key: String,
     external_ids: grenad::ReaderCursor<File>,
+    skipped_documents: RoaringBitmap,
+    position: u32,
 }
 
 impl<R> EnrichedDocumentsBatchCursor<R> {
@@ -86,6 +119,7 @@ impl<R> EnrichedDocumentsBatchCursor<R> {
     pub fn reset(&mut self) {
         self.documents.reset();
         self.external_ids.reset();
+        self.position = 0;
     }
 }
 
@@ -95,6 +129,13 @@ impl<R: io::Read + io::Seek> EnrichedDocumentsBatchCursor<R> {
     pub fn next_enriched_document(
         &mut self,
     ) -> Result<Option<EnrichedDocument>, DocumentsBatchCursorError> {
+        while self.skipped_documents.contains(self.position) {
+            self.documents.next_document()?;
+            self.external_ids.move_on_next()?;
+            self.position += 1;
+        }
+
+        self.position += 1;
         let document = self.documents.next_document()?;
         let document_id = match self.external_ids.move_on_next()? {
             Some((_, bytes)) => serde_json::from_slice(bytes).map(Some)?,
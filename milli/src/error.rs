@@ -12,7 +12,7 @@ use crate::documents::{self, DocumentsBatchCursorError};
 use crate::{CriterionError, DocumentId, FieldId, Object, SortError};
 
 pub fn is_reserved_keyword(keyword: &str) -> bool {
-    ["_geo", "_geoDistance", "_geoPoint", "_geoRadius", "_geoBoundingBox"].contains(&keyword)
+    ["_geo", "_geoDistance", "_geoPoint", "_geoRadius", "_geoBoundingBox", "_size"].contains(&keyword)
 }
 
 #[derive(Error, Debug)]
@@ -103,11 +103,44 @@ A document identifier can be of type integer or string, \
 only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and underscores (_).", .document_id.to_string()
     )]
     InvalidDocumentId { document_id: Value },
+    #[error("Invalid edit of the `{field}` field: {message}.")]
+    InvalidDocumentEditOperation { field: String, message: String },
+    #[error("Document `{document_id}` does not match the schema of the index: {reason}.")]
+    InvalidDocumentSchema { document_id: String, reason: String },
     #[error("Invalid facet distribution, {}", format_invalid_filter_distribution(.invalid_facets_name, .valid_facets_name))]
     InvalidFacetsDistribution {
         invalid_facets_name: BTreeSet<String>,
         valid_facets_name: BTreeSet<String>,
     },
+    #[error("Invalid facet ranges, {}", format_invalid_filter_distribution(.invalid_facets_name, .valid_facets_name))]
+    InvalidFacetRanges {
+        invalid_facets_name: BTreeSet<String>,
+        valid_facets_name: BTreeSet<String>,
+    },
+    #[error("Invalid facet histogram, {}", format_invalid_filter_distribution(.invalid_facets_name, .valid_facets_name))]
+    InvalidFacetHistogram {
+        invalid_facets_name: BTreeSet<String>,
+        valid_facets_name: BTreeSet<String>,
+    },
+    #[error("The `{facet_name}` facet is not a hierarchical facet, the hierarchical facets are: {}.", .valid_facets_name.iter().map(|name| format!("`{name}`")).collect::<Vec<_>>().join(", "))]
+    InvalidFacetPath { facet_name: String, valid_facets_name: BTreeSet<String> },
+    #[error("The separator of the `{facet_name}` hierarchical facet must not be empty.")]
+    InvalidHierarchicalFacetSeparator { facet_name: String },
+    #[error("The `{attribute_name}` localized attribute has an invalid locale `{locale}`, expected an ISO 639-3 language code such as `jpn` or `cmn`.")]
+    InvalidLocalizedAttributeLocale { attribute_name: String, locale: String },
+    #[error("Invalid locale `{locale}`, expected an ISO 639-3 language code such as `jpn` or `cmn`.")]
+    InvalidSearchLocale { locale: String },
+    #[error("Stemming is not supported for the locale `{locale}`, expected the ISO 639-3 code of a language with a stemmer such as `eng` or `fra`.")]
+    InvalidStemmingLocale { locale: String },
+    #[error("The interval of the `{facet_name}` facet histogram must be a positive number, but found `{interval}`.")]
+    InvalidFacetHistogramInterval { facet_name: String, interval: f64 },
+    #[error("The `{facet_name}` facet histogram would contain {buckets} buckets with an interval of `{interval}`, but it is limited to {max_buckets} buckets. Use a larger interval.")]
+    TooManyFacetHistogramBuckets {
+        facet_name: String,
+        interval: f64,
+        buckets: f64,
+        max_buckets: usize,
+    },
     #[error(transparent)]
     InvalidGeoField(#[from] GeoError),
     #[error("{0}")]
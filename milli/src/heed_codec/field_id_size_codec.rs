@@ -0,0 +1,28 @@
+use std::borrow::Cow;
+
+use crate::{try_split_array_at, FieldId};
+
+pub struct FieldIdSizeCodec;
+
+impl<'a> heed::BytesDecode<'a> for FieldIdSizeCodec {
+    type DItem = (FieldId, u32);
+
+    fn bytes_decode(bytes: &'a [u8]) -> Option<Self::DItem> {
+        let (field_id_bytes, bytes) = try_split_array_at(bytes)?;
+        let field_id = u16::from_be_bytes(field_id_bytes);
+        let (size_bytes, _nothing) = try_split_array_at(bytes)?;
+        let size = u32::from_be_bytes(size_bytes);
+        Some((field_id, size))
+    }
+}
+
+impl<'a> heed::BytesEncode<'a> for FieldIdSizeCodec {
+    type EItem = (FieldId, u32);
+
+    fn bytes_encode((field_id, size): &Self::EItem) -> Option<Cow<[u8]>> {
+        let mut bytes = Vec::with_capacity(2 + 4);
+        bytes.extend_from_slice(&field_id.to_be_bytes());
+        bytes.extend_from_slice(&size.to_be_bytes());
+        Some(Cow::Owned(bytes))
+    }
+}
@@ -1,6 +1,7 @@
 mod beu32_str_codec;
 mod byte_slice_ref;
 pub mod facet;
+mod field_id_size_codec;
 mod field_id_word_count_codec;
 mod obkv_codec;
 mod roaring_bitmap;
@@ -8,12 +9,14 @@ mod roaring_bitmap_length;
 mod script_language_codec;
 mod str_beu32_codec;
 mod str_ref;
+mod str_str_codec;
 mod str_str_u8_codec;
 
 pub use byte_slice_ref::ByteSliceRefCodec;
 pub use str_ref::StrRefCodec;
 
 pub use self::beu32_str_codec::BEU32StrCodec;
+pub use self::field_id_size_codec::FieldIdSizeCodec;
 pub use self::field_id_word_count_codec::FieldIdWordCountCodec;
 pub use self::obkv_codec::ObkvCodec;
 pub use self::roaring_bitmap::{BoRoaringBitmapCodec, CboRoaringBitmapCodec, RoaringBitmapCodec};
@@ -22,4 +25,5 @@ pub use self::roaring_bitmap_length::{
 };
 pub use self::script_language_codec::ScriptLanguageCodec;
 pub use self::str_beu32_codec::{StrBEU16Codec, StrBEU32Codec};
+pub use self::str_str_codec::StrStrCodec;
 pub use self::str_str_u8_codec::{U8StrStrCodec, UncheckedU8StrStrCodec};
@@ -0,0 +1,30 @@
+use std::borrow::Cow;
+use std::str;
+
+/// A codec for a pair of strings separated by a zero byte.
+pub struct StrStrCodec;
+
+impl<'a> heed::BytesDecode<'a> for StrStrCodec {
+    type DItem = (&'a str, &'a str);
+
+    fn bytes_decode(bytes: &'a [u8]) -> Option<Self::DItem> {
+        let s1_end = bytes.iter().position(|b| *b == 0)?;
+        let (s1_bytes, rest) = bytes.split_at(s1_end);
+        let s2_bytes = &rest[1..];
+        let s1 = str::from_utf8(s1_bytes).ok()?;
+        let s2 = str::from_utf8(s2_bytes).ok()?;
+        Some((s1, s2))
+    }
+}
+
+impl<'a> heed::BytesEncode<'a> for StrStrCodec {
+    type EItem = (&'a str, &'a str);
+
+    fn bytes_encode((s1, s2): &Self::EItem) -> Option<Cow<[u8]>> {
+        let mut bytes = Vec::with_capacity(s1.len() + s2.len() + 1);
+        bytes.extend_from_slice(s1.as_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(s2.as_bytes());
+        Some(Cow::Owned(bytes))
+    }
+}
@@ -1,5 +1,5 @@
 use std::borrow::Cow;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs::File;
 use std::mem::size_of;
 use std::path::Path;
@@ -12,7 +12,7 @@ use roaring::RoaringBitmap;
 use rstar::RTree;
 use time::OffsetDateTime;
 
-use crate::error::{InternalError, UserError};
+use crate::error::{FieldIdMapMissingEntry, InternalError, UserError};
 use crate::facet::FacetType;
 use crate::fields_ids_map::FieldsIdsMap;
 use crate::heed_codec::facet::{
@@ -21,10 +21,11 @@ use crate::heed_codec::facet::{
 };
 use crate::heed_codec::{ScriptLanguageCodec, StrBEU16Codec, StrRefCodec};
 use crate::{
-    default_criteria, BEU32StrCodec, BoRoaringBitmapCodec, CboRoaringBitmapCodec, Criterion,
-    DocumentId, ExternalDocumentsIds, FacetDistribution, FieldDistribution, FieldId,
-    FieldIdWordCountCodec, GeoPoint, ObkvCodec, Result, RoaringBitmapCodec, RoaringBitmapLenCodec,
-    Search, U8StrStrCodec, BEU16, BEU32,
+    default_criteria, locales_allow_list, parse_locale, BEU32StrCodec, BoRoaringBitmapCodec,
+    CboRoaringBitmapCodec, Criterion, DocumentId, DocumentSchema, ExternalDocumentsIds,
+    FacetDistribution, FieldDistribution, FieldId, FieldIdSizeCodec, FieldIdWordCountCodec,
+    GeoPoint, IngestionRules, ObkvCodec, OrderBy, Result, RoaringBitmapCodec,
+    RoaringBitmapLenCodec, Search, StrStrCodec, U8StrStrCodec, BEU16, BEU32,
 };
 
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
@@ -48,6 +49,8 @@ pub mod main_key {
     pub const PRIMARY_KEY_KEY: &str = "primary-key";
     pub const SEARCHABLE_FIELDS_KEY: &str = "searchable-fields";
     pub const USER_DEFINED_SEARCHABLE_FIELDS_KEY: &str = "user-defined-searchable-fields";
+    pub const VERSION_FIELD_KEY: &str = "version-field-key";
+    pub const EXPIRY_FIELD_KEY: &str = "expiry-field-key";
     pub const SOFT_EXTERNAL_DOCUMENTS_IDS_KEY: &str = "soft-external-documents-ids";
     pub const STOP_WORDS_KEY: &str = "stop-words";
     pub const STRING_FACETED_DOCUMENTS_IDS_PREFIX: &str = "string-faceted-documents-ids";
@@ -59,9 +62,21 @@ pub mod main_key {
     pub const AUTHORIZE_TYPOS: &str = "authorize-typos";
     pub const ONE_TYPO_WORD_LEN: &str = "one-typo-word-len";
     pub const TWO_TYPOS_WORD_LEN: &str = "two-typos-word-len";
+    pub const DISABLE_TYPOS_ON_NUMBERS: &str = "disable-typos-on-numbers";
     pub const EXACT_WORDS: &str = "exact-words";
     pub const EXACT_ATTRIBUTES: &str = "exact-attributes";
+    pub const INFIX_ATTRIBUTES: &str = "infix-attributes";
+    pub const PHONETIC_ATTRIBUTES: &str = "phonetic-attributes";
     pub const MAX_VALUES_PER_FACET: &str = "max-values-per-facet";
+    pub const SORT_FACET_VALUES_BY: &str = "sort-facet-values-by";
+    pub const HIERARCHICAL_FACETS: &str = "hierarchical-facets";
+    pub const SEPARATOR_TOKENS: &str = "separator-tokens";
+    pub const NON_SEPARATOR_TOKENS: &str = "non-separator-tokens";
+    pub const DICTIONARY: &str = "dictionary";
+    pub const LOCALIZED_ATTRIBUTES: &str = "localized-attributes";
+    pub const STEMMING: &str = "stemming";
+    pub const INGESTION_RULES: &str = "ingestion-rules";
+    pub const DOCUMENT_SCHEMA: &str = "document-schema";
     pub const PAGINATION_MAX_TOTAL_HITS: &str = "pagination-max-total-hits";
 }
 
@@ -79,14 +94,21 @@ pub mod db_name {
     pub const WORD_FIELD_ID_DOCIDS: &str = "word-field-id-docids";
     pub const WORD_PREFIX_POSITION_DOCIDS: &str = "word-prefix-position-docids";
     pub const WORD_PREFIX_FIELD_ID_DOCIDS: &str = "word-prefix-field-id-docids";
+    pub const NGRAM_WORD_DOCIDS: &str = "ngram-word-docids";
+    pub const PHONETIC_WORD_DOCIDS: &str = "phonetic-word-docids";
+    pub const STEM_WORD_DOCIDS: &str = "stem-word-docids";
     pub const FIELD_ID_WORD_COUNT_DOCIDS: &str = "field-id-word-count-docids";
     pub const FACET_ID_F64_DOCIDS: &str = "facet-id-f64-docids";
     pub const FACET_ID_EXISTS_DOCIDS: &str = "facet-id-exists-docids";
     pub const FACET_ID_IS_NULL_DOCIDS: &str = "facet-id-is-null-docids";
     pub const FACET_ID_IS_EMPTY_DOCIDS: &str = "facet-id-is-empty-docids";
+    pub const FACET_ID_IS_STRING_DOCIDS: &str = "facet-id-is-string-docids";
+    pub const FACET_ID_SIZE_DOCIDS: &str = "facet-id-size-docids";
     pub const FACET_ID_STRING_DOCIDS: &str = "facet-id-string-docids";
     pub const FIELD_ID_DOCID_FACET_F64S: &str = "field-id-docid-facet-f64s";
     pub const FIELD_ID_DOCID_FACET_STRINGS: &str = "field-id-docid-facet-strings";
+    pub const FIELD_ID_DOCID_FACET_ELEMENT_F64S: &str = "field-id-docid-facet-element-f64s";
+    pub const FIELD_ID_DOCID_FACET_ELEMENT_STRINGS: &str = "field-id-docid-facet-element-strings";
     pub const DOCUMENTS: &str = "documents";
     pub const SCRIPT_LANGUAGE_DOCIDS: &str = "script_language_docids";
 }
@@ -133,6 +155,16 @@ pub struct Index {
     /// Maps the word prefix and a field id with all the docids where the prefix appears inside the field
     pub word_prefix_fid_docids: Database<StrBEU16Codec, CboRoaringBitmapCodec>,
 
+    /// Maps a character n-gram and a word of the infix attributes containing it
+    /// with the documents ids containing this word in one of these attributes.
+    pub ngram_word_docids: Database<StrStrCodec, CboRoaringBitmapCodec>,
+    /// Maps a phonetic code and a word of the phonetic attributes encoded by it
+    /// with the documents ids containing this word in one of these attributes.
+    pub phonetic_word_docids: Database<StrStrCodec, CboRoaringBitmapCodec>,
+    /// Maps a stem, in one of the languages for which the stemming is enabled,
+    /// and a word sharing it with the documents ids containing this word.
+    pub stem_word_docids: Database<StrStrCodec, CboRoaringBitmapCodec>,
+
     /// Maps the script and language with all the docids that corresponds to it.
     pub script_language_docids: Database<ScriptLanguageCodec, RoaringBitmapCodec>,
 
@@ -142,6 +174,10 @@ pub struct Index {
     pub facet_id_is_null_docids: Database<FieldIdCodec, CboRoaringBitmapCodec>,
     /// Maps the facet field id and the docids for which this field is considered empty
     pub facet_id_is_empty_docids: Database<FieldIdCodec, CboRoaringBitmapCodec>,
+    /// Maps the facet field id and the docids for which this field contains a string
+    pub facet_id_is_string_docids: Database<FieldIdCodec, CboRoaringBitmapCodec>,
+    /// Maps the facet field id and the size of the array with the docids that corresponds to them
+    pub facet_id_size_docids: Database<FieldIdSizeCodec, CboRoaringBitmapCodec>,
 
     /// Maps the facet field id and ranges of numbers with the docids that corresponds to them.
     pub facet_id_f64_docids: Database<FacetGroupKeyCodec<OrderedF64Codec>, FacetGroupValueCodec>,
@@ -153,6 +189,16 @@ pub struct Index {
     /// Maps the document id, the facet field id and the strings.
     pub field_id_docid_facet_strings: Database<FieldDocIdFacetStringCodec, Str>,
 
+    /// Maps the facet field id nested in an array of objects, the document id and the numbers
+    /// with the indexes of the array elements in which they appear.
+    pub field_id_docid_facet_element_f64s: Database<FieldDocIdFacetF64Codec, CboRoaringBitmapCodec>,
+    /// Maps the facet field id nested in an array of objects, the document id and the strings
+    /// with the indexes of the array elements in which they appear.
+    ///
+    /// The field id of the array itself is associated with an empty string and all its elements indexes.
+    pub field_id_docid_facet_element_strings:
+        Database<FieldDocIdFacetStringCodec, CboRoaringBitmapCodec>,
+
     /// Maps the document id to the document as an obkv store.
     pub(crate) documents: Database<OwnedType<BEU32>, ObkvCodec>,
 }
@@ -166,7 +212,7 @@ impl Index {
     ) -> Result<Index> {
         use db_name::*;
 
-        options.max_dbs(23);
+        options.max_dbs(30);
         unsafe { options.flag(Flags::MdbAlwaysFreePages) };
 
         let env = options.open(path)?;
@@ -187,15 +233,24 @@ impl Index {
         let field_id_word_count_docids = env.create_database(Some(FIELD_ID_WORD_COUNT_DOCIDS))?;
         let word_prefix_position_docids = env.create_database(Some(WORD_PREFIX_POSITION_DOCIDS))?;
         let word_prefix_fid_docids = env.create_database(Some(WORD_PREFIX_FIELD_ID_DOCIDS))?;
+        let ngram_word_docids = env.create_database(Some(NGRAM_WORD_DOCIDS))?;
+        let phonetic_word_docids = env.create_database(Some(PHONETIC_WORD_DOCIDS))?;
+        let stem_word_docids = env.create_database(Some(STEM_WORD_DOCIDS))?;
         let facet_id_f64_docids = env.create_database(Some(FACET_ID_F64_DOCIDS))?;
         let facet_id_string_docids = env.create_database(Some(FACET_ID_STRING_DOCIDS))?;
         let facet_id_exists_docids = env.create_database(Some(FACET_ID_EXISTS_DOCIDS))?;
         let facet_id_is_null_docids = env.create_database(Some(FACET_ID_IS_NULL_DOCIDS))?;
         let facet_id_is_empty_docids = env.create_database(Some(FACET_ID_IS_EMPTY_DOCIDS))?;
+        let facet_id_is_string_docids = env.create_database(Some(FACET_ID_IS_STRING_DOCIDS))?;
+        let facet_id_size_docids = env.create_database(Some(FACET_ID_SIZE_DOCIDS))?;
 
         let field_id_docid_facet_f64s = env.create_database(Some(FIELD_ID_DOCID_FACET_F64S))?;
         let field_id_docid_facet_strings =
             env.create_database(Some(FIELD_ID_DOCID_FACET_STRINGS))?;
+        let field_id_docid_facet_element_f64s =
+            env.create_database(Some(FIELD_ID_DOCID_FACET_ELEMENT_F64S))?;
+        let field_id_docid_facet_element_strings =
+            env.create_database(Some(FIELD_ID_DOCID_FACET_ELEMENT_STRINGS))?;
         let documents = env.create_database(Some(DOCUMENTS))?;
 
         Index::set_creation_dates(&env, main, created_at, updated_at)?;
@@ -216,14 +271,21 @@ impl Index {
             word_fid_docids,
             word_prefix_position_docids,
             word_prefix_fid_docids,
+            ngram_word_docids,
+            phonetic_word_docids,
+            stem_word_docids,
             field_id_word_count_docids,
             facet_id_f64_docids,
             facet_id_string_docids,
             facet_id_exists_docids,
             facet_id_is_null_docids,
             facet_id_is_empty_docids,
+            facet_id_is_string_docids,
+            facet_id_size_docids,
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
+            field_id_docid_facet_element_f64s,
+            field_id_docid_facet_element_strings,
             documents,
         })
     }
@@ -795,6 +857,9 @@ impl Index {
         if let Some(field) = distinct_field {
             faceted_fields.insert(field.to_owned());
         }
+        if let Some(field) = self.expiry_field(rtxn)? {
+            faceted_fields.insert(field.to_owned());
+        }
 
         Ok(faceted_fields)
     }
@@ -878,6 +943,18 @@ impl Index {
         }
     }
 
+    /// Retrieve all the documents which contain a string in this field id
+    pub fn string_typed_documents_ids(
+        &self,
+        rtxn: &RoTxn,
+        field_id: FieldId,
+    ) -> heed::Result<RoaringBitmap> {
+        match self.facet_id_is_string_docids.get(rtxn, &BEU16::new(field_id))? {
+            Some(docids) => Ok(docids),
+            None => Ok(RoaringBitmap::new()),
+        }
+    }
+
     /// Retrieve all the documents which contain this field id
     pub fn exists_faceted_documents_ids(
         &self,
@@ -908,6 +985,97 @@ impl Index {
         self.main.delete::<_, Str>(wtxn, main_key::DISTINCT_FIELD_KEY)
     }
 
+    /* version field */
+
+    pub(crate) fn put_version_field(
+        &self,
+        wtxn: &mut RwTxn,
+        version_field: &str,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, Str>(wtxn, main_key::VERSION_FIELD_KEY, version_field)
+    }
+
+    /// The field holding the version of the documents, an incoming document with a lower
+    /// version than the stored one is not indexed.
+    pub fn version_field<'a>(&self, rtxn: &'a RoTxn) -> heed::Result<Option<&'a str>> {
+        self.main.get::<_, Str, Str>(rtxn, main_key::VERSION_FIELD_KEY)
+    }
+
+    pub(crate) fn delete_version_field(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::VERSION_FIELD_KEY)
+    }
+
+    /* expiry field */
+
+    pub(crate) fn put_expiry_field(
+        &self,
+        wtxn: &mut RwTxn,
+        expiry_field: &str,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, Str>(wtxn, main_key::EXPIRY_FIELD_KEY, expiry_field)
+    }
+
+    /// The field holding the expiry date of the documents, as a Unix timestamp in seconds
+    /// or an RFC 3339 date.
+    pub fn expiry_field<'a>(&self, rtxn: &'a RoTxn) -> heed::Result<Option<&'a str>> {
+        self.main.get::<_, Str, Str>(rtxn, main_key::EXPIRY_FIELD_KEY)
+    }
+
+    pub(crate) fn delete_expiry_field(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::EXPIRY_FIELD_KEY)
+    }
+
+    /// Returns the documents whose expiry date is lower than or equal to `now`,
+    /// a Unix timestamp in seconds. Documents without an expiry date never expire.
+    pub fn expired_documents_ids(&self, rtxn: &RoTxn, now: i64) -> Result<RoaringBitmap> {
+        let field_id = match self.expiry_field(rtxn)? {
+            Some(field) => self.fields_ids_map(rtxn)?.id(field),
+            None => None,
+        };
+
+        match field_id {
+            Some(field_id) => {
+                crate::search::facet::expired_documents_ids(self, rtxn, field_id, now as f64)
+            }
+            None => Ok(RoaringBitmap::new()),
+        }
+    }
+
+    /// Returns the external ids of the documents whose expiry date is lower than or equal
+    /// to `now`, a Unix timestamp in seconds.
+    pub fn expired_documents_external_ids(&self, rtxn: &RoTxn, now: i64) -> Result<Vec<String>> {
+        let expired = self.expired_documents_ids(rtxn, now)?;
+        if expired.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let primary_key = self.primary_key(rtxn)?.ok_or(InternalError::DatabaseMissingEntry {
+            db_name: db_name::MAIN,
+            key: Some(main_key::PRIMARY_KEY_KEY),
+        })?;
+        let primary_key_id = self.fields_ids_map(rtxn)?.id(primary_key).ok_or(
+            FieldIdMapMissingEntry::FieldName {
+                field_name: primary_key.to_string(),
+                process: "Index::expired_documents_external_ids",
+            },
+        )?;
+
+        self.iter_documents(rtxn, expired)?
+            .map(|result| {
+                let (_, document) = result?;
+                let external_id =
+                    document.get(primary_key_id).ok_or(InternalError::DatabaseMissingEntry {
+                        db_name: db_name::DOCUMENTS,
+                        key: None,
+                    })?;
+                match serde_json::from_slice(external_id).map_err(InternalError::SerdeJson)? {
+                    serde_json::Value::String(external_id) => Ok(external_id),
+                    external_id => Ok(external_id.to_string()),
+                }
+            })
+            .collect()
+    }
+
     /* criteria */
 
     pub(crate) fn put_criteria(
@@ -1162,6 +1330,33 @@ impl Index {
         Ok(())
     }
 
+    /// Returns `true` if typos are not allowed on the words only made of digits.
+    pub fn disable_typos_on_numbers(&self, txn: &RoTxn) -> heed::Result<bool> {
+        // It is not possible to put a bool in heed with OwnedType, so we put a u8 instead. We
+        // identify 0 as being false, and anything else as true. The absence of a value is false,
+        // because by default, we authorize typos on numbers.
+        match self.main.get::<_, Str, OwnedType<u8>>(txn, main_key::DISABLE_TYPOS_ON_NUMBERS)? {
+            Some(0) | None => Ok(false),
+            _ => Ok(true),
+        }
+    }
+
+    pub(crate) fn put_disable_typos_on_numbers(
+        &self,
+        txn: &mut RwTxn,
+        flag: bool,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, OwnedType<u8>>(
+            txn,
+            main_key::DISABLE_TYPOS_ON_NUMBERS,
+            &(flag as u8),
+        )
+    }
+
+    pub(crate) fn delete_disable_typos_on_numbers(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::DISABLE_TYPOS_ON_NUMBERS)
+    }
+
     /// List the words on which typo are not allowed
     pub fn exact_words<'t>(&self, txn: &'t RoTxn) -> Result<Option<fst::Set<Cow<'t, [u8]>>>> {
         match self.main.get::<_, Str, ByteSlice>(txn, main_key::EXACT_WORDS)? {
@@ -1209,6 +1404,238 @@ impl Index {
         self.main.delete::<_, Str>(txn, main_key::EXACT_ATTRIBUTES)
     }
 
+    /* infix attributes */
+
+    /// Returns the infix attributes: attributes whose words can be matched by a part of them.
+    pub fn infix_attributes<'t>(&self, txn: &'t RoTxn) -> Result<Vec<&'t str>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeBincode<Vec<&str>>>(txn, main_key::INFIX_ATTRIBUTES)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the list of infix attributes field ids.
+    pub fn infix_attributes_ids(&self, txn: &RoTxn) -> Result<HashSet<FieldId>> {
+        let attrs = self.infix_attributes(txn)?;
+        let fid_map = self.fields_ids_map(txn)?;
+        Ok(attrs.iter().filter_map(|attr| fid_map.id(attr)).collect())
+    }
+
+    /// Writes the infix attributes to the database.
+    pub(crate) fn put_infix_attributes(&self, txn: &mut RwTxn, attrs: &[&str]) -> Result<()> {
+        self.main.put::<_, Str, SerdeBincode<&[&str]>>(txn, main_key::INFIX_ATTRIBUTES, &attrs)?;
+        Ok(())
+    }
+
+    /// Clears the infix attributes from the store.
+    pub(crate) fn delete_infix_attributes(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::INFIX_ATTRIBUTES)
+    }
+
+    /* phonetic attributes */
+
+    /// Returns the phonetic attributes: attributes whose words can be matched by similar sounding words.
+    pub fn phonetic_attributes<'t>(&self, txn: &'t RoTxn) -> Result<Vec<&'t str>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeBincode<Vec<&str>>>(txn, main_key::PHONETIC_ATTRIBUTES)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the list of phonetic attributes field ids.
+    pub fn phonetic_attributes_ids(&self, txn: &RoTxn) -> Result<HashSet<FieldId>> {
+        let attrs = self.phonetic_attributes(txn)?;
+        let fid_map = self.fields_ids_map(txn)?;
+        Ok(attrs.iter().filter_map(|attr| fid_map.id(attr)).collect())
+    }
+
+    /// Writes the phonetic attributes to the database.
+    pub(crate) fn put_phonetic_attributes(&self, txn: &mut RwTxn, attrs: &[&str]) -> Result<()> {
+        self.main.put::<_, Str, SerdeBincode<&[&str]>>(
+            txn,
+            main_key::PHONETIC_ATTRIBUTES,
+            &attrs,
+        )?;
+        Ok(())
+    }
+
+    /// Clears the phonetic attributes from the store.
+    pub(crate) fn delete_phonetic_attributes(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::PHONETIC_ATTRIBUTES)
+    }
+
+    /* separator tokens */
+
+    /// Returns the tokens that must be considered as separators in addition to the default ones.
+    pub fn separator_tokens(&self, txn: &RoTxn) -> heed::Result<Option<BTreeSet<String>>> {
+        self.main.get::<_, Str, SerdeJson<BTreeSet<String>>>(txn, main_key::SEPARATOR_TOKENS)
+    }
+
+    pub(crate) fn put_separator_tokens(
+        &self,
+        txn: &mut RwTxn,
+        val: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<BTreeSet<String>>>(txn, main_key::SEPARATOR_TOKENS, val)
+    }
+
+    pub(crate) fn delete_separator_tokens(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::SEPARATOR_TOKENS)
+    }
+
+    /* non separator tokens */
+
+    /// Returns the default separators that must not be considered as separators.
+    pub fn non_separator_tokens(&self, txn: &RoTxn) -> heed::Result<Option<BTreeSet<String>>> {
+        self.main.get::<_, Str, SerdeJson<BTreeSet<String>>>(txn, main_key::NON_SEPARATOR_TOKENS)
+    }
+
+    pub(crate) fn put_non_separator_tokens(
+        &self,
+        txn: &mut RwTxn,
+        val: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<BTreeSet<String>>>(
+            txn,
+            main_key::NON_SEPARATOR_TOKENS,
+            val,
+        )
+    }
+
+    pub(crate) fn delete_non_separator_tokens(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::NON_SEPARATOR_TOKENS)
+    }
+
+    /// Returns the separators the tokenizer must use, or `None` if they are the default ones.
+    pub fn allowed_separators(&self, txn: &RoTxn) -> heed::Result<Option<BTreeSet<String>>> {
+        let default_separators =
+            charabia::separators::DEFAULT_SEPARATORS.iter().map(|s| s.to_string());
+
+        let mut separators: Option<BTreeSet<_>> = None;
+        if let Some(mut separator_tokens) = self.separator_tokens(txn)? {
+            separator_tokens.extend(default_separators.clone());
+            separators = Some(separator_tokens);
+        }
+
+        if let Some(non_separator_tokens) = self.non_separator_tokens(txn)? {
+            separators = separators
+                .or_else(|| Some(default_separators.collect()))
+                .map(|separators| &separators - &non_separator_tokens);
+        }
+
+        Ok(separators)
+    }
+
+    /* dictionary */
+
+    /// Returns the words that must be kept as a single token by the tokenizer.
+    pub fn dictionary(&self, txn: &RoTxn) -> heed::Result<Option<BTreeSet<String>>> {
+        self.main.get::<_, Str, SerdeJson<BTreeSet<String>>>(txn, main_key::DICTIONARY)
+    }
+
+    pub(crate) fn put_dictionary(
+        &self,
+        txn: &mut RwTxn,
+        val: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<BTreeSet<String>>>(txn, main_key::DICTIONARY, val)
+    }
+
+    pub(crate) fn delete_dictionary(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::DICTIONARY)
+    }
+
+    /* localized attributes */
+
+    /// Returns the locales the tokenizer must restrict its language detection to, by attribute name.
+    pub fn localized_attributes(&self, txn: &RoTxn) -> heed::Result<BTreeMap<String, Vec<String>>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<BTreeMap<String, Vec<String>>>>(
+                txn,
+                main_key::LOCALIZED_ATTRIBUTES,
+            )?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_localized_attributes(
+        &self,
+        txn: &mut RwTxn,
+        val: &BTreeMap<String, Vec<String>>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<BTreeMap<String, Vec<String>>>>(
+            txn,
+            main_key::LOCALIZED_ATTRIBUTES,
+            val,
+        )
+    }
+
+    pub(crate) fn delete_localized_attributes(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::LOCALIZED_ATTRIBUTES)
+    }
+
+    /* stemming */
+
+    /// Returns the locales of the languages in which the words are stemmed.
+    pub fn stemming(&self, txn: &RoTxn) -> heed::Result<BTreeSet<String>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<BTreeSet<String>>>(txn, main_key::STEMMING)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_stemming(&self, txn: &mut RwTxn, val: &BTreeSet<String>) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<BTreeSet<String>>>(txn, main_key::STEMMING, val)
+    }
+
+    pub(crate) fn delete_stemming(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::STEMMING)
+    }
+
+    /* ingestion rules */
+
+    /// Returns the rules applied to the documents before they are indexed.
+    pub fn ingestion_rules(&self, txn: &RoTxn) -> heed::Result<IngestionRules> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<IngestionRules>>(txn, main_key::INGESTION_RULES)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_ingestion_rules(
+        &self,
+        txn: &mut RwTxn,
+        val: &IngestionRules,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<IngestionRules>>(txn, main_key::INGESTION_RULES, val)
+    }
+
+    pub(crate) fn delete_ingestion_rules(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::INGESTION_RULES)
+    }
+
+    /* document schema */
+
+    /// Returns the types and the required fields the documents must match to be indexed.
+    pub fn document_schema(&self, txn: &RoTxn) -> heed::Result<DocumentSchema> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<DocumentSchema>>(txn, main_key::DOCUMENT_SCHEMA)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_document_schema(
+        &self,
+        txn: &mut RwTxn,
+        val: &DocumentSchema,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<DocumentSchema>>(txn, main_key::DOCUMENT_SCHEMA, val)
+    }
+
+    pub(crate) fn delete_document_schema(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::DOCUMENT_SCHEMA)
+    }
+
     pub fn max_values_per_facet(&self, txn: &RoTxn) -> heed::Result<Option<usize>> {
         self.main.get::<_, Str, OwnedType<usize>>(txn, main_key::MAX_VALUES_PER_FACET)
     }
@@ -1221,6 +1648,57 @@ impl Index {
         self.main.delete::<_, Str>(txn, main_key::MAX_VALUES_PER_FACET)
     }
 
+    /// Returns the order of the facet values in the facet distribution, by facet name.
+    pub fn sort_facet_values_by(&self, txn: &RoTxn) -> heed::Result<HashMap<String, OrderBy>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<HashMap<String, OrderBy>>>(
+                txn,
+                main_key::SORT_FACET_VALUES_BY,
+            )?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_sort_facet_values_by(
+        &self,
+        txn: &mut RwTxn,
+        val: &HashMap<String, OrderBy>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<HashMap<String, OrderBy>>>(
+            txn,
+            main_key::SORT_FACET_VALUES_BY,
+            val,
+        )
+    }
+
+    pub(crate) fn delete_sort_facet_values_by(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::SORT_FACET_VALUES_BY)
+    }
+
+    /// Returns the separator of the levels of the hierarchical facets, by facet name.
+    pub fn hierarchical_facets(&self, txn: &RoTxn) -> heed::Result<BTreeMap<String, String>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<BTreeMap<String, String>>>(txn, main_key::HIERARCHICAL_FACETS)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_hierarchical_facets(
+        &self,
+        txn: &mut RwTxn,
+        val: &BTreeMap<String, String>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<BTreeMap<String, String>>>(
+            txn,
+            main_key::HIERARCHICAL_FACETS,
+            val,
+        )
+    }
+
+    pub(crate) fn delete_hierarchical_facets(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::HIERARCHICAL_FACETS)
+    }
+
     pub fn pagination_max_total_hits(&self, txn: &RoTxn) -> heed::Result<Option<usize>> {
         self.main.get::<_, Str, OwnedType<usize>>(txn, main_key::PAGINATION_MAX_TOTAL_HITS)
     }
@@ -1279,6 +1757,27 @@ impl Index {
 
         Ok(script_language)
     }
+
+    /// Returns the languages used to tokenize a query that doesn't specify its locales.
+    ///
+    /// The scripts of the locales of the localized attributes are restricted to these locales,
+    /// as they are when indexing these attributes, the other scripts use the languages
+    /// detected in the documents.
+    pub fn query_script_language(
+        &self,
+        rtxn: &RoTxn,
+    ) -> heed::Result<HashMap<Script, Vec<Language>>> {
+        let localized_languages: Vec<_> = self
+            .localized_attributes(rtxn)?
+            .values()
+            .flatten()
+            .filter_map(|locale| parse_locale(locale))
+            .collect();
+
+        let mut script_language = self.script_language(rtxn)?;
+        script_language.extend(locales_allow_list(&localized_languages));
+        Ok(script_language)
+    }
 }
 
 #[cfg(test)]
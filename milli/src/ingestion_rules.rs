@@ -0,0 +1,305 @@
+use deserr::Deserr;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::update::IndexDocumentsMethod;
+use crate::Object;
+
+/// The operations of an edited document whose operand is a value the rules can transform.
+const VALUE_OPERATIONS: [&str; 3] = ["_set", "_append", "_remove"];
+
+/// The rules applied, in order, to every document sent to an index before it is indexed.
+///
+/// The version is bumped every time the rules are updated, the documents indexed
+/// before an update are not modified by the new rules.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct IngestionRules {
+    pub version: u32,
+    pub rules: Vec<IngestionRule>,
+}
+
+/// A transformation applied to the top-level fields of a document.
+///
+/// The rules never fail: a rule referencing a missing field, or a field
+/// holding a value it cannot process, leaves the document untouched.
+///
+/// The fields of an edited document hold operations, the rules transforming the value
+/// of a field transform the values given to the `_set`, `_append` and `_remove` operations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr)]
+#[serde(tag = "type", deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(tag = "type", deny_unknown_fields, rename_all = camelCase)]
+pub enum IngestionRule {
+    /// Joins the values of the fields with the separator and stores the result in `into`.
+    Concat {
+        fields: Vec<String>,
+        #[serde(default)]
+        #[deserr(default)]
+        separator: String,
+        into: String,
+    },
+    /// Lowercases the string, or the strings of the array, of the field.
+    Lowercase { field: String },
+    /// Trims the whitespaces around the string, or the strings of the array, of the field.
+    Trim { field: String },
+    /// Moves the value of the `from` field into the `to` field.
+    Rename { from: String, to: String },
+    /// Removes the field from the document.
+    Drop { field: String },
+    /// Sets the value of the field when it is missing or `null`.
+    Default { field: String, value: Value },
+    /// Builds the `_geo` field from the latitude and longitude fields.
+    Geo { lat: String, lng: String },
+    /// Splits the string of the field on the separator into an array of trimmed strings.
+    Split { field: String, separator: String },
+}
+
+impl IngestionRules {
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Applies all the rules to the document, in order.
+    ///
+    /// The documents of a partial update or of an edition only contain the fields to update.
+    /// The rules synthesizing a field are not applied to them, they would overwrite the stored
+    /// value of the field with a value computed from an incomplete document.
+    pub fn apply(&self, document: &mut Object, update_method: IndexDocumentsMethod) {
+        let partial = update_method != IndexDocumentsMethod::ReplaceDocuments;
+        let edition = update_method == IndexDocumentsMethod::EditDocuments;
+        for rule in &self.rules {
+            if !(partial && rule.synthesizes_field()) {
+                rule.apply(document, edition);
+            }
+        }
+    }
+}
+
+impl IngestionRule {
+    /// Returns `true` if the rule computes a field from the other fields of the document,
+    /// or from nothing at all, rather than transforming a field sent by the user.
+    pub fn synthesizes_field(&self) -> bool {
+        matches!(
+            self,
+            IngestionRule::Concat { .. }
+                | IngestionRule::Default { .. }
+                | IngestionRule::Geo { .. }
+        )
+    }
+
+    /// Applies the rule to the document, the fields of an edited document hold operations.
+    pub fn apply(&self, document: &mut Object, edition: bool) {
+        match self {
+            IngestionRule::Concat { fields, separator, into } => {
+                let values: Vec<String> = fields
+                    .iter()
+                    .filter_map(|field| match document.get(field)? {
+                        Value::Null => None,
+                        Value::String(s) => Some(s.clone()),
+                        value => Some(value.to_string()),
+                    })
+                    .collect();
+                if !values.is_empty() {
+                    document.insert(into.clone(), Value::String(values.join(separator)));
+                }
+            }
+            IngestionRule::Lowercase { field } => {
+                map_field_values(document, field, edition, |value| {
+                    map_strings(value, |s| s.to_lowercase())
+                })
+            }
+            IngestionRule::Trim { field } => map_field_values(document, field, edition, |value| {
+                map_strings(value, |s| s.trim().to_string())
+            }),
+            IngestionRule::Rename { from, to } => {
+                if let Some(value) = document.remove(from) {
+                    document.insert(to.clone(), value);
+                }
+            }
+            IngestionRule::Drop { field } => {
+                document.remove(field);
+            }
+            IngestionRule::Default { field, value } => match document.get_mut(field) {
+                Some(current @ Value::Null) => *current = value.clone(),
+                Some(_) => (),
+                None => {
+                    document.insert(field.clone(), value.clone());
+                }
+            },
+            IngestionRule::Geo { lat, lng } => match (document.get(lat), document.get(lng)) {
+                (Some(lat), Some(lng)) if !lat.is_null() && !lng.is_null() => {
+                    let mut geo = Map::new();
+                    geo.insert("lat".to_string(), lat.clone());
+                    geo.insert("lng".to_string(), lng.clone());
+                    document.insert("_geo".to_string(), Value::Object(geo));
+                }
+                _ => (),
+            },
+            IngestionRule::Split { field, separator } => {
+                map_field_values(document, field, edition, |value| {
+                    if let Value::String(s) = value {
+                        let parts = s
+                            .split(separator.as_str())
+                            .map(str::trim)
+                            .filter(|part| !part.is_empty())
+                            .map(|part| Value::String(part.to_string()))
+                            .collect();
+                        *value = Value::Array(parts);
+                    }
+                })
+            }
+        }
+    }
+}
+
+/// Applies `f` to the value of the field or, when the document is edited, to the values
+/// given to the operations of the field.
+fn map_field_values(document: &mut Object, field: &str, edition: bool, f: impl Fn(&mut Value)) {
+    match document.get_mut(field) {
+        Some(Value::Object(operation)) if edition => {
+            for (name, operand) in operation {
+                if VALUE_OPERATIONS.contains(&name.as_str()) {
+                    f(operand);
+                }
+            }
+        }
+        Some(value) if !edition => f(value),
+        _ => (),
+    }
+}
+
+/// Applies `f` to the string, or to every string of the array, leaving other values untouched.
+fn map_strings(value: &mut Value, f: impl Fn(&str) -> String) {
+    match value {
+        Value::String(s) => *s = f(s),
+        Value::Array(values) => {
+            for value in values {
+                if let Value::String(s) = value {
+                    *s = f(s);
+                }
+            }
+        }
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn apply(rules: Value, document: Value) -> Value {
+        apply_with(rules, document, IndexDocumentsMethod::ReplaceDocuments)
+    }
+
+    fn apply_with(rules: Value, document: Value, update_method: IndexDocumentsMethod) -> Value {
+        let rules = IngestionRules { version: 1, rules: serde_json::from_value(rules).unwrap() };
+        let mut document = match document {
+            Value::Object(document) => document,
+            _ => panic!("documents must be objects"),
+        };
+        rules.apply(&mut document, update_method);
+        Value::Object(document)
+    }
+
+    #[test]
+    fn rules_are_applied_in_order() {
+        let rules = json!([
+            { "type": "trim", "field": "first" },
+            { "type": "concat", "fields": ["first", "middle", "last"], "separator": " ", "into": "name" },
+            { "type": "lowercase", "field": "name" },
+            { "type": "drop", "field": "first" },
+            { "type": "rename", "from": "last", "to": "family" },
+            { "type": "default", "field": "country", "value": "unknown" },
+            { "type": "default", "field": "age", "value": 0 },
+            { "type": "geo", "lat": "latitude", "lng": "longitude" },
+            { "type": "split", "field": "tags", "separator": "," },
+        ]);
+        let document = json!({
+            "id": 1,
+            "first": "  Jane ",
+            "last": "DOE",
+            "age": 42,
+            "country": null,
+            "latitude": 45.3,
+            "longitude": "2.5",
+            "tags": "red, blue,,green",
+        });
+
+        assert_eq!(
+            apply(rules, document),
+            json!({
+                "id": 1,
+                "family": "DOE",
+                "age": 42,
+                "country": "unknown",
+                "latitude": 45.3,
+                "longitude": "2.5",
+                "tags": ["red", "blue", "green"],
+                "name": "jane doe",
+                "_geo": { "lat": 45.3, "lng": "2.5" },
+            })
+        );
+    }
+
+    #[test]
+    fn rules_ignore_missing_and_unexpected_values() {
+        let rules = json!([
+            { "type": "concat", "fields": ["a", "b"], "into": "c" },
+            { "type": "lowercase", "field": "n" },
+            { "type": "split", "field": "n", "separator": "," },
+            { "type": "rename", "from": "missing", "to": "n" },
+            { "type": "geo", "lat": "n", "lng": "missing" },
+        ]);
+        let document = json!({ "id": 1, "n": 12 });
+
+        assert_eq!(apply(rules, document), json!({ "id": 1, "n": 12 }));
+    }
+
+    #[test]
+    fn synthesizing_rules_are_not_applied_to_partial_documents() {
+        let rules = json!([
+            { "type": "concat", "fields": ["first", "last"], "separator": " ", "into": "name" },
+            { "type": "default", "field": "country", "value": "unknown" },
+            { "type": "geo", "lat": "latitude", "lng": "longitude" },
+            { "type": "lowercase", "field": "last" },
+        ]);
+        let document = json!({ "id": 1, "last": "DOE", "latitude": 45.3, "longitude": 2.5 });
+
+        assert_eq!(
+            apply_with(rules, document, IndexDocumentsMethod::UpdateDocuments),
+            json!({ "id": 1, "last": "doe", "latitude": 45.3, "longitude": 2.5 })
+        );
+    }
+
+    #[test]
+    fn rules_are_applied_to_the_values_of_the_edit_operations() {
+        let rules = json!([
+            { "type": "trim", "field": "title" },
+            { "type": "lowercase", "field": "title" },
+            { "type": "split", "field": "tags", "separator": "," },
+            { "type": "lowercase", "field": "genres" },
+            { "type": "trim", "field": "stock" },
+            { "type": "default", "field": "country", "value": "unknown" },
+        ]);
+        let document = json!({
+            "id": 1,
+            "title": { "_set": "  DUNE " },
+            "tags": { "_append": "scifi, classic" },
+            "genres": { "_remove": ["Space", "Desert"] },
+            "stock": { "_increment": 1 },
+        });
+
+        assert_eq!(
+            apply_with(rules, document, IndexDocumentsMethod::EditDocuments),
+            json!({
+                "id": 1,
+                "title": { "_set": "dune" },
+                "tags": { "_append": ["scifi", "classic"] },
+                "genres": { "_remove": ["space", "desert"] },
+                "stock": { "_increment": 1 },
+            })
+        );
+    }
+}
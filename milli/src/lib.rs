@@ -56,12 +56,16 @@ pub mod documents;
 
 mod asc_desc;
 mod criterion;
+mod document_schema;
 mod error;
 mod external_documents_ids;
 pub mod facet;
 mod fields_ids_map;
 pub mod heed_codec;
 pub mod index;
+mod ingestion_rules;
+mod locales;
+mod phonetic;
 pub mod proximity;
 mod search;
 pub mod update;
@@ -87,6 +91,9 @@ pub use {charabia as tokenizer, heed};
 
 pub use self::asc_desc::{AscDesc, AscDescError, Member, SortError};
 pub use self::criterion::{default_criteria, Criterion, CriterionError};
+pub use self::document_schema::{
+    DocumentSchema, FieldType, InvalidDocument, InvalidDocumentPolicy, InvalidFieldType,
+};
 pub use self::error::{
     Error, FieldIdMapMissingEntry, InternalError, SerializationError, UserError,
 };
@@ -94,13 +101,19 @@ pub use self::external_documents_ids::ExternalDocumentsIds;
 pub use self::fields_ids_map::FieldsIdsMap;
 pub use self::heed_codec::{
     BEU32StrCodec, BoRoaringBitmapCodec, BoRoaringBitmapLenCodec, CboRoaringBitmapCodec,
-    CboRoaringBitmapLenCodec, FieldIdWordCountCodec, ObkvCodec, RoaringBitmapCodec,
-    RoaringBitmapLenCodec, StrBEU32Codec, U8StrStrCodec, UncheckedU8StrStrCodec,
+    CboRoaringBitmapLenCodec, FieldIdSizeCodec, FieldIdWordCountCodec, ObkvCodec,
+    RoaringBitmapCodec, RoaringBitmapLenCodec, StrBEU32Codec, StrStrCodec, U8StrStrCodec,
+    UncheckedU8StrStrCodec,
 };
 pub use self::index::Index;
+pub use self::ingestion_rules::{IngestionRule, IngestionRules};
+pub use self::locales::{locales_allow_list, parse_locale, stemming_algorithm, Stemmers};
+pub use self::phonetic::{double_metaphone, phonetic_codes};
 pub use self::search::{
-    FacetDistribution, Filter, FormatOptions, MatchBounds, MatcherBuilder, MatchingWords, Search,
-    SearchResult, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    FacetDistribution, FacetHierarchy, FacetNumberStats, FacetRange, Filter, FormatOptions,
+    MatchBounds, MatcherBuilder, MatchingWords, OrderBy, Search, SearchResult,
+    TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET, FACET_STATS_PERCENTILES,
+    MAX_FACET_HISTOGRAM_BUCKETS,
 };
 
 pub type Result<T> = std::result::Result<T, error::Error>;
@@ -144,6 +157,16 @@ pub const MAX_WORD_LENGTH: usize = MAX_LMDB_KEY_LENGTH / 2;
 
 pub const MAX_POSITION_PER_ATTRIBUTE: u32 = u16::MAX as u32 + 1;
 
+/// The number of characters of the n-grams indexed for the infix search.
+pub const INFIX_NGRAM_LENGTH: usize = 3;
+
+/// Returns the character n-grams of the word that are indexed for the infix search,
+/// a word shorter than an n-gram doesn't have any.
+pub fn infix_ngrams(word: &str) -> Vec<&str> {
+    let boundaries: Vec<_> = word.char_indices().map(|(i, _)| i).chain(Some(word.len())).collect();
+    boundaries.windows(INFIX_NGRAM_LENGTH + 1).map(|w| &word[w[0]..w[INFIX_NGRAM_LENGTH]]).collect()
+}
+
 // Convert an absolute word position into a relative position.
 // Return the field id of the attribute related to the absolute position
 // and the relative position in the attribute.
@@ -335,6 +358,13 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn infix_ngrams_of_words() {
+        assert_eq!(infix_ngrams("45219"), ["452", "521", "219"]);
+        assert_eq!(infix_ngrams("été"), ["été"]);
+        assert!(infix_ngrams("ab").is_empty());
+    }
+
     #[test]
     fn json_to_string_object() {
         let value = json!({
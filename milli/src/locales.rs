@@ -0,0 +1,149 @@
+use std::collections::{BTreeSet, HashMap};
+
+use charabia::{Language, Script};
+use rust_stemmers::{Algorithm, Stemmer};
+
+/// Returns the language corresponding to the given locale,
+/// a locale being the ISO 639-3 code of a language, e.g. `jpn` or `cmn`.
+pub fn parse_locale(locale: &str) -> Option<Language> {
+    let language = Language::from_name(locale);
+    // unknown names are converted into `Language::Other`, we make sure the name round-trips.
+    (language.name() == locale).then_some(language)
+}
+
+/// Builds the allow list given to the tokenizer so that it only detects the given languages.
+pub fn locales_allow_list(languages: &[Language]) -> HashMap<Script, Vec<Language>> {
+    let mut allow_list: HashMap<Script, Vec<Language>> = HashMap::new();
+    for &language in languages {
+        for &script in language_scripts(language) {
+            let languages = allow_list.entry(script).or_default();
+            if !languages.contains(&language) {
+                languages.push(language);
+            }
+        }
+    }
+    allow_list
+}
+
+/// Returns the stemming algorithm of the language, if stemming is supported for it.
+pub fn stemming_algorithm(language: Language) -> Option<Algorithm> {
+    use Language::*;
+
+    let algorithm = match language {
+        Ara => Algorithm::Arabic,
+        Dan => Algorithm::Danish,
+        Deu => Algorithm::German,
+        Ell => Algorithm::Greek,
+        Eng => Algorithm::English,
+        Fin => Algorithm::Finnish,
+        Fra => Algorithm::French,
+        Hun => Algorithm::Hungarian,
+        Ita => Algorithm::Italian,
+        Nld => Algorithm::Dutch,
+        Nob => Algorithm::Norwegian,
+        Por => Algorithm::Portuguese,
+        Ron => Algorithm::Romanian,
+        Rus => Algorithm::Russian,
+        Spa => Algorithm::Spanish,
+        Swe => Algorithm::Swedish,
+        Tam => Algorithm::Tamil,
+        Tur => Algorithm::Turkish,
+        _ => return None,
+    };
+    Some(algorithm)
+}
+
+/// The stemmers of the languages for which the stemming is enabled on an index.
+#[derive(Default)]
+pub struct Stemmers {
+    stemmers: Vec<Stemmer>,
+}
+
+impl Stemmers {
+    /// Creates the stemmers of the given locales, ignoring the ones that do not support stemming.
+    pub fn new(locales: &BTreeSet<String>) -> Stemmers {
+        let stemmers = locales
+            .iter()
+            .filter_map(|locale| parse_locale(locale).and_then(stemming_algorithm))
+            .map(Stemmer::create)
+            .collect();
+        Stemmers { stemmers }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stemmers.is_empty()
+    }
+
+    /// Returns the stems of the word in the enabled languages,
+    /// the word itself is returned when it is its own stem.
+    pub fn stems(&self, word: &str) -> BTreeSet<String> {
+        self.stemmers
+            .iter()
+            .map(|stemmer| stemmer.stem(word))
+            .filter(|stem| !stem.is_empty())
+            .map(|stem| stem.into_owned())
+            .collect()
+    }
+}
+
+/// Returns the scripts the language detection of the tokenizer can associate with the language.
+fn language_scripts(language: Language) -> &'static [Script] {
+    use Language::*;
+
+    match language {
+        Cmn | Jpn => &[Script::Cj],
+        Kor => &[Script::Hangul],
+        Ara | Pes | Urd => &[Script::Arabic],
+        Heb | Yid => &[Script::Hebrew],
+        Rus | Ukr | Bel | Bul | Mkd => &[Script::Cyrillic],
+        Srp => &[Script::Cyrillic, Script::Latin],
+        Ell => &[Script::Greek],
+        Hin | Mar | Nep => &[Script::Devanagari],
+        Ben => &[Script::Bengali],
+        Guj => &[Script::Gujarati],
+        Pan => &[Script::Gurmukhi],
+        Kan => &[Script::Kannada],
+        Mal => &[Script::Malayalam],
+        Ori => &[Script::Oriya],
+        Tam => &[Script::Tamil],
+        Tel => &[Script::Telugu],
+        Sin => &[Script::Sinhala],
+        Tha => &[Script::Thai],
+        Khm => &[Script::Khmer],
+        Mya => &[Script::Myanmar],
+        Kat => &[Script::Georgian],
+        Hye => &[Script::Armenian],
+        Amh => &[Script::Ethiopic],
+        _ => &[Script::Latin],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_locales() {
+        assert_eq!(parse_locale("jpn"), Some(Language::Jpn));
+        assert_eq!(parse_locale("cmn"), Some(Language::Cmn));
+        assert_eq!(parse_locale("japanese"), None);
+        assert_eq!(parse_locale(""), None);
+    }
+
+    #[test]
+    fn allow_list_groups_languages_by_script() {
+        let allow_list = locales_allow_list(&[Language::Jpn, Language::Cmn, Language::Jpn]);
+        assert_eq!(allow_list.len(), 1);
+        assert_eq!(allow_list[&Script::Cj], vec![Language::Jpn, Language::Cmn]);
+    }
+
+    #[test]
+    fn stems_of_enabled_languages() {
+        let stemmers = Stemmers::new(&BTreeSet::from(["eng".to_string(), "jpn".to_string()]));
+        assert!(!stemmers.is_empty());
+        assert_eq!(stemmers.stems("running"), BTreeSet::from(["run".to_string()]));
+        assert_eq!(stemmers.stems("run"), BTreeSet::from(["run".to_string()]));
+
+        assert!(Stemmers::new(&BTreeSet::from(["jpn".to_string()])).is_empty());
+    }
+}
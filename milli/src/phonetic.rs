@@ -0,0 +1,696 @@
+//! A local implementation of Lawrence Philips' Double Metaphone algorithm.
+//!
+//! Double Metaphone encodes a word into a primary and an alternate phonetic code,
+//! words that sound alike, e.g. `smith` and `smyth`, share at least one of their codes.
+
+/// The maximum length of a phonetic code.
+const MAX_CODE_LENGTH: usize = 4;
+
+/// Returns the distinct, non-empty, Double Metaphone codes of the word.
+pub fn phonetic_codes(word: &str) -> Vec<String> {
+    let (primary, alternate) = double_metaphone(word);
+    let mut codes = Vec::with_capacity(2);
+    if !primary.is_empty() {
+        codes.push(primary);
+    }
+    if !alternate.is_empty() && !codes.contains(&alternate) {
+        codes.push(alternate);
+    }
+    codes
+}
+
+/// Returns the primary and alternate Double Metaphone codes of the word.
+pub fn double_metaphone(word: &str) -> (String, String) {
+    let mut encoder = Encoder::new(word);
+    encoder.encode();
+    let Encoder { mut primary, mut alternate, .. } = encoder;
+    primary.truncate(MAX_CODE_LENGTH);
+    alternate.truncate(MAX_CODE_LENGTH);
+    (primary, alternate)
+}
+
+struct Encoder {
+    chars: Vec<char>,
+    slavo_germanic: bool,
+    primary: String,
+    alternate: String,
+}
+
+impl Encoder {
+    fn new(word: &str) -> Self {
+        let upper = word.to_uppercase();
+        let slavo_germanic = upper.contains('W')
+            || upper.contains('K')
+            || upper.contains("CZ")
+            || upper.contains("WITZ");
+        Encoder {
+            chars: upper.chars().collect(),
+            slavo_germanic,
+            primary: String::new(),
+            alternate: String::new(),
+        }
+    }
+
+    fn at(&self, i: isize) -> char {
+        usize::try_from(i).ok().and_then(|i| self.chars.get(i)).copied().unwrap_or('\0')
+    }
+
+    fn is_vowel(&self, i: isize) -> bool {
+        matches!(self.at(i), 'A' | 'E' | 'I' | 'O' | 'U' | 'Y')
+    }
+
+    /// Returns `true` if the `len` characters starting at `start` are one of the candidates.
+    fn string_at(&self, start: isize, len: usize, candidates: &[&str]) -> bool {
+        let Ok(start) = usize::try_from(start) else { return false };
+        let Some(slice) = self.chars.get(start..start + len) else { return false };
+        candidates.iter().any(|candidate| candidate.chars().eq(slice.iter().copied()))
+    }
+
+    fn add(&mut self, code: &str) {
+        self.add_both(code, code);
+    }
+
+    fn add_both(&mut self, primary: &str, alternate: &str) {
+        self.primary.push_str(primary);
+        self.alternate.push_str(alternate);
+    }
+
+    fn is_complete(&self) -> bool {
+        self.primary.len() >= MAX_CODE_LENGTH && self.alternate.len() >= MAX_CODE_LENGTH
+    }
+
+    fn encode(&mut self) {
+        let len = self.chars.len() as isize;
+        let last = len - 1;
+        let mut i = 0;
+
+        // skip these silent letters when at the start of the word
+        if self.string_at(0, 2, &["GN", "KN", "PN", "WR", "PS"]) {
+            i += 1;
+        }
+        // an initial X is pronounced Z, which maps to S, e.g. Xavier
+        if self.at(0) == 'X' {
+            self.add("S");
+            i += 1;
+        }
+
+        while i < len && !self.is_complete() {
+            i += match self.at(i) {
+                'A' | 'E' | 'I' | 'O' | 'U' | 'Y' => {
+                    // all the initial vowels map to A
+                    if i == 0 {
+                        self.add("A");
+                    }
+                    1
+                }
+                'B' => {
+                    self.add("P");
+                    if self.at(i + 1) == 'B' {
+                        2
+                    } else {
+                        1
+                    }
+                }
+                'Ç' => {
+                    self.add("S");
+                    1
+                }
+                'C' => self.encode_c(i),
+                'D' => {
+                    if self.string_at(i, 2, &["DG"]) {
+                        if self.string_at(i + 2, 1, &["I", "E", "Y"]) {
+                            self.add("J");
+                            3
+                        } else {
+                            self.add("TK");
+                            2
+                        }
+                    } else if self.string_at(i, 2, &["DT", "DD"]) {
+                        self.add("T");
+                        2
+                    } else {
+                        self.add("T");
+                        1
+                    }
+                }
+                'F' => {
+                    self.add("F");
+                    if self.at(i + 1) == 'F' {
+                        2
+                    } else {
+                        1
+                    }
+                }
+                'G' => self.encode_g(i),
+                'H' => {
+                    // only keep the H if it is after a vowel and before a vowel
+                    if (i == 0 || self.is_vowel(i - 1)) && self.is_vowel(i + 1) {
+                        self.add("H");
+                        2
+                    } else {
+                        1
+                    }
+                }
+                'J' => self.encode_j(i, last),
+                'K' => {
+                    self.add("K");
+                    if self.at(i + 1) == 'K' {
+                        2
+                    } else {
+                        1
+                    }
+                }
+                'L' => {
+                    if self.at(i + 1) == 'L' {
+                        // spanish words, e.g. cabrillo, gallegos
+                        if (i == len - 3 && self.string_at(i - 1, 4, &["ILLO", "ILLA", "ALLE"]))
+                            || ((self.string_at(last - 1, 2, &["AS", "OS"])
+                                || self.string_at(last, 1, &["A", "O"]))
+                                && self.string_at(i - 1, 4, &["ALLE"]))
+                        {
+                            self.add_both("L", "");
+                        } else {
+                            self.add("L");
+                        }
+                        2
+                    } else {
+                        self.add("L");
+                        1
+                    }
+                }
+                'M' => {
+                    self.add("M");
+                    if (self.string_at(i - 1, 3, &["UMB"])
+                        && (i + 1 == last || self.string_at(i + 2, 2, &["ER"])))
+                        || self.at(i + 1) == 'M'
+                    {
+                        2
+                    } else {
+                        1
+                    }
+                }
+                'N' => {
+                    self.add("N");
+                    if self.at(i + 1) == 'N' {
+                        2
+                    } else {
+                        1
+                    }
+                }
+                'Ñ' => {
+                    self.add("N");
+                    1
+                }
+                'P' => {
+                    if self.at(i + 1) == 'H' {
+                        self.add("F");
+                        2
+                    } else {
+                        self.add("P");
+                        if self.string_at(i + 1, 1, &["P", "B"]) {
+                            2
+                        } else {
+                            1
+                        }
+                    }
+                }
+                'Q' => {
+                    self.add("K");
+                    if self.at(i + 1) == 'Q' {
+                        2
+                    } else {
+                        1
+                    }
+                }
+                'R' => {
+                    // french words, e.g. rogier, but not e.g. hochmeier
+                    if i == last
+                        && !self.slavo_germanic
+                        && self.string_at(i - 2, 2, &["IE"])
+                        && !self.string_at(i - 4, 2, &["ME", "MA"])
+                    {
+                        self.add_both("", "R");
+                    } else {
+                        self.add("R");
+                    }
+                    if self.at(i + 1) == 'R' {
+                        2
+                    } else {
+                        1
+                    }
+                }
+                'S' => self.encode_s(i, last),
+                'T' => {
+                    if self.string_at(i, 4, &["TION"]) || self.string_at(i, 3, &["TIA", "TCH"]) {
+                        self.add("X");
+                        3
+                    } else if self.string_at(i, 2, &["TH"]) || self.string_at(i, 3, &["TTH"]) {
+                        // special case for thomas, thames or germanic words
+                        if self.string_at(i + 2, 2, &["OM", "AM"])
+                            || self.string_at(0, 4, &["VAN ", "VON "])
+                            || self.string_at(0, 3, &["SCH"])
+                        {
+                            self.add("T");
+                        } else {
+                            self.add_both("0", "T");
+                        }
+                        2
+                    } else {
+                        self.add("T");
+                        if self.string_at(i + 1, 1, &["T", "D"]) {
+                            2
+                        } else {
+                            1
+                        }
+                    }
+                }
+                'V' => {
+                    self.add("F");
+                    if self.at(i + 1) == 'V' {
+                        2
+                    } else {
+                        1
+                    }
+                }
+                'W' => self.encode_w(i, last),
+                'X' => {
+                    // french words, e.g. breaux
+                    if !(i == last
+                        && (self.string_at(i - 3, 3, &["IAU", "EAU"])
+                            || self.string_at(i - 2, 2, &["AU", "OU"])))
+                    {
+                        self.add("KS");
+                    }
+                    if self.string_at(i + 1, 1, &["C", "X"]) {
+                        2
+                    } else {
+                        1
+                    }
+                }
+                'Z' => {
+                    // chinese pinyin, e.g. zhao
+                    if self.at(i + 1) == 'H' {
+                        self.add("J");
+                        2
+                    } else {
+                        if self.string_at(i + 1, 2, &["ZO", "ZI", "ZA"])
+                            || (self.slavo_germanic && i > 0 && self.at(i - 1) != 'T')
+                        {
+                            self.add_both("S", "TS");
+                        } else {
+                            self.add("S");
+                        }
+                        if self.at(i + 1) == 'Z' {
+                            2
+                        } else {
+                            1
+                        }
+                    }
+                }
+                _ => 1,
+            };
+        }
+    }
+
+    fn encode_c(&mut self, i: isize) -> isize {
+        // various germanic words
+        if i > 1
+            && !self.is_vowel(i - 2)
+            && self.string_at(i - 1, 3, &["ACH"])
+            && self.at(i + 2) != 'I'
+            && (self.at(i + 2) != 'E' || self.string_at(i - 2, 6, &["BACHER", "MACHER"]))
+        {
+            self.add("K");
+            return 2;
+        }
+        if i == 0 && self.string_at(i, 6, &["CAESAR"]) {
+            self.add("S");
+            return 2;
+        }
+        // italian words, e.g. chianti
+        if self.string_at(i, 4, &["CHIA"]) {
+            self.add("K");
+            return 2;
+        }
+        if self.string_at(i, 2, &["CH"]) {
+            // e.g. michael
+            if i > 0 && self.string_at(i, 4, &["CHAE"]) {
+                self.add_both("K", "X");
+                return 2;
+            }
+            // greek roots, e.g. chemistry, chorus
+            if i == 0
+                && (self.string_at(i + 1, 5, &["HARAC", "HARIS"])
+                    || self.string_at(i + 1, 3, &["HOR", "HYM", "HIA", "HEM"]))
+                && !self.string_at(0, 5, &["CHORE"])
+            {
+                self.add("K");
+                return 2;
+            }
+            // germanic, greek, or otherwise CH for the KH sound
+            if self.string_at(0, 4, &["VAN ", "VON "])
+                || self.string_at(0, 3, &["SCH"])
+                || self.string_at(i - 2, 6, &["ORCHES", "ARCHIT", "ORCHID"])
+                || self.string_at(i + 2, 1, &["T", "S"])
+                || ((self.string_at(i - 1, 1, &["A", "O", "U", "E"]) || i == 0)
+                    && self.string_at(
+                        i + 2,
+                        1,
+                        &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "],
+                    ))
+            {
+                self.add("K");
+            } else if i > 0 {
+                if self.string_at(0, 2, &["MC"]) {
+                    self.add("K");
+                } else {
+                    self.add_both("X", "K");
+                }
+            } else {
+                self.add("X");
+            }
+            return 2;
+        }
+        // polish words, e.g. czerny
+        if self.string_at(i, 2, &["CZ"]) && !self.string_at(i - 2, 4, &["WICZ"]) {
+            self.add_both("S", "X");
+            return 2;
+        }
+        // italian words, e.g. focaccia
+        if self.string_at(i + 1, 3, &["CIA"]) {
+            self.add("X");
+            return 3;
+        }
+        // double C, but not when it is the start of McClellan
+        if self.string_at(i, 2, &["CC"]) && !(i == 1 && self.at(0) == 'M') {
+            if self.string_at(i + 2, 1, &["I", "E", "H"]) && !self.string_at(i + 2, 2, &["HU"]) {
+                // e.g. accident, accede, succeed
+                if (i == 1 && self.at(i - 1) == 'A')
+                    || self.string_at(i - 1, 5, &["UCCEE", "UCCES"])
+                {
+                    self.add("KS");
+                } else {
+                    // e.g. bacci, bertucci
+                    self.add("X");
+                }
+                return 3;
+            }
+            self.add("K");
+            return 2;
+        }
+        if self.string_at(i, 2, &["CK", "CG", "CQ"]) {
+            self.add("K");
+            return 2;
+        }
+        if self.string_at(i, 2, &["CI", "CE", "CY"]) {
+            // italian vs. english
+            if self.string_at(i, 3, &["CIO", "CIE", "CIA"]) {
+                self.add_both("S", "X");
+            } else {
+                self.add("S");
+            }
+            return 2;
+        }
+
+        self.add("K");
+        // e.g. mac caffrey, mac gregor
+        if self.string_at(i + 1, 2, &[" C", " Q", " G"]) {
+            3
+        } else if self.string_at(i + 1, 1, &["C", "K", "Q"])
+            && !self.string_at(i + 1, 2, &["CE", "CI"])
+        {
+            2
+        } else {
+            1
+        }
+    }
+
+    fn encode_g(&mut self, i: isize) -> isize {
+        if self.at(i + 1) == 'H' {
+            if i > 0 && !self.is_vowel(i - 1) {
+                self.add("K");
+                return 2;
+            }
+            // e.g. ghislane, ghiradelli
+            if i == 0 {
+                if self.at(i + 2) == 'I' {
+                    self.add("J");
+                } else {
+                    self.add("K");
+                }
+                return 2;
+            }
+            // parker's rule (with some further refinements), e.g. hugh, bough, broughton
+            if (i > 1 && self.string_at(i - 2, 1, &["B", "H", "D"]))
+                || (i > 2 && self.string_at(i - 3, 1, &["B", "H", "D"]))
+                || (i > 3 && self.string_at(i - 4, 1, &["B", "H"]))
+            {
+                return 2;
+            }
+            // e.g. laugh, mclaughlin, cough, gough, rough, tough
+            if i > 2
+                && self.at(i - 1) == 'U'
+                && self.string_at(i - 3, 1, &["C", "G", "L", "R", "T"])
+            {
+                self.add("F");
+            } else if i > 0 && self.at(i - 1) != 'I' {
+                self.add("K");
+            }
+            return 2;
+        }
+        if self.at(i + 1) == 'N' {
+            if i == 1 && self.is_vowel(0) && !self.slavo_germanic {
+                self.add_both("KN", "N");
+            } else if !self.string_at(i + 2, 2, &["EY"]) && !self.slavo_germanic {
+                // not e.g. cagney
+                self.add_both("N", "KN");
+            } else {
+                self.add("KN");
+            }
+            return 2;
+        }
+        // e.g. tagliaro
+        if self.string_at(i + 1, 2, &["LI"]) && !self.slavo_germanic {
+            self.add_both("KL", "L");
+            return 2;
+        }
+        // -ges-, -gep-, -gel-, -gie- at the beginning of the word
+        if i == 0
+            && (self.at(i + 1) == 'Y'
+                || self.string_at(
+                    i + 1,
+                    2,
+                    &["ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER"],
+                ))
+        {
+            self.add_both("K", "J");
+            return 2;
+        }
+        // -ger-, -gy-
+        if (self.string_at(i + 1, 2, &["ER"]) || self.at(i + 1) == 'Y')
+            && !self.string_at(0, 6, &["DANGER", "RANGER", "MANGER"])
+            && !self.string_at(i - 1, 1, &["E", "I"])
+            && !self.string_at(i - 1, 3, &["RGY", "OGY"])
+        {
+            self.add_both("K", "J");
+            return 2;
+        }
+        // italian words, e.g. biaggi
+        if self.string_at(i + 1, 1, &["E", "I", "Y"]) || self.string_at(i - 1, 4, &["AGGI", "OGGI"])
+        {
+            // obvious germanic words
+            if self.string_at(0, 4, &["VAN ", "VON "])
+                || self.string_at(0, 3, &["SCH"])
+                || self.string_at(i + 1, 2, &["ET"])
+            {
+                self.add("K");
+            } else if self.string_at(i + 1, 4, &["IER "]) {
+                self.add("J");
+            } else {
+                self.add_both("J", "K");
+            }
+            return 2;
+        }
+
+        self.add("K");
+        if self.at(i + 1) == 'G' {
+            2
+        } else {
+            1
+        }
+    }
+
+    fn encode_j(&mut self, i: isize, last: isize) -> isize {
+        // obvious spanish words, e.g. jose, san jacinto
+        if self.string_at(i, 4, &["JOSE"]) || self.string_at(0, 4, &["SAN "]) {
+            if (i == 0 && self.at(i + 4) == ' ') || self.string_at(0, 4, &["SAN "]) {
+                self.add("H");
+            } else {
+                self.add_both("J", "H");
+            }
+            return 1;
+        }
+
+        if i == 0 {
+            // e.g. yankelovich, jankelowicz
+            self.add_both("J", "A");
+        } else if self.is_vowel(i - 1)
+            && !self.slavo_germanic
+            && matches!(self.at(i + 1), 'A' | 'O')
+        {
+            // spanish pronunciation, e.g. bajador
+            self.add_both("J", "H");
+        } else if i == last {
+            self.add_both("J", "");
+        } else if !self.string_at(i + 1, 1, &["L", "T", "K", "S", "N", "M", "B", "Z"])
+            && !self.string_at(i - 1, 1, &["S", "K", "L"])
+        {
+            self.add("J");
+        }
+
+        if self.at(i + 1) == 'J' {
+            2
+        } else {
+            1
+        }
+    }
+
+    fn encode_s(&mut self, i: isize, last: isize) -> isize {
+        // special cases, e.g. island, isle, carlisle, carlysle
+        if self.string_at(i - 1, 3, &["ISL", "YSL"]) {
+            return 1;
+        }
+        // special case, sugar
+        if i == 0 && self.string_at(i, 5, &["SUGAR"]) {
+            self.add_both("X", "S");
+            return 1;
+        }
+        if self.string_at(i, 2, &["SH"]) {
+            // germanic words
+            if self.string_at(i + 1, 4, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                self.add("S");
+            } else {
+                self.add("X");
+            }
+            return 2;
+        }
+        // italian and armenian words
+        if self.string_at(i, 3, &["SIO", "SIA"]) || self.string_at(i, 4, &["SIAN"]) {
+            if self.slavo_germanic {
+                self.add("S");
+            } else {
+                self.add_both("S", "X");
+            }
+            return 3;
+        }
+        // german and anglicisations, e.g. smith matches schmidt, snider matches schneider
+        if (i == 0 && self.string_at(i + 1, 1, &["M", "N", "L", "W"]))
+            || self.string_at(i + 1, 1, &["Z"])
+        {
+            self.add_both("S", "X");
+            return if self.string_at(i + 1, 1, &["Z"]) { 2 } else { 1 };
+        }
+        if self.string_at(i, 2, &["SC"]) {
+            // schlesinger's rule
+            if self.at(i + 2) == 'H' {
+                if self.string_at(i + 3, 2, &["OO", "ER", "EN", "UY", "ED", "EM"]) {
+                    // dutch origin, e.g. school, schooner
+                    if self.string_at(i + 3, 2, &["ER", "EN"]) {
+                        // e.g. schermerhorn, schenker
+                        self.add_both("X", "SK");
+                    } else {
+                        self.add("SK");
+                    }
+                } else if i == 0 && !self.is_vowel(3) && self.at(3) != 'W' {
+                    self.add_both("X", "S");
+                } else {
+                    self.add("X");
+                }
+            } else if self.string_at(i + 2, 1, &["I", "E", "Y"]) {
+                self.add("S");
+            } else {
+                self.add("SK");
+            }
+            return 3;
+        }
+
+        // french words, e.g. resnais, artois
+        if i == last && self.string_at(i - 2, 2, &["AI", "OI"]) {
+            self.add_both("", "S");
+        } else {
+            self.add("S");
+        }
+        if self.string_at(i + 1, 1, &["S", "Z"]) {
+            2
+        } else {
+            1
+        }
+    }
+
+    fn encode_w(&mut self, i: isize, last: isize) -> isize {
+        // can also be in the middle of a word
+        if self.string_at(i, 2, &["WR"]) {
+            self.add("R");
+            return 2;
+        }
+        if i == 0 && (self.is_vowel(i + 1) || self.string_at(i, 2, &["WH"])) {
+            // wasserman should match vasserman
+            if self.is_vowel(i + 1) {
+                self.add_both("A", "F");
+            } else {
+                // need the unaspirated form of W, e.g. whumpf
+                self.add("A");
+            }
+        }
+        // e.g. arnow should match arnoff
+        if (i == last && self.is_vowel(i - 1))
+            || self.string_at(i - 1, 5, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+            || self.string_at(0, 3, &["SCH"])
+        {
+            self.add_both("", "F");
+            return 1;
+        }
+        // polish words, e.g. filipowicz
+        if self.string_at(i, 4, &["WICZ", "WITZ"]) {
+            self.add_both("TS", "FX");
+            return 4;
+        }
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(word: &str) -> (String, String) {
+        double_metaphone(word)
+    }
+
+    #[test]
+    fn double_metaphone_codes() {
+        assert_eq!(codes("smith"), ("SM0".to_string(), "XMT".to_string()));
+        assert_eq!(codes("schmidt"), ("XMT".to_string(), "SMT".to_string()));
+        assert_eq!(codes("meyer"), ("MR".to_string(), "MR".to_string()));
+
+        assert_eq!(codes("knight"), ("NT".to_string(), "NT".to_string()));
+        assert_eq!(codes("xavier"), ("SF".to_string(), "SFR".to_string()));
+        assert_eq!(codes("filipowicz"), ("FLPT".to_string(), "FLPF".to_string()));
+        assert_eq!(codes("12345"), (String::new(), String::new()));
+    }
+
+    #[test]
+    fn similar_sounding_words_share_a_code() {
+        let share_a_code = |left: &str, right: &str| {
+            let right = phonetic_codes(right);
+            phonetic_codes(left).iter().any(|code| right.contains(code))
+        };
+
+        assert!(share_a_code("smith", "smyth"));
+        assert!(share_a_code("smith", "schmidt"));
+        assert!(share_a_code("meyer", "maier"));
+        assert!(share_a_code("catherine", "kathryn"));
+        assert!(!share_a_code("smith", "meyer"));
+        assert!(phonetic_codes("2024").is_empty());
+    }
+}
@@ -1,19 +1,24 @@
-use std::collections::{BTreeMap, HashSet};
-use std::ops::ControlFlow;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::{Bound, ControlFlow};
 use std::{fmt, mem};
 
 use heed::types::ByteSlice;
 use heed::BytesDecode;
+use indexmap::IndexMap;
 use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
 
 use crate::error::UserError;
 use crate::facet::FacetType;
 use crate::heed_codec::facet::{
-    FacetGroupKeyCodec, FacetGroupValueCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec,
-    OrderedF64Codec,
+    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, FieldDocIdFacetF64Codec,
+    FieldDocIdFacetStringCodec, OrderedF64Codec,
 };
 use crate::heed_codec::{ByteSliceRefCodec, StrRefCodec};
-use crate::search::facet::facet_distribution_iter;
+use crate::search::facet::{
+    facet_distribution_iter, facet_max_value, facet_min_value, facet_range_search,
+};
 use crate::{FieldId, Index, Result};
 
 /// The default number of values by facets that will
@@ -24,10 +29,60 @@ pub const DEFAULT_VALUES_PER_FACET: usize = 100;
 /// the system to choose between one algorithm or another.
 const CANDIDATES_THRESHOLD: u64 = 3000;
 
+/// The maximum number of buckets that can be returned by a facet histogram.
+pub const MAX_FACET_HISTOGRAM_BUCKETS: usize = 1000;
+
+/// The number of documents in the levels of a hierarchical facet.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FacetHierarchy {
+    /// The selected path, from its top level value to the deepest one.
+    pub path: Vec<(String, u64)>,
+    /// The direct children of the selected path.
+    pub children: Vec<(String, u64)>,
+}
+
+/// The percentiles returned in the [`FacetNumberStats`].
+pub const FACET_STATS_PERCENTILES: [u8; 5] = [25, 50, 75, 90, 99];
+
+/// Statistics about the numeric values of a facet for a set of documents.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetNumberStats {
+    pub min: f64,
+    pub max: f64,
+    /// The number of documents with at least one value.
+    pub count: u64,
+    pub sum: f64,
+    /// The mean of the values, a document with several values counts each of them.
+    pub avg: f64,
+    /// The number of distinct values.
+    pub distinct_count: u64,
+    /// The value at each of the [`FACET_STATS_PERCENTILES`].
+    pub percentiles: Vec<(u8, f64)>,
+}
+
+/// A range of numeric facet values, from an inclusive lower bound to an exclusive upper bound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FacetRange {
+    pub from: Option<f64>,
+    pub to: Option<f64>,
+}
+
+/// The order in which the values of a facet are returned by the facet distribution.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OrderBy {
+    /// Returns the facet values in lexicographic order.
+    #[default]
+    Lexicographic,
+    /// Returns the facet values by decreasing number of documents.
+    Count,
+}
+
 pub struct FacetDistribution<'a> {
     facets: Option<HashSet<String>>,
     candidates: Option<RoaringBitmap>,
     max_values_per_facet: usize,
+    order_by: HashMap<String, OrderBy>,
     rtxn: &'a heed::RoTxn<'a>,
     index: &'a Index,
 }
@@ -38,6 +93,7 @@ impl<'a> FacetDistribution<'a> {
             facets: None,
             candidates: None,
             max_values_per_facet: DEFAULT_VALUES_PER_FACET,
+            order_by: HashMap::new(),
             rtxn,
             index,
         }
@@ -58,6 +114,19 @@ impl<'a> FacetDistribution<'a> {
         self
     }
 
+    /// Defines the order of the values of each facet, by facet name.
+    ///
+    /// The `*` entry applies to the facets without their own entry, the facet values
+    /// are returned in lexicographic order by default.
+    pub fn order_by(&mut self, order_by: HashMap<String, OrderBy>) -> &mut Self {
+        self.order_by = order_by;
+        self
+    }
+
+    fn facet_order(&self, name: &str) -> OrderBy {
+        self.order_by.get(name).or_else(|| self.order_by.get("*")).copied().unwrap_or_default()
+    }
+
     /// There is a small amount of candidates OR we ask for facet string values so we
     /// decide to iterate over the facet values of each one of them, one by one.
     fn facet_distribution_from_documents(
@@ -239,6 +308,72 @@ impl<'a> FacetDistribution<'a> {
         Ok(distribution)
     }
 
+    /// Returns the most frequent facet values among the candidates, or among all the
+    /// documents for a placeholder search, by decreasing number of documents.
+    fn facet_values_by_count(&self, field_id: FieldId) -> Result<IndexMap<String, u64>> {
+        let candidates = match self.candidates {
+            Some(ref candidates) => Cow::Borrowed(candidates),
+            None => Cow::Owned(self.index.documents_ids(self.rtxn)?),
+        };
+
+        let mut values = Vec::new();
+        facet_distribution_iter::count_iterate_over_facet_distribution(
+            self.rtxn,
+            self.index
+                .facet_id_f64_docids
+                .remap_key_type::<FacetGroupKeyCodec<ByteSliceRefCodec>>(),
+            field_id,
+            &candidates,
+            |facet_key, nbr_docids, _| {
+                let facet_key = OrderedF64Codec::bytes_decode(facet_key).unwrap();
+                values.push((facet_key.to_string(), nbr_docids));
+                if values.len() == self.max_values_per_facet {
+                    Ok(ControlFlow::Break(()))
+                } else {
+                    Ok(ControlFlow::Continue(()))
+                }
+            },
+        )?;
+
+        let mut string_values = Vec::new();
+        facet_distribution_iter::count_iterate_over_facet_distribution(
+            self.rtxn,
+            self.index
+                .facet_id_string_docids
+                .remap_key_type::<FacetGroupKeyCodec<ByteSliceRefCodec>>(),
+            field_id,
+            &candidates,
+            |facet_key, nbr_docids, any_docid| {
+                let facet_key = StrRefCodec::bytes_decode(facet_key).unwrap();
+
+                let key: (FieldId, _, &str) = (field_id, any_docid, facet_key);
+                let original_string = self
+                    .index
+                    .field_id_docid_facet_strings
+                    .get(self.rtxn, &key)?
+                    .unwrap()
+                    .to_owned();
+
+                string_values.push((original_string, nbr_docids));
+                if string_values.len() == self.max_values_per_facet {
+                    Ok(ControlFlow::Break(()))
+                } else {
+                    Ok(ControlFlow::Continue(()))
+                }
+            },
+        )?;
+
+        // Both lists are the exact top values of their type, we merge them
+        // to only keep the overall top values.
+        values.extend(string_values);
+        values.sort_by(|(lvalue, lcount), (rvalue, rcount)| {
+            rcount.cmp(lcount).then_with(|| lvalue.cmp(rvalue))
+        });
+        values.truncate(self.max_values_per_facet);
+
+        Ok(values.into_iter().collect())
+    }
+
     fn facet_values(&self, field_id: FieldId) -> heed::Result<BTreeMap<String, u64>> {
         use FacetType::{Number, String};
 
@@ -337,7 +472,360 @@ impl<'a> FacetDistribution<'a> {
         Ok(distribution)
     }
 
-    pub fn execute(&self) -> Result<BTreeMap<String, BTreeMap<String, u64>>> {
+    /// Returns, for each numeric facet, detailed statistics about the facet values of the candidates.
+    ///
+    /// Contrary to [`Self::compute_stats`], all the facet values of the candidates are read
+    /// from the level 0 of the facet number database.
+    pub fn compute_detailed_stats(&self) -> Result<BTreeMap<String, FacetNumberStats>> {
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let candidates = match self.candidates {
+            Some(ref candidates) => candidates,
+            None => return Ok(Default::default()),
+        };
+        let fields = match &self.facets {
+            Some(facets) => {
+                self.check_faceted(facets.iter(), |invalid_facets_name, valid_facets_name| {
+                    UserError::InvalidFacetsDistribution { invalid_facets_name, valid_facets_name }
+                })?;
+                facets.clone()
+            }
+            None => self.index.filterable_fields(self.rtxn)?,
+        };
+
+        let mut distribution = BTreeMap::new();
+        for (fid, name) in fields_ids_map.iter() {
+            if crate::is_faceted(name, &fields) {
+                if let Some(stats) = self.facet_number_stats(fid, candidates)? {
+                    distribution.insert(name.to_string(), stats);
+                }
+            }
+        }
+
+        Ok(distribution)
+    }
+
+    fn facet_number_stats(
+        &self,
+        field_id: FieldId,
+        candidates: &RoaringBitmap,
+    ) -> Result<Option<FacetNumberStats>> {
+        let mut prefix = vec![];
+        prefix.extend_from_slice(&field_id.to_be_bytes());
+        prefix.push(0); // read values from level 0 only
+
+        let iter = self
+            .index
+            .facet_id_f64_docids
+            .as_polymorph()
+            .prefix_iter::<_, ByteSlice, ByteSlice>(self.rtxn, prefix.as_slice())?
+            .remap_types::<FacetGroupKeyCodec<OrderedF64Codec>, FacetGroupValueCodec>();
+
+        // the facet values of the candidates by ascending order, with their number of documents
+        let mut values = Vec::new();
+        let mut documents = RoaringBitmap::new();
+        let mut values_count = 0;
+        let mut sum = 0.0;
+        for result in iter {
+            let (key, value) = result?;
+            let docids = value.bitmap & candidates;
+            let count = docids.len();
+            if count != 0 {
+                values.push((key.left_bound, count));
+                values_count += count;
+                sum += key.left_bound * count as f64;
+                documents |= docids;
+            }
+        }
+
+        let (min, max) = match (values.first(), values.last()) {
+            (Some(&(min, _)), Some(&(max, _))) => (min, max),
+            _ => return Ok(None),
+        };
+
+        let mut percentiles = Vec::with_capacity(FACET_STATS_PERCENTILES.len());
+        let mut values_iter = values.iter();
+        let mut seen = 0;
+        let mut current = min;
+        for percentile in FACET_STATS_PERCENTILES {
+            // nearest-rank method: the smallest value greater than or equal to
+            // the given percentage of the values
+            let rank = ((percentile as f64 / 100.0) * values_count as f64).ceil().max(1.0) as u64;
+            while seen < rank {
+                match values_iter.next() {
+                    Some(&(value, count)) => {
+                        seen += count;
+                        current = value;
+                    }
+                    None => break,
+                }
+            }
+            percentiles.push((percentile, current));
+        }
+
+        Ok(Some(FacetNumberStats {
+            min,
+            max,
+            count: documents.len(),
+            sum,
+            avg: sum / values_count as f64,
+            distinct_count: values.len() as u64,
+            percentiles,
+        }))
+    }
+
+    /// Returns, for each requested hierarchical facet, the number of candidates in each level of
+    /// the selected path and in each of its direct children.
+    ///
+    /// The facets without a selected path return the top level values.
+    pub fn compute_hierarchies(
+        &self,
+        paths: &BTreeMap<String, String>,
+    ) -> Result<BTreeMap<String, FacetHierarchy>> {
+        let hierarchical_facets = self.index.hierarchical_facets(self.rtxn)?;
+        if let Some(name) = paths.keys().find(|name| !hierarchical_facets.contains_key(*name)) {
+            return Err(UserError::InvalidFacetPath {
+                facet_name: name.clone(),
+                valid_facets_name: hierarchical_facets.into_keys().collect(),
+            }
+            .into());
+        }
+
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+        let candidates = self.candidates_or_all_documents()?;
+
+        let mut hierarchies = BTreeMap::new();
+        for (name, separator) in &hierarchical_facets {
+            let requested = match &self.facets {
+                Some(facets) => facets.contains(name) || facets.contains("*"),
+                None => true,
+            };
+            if !requested || !crate::is_faceted(name, &filterable_fields) {
+                continue;
+            }
+
+            let path: Vec<_> = paths
+                .get(name)
+                .map(|path| path.split(separator.as_str()).map(str::trim))
+                .into_iter()
+                .flatten()
+                .filter(|level| !level.is_empty())
+                .collect();
+
+            let hierarchy = match fields_ids_map.id(name) {
+                Some(field_id) => self.facet_hierarchy(field_id, separator, &path, &candidates)?,
+                None => FacetHierarchy::default(),
+            };
+            hierarchies.insert(name.clone(), hierarchy);
+        }
+
+        Ok(hierarchies)
+    }
+
+    fn facet_hierarchy(
+        &self,
+        field_id: FieldId,
+        separator: &str,
+        path: &[&str],
+        candidates: &RoaringBitmap,
+    ) -> Result<FacetHierarchy> {
+        let db = self.index.facet_id_string_docids;
+        let mut hierarchy = FacetHierarchy::default();
+
+        for depth in 1..=path.len() {
+            let ancestor = path[..depth].join(separator);
+            let normalized = crate::normalize_facet(&ancestor);
+            let count = db
+                .get(self.rtxn, &FacetGroupKey { field_id, level: 0, left_bound: &normalized })?
+                .map_or(0, |value| value.bitmap.intersection_len(candidates));
+            hierarchy.path.push((ancestor, count));
+        }
+
+        // The paths are stored normalized in the level 0 of the facet database, right after the
+        // field id and the level, the children of the selected path are found by a prefix scan.
+        let normalized_path = crate::normalize_facet(&path.join(separator));
+        let mut prefix = vec![];
+        prefix.extend_from_slice(&field_id.to_be_bytes());
+        prefix.push(0);
+        prefix.extend_from_slice(normalized_path.as_bytes());
+
+        let iter = db
+            .as_polymorph()
+            .prefix_iter::<_, ByteSlice, ByteSlice>(self.rtxn, prefix.as_slice())?
+            .remap_types::<FacetGroupKeyCodec<StrRefCodec>, FacetGroupValueCodec>();
+
+        for result in iter {
+            let (key, value) = result?;
+            let count = value.bitmap.intersection_len(candidates);
+            if count == 0 {
+                continue;
+            }
+
+            let docid = value.bitmap.iter().next().unwrap();
+            let original = match self
+                .index
+                .field_id_docid_facet_strings
+                .get(self.rtxn, &(field_id, docid, key.left_bound))?
+            {
+                Some(original) => original,
+                None => continue,
+            };
+
+            // The prefix scan also returns the deeper descendants and the values
+            // sharing the same prefix without being in the selected path.
+            let levels: Vec<_> = original.split(separator).map(str::trim).collect();
+            if levels.len() == path.len() + 1
+                && crate::normalize_facet(&levels[..path.len()].join(separator)) == normalized_path
+            {
+                hierarchy.children.push((original.to_string(), count));
+                if hierarchy.children.len() == self.max_values_per_facet {
+                    break;
+                }
+            }
+        }
+
+        Ok(hierarchy)
+    }
+
+    /// Returns the number of candidates whose facet value is in each of the given ranges.
+    pub fn compute_ranges(
+        &self,
+        ranges: &BTreeMap<String, Vec<FacetRange>>,
+    ) -> Result<BTreeMap<String, Vec<(FacetRange, u64)>>> {
+        self.check_faceted(ranges.keys(), |invalid_facets_name, valid_facets_name| {
+            UserError::InvalidFacetRanges { invalid_facets_name, valid_facets_name }
+        })?;
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let candidates = self.candidates_or_all_documents()?;
+
+        let mut distribution = BTreeMap::new();
+        for (name, ranges) in ranges {
+            let field_id = fields_ids_map.id(name);
+            let mut counts = Vec::with_capacity(ranges.len());
+            for range in ranges {
+                counts.push((*range, self.count_in_range(field_id, range, &candidates)?));
+            }
+            distribution.insert(name.clone(), counts);
+        }
+
+        Ok(distribution)
+    }
+
+    /// Returns the number of candidates in fixed-width ranges of numeric facet values.
+    ///
+    /// The ranges are aligned on multiples of the interval and go from the one containing
+    /// the smallest facet value of the candidates to the one containing the largest.
+    pub fn compute_histogram(
+        &self,
+        intervals: &BTreeMap<String, f64>,
+    ) -> Result<BTreeMap<String, Vec<(FacetRange, u64)>>> {
+        self.check_faceted(intervals.keys(), |invalid_facets_name, valid_facets_name| {
+            UserError::InvalidFacetHistogram { invalid_facets_name, valid_facets_name }
+        })?;
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let candidates = self.candidates_or_all_documents()?;
+
+        let mut distribution = BTreeMap::new();
+        for (name, &interval) in intervals {
+            if !interval.is_finite() || interval <= 0.0 {
+                return Err(UserError::InvalidFacetHistogramInterval {
+                    facet_name: name.clone(),
+                    interval,
+                }
+                .into());
+            }
+
+            let mut counts = Vec::new();
+            if let Some(field_id) = fields_ids_map.id(name) {
+                let min = facet_min_value(self.index, self.rtxn, field_id, candidates.clone())?;
+                let max = facet_max_value(self.index, self.rtxn, field_id, candidates.clone())?;
+                if let (Some(min), Some(max)) = (min, max) {
+                    let first = (min / interval).floor();
+                    let buckets = (max / interval).floor() - first + 1.0;
+                    if buckets > MAX_FACET_HISTOGRAM_BUCKETS as f64 {
+                        return Err(UserError::TooManyFacetHistogramBuckets {
+                            facet_name: name.clone(),
+                            interval,
+                            buckets,
+                            max_buckets: MAX_FACET_HISTOGRAM_BUCKETS,
+                        }
+                        .into());
+                    }
+
+                    for i in 0..buckets as usize {
+                        // computing each bound from the first one avoids accumulating rounding errors
+                        let from = (first + i as f64) * interval;
+                        let to = (first + i as f64 + 1.0) * interval;
+                        let range = FacetRange { from: Some(from), to: Some(to) };
+                        counts.push((
+                            range,
+                            self.count_in_range(Some(field_id), &range, &candidates)?,
+                        ));
+                    }
+                }
+            }
+            distribution.insert(name.clone(), counts);
+        }
+
+        Ok(distribution)
+    }
+
+    fn count_in_range(
+        &self,
+        field_id: Option<FieldId>,
+        range: &FacetRange,
+        candidates: &RoaringBitmap,
+    ) -> Result<u64> {
+        let field_id = match field_id {
+            Some(field_id) => field_id,
+            None => return Ok(0),
+        };
+        if let (Some(from), Some(to)) = (range.from, range.to) {
+            if from >= to {
+                return Ok(0);
+            }
+        }
+
+        let left = range.from.map_or(Bound::Unbounded, Bound::Included);
+        let right = range.to.map_or(Bound::Unbounded, Bound::Excluded);
+        let mut docids = RoaringBitmap::new();
+        facet_range_search::find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+            self.rtxn,
+            self.index.facet_id_f64_docids,
+            field_id,
+            &left,
+            &right,
+            &mut docids,
+        )?;
+
+        Ok(docids.intersection_len(candidates))
+    }
+
+    fn candidates_or_all_documents(&self) -> Result<RoaringBitmap> {
+        match self.candidates {
+            Some(ref candidates) => Ok(candidates.clone()),
+            None => Ok(self.index.documents_ids(self.rtxn)?),
+        }
+    }
+
+    /// Returns the error built from the invalid and the filterable facets
+    /// if one of the given facets isn't filterable.
+    fn check_faceted<'n>(
+        &self,
+        facets: impl Iterator<Item = &'n String>,
+        error: impl FnOnce(BTreeSet<String>, BTreeSet<String>) -> UserError,
+    ) -> Result<()> {
+        let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+        let invalid_fields: BTreeSet<_> =
+            facets.filter(|facet| !crate::is_faceted(facet, &filterable_fields)).cloned().collect();
+        if invalid_fields.is_empty() {
+            Ok(())
+        } else {
+            Err(error(invalid_fields, filterable_fields.into_iter().collect()).into())
+        }
+    }
+
+    pub fn execute(&self) -> Result<BTreeMap<String, IndexMap<String, u64>>> {
         let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
         let filterable_fields = self.index.filterable_fields(self.rtxn)?;
 
@@ -363,7 +851,10 @@ impl<'a> FacetDistribution<'a> {
         let mut distribution = BTreeMap::new();
         for (fid, name) in fields_ids_map.iter() {
             if crate::is_faceted(name, &fields) {
-                let values = self.facet_values(fid)?;
+                let values = match self.facet_order(name) {
+                    OrderBy::Lexicographic => self.facet_values(fid)?.into_iter().collect(),
+                    OrderBy::Count => self.facet_values_by_count(fid)?,
+                };
                 distribution.insert(name.to_string(), values);
             }
         }
@@ -374,25 +865,34 @@ impl<'a> FacetDistribution<'a> {
 
 impl fmt::Debug for FacetDistribution<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let FacetDistribution { facets, candidates, max_values_per_facet, rtxn: _, index: _ } =
-            self;
+        let FacetDistribution {
+            facets,
+            candidates,
+            max_values_per_facet,
+            order_by,
+            rtxn: _,
+            index: _,
+        } = self;
 
         f.debug_struct("FacetDistribution")
             .field("facets", facets)
             .field("candidates", candidates)
             .field("max_values_per_facet", max_values_per_facet)
+            .field("order_by", order_by)
             .finish()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
     use big_s::S;
     use maplit::hashset;
 
     use crate::documents::documents_batch_reader_from_objects;
     use crate::index::tests::TempIndex;
-    use crate::{milli_snap, FacetDistribution};
+    use crate::{milli_snap, Error, FacetDistribution, FacetRange, OrderBy, UserError};
 
     #[test]
     fn few_candidates_few_facet_values() {
@@ -649,6 +1149,56 @@ mod tests {
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (217.0, 776.0)}"###);
     }
 
+    #[test]
+    fn facet_detailed_stats() {
+        let mut index = TempIndex::new_with_map_size(4096 * 10_000);
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| settings.set_filterable_fields(hashset! { S("colour") }))
+            .unwrap();
+
+        let mut documents = vec![];
+        for i in 0..1000 {
+            let document = serde_json::json!({
+                "colour": i,
+            })
+            .as_object()
+            .unwrap()
+            .clone();
+            documents.push(document);
+        }
+
+        let documents = documents_batch_reader_from_objects(documents);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(std::iter::once("colour"))
+            .compute_detailed_stats()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), "no_candidates", @"{}");
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(std::iter::once("colour"))
+            .candidates((0..1000).collect())
+            .compute_detailed_stats()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), "candidates_0_1000", @r###"{"colour": FacetNumberStats { min: 0.0, max: 999.0, count: 1000, sum: 499500.0, avg: 499.5, distinct_count: 1000, percentiles: [(25, 249.0), (50, 499.0), (75, 749.0), (90, 899.0), (99, 989.0)] }}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(std::iter::once("colour"))
+            .candidates((217..777).collect())
+            .compute_detailed_stats()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": FacetNumberStats { min: 217.0, max: 776.0, count: 560, sum: 278040.0, avg: 496.5, distinct_count: 560, percentiles: [(25, 356.0), (50, 496.0), (75, 636.0), (90, 720.0), (99, 771.0)] }}"###);
+    }
+
     #[test]
     fn facet_stats_array() {
         let mut index = TempIndex::new_with_map_size(4096 * 10_000);
@@ -808,4 +1358,132 @@ mod tests {
 
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (218.0, 1776.0)}"###);
     }
+
+    #[test]
+    fn order_by_count() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| settings.set_filterable_fields(hashset! { S("colour") }))
+            .unwrap();
+
+        let documents = documents!([
+            { "colour": "Blue" },
+            { "colour": "blue" },
+            { "colour": "Blue" },
+            { "colour": "RED" },
+            { "colour": "red" },
+            { "colour": 1 },
+            { "colour": 1 },
+            { "colour": 1 },
+            { "colour": 1 },
+            { "colour": "green" },
+            { "colour": "green" },
+            { "colour": "green" },
+            { "colour": "green" },
+            { "colour": "green" }
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(std::iter::once("colour"))
+            .max_values_per_facet(2)
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"1": 4, "Blue": 3}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(std::iter::once("colour"))
+            .max_values_per_facet(2)
+            .order_by(HashMap::from([(S("colour"), OrderBy::Count)]))
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"green": 5, "1": 4}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(std::iter::once("colour"))
+            .candidates((0..9).collect())
+            .order_by(HashMap::from([(S("*"), OrderBy::Count)]))
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"1": 4, "Blue": 3, "RED": 2}}"###);
+    }
+
+    #[test]
+    fn ranges_and_histogram() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| settings.set_filterable_fields(hashset! { S("price") }))
+            .unwrap();
+
+        let documents = documents!([
+            { "price": 3 },
+            { "price": 9.5 },
+            { "price": 10 },
+            { "price": [12, 48] },
+            { "price": 50 },
+            { "price": 120 },
+            { "price": "cheap" }
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let ranges = BTreeMap::from([(
+            S("price"),
+            vec![
+                FacetRange { from: Some(0.), to: Some(10.) },
+                FacetRange { from: Some(10.), to: Some(50.) },
+                FacetRange { from: Some(50.), to: None },
+            ],
+        )]);
+        let map = FacetDistribution::new(&txn, &index).compute_ranges(&ranges).unwrap();
+        let counts: Vec<_> = map["price"].iter().map(|(_, count)| *count).collect();
+        assert_eq!(counts, [2, 2, 2]);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates([0, 1, 3].iter().copied().collect())
+            .compute_ranges(&ranges)
+            .unwrap();
+        let counts: Vec<_> = map["price"].iter().map(|(_, count)| *count).collect();
+        assert_eq!(counts, [2, 1, 0]);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates((0..5).collect())
+            .compute_histogram(&BTreeMap::from([(S("price"), 20.)]))
+            .unwrap();
+        milli_snap!(format!("{map:?}"), @r###"{"price": [(FacetRange { from: Some(0.0), to: Some(20.0) }, 4), (FacetRange { from: Some(20.0), to: Some(40.0) }, 0), (FacetRange { from: Some(40.0), to: Some(60.0) }, 2)]}"###);
+
+        let error = FacetDistribution::new(&txn, &index)
+            .compute_histogram(&BTreeMap::from([(S("price"), 0.1)]))
+            .unwrap_err();
+        assert!(error.to_string().contains("limited to 1000 buckets"), "{error}");
+
+        let error = FacetDistribution::new(&txn, &index)
+            .compute_histogram(&BTreeMap::from([(S("price"), -1.)]))
+            .unwrap_err();
+        assert!(error.to_string().contains("must be a positive number"), "{error}");
+
+        let error = FacetDistribution::new(&txn, &index)
+            .compute_ranges(&BTreeMap::from([(S("title"), vec![])]))
+            .unwrap_err();
+        assert!(matches!(error, Error::UserError(UserError::InvalidFacetRanges { .. })));
+        assert!(error.to_string().contains("not filterable"), "{error}");
+
+        let error = FacetDistribution::new(&txn, &index)
+            .compute_histogram(&BTreeMap::from([(S("title"), 20.)]))
+            .unwrap_err();
+        assert!(matches!(error, Error::UserError(UserError::InvalidFacetHistogram { .. })));
+        assert!(error.to_string().contains("not filterable"), "{error}");
+    }
 }
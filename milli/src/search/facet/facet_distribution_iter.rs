@@ -1,3 +1,5 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
 use std::ops::ControlFlow;
 
 use heed::Result;
@@ -44,6 +46,84 @@ where
     }
 }
 
+/// Call the given closure on the facet distribution of the candidate documents, in
+/// decreasing order of number of documents. Facet values with the same number of documents
+/// are given in increasing order of facet value.
+///
+/// The facet groups of the upper levels are visited with a max-heap on their number of
+/// candidates, so that a facet value is given to the closure only once we know that no
+/// other group can contain a facet value with more documents. It makes it possible to
+/// stop the iteration early while still returning the exact top facet values.
+///
+/// The arguments and return value of the closure are the same as the ones of
+/// [`iterate_over_facet_distribution`].
+pub fn count_iterate_over_facet_distribution<'t, CB>(
+    rtxn: &'t heed::RoTxn<'t>,
+    db: heed::Database<FacetGroupKeyCodec<ByteSliceRefCodec>, FacetGroupValueCodec>,
+    field_id: u16,
+    candidates: &RoaringBitmap,
+    mut callback: CB,
+) -> Result<()>
+where
+    CB: FnMut(&'t [u8], u64, DocumentId) -> Result<ControlFlow<()>>,
+{
+    #[derive(PartialEq, Eq, PartialOrd, Ord)]
+    struct LevelEntry<'t> {
+        count: u64,
+        left_bound: Reverse<&'t [u8]>,
+        level: Reverse<u8>,
+        group_size: u8,
+        any_docid: DocumentId,
+    }
+
+    let highest_level = get_highest_level(rtxn, db, field_id)?;
+    let first_bound = match get_first_facet_value::<ByteSliceRefCodec>(rtxn, db, field_id)? {
+        Some(first_bound) => first_bound,
+        None => return Ok(()),
+    };
+
+    let mut heap = BinaryHeap::new();
+    let push_groups = |heap: &mut BinaryHeap<LevelEntry<'t>>,
+                       level: u8,
+                       starting_bound: &'t [u8],
+                       group_size: usize|
+     -> Result<()> {
+        let starting_key = FacetGroupKey { field_id, level, left_bound: starting_bound };
+        for el in db.range(rtxn, &(&starting_key..))?.take(group_size) {
+            let (key, value) = el?;
+            // The range is unbounded on the right and the group size for the highest level is MAX,
+            // so we need to check that we are not iterating over the next field id
+            if key.field_id != field_id {
+                break;
+            }
+            let docids_in_common = value.bitmap & candidates;
+            if let Some(any_docid) = docids_in_common.min() {
+                heap.push(LevelEntry {
+                    count: docids_in_common.len(),
+                    left_bound: Reverse(key.left_bound),
+                    level: Reverse(key.level),
+                    group_size: value.size,
+                    any_docid,
+                });
+            }
+        }
+        Ok(())
+    };
+
+    push_groups(&mut heap, highest_level, first_bound, usize::MAX)?;
+    while let Some(LevelEntry { count, left_bound, level, group_size, any_docid }) = heap.pop() {
+        if level.0 == 0 {
+            if callback(left_bound.0, count, any_docid)?.is_break() {
+                break;
+            }
+        } else {
+            push_groups(&mut heap, level.0 - 1, left_bound.0, group_size as usize)?;
+        }
+    }
+
+    Ok(())
+}
+
 struct FacetDistribution<'t, CB>
 where
     CB: FnMut(&'t [u8], u64, DocumentId) -> Result<ControlFlow<()>>,
@@ -132,7 +212,7 @@ mod tests {
     use heed::BytesDecode;
     use roaring::RoaringBitmap;
 
-    use super::iterate_over_facet_distribution;
+    use super::{count_iterate_over_facet_distribution, iterate_over_facet_distribution};
     use crate::heed_codec::facet::OrderedF64Codec;
     use crate::milli_snap;
     use crate::search::facet::tests::{get_random_looking_index, get_simple_index};
@@ -191,4 +271,48 @@ mod tests {
             txn.commit().unwrap();
         }
     }
+
+    #[test]
+    fn count_distribution_matches_lexicographic_distribution() {
+        let indexes = [get_simple_index(), get_random_looking_index()];
+        for index in indexes.iter() {
+            let txn = index.env.read_txn().unwrap();
+            let candidates = (0..=255).filter(|x| x % 3 != 0).collect::<RoaringBitmap>();
+
+            let mut expected = Vec::new();
+            iterate_over_facet_distribution(
+                &txn,
+                index.content,
+                0,
+                &candidates,
+                |facet, count, _| {
+                    expected.push((facet, count));
+                    Ok(ControlFlow::Continue(()))
+                },
+            )
+            .unwrap();
+            expected.sort_by(|(lf, lc), (rf, rc)| rc.cmp(lc).then_with(|| lf.cmp(rf)));
+            expected.truncate(20);
+
+            let mut results = Vec::new();
+            count_iterate_over_facet_distribution(
+                &txn,
+                index.content,
+                0,
+                &candidates,
+                |facet, count, _| {
+                    results.push((facet, count));
+                    if results.len() == 20 {
+                        Ok(ControlFlow::Break(()))
+                    } else {
+                        Ok(ControlFlow::Continue(()))
+                    }
+                },
+            )
+            .unwrap();
+
+            assert_eq!(results, expected);
+            txn.commit().unwrap();
+        }
+    }
 }
@@ -1,22 +1,35 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::{Debug, Display};
 use std::ops::Bound::{self, Excluded, Included};
+use std::ops::RangeBounds;
 
 use either::Either;
 pub use filter_parser::{Condition, Error as FPError, FilterCondition, Span, Token};
+use heed::types::ByteSlice;
 use roaring::RoaringBitmap;
 use serde_json::Value;
 
 use super::facet_range_search;
+use super::json_filter::JsonFilter;
 use crate::error::{Error, UserError};
+use crate::facet::FacetType;
 use crate::heed_codec::facet::{
-    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, OrderedF64Codec,
+    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, FieldDocIdFacetF64Codec,
+    FieldDocIdFacetStringCodec, OrderedF64Codec,
+};
+use crate::heed_codec::StrRefCodec;
+use crate::{
+    distance_between_two_points, lat_lng_to_xyz, DocumentId, FieldId, FieldIdSizeCodec,
+    FieldsIdsMap, Index, Result,
 };
-use crate::{distance_between_two_points, lat_lng_to_xyz, FieldId, Index, Result};
 
 /// The maximum number of filters the filter AST can process.
 const MAX_FILTER_DEPTH: usize = 2000;
 
+/// The prefix used to filter on the number of values of an array, e.g. `_size.tags > 3`.
+/// `_size` is a reserved keyword, so it can't collide with a nested `_size` field.
+const SIZE_PREFIX: &str = "_size.";
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Filter<'a> {
     condition: FilterCondition<'a>,
@@ -55,6 +68,8 @@ impl Display for BadGeoError {
 enum FilterError<'a> {
     AttributeNotFilterable { attribute: &'a str, filterable_fields: HashSet<String> },
     ParseGeoError(BadGeoError),
+    MatchesUnsupportedOperator(&'static str),
+    SizeUnsupportedOperator(&'static str),
     TooDeep,
 }
 impl<'a> std::error::Error for FilterError<'a> {}
@@ -95,6 +110,16 @@ impl<'a> Display for FilterError<'a> {
                 "Too many filter conditions, can't process more than {} filters.",
                 MAX_FILTER_DEPTH
             ),
+            Self::MatchesUnsupportedOperator(operator) => write!(
+                f,
+                "The `{}` operator is not supported inside of a `MATCHES` filter.",
+                operator
+            ),
+            Self::SizeUnsupportedOperator(operator) => write!(
+                f,
+                "The `{}` operator is not supported on the `_size` of an array.",
+                operator
+            ),
             Self::ParseGeoError(error) => write!(f, "{}", error),
         }
     }
@@ -113,6 +138,8 @@ impl<'a> From<Filter<'a>> for FilterCondition<'a> {
 }
 
 impl<'a> Filter<'a> {
+    /// Parses a filter expression, a filter written with the structured JSON syntax, or an
+    /// array of them.
     pub fn from_json(facets: &'a Value) -> Result<Option<Self>> {
         match facets {
             Value::String(expr) => {
@@ -120,43 +147,60 @@ impl<'a> Filter<'a> {
                 Ok(condition)
             }
             Value::Array(arr) => Self::parse_filter_array(arr),
+            Value::Object(_) => Self::from_conditions(vec![Self::parse_filter_object(facets)?]),
             v => Err(Error::UserError(UserError::InvalidFilterExpression(
-                &["String", "Array"],
+                &["String", "Array", "Object"],
                 v.clone(),
             ))),
         }
     }
 
+    /// Deserializes a filter written with the structured JSON syntax,
+    /// the errors point to the JSON path of the invalid node.
+    fn parse_filter_object(value: &Value) -> Result<FilterCondition<'static>> {
+        let filter = deserr::deserialize::<JsonFilter, _, deserr::errors::JsonError>(value.clone())
+            .map_err(|e| Error::UserError(UserError::InvalidFilter(e.to_string())))?;
+        Ok(FilterCondition::from(filter))
+    }
+
+    /// Parses an array of filters, the JSON filters can be mixed with the expressions.
     fn parse_filter_array(arr: &'a [Value]) -> Result<Option<Self>> {
         let mut ands = Vec::new();
         for value in arr {
             match value {
-                Value::String(s) => ands.push(Either::Right(s.as_str())),
+                Value::String(s) => ands.extend(Self::from_str(s)?.map(|f| f.condition)),
+                Value::Object(_) => ands.push(Self::parse_filter_object(value)?),
                 Value::Array(arr) => {
                     let mut ors = Vec::new();
                     for value in arr {
                         match value {
-                            Value::String(s) => ors.push(s.as_str()),
+                            Value::String(s) => ors.extend(Self::from_str(s)?.map(|f| f.condition)),
+                            Value::Object(_) => ors.push(Self::parse_filter_object(value)?),
                             v => {
                                 return Err(Error::UserError(UserError::InvalidFilterExpression(
-                                    &["String"],
+                                    &["String", "Object"],
                                     v.clone(),
                                 )))
                             }
                         }
                     }
-                    ands.push(Either::Left(ors));
+
+                    match ors.len() {
+                        0 => (),
+                        1 => ands.push(ors.pop().unwrap()),
+                        _ => ands.push(FilterCondition::Or(ors)),
+                    }
                 }
                 v => {
                     return Err(Error::UserError(UserError::InvalidFilterExpression(
-                        &["String", "[String]"],
+                        &["String", "Object", "[String]"],
                         v.clone(),
                     )))
                 }
             }
         }
 
-        Filter::from_array(ands)
+        Self::from_conditions(ands)
     }
 
     pub fn from_array<I, J>(array: I) -> Result<Option<Self>>
@@ -189,6 +233,12 @@ impl<'a> Filter<'a> {
                 }
             }
         }
+
+        Self::from_conditions(ands)
+    }
+
+    /// Combines the filters with an `AND`.
+    fn from_conditions(mut ands: Vec<FilterCondition<'a>>) -> Result<Option<Self>> {
         let and = if ands.is_empty() {
             return Ok(None);
         } else if ands.len() == 1 {
@@ -204,6 +254,31 @@ impl<'a> Filter<'a> {
         Ok(Some(Self { condition: and }))
     }
 
+    /// Replaces the `$name` placeholders of the filter by the values of the given parameters.
+    ///
+    /// The filter parameters can be strings, numbers or booleans, and all the
+    /// placeholders used in the filter must be defined.
+    pub fn bind_params(&mut self, params: &BTreeMap<String, Value>) -> Result<()> {
+        let mut values = HashMap::new();
+        for (name, value) in params {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                Value::Number(n) => n.to_string(),
+                Value::Bool(b) => b.to_string(),
+                v => {
+                    return Err(Error::UserError(UserError::InvalidFilter(format!(
+                        "Invalid value for the `{}` filter parameter: expected a string, a number, or a boolean, but found `{}`.",
+                        name, v
+                    ))))
+                }
+            };
+            values.insert(name.as_str(), value);
+        }
+
+        self.condition.bind_placeholders(&|name| values.get(name).cloned())?;
+        Ok(())
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(expression: &'a str) -> Result<Option<Self>> {
         let condition = match FilterCondition::parse(expression) {
@@ -270,6 +345,16 @@ impl<'a> Filter<'a> {
                 let exist = index.exists_faceted_documents_ids(rtxn, field_id)?;
                 return Ok(exist);
             }
+            Condition::IsNumber => {
+                let numbers = index.faceted_documents_ids(rtxn, field_id, FacetType::Number)?;
+                return Ok(numbers);
+            }
+            Condition::IsString => {
+                // The booleans are stored as strings in the facet databases,
+                // we use a dedicated database to only retrieve the real strings.
+                let strings = index.string_typed_documents_ids(rtxn, field_id)?;
+                return Ok(strings);
+            }
             Condition::Equal(val) => {
                 let string_docids = strings_db
                     .get(
@@ -298,6 +383,48 @@ impl<'a> Filter<'a> {
                 let all_ids = index.documents_ids(rtxn)?;
                 return Ok(all_ids - docids);
             }
+            Condition::StartsWith(val) => {
+                // The strings are stored normalized in the level 0 of the facet database,
+                // right after the field id and the level, we can therefore do a simple prefix scan.
+                let mut prefix = vec![];
+                prefix.extend_from_slice(&field_id.to_be_bytes());
+                prefix.push(0);
+                prefix.extend_from_slice(crate::normalize_facet(val.value()).as_bytes());
+
+                let iter = strings_db
+                    .as_polymorph()
+                    .prefix_iter::<_, ByteSlice, ByteSlice>(rtxn, prefix.as_slice())?
+                    .remap_types::<FacetGroupKeyCodec<StrRefCodec>, FacetGroupValueCodec>();
+
+                let mut docids = RoaringBitmap::new();
+                for result in iter {
+                    let (_key, value) = result?;
+                    docids |= value.bitmap;
+                }
+                return Ok(docids);
+            }
+            Condition::Contains(val) => {
+                // There is no way to use the facet levels to find the substrings,
+                // we iterate over all the normalized strings of the level 0 of this field.
+                let needle = crate::normalize_facet(val.value());
+                let mut prefix = vec![];
+                prefix.extend_from_slice(&field_id.to_be_bytes());
+                prefix.push(0);
+
+                let iter = strings_db
+                    .as_polymorph()
+                    .prefix_iter::<_, ByteSlice, ByteSlice>(rtxn, prefix.as_slice())?
+                    .remap_types::<FacetGroupKeyCodec<StrRefCodec>, FacetGroupValueCodec>();
+
+                let mut docids = RoaringBitmap::new();
+                for result in iter {
+                    let (key, value) = result?;
+                    if key.left_bound.contains(needle.as_str()) {
+                        docids |= value.bitmap;
+                    }
+                }
+                return Ok(docids);
+            }
         };
 
         let mut output = RoaringBitmap::new();
@@ -305,6 +432,189 @@ impl<'a> Filter<'a> {
         Ok(output)
     }
 
+    /// Returns the indexes of the elements of the array of objects of the given document
+    /// matching the operator.
+    fn evaluate_element_operator(
+        rtxn: &heed::RoTxn,
+        index: &Index,
+        field_id: FieldId,
+        docid: DocumentId,
+        operator: &Condition<'a>,
+        elements: &RoaringBitmap,
+    ) -> Result<RoaringBitmap> {
+        let numbers_db = index.field_id_docid_facet_element_f64s;
+        let strings_db = index.field_id_docid_facet_element_strings;
+
+        // All the values of a field in a document are stored right after the field id and the
+        // document id, we iterate over them and keep the elements of those that match.
+        let mut prefix = Vec::new();
+        prefix.extend_from_slice(&field_id.to_be_bytes());
+        prefix.extend_from_slice(&docid.to_be_bytes());
+        let strings_iter = || -> heed::Result<_> {
+            Ok(strings_db
+                .remap_key_type::<ByteSlice>()
+                .prefix_iter(rtxn, &prefix)?
+                .remap_key_type::<FieldDocIdFacetStringCodec>())
+        };
+        let numbers_iter = || -> heed::Result<_> {
+            Ok(numbers_db
+                .remap_key_type::<ByteSlice>()
+                .prefix_iter(rtxn, &prefix)?
+                .remap_key_type::<FieldDocIdFacetF64Codec>())
+        };
+
+        let range = match operator {
+            Condition::GreaterThan(val) => {
+                (Excluded(val.parse_finite_float()?), Included(f64::MAX))
+            }
+            Condition::GreaterThanOrEqual(val) => {
+                (Included(val.parse_finite_float()?), Included(f64::MAX))
+            }
+            Condition::LowerThan(val) => (Included(f64::MIN), Excluded(val.parse_finite_float()?)),
+            Condition::LowerThanOrEqual(val) => {
+                (Included(f64::MIN), Included(val.parse_finite_float()?))
+            }
+            Condition::Between { from, to } => {
+                (Included(from.parse_finite_float()?), Included(to.parse_finite_float()?))
+            }
+            // Those operators are rejected before evaluating a `MATCHES` filter.
+            Condition::Null | Condition::Empty | Condition::IsNumber | Condition::IsString => {
+                return Ok(RoaringBitmap::new())
+            }
+            Condition::Exists => {
+                let mut output = RoaringBitmap::new();
+                for result in strings_iter()? {
+                    let (_, value) = result?;
+                    output |= value;
+                }
+                for result in numbers_iter()? {
+                    let (_, value) = result?;
+                    output |= value;
+                }
+                return Ok(output);
+            }
+            Condition::Equal(val) => {
+                let value = crate::normalize_facet(val.value());
+                let mut output =
+                    strings_db.get(rtxn, &(field_id, docid, value.as_str()))?.unwrap_or_default();
+                if let Ok(number) = val.parse_finite_float() {
+                    output |= numbers_db.get(rtxn, &(field_id, docid, number))?.unwrap_or_default();
+                }
+                return Ok(output);
+            }
+            Condition::NotEqual(val) => {
+                let operator = Condition::Equal(val.clone());
+                let selected = Self::evaluate_element_operator(
+                    rtxn, index, field_id, docid, &operator, elements,
+                )?;
+                return Ok(elements - selected);
+            }
+            Condition::StartsWith(val) | Condition::Contains(val) => {
+                let needle = crate::normalize_facet(val.value());
+                let mut output = RoaringBitmap::new();
+                for result in strings_iter()? {
+                    let ((_, _, value), bitmap) = result?;
+                    let is_match = match operator {
+                        Condition::StartsWith(_) => value.starts_with(needle.as_str()),
+                        _ => value.contains(needle.as_str()),
+                    };
+                    if is_match {
+                        output |= bitmap;
+                    }
+                }
+                return Ok(output);
+            }
+        };
+
+        let mut output = RoaringBitmap::new();
+        for result in numbers_iter()? {
+            let ((_, _, value), bitmap) = result?;
+            if range.contains(&value) {
+                output |= bitmap;
+            }
+        }
+        Ok(output)
+    }
+
+    /// Returns the documents ids in which the number of values of the array matches the operator.
+    fn evaluate_size_operator(
+        rtxn: &heed::RoTxn,
+        index: &Index,
+        fid: &Token,
+        field_id: FieldId,
+        operator: &Condition<'a>,
+    ) -> Result<RoaringBitmap> {
+        let range = match operator {
+            Condition::GreaterThan(val) => {
+                (Excluded(val.parse_finite_float()?), Included(f64::MAX))
+            }
+            Condition::GreaterThanOrEqual(val) => {
+                (Included(val.parse_finite_float()?), Included(f64::MAX))
+            }
+            Condition::LowerThan(val) => (Included(f64::MIN), Excluded(val.parse_finite_float()?)),
+            Condition::LowerThanOrEqual(val) => {
+                (Included(f64::MIN), Included(val.parse_finite_float()?))
+            }
+            Condition::Between { from, to } => {
+                (Included(from.parse_finite_float()?), Included(to.parse_finite_float()?))
+            }
+            Condition::Equal(val) => {
+                let size = val.parse_finite_float()?;
+                (Included(size), Included(size))
+            }
+            Condition::NotEqual(val) => {
+                let operator = Condition::Equal(val.clone());
+                let docids = Self::evaluate_size_operator(rtxn, index, fid, field_id, &operator)?;
+                let all_ids = index.documents_ids(rtxn)?;
+                return Ok(all_ids - docids);
+            }
+            Condition::Exists => (Included(f64::MIN), Included(f64::MAX)),
+            Condition::Null
+            | Condition::Empty
+            | Condition::IsNumber
+            | Condition::IsString
+            | Condition::Contains(_)
+            | Condition::StartsWith(_) => {
+                let operator = match operator {
+                    Condition::Null => "IS NULL",
+                    Condition::Empty => "IS EMPTY",
+                    Condition::IsNumber => "IS NUMBER",
+                    Condition::IsString => "IS STRING",
+                    Condition::Contains(_) => "CONTAINS",
+                    _ => "STARTS WITH",
+                };
+                return Err(fid.as_external_error(FilterError::SizeUnsupportedOperator(operator)))?;
+            }
+        };
+
+        // There are only a few different array sizes by field,
+        // we can iterate over all of them and keep those in range.
+        let iter = index
+            .facet_id_size_docids
+            .remap_key_type::<ByteSlice>()
+            .prefix_iter(rtxn, &field_id.to_be_bytes())?
+            .remap_key_type::<FieldIdSizeCodec>();
+
+        let mut output = RoaringBitmap::new();
+        for result in iter {
+            let ((_, size), docids) = result?;
+            if range.contains(&(size as f64)) {
+                output |= docids;
+            }
+        }
+        Ok(output)
+    }
+
+    /// Returns the name of the array if the attribute is the size of a filterable array.
+    fn size_attribute<'t>(
+        attribute: &'t str,
+        filterable_fields: &HashSet<String>,
+    ) -> Option<&'t str> {
+        attribute
+            .strip_prefix(SIZE_PREFIX)
+            .filter(|array| crate::is_faceted(array, filterable_fields))
+    }
+
     /// Aggregates the documents ids that are part of the specified range automatically
     /// going deeper through the levels.
     fn explore_facet_number_levels(
@@ -348,7 +658,18 @@ impl<'a> Filter<'a> {
                 Ok(all_ids - selected)
             }
             FilterCondition::In { fid, els } => {
-                if crate::is_faceted(fid.value(), filterable_fields) {
+                if let Some(array) = Self::size_attribute(fid.value(), filterable_fields) {
+                    let field_ids_map = index.fields_ids_map(rtxn)?;
+                    let mut bitmap = RoaringBitmap::new();
+                    if let Some(field_id) = field_ids_map.id(array) {
+                        for el in els {
+                            let op = Condition::Equal(el.clone());
+                            bitmap |=
+                                Self::evaluate_size_operator(rtxn, index, fid, field_id, &op)?;
+                        }
+                    }
+                    Ok(bitmap)
+                } else if crate::is_faceted(fid.value(), filterable_fields) {
                     let field_ids_map = index.fields_ids_map(rtxn)?;
 
                     if let Some(fid) = field_ids_map.id(fid.value()) {
@@ -371,7 +692,15 @@ impl<'a> Filter<'a> {
                 }
             }
             FilterCondition::Condition { fid, op } => {
-                if crate::is_faceted(fid.value(), filterable_fields) {
+                if let Some(array) = Self::size_attribute(fid.value(), filterable_fields) {
+                    let field_ids_map = index.fields_ids_map(rtxn)?;
+                    match field_ids_map.id(array) {
+                        Some(field_id) => {
+                            Self::evaluate_size_operator(rtxn, index, fid, field_id, op)
+                        }
+                        None => Ok(RoaringBitmap::new()),
+                    }
+                } else if crate::is_faceted(fid.value(), filterable_fields) {
                     let field_ids_map = index.fields_ids_map(rtxn)?;
                     if let Some(fid) = field_ids_map.id(fid.value()) {
                         Self::evaluate_operator(rtxn, index, fid, op)
@@ -418,6 +747,52 @@ impl<'a> Filter<'a> {
                     Ok(RoaringBitmap::new())
                 }
             }
+            FilterCondition::Matches { fid, filter } => {
+                // Make sure the whole filter can be evaluated before doing anything.
+                Self::check_matches_filter(fid, filter, filterable_fields)?;
+
+                let fields_ids_map = index.fields_ids_map(rtxn)?;
+                let array_fid = match fields_ids_map.id(fid.value()) {
+                    Some(array_fid) => array_fid,
+                    None => return Ok(RoaringBitmap::new()),
+                };
+
+                // We reduce the number of documents to check by evaluating
+                // the filter on the flattened fields first.
+                let candidates =
+                    Self::matches_candidates(rtxn, index, &fields_ids_map, fid.value(), filter)?;
+
+                // The array field is associated with an empty string and the indexes
+                // of all its elements for each document containing it.
+                let iter = index
+                    .field_id_docid_facet_element_strings
+                    .remap_key_type::<ByteSlice>()
+                    .prefix_iter(rtxn, &array_fid.to_be_bytes())?
+                    .remap_key_type::<FieldDocIdFacetStringCodec>();
+
+                let mut output = RoaringBitmap::new();
+                for result in iter {
+                    let ((_, docid, value), elements) = result?;
+                    if !value.is_empty()
+                        || candidates.as_ref().map_or(false, |c| !c.contains(docid))
+                    {
+                        continue;
+                    }
+                    let selected = Self::evaluate_elements(
+                        rtxn,
+                        index,
+                        &fields_ids_map,
+                        fid.value(),
+                        filter,
+                        docid,
+                        &elements,
+                    )?;
+                    if !selected.is_empty() {
+                        output.insert(docid);
+                    }
+                }
+                Ok(output)
+            }
             FilterCondition::GeoLowerThan { point, radius } => {
                 if filterable_fields.contains("_geo") {
                     let base_point: [f64; 2] =
@@ -587,6 +962,215 @@ impl<'a> Filter<'a> {
     }
 }
 
+/// The functions used to evaluate the `MATCHES` filters, where the conditions on the
+/// fields must be verified by a single element of an array of objects.
+impl<'a> Filter<'a> {
+    /// Makes sure that the nested fields used by the filter are filterable and that
+    /// the filter only contains operators that can be evaluated on the elements.
+    fn check_matches_filter(
+        array: &Token,
+        filter: &FilterCondition,
+        filterable_fields: &HashSet<String>,
+    ) -> Result<()> {
+        let check_is_filterable = |fid: &Token| -> Result<()> {
+            let attribute = format!("{}.{}", array.value(), fid.value());
+            if crate::is_faceted(&attribute, filterable_fields) {
+                Ok(())
+            } else {
+                Err(fid.as_external_error(FilterError::AttributeNotFilterable {
+                    attribute: &attribute,
+                    filterable_fields: filterable_fields.clone(),
+                }))?
+            }
+        };
+
+        match filter {
+            FilterCondition::Not(f) => Self::check_matches_filter(array, f, filterable_fields),
+            FilterCondition::Or(subfilters) | FilterCondition::And(subfilters) => {
+                for f in subfilters {
+                    Self::check_matches_filter(array, f, filterable_fields)?;
+                }
+                Ok(())
+            }
+            FilterCondition::In { fid, .. } => check_is_filterable(fid),
+            FilterCondition::Condition { fid, op } => {
+                let unsupported = match op {
+                    Condition::Null => Some("IS NULL"),
+                    Condition::Empty => Some("IS EMPTY"),
+                    Condition::IsNumber => Some("IS NUMBER"),
+                    Condition::IsString => Some("IS STRING"),
+                    _ => None,
+                };
+                match unsupported {
+                    Some(operator) => {
+                        Err(fid
+                            .as_external_error(FilterError::MatchesUnsupportedOperator(operator)))?
+                    }
+                    None => check_is_filterable(fid),
+                }
+            }
+            FilterCondition::Matches { fid, .. } => {
+                Err(fid.as_external_error(FilterError::MatchesUnsupportedOperator("MATCHES")))?
+            }
+            FilterCondition::GeoLowerThan { point, .. } => {
+                Err(point[0]
+                    .as_external_error(FilterError::MatchesUnsupportedOperator("_geoRadius")))?
+            }
+            FilterCondition::GeoBoundingBox { top_right_point, .. } => Err(top_right_point[0]
+                .as_external_error(FilterError::MatchesUnsupportedOperator("_geoBoundingBox")))?,
+        }
+    }
+
+    /// Evaluates the filter on the flattened nested fields, ignoring the elements. It returns a
+    /// superset of the documents matching the `MATCHES` filter or `None` if it can't be reduced.
+    fn matches_candidates(
+        rtxn: &heed::RoTxn,
+        index: &Index,
+        fields_ids_map: &FieldsIdsMap,
+        array: &str,
+        filter: &FilterCondition<'a>,
+    ) -> Result<Option<RoaringBitmap>> {
+        match filter {
+            // The negations are true for the elements that doesn't contain the value,
+            // the flattened fields can't tell us anything about those.
+            FilterCondition::Not(_) => Ok(None),
+            FilterCondition::Condition {
+                op: Condition::NotEqual(_) | Condition::Null | Condition::Empty,
+                ..
+            } => Ok(None),
+            FilterCondition::Condition { fid, op } => {
+                match fields_ids_map.id(&format!("{}.{}", array, fid.value())) {
+                    Some(fid) => Self::evaluate_operator(rtxn, index, fid, op).map(Some),
+                    None => Ok(Some(RoaringBitmap::new())),
+                }
+            }
+            FilterCondition::In { fid, els } => {
+                let mut bitmap = RoaringBitmap::new();
+                if let Some(fid) = fields_ids_map.id(&format!("{}.{}", array, fid.value())) {
+                    for el in els {
+                        let op = Condition::Equal(el.clone());
+                        bitmap |= Self::evaluate_operator(rtxn, index, fid, &op)?;
+                    }
+                }
+                Ok(Some(bitmap))
+            }
+            FilterCondition::Or(subfilters) => {
+                let mut bitmap = RoaringBitmap::new();
+                for f in subfilters {
+                    match Self::matches_candidates(rtxn, index, fields_ids_map, array, f)? {
+                        Some(candidates) => bitmap |= candidates,
+                        None => return Ok(None),
+                    }
+                }
+                Ok(Some(bitmap))
+            }
+            FilterCondition::And(subfilters) => {
+                let mut bitmap: Option<RoaringBitmap> = None;
+                for f in subfilters {
+                    if let Some(candidates) =
+                        Self::matches_candidates(rtxn, index, fields_ids_map, array, f)?
+                    {
+                        bitmap = Some(match bitmap {
+                            Some(bitmap) => bitmap & candidates,
+                            None => candidates,
+                        });
+                    }
+                }
+                Ok(bitmap)
+            }
+            // Those filters are rejected before evaluating a `MATCHES` filter.
+            FilterCondition::Matches { .. }
+            | FilterCondition::GeoLowerThan { .. }
+            | FilterCondition::GeoBoundingBox { .. } => Ok(None),
+        }
+    }
+
+    /// Returns the indexes of the elements of the array of objects of the given document
+    /// matching the filter.
+    fn evaluate_elements(
+        rtxn: &heed::RoTxn,
+        index: &Index,
+        fields_ids_map: &FieldsIdsMap,
+        array: &str,
+        filter: &FilterCondition<'a>,
+        docid: DocumentId,
+        elements: &RoaringBitmap,
+    ) -> Result<RoaringBitmap> {
+        match filter {
+            FilterCondition::Not(f) => {
+                let selected = Self::evaluate_elements(
+                    rtxn,
+                    index,
+                    fields_ids_map,
+                    array,
+                    f,
+                    docid,
+                    elements,
+                )?;
+                Ok(elements - selected)
+            }
+            FilterCondition::Condition { fid, op } => {
+                match fields_ids_map.id(&format!("{}.{}", array, fid.value())) {
+                    Some(fid) => {
+                        Self::evaluate_element_operator(rtxn, index, fid, docid, op, elements)
+                    }
+                    None if matches!(op, Condition::NotEqual(_)) => Ok(elements.clone()),
+                    None => Ok(RoaringBitmap::new()),
+                }
+            }
+            FilterCondition::In { fid, els } => {
+                let mut bitmap = RoaringBitmap::new();
+                if let Some(fid) = fields_ids_map.id(&format!("{}.{}", array, fid.value())) {
+                    for el in els {
+                        let op = Condition::Equal(el.clone());
+                        bitmap |= Self::evaluate_element_operator(
+                            rtxn, index, fid, docid, &op, elements,
+                        )?;
+                    }
+                }
+                Ok(bitmap)
+            }
+            FilterCondition::Or(subfilters) => {
+                let mut bitmap = RoaringBitmap::new();
+                for f in subfilters {
+                    bitmap |= Self::evaluate_elements(
+                        rtxn,
+                        index,
+                        fields_ids_map,
+                        array,
+                        f,
+                        docid,
+                        elements,
+                    )?;
+                }
+                Ok(bitmap)
+            }
+            FilterCondition::And(subfilters) => {
+                let mut bitmap = elements.clone();
+                for f in subfilters {
+                    if bitmap.is_empty() {
+                        break;
+                    }
+                    bitmap &= Self::evaluate_elements(
+                        rtxn,
+                        index,
+                        fields_ids_map,
+                        array,
+                        f,
+                        docid,
+                        elements,
+                    )?;
+                }
+                Ok(bitmap)
+            }
+            // Those filters are rejected before evaluating a `MATCHES` filter.
+            FilterCondition::Matches { .. }
+            | FilterCondition::GeoLowerThan { .. }
+            | FilterCondition::GeoBoundingBox { .. } => Ok(RoaringBitmap::new()),
+        }
+    }
+}
+
 impl<'a> From<FilterCondition<'a>> for Filter<'a> {
     fn from(fc: FilterCondition<'a>) -> Self {
         Self { condition: fc }
@@ -595,6 +1179,7 @@ impl<'a> From<FilterCondition<'a>> for Filter<'a> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::fmt::Write;
     use std::iter::FromIterator;
 
@@ -602,8 +1187,10 @@ mod tests {
     use either::Either;
     use maplit::hashset;
     use roaring::RoaringBitmap;
+    use serde_json::Value;
 
     use crate::index::tests::TempIndex;
+    use crate::update::DeletionStrategy;
     use crate::Filter;
 
     #[test]
@@ -789,6 +1376,351 @@ mod tests {
         assert_eq!(documents_ids, vec![2]);
     }
 
+    #[test]
+    fn contains_and_starts_with() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("sku") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "sku": "AB-45219-X" },
+                { "id": 1, "sku": "ab-1234" },
+                { "id": 2, "sku": ["CD-4521", "EF-0001"] },
+                { "id": 3, "sku": 45219 },
+                { "id": 4 },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        let filter = Filter::from_str("sku STARTS WITH AB").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 1]));
+
+        let filter = Filter::from_str("sku STARTS WITH 'ab-4'").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0]));
+
+        let filter = Filter::from_str("sku NOT STARTS WITH ab").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([2, 3, 4]));
+
+        let filter = Filter::from_str("sku CONTAINS 4521").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 2]));
+
+        let filter = Filter::from_str("sku CONTAINS 0001").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([2]));
+
+        let filter = Filter::from_str("sku NOT CONTAINS 4521").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 3, 4]));
+
+        let filter = Filter::from_str("name CONTAINS 4521").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "Attribute `name` is not filterable. Available filterable attributes are: `sku`."
+        ));
+    }
+
+    #[test]
+    fn bind_params() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("brand"), S("price") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "brand": "O'Reilly", "price": 10 },
+                { "id": 1, "brand": "Manning", "price": 20 },
+                { "id": 2, "brand": "O'Reilly", "price": 30 },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        let params =
+            BTreeMap::from([(S("brand"), Value::from("O'Reilly")), (S("max"), Value::from(25))]);
+
+        let mut filter = Filter::from_str("brand = $brand AND price < $max").unwrap().unwrap();
+        filter.bind_params(&params).unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0]));
+
+        // the values of the parameters are never parsed as a filter
+        let params = BTreeMap::from([(S("brand"), Value::from("Manning OR price > 0"))]);
+        let mut filter = Filter::from_str("brand = $brand").unwrap().unwrap();
+        filter.bind_params(&params).unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert!(result.is_empty());
+
+        let mut filter = Filter::from_str("brand = $publisher").unwrap().unwrap();
+        let error = filter.bind_params(&params).unwrap_err();
+        assert!(error.to_string().starts_with("The `$publisher` placeholder has no value."));
+
+        let params = BTreeMap::from([(S("tags"), Value::from(vec!["a", "b"]))]);
+        let mut filter = Filter::from_str("tags = $tags").unwrap().unwrap();
+        let error = filter.bind_params(&params).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid value for the `tags` filter parameter: expected a string, a number, or a boolean, but found `[\"a\",\"b\"]`."
+        );
+    }
+
+    #[test]
+    fn json_filter() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("brand"), S("price") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "brand": "O'Reilly", "price": 10 },
+                { "id": 1, "brand": "Manning", "price": 20 },
+                { "id": 2, "brand": "O'Reilly", "price": 30 },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        let filter = serde_json::json!({
+            "and": [
+                { "field": "price", "op": ">", "value": 10 },
+                { "not": { "in": { "field": "brand", "values": ["Manning"] } } },
+            ]
+        });
+        let filter = Filter::from_json(&filter).unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([2]));
+
+        let filter = serde_json::json!({
+            "or": [
+                { "field": "price", "op": "TO", "value": [15, 25] },
+                { "field": "brand", "op": "STARTS WITH", "value": "O'" },
+            ]
+        });
+        let filter = Filter::from_json(&filter).unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 1, 2]));
+
+        // the JSON filters can be mixed with expressions in an array
+        let filter = serde_json::json!([
+            { "field": "brand", "op": "=", "value": "O'Reilly" },
+            ["price = 10", { "field": "price", "op": "=", "value": 20 }],
+        ]);
+        let filter = Filter::from_json(&filter).unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0]));
+
+        // the values are never interpreted as expressions
+        let filter =
+            serde_json::json!({ "field": "brand", "op": "=", "value": "\\\" OR price > 0" });
+        let filter = Filter::from_json(&filter).unwrap().unwrap();
+        assert!(filter.evaluate(&rtxn, &index).unwrap().is_empty());
+
+        let filter = serde_json::json!({
+            "geoRadius": { "lat": "1, 2, 3) OR (price > 0", "lng": 2, "distance": 3 }
+        });
+        let error = Filter::from_json(&filter).unwrap_err();
+        assert!(error.to_string().contains("`.geoRadius.lat`"), "{error}");
+
+        // the evaluation errors point to the JSON path of the invalid value
+        let filter = serde_json::json!({ "field": "author", "op": "=", "value": "Tolkien" });
+        let error = Filter::from_json(&filter).unwrap().unwrap().evaluate(&rtxn, &index);
+        let error = error.unwrap_err().to_string();
+        assert!(error.starts_with("Attribute `author` is not filterable."), "{error}");
+        assert!(error.ends_with("at `.field`"), "{error}");
+
+        // the errors point to the invalid node
+        let filter =
+            serde_json::json!({ "or": [{ "field": "price", "op": "EXISTS", "value": 1 }] });
+        let error = Filter::from_json(&filter).unwrap_err();
+        assert!(error.to_string().contains("`.or[0]`"), "{error}");
+
+        let filter = serde_json::json!({ "and": [], "or": [] });
+        assert!(Filter::from_json(&filter).is_err());
+
+        let filter = serde_json::json!({ "xor": [] });
+        let error = Filter::from_json(&filter).unwrap_err();
+        assert!(error.to_string().contains("xor"), "{error}");
+
+        let filter = serde_json::json!({ "or": [] });
+        assert!(Filter::from_json(&filter).is_err());
+
+        // the nesting of the JSON filters is limited like the one of the expressions
+        let mut filter = serde_json::json!({ "field": "price", "op": "EXISTS" });
+        for _ in 0..250 {
+            filter = serde_json::json!({ "not": filter });
+        }
+        let error = Filter::from_json(&filter).unwrap_err();
+        assert!(error.to_string().contains("maximum depth limit"), "{error}");
+    }
+
+    #[test]
+    fn matches_array_elements() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.deletion_strategy = DeletionStrategy::AlwaysHard;
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("variants"), S("brand") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "brand": "a", "variants": [{ "color": "red", "size": "M", "price": 10 }, { "color": "blue", "size": "L", "price": 20 }] },
+                { "id": 1, "brand": "b", "variants": [{ "color": "red", "size": "L", "price": 15 }, { "color": "blue", "size": "M", "price": 25 }] },
+                { "id": 2, "brand": "a", "variants": { "color": "Red", "size": "m", "price": 30 } },
+                { "id": 3, "brand": "b", "variants": [{ "color": "green" }] },
+                { "id": 4, "brand": "a" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        // the flattened fields don't care about the elements
+        let filter =
+            Filter::from_str("variants.color = red AND variants.size = M").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 1, 2]));
+
+        let filter =
+            Filter::from_str("variants MATCHES (color = red AND size = M)").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 2]));
+
+        let filter =
+            Filter::from_str("variants MATCHES (color = red AND price > 12)").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 2]));
+
+        let filter = Filter::from_str("variants MATCHES (color IN [blue, green] AND NOT size = L)")
+            .unwrap()
+            .unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 3]));
+
+        let filter =
+            Filter::from_str("variants MATCHES (color != red AND price EXISTS)").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 1]));
+
+        let filter = Filter::from_str("brand = a AND NOT variants MATCHES (color STARTS WITH re)")
+            .unwrap()
+            .unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([4]));
+
+        let filter = Filter::from_str("variants MATCHES (color IS NULL)").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("The `IS NULL` operator is not supported inside of a `MATCHES` filter."));
+
+        let filter = Filter::from_str("name MATCHES (color = red)").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with(
+            "Attribute `name.color` is not filterable. Available filterable attributes are:"
+        ));
+        drop(rtxn);
+
+        index.delete_document("0");
+        let rtxn = index.read_txn().unwrap();
+        let filter =
+            Filter::from_str("variants MATCHES (color = red AND size = M)").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([2]));
+    }
+
+    #[test]
+    fn array_size_and_value_types() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.deletion_strategy = DeletionStrategy::AlwaysHard;
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("tags"), S("price"), S("stock") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "tags": ["a", "b", "c", "d"], "price": 10 },
+                { "id": 1, "tags": ["a"], "price": "10" },
+                { "id": 2, "tags": [], "price": true },
+                { "id": 3, "tags": "a", "price": [1, "a"] },
+                { "id": 4, "tags": [1, 2, 3], "stock": { "_size": 3 } },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        let filter = Filter::from_str("_size.tags > 3").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0]));
+
+        let filter = Filter::from_str("_size.tags 1 TO 3").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 4]));
+
+        let filter = Filter::from_str("_size.tags IN [0, 4]").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 2]));
+
+        let filter = Filter::from_str("_size.tags EXISTS").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 1, 2, 4]));
+
+        // a nested `_size` field is a regular field
+        let filter = Filter::from_str("stock._size = 3").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([4]));
+
+        let filter = Filter::from_str("_size.tags != 1").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 2, 3, 4]));
+
+        let filter = Filter::from_str("_size.tags CONTAINS 1").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("The `CONTAINS` operator is not supported on the `_size` of an array."));
+
+        let filter = Filter::from_str("price IS NUMBER").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([0, 3]));
+
+        let filter = Filter::from_str("price IS STRING").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([1, 3]));
+
+        let filter = Filter::from_str("tags IS NOT STRING").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([2, 4]));
+        drop(rtxn);
+
+        index.delete_document("0");
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("_size.tags >= 3 OR price IS NUMBER").unwrap().unwrap();
+        let result = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(result, RoaringBitmap::from_iter([3, 4]));
+    }
+
     #[test]
     fn zero_radius() {
         let index = TempIndex::new();
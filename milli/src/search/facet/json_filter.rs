@@ -0,0 +1,449 @@
+//! The structured JSON syntax of the filters.
+//!
+//! Instead of a string expression, a filter can be sent as a tree of JSON objects that is
+//! directly deserialized into a [`FilterCondition`]:
+//!
+//! ```json
+//! {
+//!   "and": [
+//!     { "field": "price", "op": ">", "value": 10 },
+//!     { "not": { "in": { "field": "genres", "values": ["horror", "thriller"] } } }
+//!   ]
+//! }
+//! ```
+//!
+//! Every node is an object with exactly one of the following shapes:
+//! - `{ "and": [filter, ...] }` and `{ "or": [filter, ...] }`,
+//! - `{ "not": filter }`,
+//! - `{ "in": { "field": "...", "values": [value, ...] } }`,
+//! - `{ "matches": { "field": "...", "filter": filter } }`,
+//! - `{ "geoRadius": { "lat": value, "lng": value, "distance": value } }`,
+//! - `{ "geoBoundingBox": { "topRight": [lat, lng], "bottomLeft": [lat, lng] } }`,
+//! - `{ "field": "...", "op": "...", "value": value }`, where the `TO` operator expects a
+//!   `[from, to]` value and the `EXISTS`, `IS NULL`, `IS EMPTY`, `IS NUMBER`, and `IS STRING`
+//!   operators don't take any value.
+//!
+//! Values can be strings, numbers, or booleans, the coordinates and the distance of the geo
+//! filters must be numbers. The tokens of the filter point to the JSON path of their value.
+
+use deserr::{
+    take_cf_content, DeserializeError, Deserr, ErrorKind, IntoValue, Map, Value, ValueKind,
+    ValuePointerRef,
+};
+use filter_parser::{Condition, FilterCondition, Token};
+
+/// The maximum number of nested filters, the same as the one of the string expressions.
+const MAX_NESTING_DEPTH: usize = 200;
+
+const FILTER_KEYS: &[&str] =
+    &["and", "or", "not", "in", "matches", "geoRadius", "geoBoundingBox", "field"];
+const CONDITION_KEYS: &[&str] = &["field", "op", "value"];
+
+/// A filter written with the structured JSON syntax.
+pub struct JsonFilter(FilterCondition<'static>);
+
+impl From<JsonFilter> for FilterCondition<'static> {
+    fn from(filter: JsonFilter) -> Self {
+        filter.0
+    }
+}
+
+impl<E: DeserializeError> Deserr<E> for JsonFilter {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        if nesting_depth(location) > MAX_NESTING_DEPTH {
+            return Err(unexpected(
+                "The filter exceeded the maximum depth limit. Try rewriting the filter so that it contains fewer nested conditions.",
+                location,
+            ));
+        }
+
+        let map = match value {
+            Value::Map(map) => map,
+            value => {
+                return Err(take_cf_content(E::error::<V>(
+                    None,
+                    ErrorKind::IncorrectValueKind { actual: value, accepted: &[ValueKind::Map] },
+                    location,
+                )))
+            }
+        };
+
+        let mut entries: Vec<(String, V)> = map.into_iter().collect();
+        if entries.iter().any(|(key, _)| key == "field") {
+            return deserialize_condition(entries, location).map(JsonFilter);
+        }
+
+        let (key, value) = match entries.pop() {
+            Some(entry) if entries.is_empty() => entry,
+            _ => {
+                return Err(unexpected(
+                    "A filter object must contain exactly one of `and`, `or`, `not`, `in`, `matches`, `geoRadius`, `geoBoundingBox`, or a `field` condition.",
+                    location,
+                ))
+            }
+        };
+        let key_location = location.push_key(&key);
+        let value = value.into_value();
+
+        let condition = match key.as_str() {
+            "and" | "or" => {
+                let filters: Vec<JsonFilter> =
+                    Deserr::<E>::deserialize_from_value(value, key_location)?;
+                if filters.is_empty() {
+                    return Err(unexpected(
+                        &format!("The `{key}` filter must contain at least one filter."),
+                        key_location,
+                    ));
+                }
+                let filters = filters.into_iter().map(FilterCondition::from).collect();
+                if key == "and" {
+                    FilterCondition::And(filters)
+                } else {
+                    FilterCondition::Or(filters)
+                }
+            }
+            "not" => {
+                let JsonFilter(filter) = Deserr::<E>::deserialize_from_value(value, key_location)?;
+                FilterCondition::Not(Box::new(filter))
+            }
+            "in" => {
+                let JsonIn { field: JsonField(fid), values } =
+                    Deserr::<E>::deserialize_from_value(value, key_location)?;
+                FilterCondition::In {
+                    fid,
+                    els: values.into_iter().map(|JsonScalar(value)| value).collect(),
+                }
+            }
+            "matches" => {
+                let JsonMatches { field: JsonField(fid), filter: JsonFilter(filter) } =
+                    Deserr::<E>::deserialize_from_value(value, key_location)?;
+                FilterCondition::Matches { fid, filter: Box::new(filter) }
+            }
+            "geoRadius" => {
+                let JsonGeoRadius { lat, lng, distance } =
+                    Deserr::<E>::deserialize_from_value(value, key_location)?;
+                FilterCondition::GeoLowerThan { point: [lat.0, lng.0], radius: distance.0 }
+            }
+            "geoBoundingBox" => {
+                let JsonGeoBoundingBox { top_right, bottom_left } =
+                    Deserr::<E>::deserialize_from_value(value, key_location)?;
+                let top_right = top_right.into_iter().map(|JsonNumber(value)| value).collect();
+                let bottom_left = bottom_left.into_iter().map(|JsonNumber(value)| value).collect();
+                FilterCondition::GeoBoundingBox {
+                    top_right_point: pair::<E>(top_right, key_location.push_key("topRight"))?,
+                    bottom_left_point: pair::<E>(bottom_left, key_location.push_key("bottomLeft"))?,
+                }
+            }
+            _ => {
+                return Err(take_cf_content(E::error::<V>(
+                    None,
+                    ErrorKind::UnknownKey { key: &key, accepted: FILTER_KEYS },
+                    location,
+                )))
+            }
+        };
+
+        Ok(JsonFilter(condition))
+    }
+}
+
+/// Deserializes a `{ "field": "...", "op": "...", "value": value }` condition.
+fn deserialize_condition<V: IntoValue, E: DeserializeError>(
+    entries: Vec<(String, V)>,
+    location: ValuePointerRef,
+) -> Result<FilterCondition<'static>, E> {
+    let mut field = None;
+    let mut op = None;
+    let mut value = None;
+    for (key, entry) in entries {
+        let key_location = location.push_key(&key);
+        match key.as_str() {
+            "field" => {
+                let JsonField(fid) =
+                    Deserr::<E>::deserialize_from_value(entry.into_value(), key_location)?;
+                field = Some(fid)
+            }
+            "op" => {
+                op = Some(<JsonOperator as Deserr<E>>::deserialize_from_value(
+                    entry.into_value(),
+                    key_location,
+                )?)
+            }
+            "value" => value = Some(entry),
+            _ => {
+                return Err(take_cf_content(E::error::<V>(
+                    None,
+                    ErrorKind::UnknownKey { key: &key, accepted: CONDITION_KEYS },
+                    location,
+                )))
+            }
+        }
+    }
+
+    let field = field.ok_or_else(|| missing_field::<V, E>("field", location))?;
+    let op = op.ok_or_else(|| missing_field::<V, E>("op", location))?;
+    let value_location = location.push_key("value");
+
+    let op = match op {
+        JsonOperator::Exists => {
+            without_value::<V, E>(value, "EXISTS", location, Condition::Exists)?
+        }
+        JsonOperator::IsNull => without_value::<V, E>(value, "IS NULL", location, Condition::Null)?,
+        JsonOperator::IsEmpty => {
+            without_value::<V, E>(value, "IS EMPTY", location, Condition::Empty)?
+        }
+        JsonOperator::IsNumber => {
+            without_value::<V, E>(value, "IS NUMBER", location, Condition::IsNumber)?
+        }
+        JsonOperator::IsString => {
+            without_value::<V, E>(value, "IS STRING", location, Condition::IsString)?
+        }
+        JsonOperator::Between => {
+            let value = value.ok_or_else(|| missing_field::<V, E>("value", location))?;
+            let values: Vec<JsonScalar> =
+                Deserr::<E>::deserialize_from_value(value.into_value(), value_location)?;
+            let values = values.into_iter().map(|JsonScalar(value)| value).collect();
+            let [from, to] = pair::<E>(values, value_location)?;
+            Condition::Between { from, to }
+        }
+        op => {
+            let value = value.ok_or_else(|| missing_field::<V, E>("value", location))?;
+            let JsonScalar(value) =
+                Deserr::<E>::deserialize_from_value(value.into_value(), value_location)?;
+            match op {
+                JsonOperator::Equal => Condition::Equal(value),
+                JsonOperator::NotEqual => Condition::NotEqual(value),
+                JsonOperator::GreaterThan => Condition::GreaterThan(value),
+                JsonOperator::GreaterThanOrEqual => Condition::GreaterThanOrEqual(value),
+                JsonOperator::LowerThan => Condition::LowerThan(value),
+                JsonOperator::LowerThanOrEqual => Condition::LowerThanOrEqual(value),
+                JsonOperator::Contains => Condition::Contains(value),
+                JsonOperator::StartsWith => Condition::StartsWith(value),
+                _ => unreachable!(),
+            }
+        }
+    };
+
+    Ok(FilterCondition::Condition { fid: field, op })
+}
+
+fn without_value<V: IntoValue, E: DeserializeError>(
+    value: Option<V>,
+    operator: &str,
+    location: ValuePointerRef,
+    condition: Condition<'static>,
+) -> Result<Condition<'static>, E> {
+    match value {
+        Some(_) => Err(unexpected(
+            &format!("The `{operator}` operator does not take a `value`."),
+            location,
+        )),
+        None => Ok(condition),
+    }
+}
+
+/// Checks that a `[latitude, longitude]` or `[from, to]` pair contains two values.
+fn pair<E: DeserializeError>(
+    values: Vec<Token<'static>>,
+    location: ValuePointerRef,
+) -> Result<[Token<'static>; 2], E> {
+    match <[Token; 2]>::try_from(values) {
+        Ok(pair) => Ok(pair),
+        Err(values) => Err(unexpected(
+            &format!("Expected an array of two values but found {} values.", values.len()),
+            location,
+        )),
+    }
+}
+
+fn token(value: String, location: ValuePointerRef) -> Token<'static> {
+    Token::new_located(value, json_path(location))
+}
+
+/// Formats the location the same way as the deserialization errors, e.g. `.and[0].field`.
+fn json_path(location: ValuePointerRef) -> String {
+    match location {
+        ValuePointerRef::Origin => String::new(),
+        ValuePointerRef::Key { key, prev } => format!("{}.{key}", json_path(*prev)),
+        ValuePointerRef::Index { index, prev } => format!("{}[{index}]", json_path(*prev)),
+    }
+}
+
+/// Returns the number of filters the filter at this location is nested in.
+fn nesting_depth(location: ValuePointerRef) -> usize {
+    let mut depth = 0;
+    let mut location = location;
+    loop {
+        match location {
+            ValuePointerRef::Origin => return depth,
+            ValuePointerRef::Key { key, prev } => {
+                if matches!(key, "and" | "or" | "not" | "matches") {
+                    depth += 1;
+                }
+                location = *prev;
+            }
+            ValuePointerRef::Index { prev, .. } => location = *prev,
+        }
+    }
+}
+
+fn missing_field<V: IntoValue, E: DeserializeError>(field: &str, location: ValuePointerRef) -> E {
+    take_cf_content(E::error::<V>(None, ErrorKind::MissingField { field }, location))
+}
+
+fn unexpected<E: DeserializeError>(msg: &str, location: ValuePointerRef) -> E {
+    take_cf_content(E::error::<serde_json::Value>(
+        None,
+        ErrorKind::Unexpected { msg: msg.to_string() },
+        location,
+    ))
+}
+
+#[derive(Deserr)]
+#[deserr(deny_unknown_fields)]
+struct JsonIn {
+    field: JsonField,
+    values: Vec<JsonScalar>,
+}
+
+#[derive(Deserr)]
+#[deserr(deny_unknown_fields)]
+struct JsonMatches {
+    field: JsonField,
+    filter: JsonFilter,
+}
+
+#[derive(Deserr)]
+#[deserr(deny_unknown_fields)]
+struct JsonGeoRadius {
+    lat: JsonNumber,
+    lng: JsonNumber,
+    distance: JsonNumber,
+}
+
+#[derive(Deserr)]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+struct JsonGeoBoundingBox {
+    top_right: Vec<JsonNumber>,
+    bottom_left: Vec<JsonNumber>,
+}
+
+#[derive(Deserr)]
+enum JsonOperator {
+    #[deserr(rename = "=")]
+    Equal,
+    #[deserr(rename = "!=")]
+    NotEqual,
+    #[deserr(rename = ">")]
+    GreaterThan,
+    #[deserr(rename = ">=")]
+    GreaterThanOrEqual,
+    #[deserr(rename = "<")]
+    LowerThan,
+    #[deserr(rename = "<=")]
+    LowerThanOrEqual,
+    #[deserr(rename = "TO")]
+    Between,
+    #[deserr(rename = "CONTAINS")]
+    Contains,
+    #[deserr(rename = "STARTS WITH")]
+    StartsWith,
+    #[deserr(rename = "EXISTS")]
+    Exists,
+    #[deserr(rename = "IS NULL")]
+    IsNull,
+    #[deserr(rename = "IS EMPTY")]
+    IsEmpty,
+    #[deserr(rename = "IS NUMBER")]
+    IsNumber,
+    #[deserr(rename = "IS STRING")]
+    IsString,
+}
+
+/// The name of the field a filter applies to.
+struct JsonField(Token<'static>);
+
+impl<E: DeserializeError> Deserr<E> for JsonField {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        match value {
+            Value::String(s) => Ok(JsonField(token(s, location))),
+            value => Err(take_cf_content(E::error::<V>(
+                None,
+                ErrorKind::IncorrectValueKind { actual: value, accepted: &[ValueKind::String] },
+                location,
+            ))),
+        }
+    }
+}
+
+/// A value compared by a filter, numbers and booleans are converted to strings.
+struct JsonScalar(Token<'static>);
+
+impl<E: DeserializeError> Deserr<E> for JsonScalar {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        let value = match value {
+            Value::String(s) => s,
+            Value::Integer(n) => n.to_string(),
+            Value::NegativeInteger(n) => n.to_string(),
+            Value::Float(f) => f.to_string(),
+            Value::Boolean(b) => b.to_string(),
+            value => {
+                return Err(take_cf_content(E::error::<V>(
+                    None,
+                    ErrorKind::IncorrectValueKind {
+                        actual: value,
+                        accepted: &[
+                            ValueKind::String,
+                            ValueKind::Integer,
+                            ValueKind::NegativeInteger,
+                            ValueKind::Float,
+                            ValueKind::Boolean,
+                        ],
+                    },
+                    location,
+                )))
+            }
+        };
+        Ok(JsonScalar(token(value, location)))
+    }
+}
+
+/// A coordinate or a distance of a geo filter, only numbers are accepted.
+struct JsonNumber(Token<'static>);
+
+impl<E: DeserializeError> Deserr<E> for JsonNumber {
+    fn deserialize_from_value<V: IntoValue>(
+        value: Value<V>,
+        location: ValuePointerRef,
+    ) -> Result<Self, E> {
+        let value = match value {
+            Value::Integer(n) => n.to_string(),
+            Value::NegativeInteger(n) => n.to_string(),
+            Value::Float(f) => f.to_string(),
+            value => {
+                return Err(take_cf_content(E::error::<V>(
+                    None,
+                    ErrorKind::IncorrectValueKind {
+                        actual: value,
+                        accepted: &[
+                            ValueKind::Integer,
+                            ValueKind::NegativeInteger,
+                            ValueKind::Float,
+                        ],
+                    },
+                    location,
+                )))
+            }
+        };
+        Ok(JsonNumber(token(value, location)))
+    }
+}
@@ -1,10 +1,15 @@
+use std::ops::Bound;
+
 pub use facet_sort_ascending::ascending_facet_sort;
 pub use facet_sort_descending::descending_facet_sort;
 use heed::types::{ByteSlice, DecodeIgnore};
 use heed::{BytesDecode, RoTxn};
 use roaring::RoaringBitmap;
 
-pub use self::facet_distribution::{FacetDistribution, DEFAULT_VALUES_PER_FACET};
+pub use self::facet_distribution::{
+    FacetDistribution, FacetHierarchy, FacetNumberStats, FacetRange, OrderBy,
+    DEFAULT_VALUES_PER_FACET, FACET_STATS_PERCENTILES, MAX_FACET_HISTOGRAM_BUCKETS,
+};
 pub use self::filter::{BadGeoError, Filter};
 use crate::heed_codec::facet::{FacetGroupKeyCodec, FacetGroupValueCodec, OrderedF64Codec};
 use crate::heed_codec::ByteSliceRefCodec;
@@ -15,6 +20,7 @@ mod facet_range_search;
 mod facet_sort_ascending;
 mod facet_sort_descending;
 mod filter;
+mod json_filter;
 
 fn facet_extreme_value<'t>(
     mut extreme_it: impl Iterator<Item = heed::Result<(RoaringBitmap, &'t [u8])>> + 't,
@@ -48,6 +54,26 @@ pub fn facet_max_value<'t>(
     facet_extreme_value(it)
 }
 
+/// Returns the documents whose number facet value for the given field is lower than or equal to `now`.
+pub(crate) fn expired_documents_ids(
+    index: &Index,
+    rtxn: &RoTxn,
+    field_id: u16,
+    now: f64,
+) -> Result<RoaringBitmap> {
+    let mut docids = RoaringBitmap::new();
+    facet_range_search::find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+        rtxn,
+        index.facet_id_f64_docids,
+        field_id,
+        &Bound::Unbounded,
+        &Bound::Included(now),
+        &mut docids,
+    )?;
+
+    Ok(docids - index.soft_deleted_documents_ids(rtxn)?)
+}
+
 /// Get the first facet value in the facet database
 pub(crate) fn get_first_facet_value<'t, BoundCodec>(
     txn: &'t RoTxn,
@@ -1,10 +1,14 @@
 use std::fmt;
 
+use charabia::Language;
 use levenshtein_automata::{LevenshteinAutomatonBuilder as LevBuilder, DFA};
 use once_cell::sync::Lazy;
 use roaring::bitmap::RoaringBitmap;
 
-pub use self::facet::{FacetDistribution, Filter, DEFAULT_VALUES_PER_FACET};
+pub use self::facet::{
+    FacetDistribution, FacetHierarchy, FacetNumberStats, FacetRange, Filter, OrderBy,
+    DEFAULT_VALUES_PER_FACET, FACET_STATS_PERCENTILES, MAX_FACET_HISTOGRAM_BUCKETS,
+};
 pub use self::new::matches::{FormatOptions, MatchBounds, Matcher, MatcherBuilder, MatchingWords};
 use self::new::PartialSearchResult;
 use crate::{
@@ -31,6 +35,7 @@ pub struct Search<'a> {
     terms_matching_strategy: TermsMatchingStrategy,
     words_limit: usize,
     exhaustive_number_hits: bool,
+    locales: Option<Vec<Language>>,
     rtxn: &'a heed::RoTxn<'a>,
     index: &'a Index,
 }
@@ -47,6 +52,7 @@ impl<'a> Search<'a> {
             terms_matching_strategy: TermsMatchingStrategy::default(),
             exhaustive_number_hits: false,
             words_limit: 10,
+            locales: None,
             rtxn,
             index,
         }
@@ -87,6 +93,12 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Restrict the language detection of the query tokenizer to the given languages.
+    pub fn locales(&mut self, locales: Vec<Language>) -> &mut Search<'a> {
+        self.locales = Some(locales);
+        self
+    }
+
     #[cfg(test)]
     pub fn geo_sort_strategy(&mut self, strategy: new::GeoSortStrategy) -> &mut Search<'a> {
         self.geo_strategy = strategy;
@@ -101,7 +113,7 @@ impl<'a> Search<'a> {
     }
 
     pub fn execute(&self) -> Result<SearchResult> {
-        let mut ctx = SearchContext::new(self.index, self.rtxn);
+        let mut ctx = SearchContext::new(self.index, self.rtxn)?;
         let PartialSearchResult { located_query_terms, candidates, documents_ids } =
             execute_search(
                 &mut ctx,
@@ -114,6 +126,7 @@ impl<'a> Search<'a> {
                 self.offset,
                 self.limit,
                 Some(self.words_limit),
+                self.locales.as_deref(),
                 &mut DefaultSearchLogger,
                 &mut DefaultSearchLogger,
             )?;
@@ -140,6 +153,7 @@ impl fmt::Debug for Search<'_> {
             terms_matching_strategy,
             words_limit,
             exhaustive_number_hits,
+            locales,
             rtxn: _,
             index: _,
         } = self;
@@ -152,6 +166,7 @@ impl fmt::Debug for Search<'_> {
             .field("terms_matching_strategy", terms_matching_strategy)
             .field("exhaustive_number_hits", exhaustive_number_hits)
             .field("words_limit", words_limit)
+            .field("locales", locales)
             .finish()
     }
 }
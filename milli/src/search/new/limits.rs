@@ -4,10 +4,16 @@ pub const MAX_TOKEN_COUNT: usize = 1_000;
 
 /// Maximum number of prefixes that can be derived from a single word.
 pub const MAX_PREFIX_COUNT: usize = 1_000;
+/// Maximum number of words containing a single word that can be derived from it.
+pub const MAX_INFIX_COUNT: usize = 1_000;
 /// Maximum number of words that can be derived from a single word with a distance of one to that word.
 pub const MAX_ONE_TYPO_COUNT: usize = 150;
 /// Maximum number of words that can be derived from a single word with a distance of two to that word.
 pub const MAX_TWO_TYPOS_COUNT: usize = 50;
+/// Maximum number of words that can be derived from a single word by sounding like it.
+pub const MAX_PHONETIC_COUNT: usize = 50;
+/// Maximum number of words that can be derived from a single word by sharing its stem.
+pub const MAX_STEM_COUNT: usize = 50;
 
 /// Maximum amount of synonym phrases that can be derived from a single word.
 pub const MAX_SYNONYM_PHRASE_COUNT: usize = 50;
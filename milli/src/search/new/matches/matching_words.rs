@@ -19,6 +19,9 @@ pub struct LocatedMatchingWords {
     pub positions: RangeInclusive<WordId>,
     pub is_prefix: bool,
     pub original_char_count: usize,
+    /// The original word and the words of the infix attributes containing it,
+    /// these words only match the text of the infix attributes.
+    pub infix: Option<(String, Vec<Interned<String>>)>,
 }
 
 /// Structure created from a query tree
@@ -41,6 +44,7 @@ impl MatchingWords {
         for located_term in located_terms {
             let term = ctx.term_interner.get(located_term.value);
             let (matching_words, matching_phrases) = term.all_computed_derivations();
+            let infix_words = term.infix_derivations();
 
             for matching_phrase in matching_phrases {
                 phrases.push(LocatedMatchingPhrase {
@@ -54,6 +58,7 @@ impl MatchingWords {
                 positions: located_term.positions.clone(),
                 is_prefix: term.is_prefix(),
                 original_char_count: term.original_word(&ctx).chars().count(),
+                infix: (!infix_words.is_empty()).then(|| (term.original_word(&ctx), infix_words)),
             });
         }
 
@@ -70,12 +75,37 @@ impl MatchingWords {
 
     /// Returns an iterator over terms that match or partially match the given token.
     pub fn match_token<'a, 'b>(&'a self, token: &'b Token<'b>) -> MatchesIter<'a, 'b> {
-        MatchesIter { matching_words: self, phrases: Box::new(self.phrases.iter()), token }
+        let phrases = Box::new(self.phrases.iter());
+        MatchesIter { matching_words: self, phrases, token, infix: false }
+    }
+
+    /// Returns an iterator over terms that match or partially match the given token
+    /// of an infix attribute, the words containing the query words match too.
+    pub fn match_infix_token<'a, 'b>(&'a self, token: &'b Token<'b>) -> MatchesIter<'a, 'b> {
+        let phrases = Box::new(self.phrases.iter());
+        MatchesIter { matching_words: self, phrases, token, infix: true }
     }
 
     /// Try to match the token with one of the located_words.
-    fn match_unique_words<'a>(&'a self, token: &Token) -> Option<MatchType<'a>> {
+    fn match_unique_words<'a>(&'a self, token: &Token, infix: bool) -> Option<MatchType<'a>> {
         for located_words in &self.words {
+            // if the word contains the original word, we only match the contained part.
+            if let Some((original, infix_words)) = located_words.infix.as_ref().filter(|_| infix) {
+                for word in infix_words {
+                    let word = self.word_interner.get(*word);
+                    if word == original || token.lemma() != word {
+                        continue;
+                    }
+                    if let Some(byte_start) = word.find(original.as_str()) {
+                        let char_start = token.original_lengths(byte_start).0;
+                        let char_end = token.original_lengths(byte_start + original.len()).0;
+                        let char_len = char_end - char_start;
+                        let ids = &located_words.positions;
+                        return Some(MatchType::Infix { char_start, char_len, ids });
+                    }
+                }
+            }
+
             for word in &located_words.value {
                 let word = self.word_interner.get(*word);
                 // if the word is a prefix we match using starts_with.
@@ -106,6 +136,7 @@ pub struct MatchesIter<'a, 'b> {
     matching_words: &'a MatchingWords,
     phrases: Box<dyn Iterator<Item = &'a LocatedMatchingPhrase> + 'a>,
     token: &'b Token<'b>,
+    infix: bool,
 }
 
 impl<'a> Iterator for MatchesIter<'a, '_> {
@@ -133,7 +164,7 @@ impl<'a> Iterator for MatchesIter<'a, '_> {
                 partial.match_token(self.token).or_else(|| self.next())
             }
             // If no phrases matches, try to match uiques words.
-            None => self.matching_words.match_unique_words(self.token),
+            None => self.matching_words.match_unique_words(self.token, self.infix),
         }
     }
 }
@@ -147,7 +178,16 @@ pub type WordId = u16;
 /// In these cases we need to match consecutively several tokens to consider that the match is full.
 #[derive(Debug, PartialEq)]
 pub enum MatchType<'a> {
-    Full { char_len: usize, ids: &'a RangeInclusive<WordId> },
+    Full {
+        char_len: usize,
+        ids: &'a RangeInclusive<WordId>,
+    },
+    /// The token contains the query word, only the characters from `char_start` are matching.
+    Infix {
+        char_start: usize,
+        char_len: usize,
+        ids: &'a RangeInclusive<WordId>,
+    },
     Partial(PartialMatch<'a>),
 }
 
@@ -255,7 +295,7 @@ pub(crate) mod tests {
     fn matching_words() {
         let temp_index = temp_index_with_documents();
         let rtxn = temp_index.read_txn().unwrap();
-        let mut ctx = SearchContext::new(&temp_index, &rtxn);
+        let mut ctx = SearchContext::new(&temp_index, &rtxn).unwrap();
         let tokenizer = TokenizerBuilder::new().build();
         let tokens = tokenizer.tokenize("split this world");
         let query_terms = located_query_terms_from_tokens(&mut ctx, tokens, None).unwrap();
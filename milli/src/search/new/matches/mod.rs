@@ -12,16 +12,16 @@ const DEFAULT_HIGHLIGHT_PREFIX: &str = "<em>";
 const DEFAULT_HIGHLIGHT_SUFFIX: &str = "</em>";
 
 /// Structure used to build a Matcher allowing to customize formating tags.
-pub struct MatcherBuilder<'a, A> {
+pub struct MatcherBuilder<'m> {
     matching_words: MatchingWords,
-    tokenizer: Tokenizer<'a, 'a, A>,
+    tokenizer: Tokenizer<'m>,
     crop_marker: Option<String>,
     highlight_prefix: Option<String>,
     highlight_suffix: Option<String>,
 }
 
-impl<'a, A> MatcherBuilder<'a, A> {
-    pub fn new(matching_words: MatchingWords, tokenizer: Tokenizer<'a, 'a, A>) -> Self {
+impl<'m> MatcherBuilder<'m> {
+    pub fn new(matching_words: MatchingWords, tokenizer: Tokenizer<'m>) -> Self {
         Self {
             matching_words,
             tokenizer,
@@ -46,7 +46,17 @@ impl<'a, A> MatcherBuilder<'a, A> {
         self
     }
 
-    pub fn build<'t, 'm>(&'m self, text: &'t str) -> Matcher<'t, 'm, A> {
+    pub fn build<'t>(&self, text: &'t str) -> Matcher<'t, 'm, '_> {
+        self.build_matcher(text, false)
+    }
+
+    /// Builds a matcher for the text of an infix attribute, only the part of the words
+    /// containing a query word is matched.
+    pub fn build_infix<'t>(&self, text: &'t str) -> Matcher<'t, 'm, '_> {
+        self.build_matcher(text, true)
+    }
+
+    fn build_matcher<'t>(&self, text: &'t str, infix: bool) -> Matcher<'t, 'm, '_> {
         let crop_marker = match &self.crop_marker {
             Some(marker) => marker.as_str(),
             None => DEFAULT_CROP_MARKER,
@@ -67,6 +77,7 @@ impl<'a, A> MatcherBuilder<'a, A> {
             crop_marker,
             highlight_prefix,
             highlight_suffix,
+            infix,
             matches: None,
         }
     }
@@ -87,6 +98,8 @@ impl FormatOptions {
 #[derive(Clone, Debug)]
 pub struct Match {
     match_len: usize,
+    // number of characters of the token before the match, only non-zero for infix matches.
+    match_start: usize,
     // ids of the query words that matches.
     ids: Vec<WordId>,
     // position of the word in the whole text.
@@ -103,17 +116,19 @@ pub struct MatchBounds {
 
 /// Structure used to analize a string, compute words that match,
 /// and format the source string, returning a highlighted and cropped sub-string.
-pub struct Matcher<'t, 'm, A> {
+pub struct Matcher<'t, 'tokenizer, 'b> {
     text: &'t str,
-    matching_words: &'m MatchingWords,
-    tokenizer: &'m Tokenizer<'m, 'm, A>,
-    crop_marker: &'m str,
-    highlight_prefix: &'m str,
-    highlight_suffix: &'m str,
+    matching_words: &'b MatchingWords,
+    tokenizer: &'b Tokenizer<'tokenizer>,
+    crop_marker: &'b str,
+    highlight_prefix: &'b str,
+    highlight_suffix: &'b str,
+    // whether the text belongs to an infix attribute.
+    infix: bool,
     matches: Option<(Vec<Token<'t>>, Vec<Match>)>,
 }
 
-impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
+impl<'t> Matcher<'t, '_, '_> {
     /// Iterates over tokens and save any of them that matches the query.
     fn compute_matches(&mut self) -> &mut Self {
         /// some words are counted as matches only if they are close together and in the good order,
@@ -142,6 +157,7 @@ impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
                         let iter = potential_matches.into_iter().map(
                             |(token_position, word_position, match_len)| Match {
                                 match_len,
+                                match_start: 0,
                                 ids: ids.clone(),
                                 word_position,
                                 token_position,
@@ -152,6 +168,7 @@ impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
                         // save the token that closes the partial match as a match.
                         matches.push(Match {
                             match_len: char_len,
+                            match_start: 0,
                             ids,
                             word_position,
                             token_position,
@@ -160,8 +177,9 @@ impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
                         // the match is complete, we return true.
                         return true;
                     }
-                    // no match, continue to next match.
-                    None => break,
+                    // no match, continue to next match,
+                    // a partial match is never completed by an infix.
+                    None | Some(MatchType::Infix { .. }) => break,
                 };
             }
 
@@ -187,7 +205,12 @@ impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
             .filter(|(_, _, token)| !token.is_separator());
 
         while let Some((token_position, word_position, word)) = words_positions.next() {
-            for match_type in self.matching_words.match_token(word) {
+            let match_types = if self.infix {
+                self.matching_words.match_infix_token(word)
+            } else {
+                self.matching_words.match_token(word)
+            };
+            for match_type in match_types {
                 match match_type {
                     // we match, we save the current token as a match,
                     // then we continue the rest of the tokens.
@@ -195,6 +218,19 @@ impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
                         let ids: Vec<_> = ids.clone().collect();
                         matches.push(Match {
                             match_len: char_len,
+                            match_start: 0,
+                            ids,
+                            word_position,
+                            token_position,
+                        });
+                        break;
+                    }
+                    // the token contains the query word, only this part of the token is saved.
+                    MatchType::Infix { char_start, char_len, ids } => {
+                        let ids: Vec<_> = ids.clone().collect();
+                        matches.push(Match {
+                            match_len: char_len,
+                            match_start: char_start,
                             ids,
                             word_position,
                             token_position,
@@ -231,9 +267,14 @@ impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
             None => self.compute_matches().matches(),
             Some((tokens, matches)) => matches
                 .iter()
-                .map(|m| MatchBounds {
-                    start: tokens[m.token_position].byte_start,
-                    length: m.match_len,
+                .map(|m| {
+                    let token = &tokens[m.token_position];
+                    let start = self.text[token.byte_start..]
+                        .char_indices()
+                        .enumerate()
+                        .find(|(i, _)| *i == m.match_start)
+                        .map_or(token.byte_end, |(_, (i, _))| i + token.byte_start);
+                    MatchBounds { start, length: m.match_len }
                 })
                 .collect(),
         }
@@ -454,13 +495,22 @@ impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
                                 formatted.push(&self.text[byte_index..token.byte_start]);
                             }
 
+                            let highlight_byte_start = self.text[token.byte_start..]
+                                .char_indices()
+                                .enumerate()
+                                .find(|(i, _)| *i == m.match_start)
+                                .map_or(token.byte_end, |(_, (i, _))| i + token.byte_start);
                             let highlight_byte_index = self.text[token.byte_start..]
                                 .char_indices()
                                 .enumerate()
-                                .find(|(i, _)| *i == m.match_len)
+                                .find(|(i, _)| *i == m.match_start + m.match_len)
                                 .map_or(token.byte_end, |(_, (i, _))| i + token.byte_start);
+                            // if it's an infix highlight, we put the start of the word before the highlight marker.
+                            if token.byte_start < highlight_byte_start {
+                                formatted.push(&self.text[token.byte_start..highlight_byte_start]);
+                            }
                             formatted.push(self.highlight_prefix);
-                            formatted.push(&self.text[token.byte_start..highlight_byte_index]);
+                            formatted.push(&self.text[highlight_byte_start..highlight_byte_index]);
                             formatted.push(self.highlight_suffix);
                             // if it's a prefix highlight, we put the end of the word after the highlight marker.
                             if highlight_byte_index < token.byte_end {
@@ -496,6 +546,8 @@ impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use charabia::TokenizerBuilder;
     use matching_words::tests::temp_index_with_documents;
 
@@ -503,9 +555,9 @@ mod tests {
     use crate::index::tests::TempIndex;
     use crate::{execute_search, SearchContext};
 
-    impl<'a> MatcherBuilder<'a, &[u8]> {
+    impl<'a> MatcherBuilder<'a> {
         fn new_test(rtxn: &'a heed::RoTxn, index: &'a TempIndex, query: &str) -> Self {
-            let mut ctx = SearchContext::new(index, rtxn);
+            let mut ctx = SearchContext::new(index, rtxn).unwrap();
             let crate::search::PartialSearchResult { located_query_terms, .. } = execute_search(
                 &mut ctx,
                 &Some(query.to_string()),
@@ -517,6 +569,7 @@ mod tests {
                 0,
                 100,
                 Some(10),
+                None,
                 &mut crate::DefaultSearchLogger,
                 &mut crate::DefaultSearchLogger,
             )
@@ -853,4 +906,41 @@ mod tests {
             @"_the_ _do_ _or_ die can't be he do and or isn'_t_ _he_"
         );
     }
+    #[test]
+    fn highlight_infix() {
+        let temp_index = TempIndex::new();
+        temp_index
+            .update_settings(|s| s.set_infix_attributes(HashSet::from(["reference".to_string()])))
+            .unwrap();
+        temp_index
+            .add_documents(documents!([
+                { "id": 1, "reference": "AB-45219-X" },
+                { "id": 2, "reference": "CD-15219-Y" },
+            ]))
+            .unwrap();
+        let rtxn = temp_index.read_txn().unwrap();
+        let builder = MatcherBuilder::new_test(&rtxn, &temp_index, "5219");
+
+        let format_options = FormatOptions { highlight: true, crop: None };
+
+        // only the part of the words containing the query word is highlighted.
+        let text = "AB-45219-X and CD-15219-Y";
+        let mut matcher = builder.build_infix(text);
+        insta::assert_snapshot!(
+            matcher.format(format_options),
+            @"AB-4<em>5219</em>-X and CD-1<em>5219</em>-Y"
+        );
+
+        let mut matcher = builder.build_infix(text);
+        assert_eq!(
+            matcher.matches(),
+            vec![MatchBounds { start: 4, length: 4 }, MatchBounds { start: 19, length: 4 }]
+        );
+
+        // the words containing the query word only match in the infix attributes.
+        let mut matcher = builder.build(text);
+        insta::assert_snapshot!(matcher.format(format_options), @"AB-45219-X and CD-15219-Y");
+        let mut matcher = builder.build(text);
+        assert!(matcher.matches().is_empty());
+    }
 }
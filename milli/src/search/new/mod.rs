@@ -27,7 +27,7 @@ mod tests;
 use std::collections::HashSet;
 
 use bucket_sort::{bucket_sort, BucketSortOutput};
-use charabia::TokenizerBuilder;
+use charabia::{Language, TokenizerBuilder};
 use db_cache::DatabaseCache;
 use exact_attribute::ExactAttribute;
 use graph_based_ranking_rule::{Exactness, Fid, Position, Proximity, Typo};
@@ -43,13 +43,17 @@ use ranking_rules::{
 use resolve_query_graph::{compute_query_graph_docids, PhraseDocIdsCache};
 use roaring::RoaringBitmap;
 use sort::Sort;
+use time::OffsetDateTime;
 use words::Words;
 
 use self::geo_sort::GeoSort;
 pub use self::geo_sort::Strategy as GeoSortStrategy;
 use self::interner::Interned;
 use crate::search::new::distinct::apply_distinct_rule;
-use crate::{AscDesc, DocumentId, Filter, Index, Member, Result, TermsMatchingStrategy, UserError};
+use crate::{
+    locales_allow_list, AscDesc, DocumentId, Filter, Index, Member, Result, Stemmers,
+    TermsMatchingStrategy, UserError,
+};
 
 /// A structure used throughout the execution of a search query.
 pub struct SearchContext<'ctx> {
@@ -60,11 +64,13 @@ pub struct SearchContext<'ctx> {
     pub phrase_interner: DedupInterner<Phrase>,
     pub term_interner: Interner<QueryTerm>,
     pub phrase_docids: PhraseDocIdsCache,
+    /// The stemmers of the languages for which the stemming is enabled on the index
+    pub stemmers: Stemmers,
 }
 
 impl<'ctx> SearchContext<'ctx> {
-    pub fn new(index: &'ctx Index, txn: &'ctx RoTxn<'ctx>) -> Self {
-        Self {
+    pub fn new(index: &'ctx Index, txn: &'ctx RoTxn<'ctx>) -> Result<Self> {
+        Ok(Self {
             index,
             txn,
             db_cache: <_>::default(),
@@ -72,7 +78,8 @@ impl<'ctx> SearchContext<'ctx> {
             phrase_interner: <_>::default(),
             term_interner: <_>::default(),
             phrase_docids: <_>::default(),
-        }
+            stemmers: Stemmers::new(&index.stemming(txn)?),
+        })
     }
 }
 
@@ -356,6 +363,7 @@ pub fn execute_search(
     from: usize,
     length: usize,
     words_limit: Option<usize>,
+    locales: Option<&[Language]>,
     placeholder_search_logger: &mut dyn SearchLogger<PlaceholderQuery>,
     query_graph_logger: &mut dyn SearchLogger<QueryGraph>,
 ) -> Result<PartialSearchResult> {
@@ -364,6 +372,9 @@ pub fn execute_search(
     } else {
         ctx.index.documents_ids(ctx.txn)?
     };
+    // the expired documents are hidden until the task deleting them is processed
+    universe -=
+        ctx.index.expired_documents_ids(ctx.txn, OffsetDateTime::now_utc().unix_timestamp())?;
 
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
 
@@ -378,7 +389,26 @@ pub fn execute_search(
             tokbuilder.stop_words(stop_words);
         }
 
-        let script_lang_map = ctx.index.script_language(ctx.txn)?;
+        let separators = ctx.index.allowed_separators(ctx.txn)?;
+        let separators: Option<Vec<_>> =
+            separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref separators) = separators {
+            tokbuilder.separators(separators);
+        }
+
+        let dictionary = ctx.index.dictionary(ctx.txn)?;
+        let dictionary: Option<Vec<_>> =
+            dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        if let Some(ref dictionary) = dictionary {
+            tokbuilder.words_dict(dictionary);
+        }
+
+        // the locales of the query take precedence over the ones of the localized attributes
+        // and the languages detected in the documents.
+        let script_lang_map = match locales {
+            Some(locales) => locales_allow_list(locales),
+            None => ctx.index.query_script_language(ctx.txn)?,
+        };
         if !script_lang_map.is_empty() {
             tokbuilder.allow_list(&script_lang_map);
         }
@@ -4,14 +4,14 @@ use std::ops::ControlFlow;
 
 use fst::automaton::Str;
 use fst::{Automaton, IntoStreamer, Streamer};
-use heed::types::DecodeIgnore;
+use heed::types::{ByteSlice, DecodeIgnore};
 
 use super::*;
 use crate::search::fst_utils::{Complement, Intersection, StartsWith, Union};
 use crate::search::new::query_term::TwoTypoTerm;
 use crate::search::new::{limits, SearchContext};
 use crate::search::{build_dfa, get_first};
-use crate::{Result, MAX_WORD_LENGTH};
+use crate::{infix_ngrams, phonetic_codes, Result, MAX_WORD_LENGTH};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum NumberOfTypos {
@@ -71,6 +71,103 @@ fn find_zero_typo_prefix_derivations(
     Ok(())
 }
 
+/// Finds the words of the infix attributes that contain the given word, the candidates are the
+/// words sharing its first n-gram and they are then checked to really contain the whole word.
+fn find_zero_typo_infix_derivations(
+    ctx: &mut SearchContext,
+    word_interned: Interned<String>,
+    mut visit: impl FnMut(Interned<String>) -> Result<ControlFlow<()>>,
+) -> Result<()> {
+    let word = ctx.word_interner.get(word_interned).to_owned();
+    let Some(ngram) = infix_ngrams(&word).into_iter().next() else { return Ok(()) };
+
+    let mut prefix = ngram.as_bytes().to_vec();
+    prefix.push(0);
+
+    let (index, txn) = (ctx.index, ctx.txn);
+    let mut iter = index
+        .ngram_word_docids
+        .remap_types::<ByteSlice, DecodeIgnore>()
+        .prefix_iter(txn, &prefix)?;
+    while let Some(result) = iter.next() {
+        let (key, ()) = result?;
+        let derived_word = std::str::from_utf8(&key[prefix.len()..])?;
+        if derived_word != word && derived_word.contains(word.as_str()) {
+            let derived_word_interned = ctx.word_interner.insert(derived_word.to_owned());
+            let cf = visit(derived_word_interned)?;
+            if cf.is_break() {
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn find_phonetic_derivations(
+    ctx: &mut SearchContext,
+    word_interned: Interned<String>,
+    mut visit: impl FnMut(Interned<String>) -> Result<ControlFlow<()>>,
+) -> Result<()> {
+    let word = ctx.word_interner.get(word_interned).to_owned();
+
+    for code in phonetic_codes(&word) {
+        let mut prefix = code.into_bytes();
+        prefix.push(0);
+
+        let (index, txn) = (ctx.index, ctx.txn);
+        let mut iter = index
+            .phonetic_word_docids
+            .remap_types::<ByteSlice, DecodeIgnore>()
+            .prefix_iter(txn, &prefix)?;
+        while let Some(result) = iter.next() {
+            let (key, ()) = result?;
+            let derived_word = std::str::from_utf8(&key[prefix.len()..])?;
+            if derived_word != word {
+                let derived_word_interned = ctx.word_interner.insert(derived_word.to_owned());
+                let cf = visit(derived_word_interned)?;
+                if cf.is_break() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn find_stem_derivations(
+    ctx: &mut SearchContext,
+    word_interned: Interned<String>,
+    mut visit: impl FnMut(Interned<String>) -> Result<ControlFlow<()>>,
+) -> Result<()> {
+    let word = ctx.word_interner.get(word_interned).to_owned();
+
+    for stem in ctx.stemmers.stems(&word) {
+        let mut prefix = stem.into_bytes();
+        prefix.push(0);
+
+        let (index, txn) = (ctx.index, ctx.txn);
+        let mut iter = index
+            .stem_word_docids
+            .remap_types::<ByteSlice, DecodeIgnore>()
+            .prefix_iter(txn, &prefix)?;
+        while let Some(result) = iter.next() {
+            let (key, ()) = result?;
+            let derived_word = std::str::from_utf8(&key[prefix.len()..])?;
+            if derived_word != word {
+                let derived_word_interned = ctx.word_interner.insert(derived_word.to_owned());
+                let cf = visit(derived_word_interned)?;
+                if cf.is_break() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 fn find_zero_one_typo_derivations(
     ctx: &mut SearchContext,
     word_interned: Interned<String>,
@@ -191,6 +288,7 @@ pub fn partially_initialized_term_from_word(
                 zero_typo: <_>::default(),
                 one_typo: Lazy::Init(<_>::default()),
                 two_typo: Lazy::Init(<_>::default()),
+                phonetic: <_>::default(),
             }
         });
     }
@@ -215,11 +313,44 @@ pub fn partially_initialized_term_from_word(
 
     let mut zero_typo = None;
     let mut prefix_of = BTreeSet::new();
+    let mut infix_of = BTreeSet::new();
 
     if fst.contains(word) {
         zero_typo = Some(word_interned);
     }
 
+    // The words sharing a stem with the original word are derivations of it
+    // and are therefore ranked below it by the exactness ranking rule.
+    let mut stems = BTreeSet::new();
+    find_stem_derivations(ctx, word_interned, |derived_word| {
+        if stems.len() < limits::MAX_STEM_COUNT {
+            stems.insert(derived_word);
+            Ok(ControlFlow::Continue(()))
+        } else {
+            Ok(ControlFlow::Break(()))
+        }
+    })?;
+
+    find_zero_typo_infix_derivations(ctx, word_interned, |derived_word| {
+        if infix_of.len() < limits::MAX_INFIX_COUNT {
+            infix_of.insert(derived_word);
+            Ok(ControlFlow::Continue(()))
+        } else {
+            Ok(ControlFlow::Break(()))
+        }
+    })?;
+
+    // The phonetic derivations are ranked below the typo derivations by the typo ranking rule.
+    let mut phonetic = BTreeSet::new();
+    find_phonetic_derivations(ctx, word_interned, |derived_word| {
+        if phonetic.len() < limits::MAX_PHONETIC_COUNT {
+            phonetic.insert(derived_word);
+            Ok(ControlFlow::Continue(()))
+        } else {
+            Ok(ControlFlow::Break(()))
+        }
+    })?;
+
     if is_prefix && use_prefix_db.is_none() {
         find_zero_typo_prefix_derivations(
             word_interned,
@@ -252,8 +383,15 @@ pub fn partially_initialized_term_from_word(
             Some(ctx.phrase_interner.insert(Phrase { words }))
         })
         .collect();
-    let zero_typo =
-        ZeroTypoTerm { phrase: None, exact: zero_typo, prefix_of, synonyms, use_prefix_db };
+    let zero_typo = ZeroTypoTerm {
+        phrase: None,
+        exact: zero_typo,
+        prefix_of,
+        infix_of,
+        synonyms,
+        stems,
+        use_prefix_db,
+    };
 
     Ok(QueryTerm {
         original: word_interned,
@@ -263,6 +401,7 @@ pub fn partially_initialized_term_from_word(
         zero_typo,
         one_typo: Lazy::Uninit,
         two_typo: Lazy::Uninit,
+        phonetic,
     })
 }
 
@@ -285,6 +424,7 @@ impl Interned<QueryTerm> {
             return Ok(());
         }
         let mut one_typo_words = BTreeSet::new();
+        let disable_typos_on_numbers = ctx.index.disable_typos_on_numbers(ctx.txn)?;
 
         find_zero_one_typo_derivations(ctx, original, is_prefix, |derived_word, nbr_typos| {
             match nbr_typos {
@@ -299,6 +439,10 @@ impl Interned<QueryTerm> {
             }
             Ok(ControlFlow::Continue(()))
         })?;
+        if disable_typos_on_numbers {
+            // numbers must not be reached with typos, even from a query word that is not a number
+            one_typo_words.retain(|word| !is_number(ctx.word_interner.get(*word)));
+        }
         let original_str = ctx.word_interner.get(original).to_owned();
         let split_words = find_split_words(ctx, original_str.as_str())?;
 
@@ -334,6 +478,7 @@ impl Interned<QueryTerm> {
         }
         let mut one_typo_words = BTreeSet::new();
         let mut two_typo_words = BTreeSet::new();
+        let disable_typos_on_numbers = ctx.index.disable_typos_on_numbers(ctx.txn)?;
 
         find_zero_one_two_typo_derivations(
             *original,
@@ -363,6 +508,11 @@ impl Interned<QueryTerm> {
                 Ok(ControlFlow::Continue(()))
             },
         )?;
+        if disable_typos_on_numbers {
+            // numbers must not be reached with typos, even from a query word that is not a number
+            one_typo_words.retain(|word| !is_number(ctx.word_interner.get(*word)));
+            two_typo_words.retain(|word| !is_number(ctx.word_interner.get(*word)));
+        }
         let split_words = find_split_words(ctx, original_str.as_str())?;
         let self_mut = ctx.term_interner.get_mut(self);
 
@@ -17,6 +17,11 @@ use super::interner::{DedupInterner, Interned};
 use super::{limits, SearchContext, Word};
 use crate::Result;
 
+/// Returns `true` if the word is only made of ASCII digits.
+fn is_number(word: &str) -> bool {
+    !word.is_empty() && word.bytes().all(|b| b.is_ascii_digit())
+}
+
 /// A set of word derivations attached to a location in the search query.
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct LocatedQueryTermSubset {
@@ -31,6 +36,7 @@ pub struct QueryTermSubset {
     zero_typo_subset: NTypoTermSubset,
     one_typo_subset: NTypoTermSubset,
     two_typo_subset: NTypoTermSubset,
+    phonetic_subset: NTypoTermSubset,
     /// `true` if the term cannot be deleted through the term matching strategy
     ///
     /// Note that there are other reasons for which a term cannot be deleted, such as
@@ -50,6 +56,8 @@ pub struct QueryTerm {
     one_typo: Lazy<OneTypoTerm>,
     // May not be computed yet
     two_typo: Lazy<TwoTypoTerm>,
+    /// All the words of the phonetic attributes that sound like the original word
+    phonetic: BTreeSet<Interned<String>>,
 }
 
 // SubTerms will be in a dedup interner
@@ -61,8 +69,12 @@ struct ZeroTypoTerm {
     exact: Option<Interned<String>>,
     /// All the words that contain the original word as prefix
     prefix_of: BTreeSet<Interned<String>>,
+    /// All the words of the infix attributes that contain the original word
+    infix_of: BTreeSet<Interned<String>>,
     /// All the synonyms of the original word or phrase
     synonyms: BTreeSet<Interned<Phrase>>,
+    /// The words of the index sharing a stem with the original word
+    stems: BTreeSet<Interned<String>>,
     /// A prefix in the prefix databases matching the original word
     use_prefix_db: Option<Interned<String>>,
 }
@@ -148,6 +160,7 @@ impl QueryTermSubset {
             zero_typo_subset: NTypoTermSubset::Nothing,
             one_typo_subset: NTypoTermSubset::Nothing,
             two_typo_subset: NTypoTermSubset::Nothing,
+            phonetic_subset: NTypoTermSubset::Nothing,
             mandatory: false,
         }
     }
@@ -157,6 +170,7 @@ impl QueryTermSubset {
             zero_typo_subset: NTypoTermSubset::All,
             one_typo_subset: NTypoTermSubset::All,
             two_typo_subset: NTypoTermSubset::All,
+            phonetic_subset: NTypoTermSubset::All,
             mandatory: false,
         }
     }
@@ -166,12 +180,14 @@ impl QueryTermSubset {
         self.zero_typo_subset.union(&other.zero_typo_subset);
         self.one_typo_subset.union(&other.one_typo_subset);
         self.two_typo_subset.union(&other.two_typo_subset);
+        self.phonetic_subset.union(&other.phonetic_subset);
     }
     pub fn intersect(&mut self, other: &Self) {
         assert!(self.original == other.original);
         self.zero_typo_subset.intersect(&other.zero_typo_subset);
         self.one_typo_subset.intersect(&other.one_typo_subset);
         self.two_typo_subset.intersect(&other.two_typo_subset);
+        self.phonetic_subset.intersect(&other.phonetic_subset);
     }
 
     pub fn use_prefix_db(&self, ctx: &SearchContext) -> Option<Word> {
@@ -216,7 +232,9 @@ impl QueryTermSubset {
                     phrase: _,
                     exact: zero_typo,
                     prefix_of,
+                    infix_of,
                     synonyms: _,
+                    stems,
                     use_prefix_db: _,
                 } = &original.zero_typo;
                 result.extend(zero_typo.iter().copied().map(|w| {
@@ -233,13 +251,17 @@ impl QueryTermSubset {
                         Word::Original(w)
                     }
                 }));
+                result.extend(infix_of.iter().copied().map(Word::Derived));
+                result.extend(stems.iter().copied().map(Word::Derived));
             }
             NTypoTermSubset::Subset { words, phrases: _ } => {
                 let ZeroTypoTerm {
                     phrase: _,
                     exact: zero_typo,
                     prefix_of,
+                    infix_of,
                     synonyms: _,
+                    stems,
                     use_prefix_db: _,
                 } = &original.zero_typo;
                 if let Some(zero_typo) = zero_typo {
@@ -258,6 +280,8 @@ impl QueryTermSubset {
                         Word::Original(w)
                     }
                 }));
+                result.extend(infix_of.intersection(words).copied().map(Word::Derived));
+                result.extend(stems.intersection(words).copied().map(Word::Derived));
             }
             NTypoTermSubset::Nothing => {}
         }
@@ -294,6 +318,16 @@ impl QueryTermSubset {
             NTypoTermSubset::Nothing => {}
         };
 
+        match &self.phonetic_subset {
+            NTypoTermSubset::All => {
+                result.extend(original.phonetic.iter().copied().map(Word::Derived));
+            }
+            NTypoTermSubset::Subset { words, phrases: _ } => {
+                result.extend(original.phonetic.intersection(words).copied().map(Word::Derived));
+            }
+            NTypoTermSubset::Nothing => {}
+        };
+
         Ok(result)
     }
     pub fn all_phrases(&self, ctx: &mut SearchContext) -> Result<BTreeSet<Interned<Phrase>>> {
@@ -305,8 +339,15 @@ impl QueryTermSubset {
         }
         let original = ctx.term_interner.get_mut(self.original);
 
-        let ZeroTypoTerm { phrase, exact: _, prefix_of: _, synonyms, use_prefix_db: _ } =
-            &original.zero_typo;
+        let ZeroTypoTerm {
+            phrase,
+            exact: _,
+            prefix_of: _,
+            infix_of: _,
+            synonyms,
+            stems: _,
+            use_prefix_db: _,
+        } = &original.zero_typo;
         result.extend(phrase.iter().copied());
         result.extend(synonyms.iter().copied());
 
@@ -377,6 +418,7 @@ impl QueryTermSubset {
                     };
                     self.clear_one_typo_subset();
                     self.clear_two_typo_subset();
+                    self.clear_phonetic_subset();
                 }
                 ExactTerm::Word(w) => {
                     self.zero_typo_subset = NTypoTermSubset::Subset {
@@ -385,6 +427,7 @@ impl QueryTermSubset {
                     };
                     self.clear_one_typo_subset();
                     self.clear_two_typo_subset();
+                    self.clear_phonetic_subset();
                 }
             }
         }
@@ -398,6 +441,20 @@ impl QueryTermSubset {
     pub fn clear_two_typo_subset(&mut self) {
         self.two_typo_subset = NTypoTermSubset::Nothing;
     }
+    pub fn clear_phonetic_subset(&mut self) {
+        self.phonetic_subset = NTypoTermSubset::Nothing;
+    }
+    /// Returns `true` if the subset contains words that sound like the original word
+    pub fn has_phonetic_derivations(&self, ctx: &SearchContext) -> bool {
+        let original = ctx.term_interner.get(self.original);
+        match &self.phonetic_subset {
+            NTypoTermSubset::All => !original.phonetic.is_empty(),
+            NTypoTermSubset::Subset { words, phrases: _ } => {
+                original.phonetic.intersection(words).next().is_some()
+            }
+            NTypoTermSubset::Nothing => false,
+        }
+    }
     pub fn description(&self, ctx: &SearchContext) -> String {
         let t = ctx.term_interner.get(self.original);
         ctx.word_interner.get(t.original).to_owned()
@@ -406,11 +463,21 @@ impl QueryTermSubset {
 
 impl ZeroTypoTerm {
     fn is_empty(&self) -> bool {
-        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, use_prefix_db } = self;
+        let ZeroTypoTerm {
+            phrase,
+            exact: zero_typo,
+            prefix_of,
+            infix_of,
+            synonyms,
+            stems,
+            use_prefix_db,
+        } = self;
         phrase.is_none()
             && zero_typo.is_none()
             && prefix_of.is_empty()
+            && infix_of.is_empty()
             && synonyms.is_empty()
+            && stems.is_empty()
             && use_prefix_db.is_none()
     }
 }
@@ -436,7 +503,10 @@ impl QueryTerm {
             return false;
         };
 
-        self.zero_typo.is_empty() && one_typo.is_empty() && two_typo.is_empty()
+        self.zero_typo.is_empty()
+            && one_typo.is_empty()
+            && two_typo.is_empty()
+            && self.phonetic.is_empty()
     }
 }
 
@@ -476,14 +546,27 @@ impl QueryTerm {
     pub fn original_word(&self, ctx: &SearchContext) -> String {
         ctx.word_interner.get(self.original).clone()
     }
+    /// Returns the words of the infix attributes that contain the original word,
+    /// they are not part of the other derivations.
+    pub fn infix_derivations(&self) -> Vec<Interned<String>> {
+        self.zero_typo.infix_of.iter().copied().collect()
+    }
     pub fn all_computed_derivations(&self) -> (Vec<Interned<String>>, Vec<Interned<Phrase>>) {
         let mut words = BTreeSet::new();
         let mut phrases = BTreeSet::new();
 
-        let ZeroTypoTerm { phrase, exact: zero_typo, prefix_of, synonyms, use_prefix_db: _ } =
-            &self.zero_typo;
+        let ZeroTypoTerm {
+            phrase,
+            exact: zero_typo,
+            prefix_of,
+            infix_of: _,
+            synonyms,
+            stems,
+            use_prefix_db: _,
+        } = &self.zero_typo;
         words.extend(zero_typo.iter().copied());
         words.extend(prefix_of.iter().copied());
+        words.extend(stems.iter().copied());
         phrases.extend(phrase.iter().copied());
         phrases.extend(synonyms.iter().copied());
 
@@ -496,6 +579,8 @@ impl QueryTerm {
             words.extend(two_typos.iter().copied());
         };
 
+        words.extend(self.phonetic.iter().copied());
+
         (words.into_iter().collect(), phrases.into_iter().collect())
     }
 }
@@ -7,7 +7,7 @@ use crate::{Result, SearchContext, MAX_WORD_LENGTH};
 /// Convert the tokenised search query into a list of located query terms.
 pub fn located_query_terms_from_tokens(
     ctx: &mut SearchContext,
-    query: NormalizedTokenIter<&[u8]>,
+    query: NormalizedTokenIter,
     words_limit: Option<usize>,
 ) -> Result<Vec<LocatedQueryTerm>> {
     let nbr_typos = number_of_typos_allowed(ctx)?;
@@ -145,11 +145,13 @@ pub fn number_of_typos_allowed<'ctx>(
 
     // TODO: should `exact_words` also disable prefix search, ngrams, split words, or synonyms?
     let exact_words = ctx.index.exact_words(ctx.txn)?;
+    let disable_typos_on_numbers = ctx.index.disable_typos_on_numbers(ctx.txn)?;
 
     Ok(Box::new(move |word: &str| {
         if !authorize_typos
             || word.len() < min_len_one_typo as usize
             || exact_words.as_ref().map_or(false, |fst| fst.contains(word))
+            || (disable_typos_on_numbers && is_number(word))
         {
             0
         } else if word.len() < min_len_two_typos as usize {
@@ -221,6 +223,7 @@ pub fn make_ngram(
         zero_typo: term.zero_typo,
         one_typo: Lazy::Uninit,
         two_typo: Lazy::Uninit,
+        phonetic: term.phonetic,
     };
 
     let term = LocatedQueryTerm { value: ctx.term_interner.push(term), positions: start..=end };
@@ -277,11 +280,14 @@ impl PhraseBuilder {
                         phrase: Some(phrase),
                         exact: None,
                         prefix_of: BTreeSet::default(),
+                        infix_of: BTreeSet::default(),
                         synonyms: BTreeSet::default(),
+                        stems: BTreeSet::default(),
                         use_prefix_db: None,
                     },
                     one_typo: Lazy::Uninit,
                     two_typo: Lazy::Uninit,
+                    phonetic: BTreeSet::default(),
                 }
             }),
             positions: self.start..=self.end,
@@ -7,10 +7,15 @@ use crate::search::new::resolve_query_graph::compute_query_term_subset_docids;
 use crate::search::new::SearchContext;
 use crate::Result;
 
+/// The cost of matching a term with a word that sounds like it,
+/// which is greater than the cost of matching it with the maximum number of typos.
+const PHONETIC_COST: u32 = 3;
+
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub struct TypoCondition {
     term: LocatedQueryTermSubset,
     nbr_typos: u8,
+    phonetic: bool,
 }
 
 pub enum TypoGraph {}
@@ -67,10 +72,23 @@ impl RankingRuleGraphTrait for TypoGraph {
                 }
                 _ => panic!(),
             };
+            term.term_subset.clear_phonetic_subset();
 
             edges.push((
                 nbr_typos as u32 + base_cost,
-                conditions_interner.insert(TypoCondition { term, nbr_typos }),
+                conditions_interner.insert(TypoCondition { term, nbr_typos, phonetic: false }),
+            ));
+        }
+
+        if term.term_subset.has_phonetic_derivations(ctx) {
+            let mut term = term.clone();
+            term.term_subset.clear_zero_typo_subset();
+            term.term_subset.clear_one_typo_subset();
+            term.term_subset.clear_two_typo_subset();
+
+            edges.push((
+                PHONETIC_COST + base_cost,
+                conditions_interner.insert(TypoCondition { term, nbr_typos: 0, phonetic: true }),
             ));
         }
         Ok(edges)
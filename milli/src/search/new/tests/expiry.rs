@@ -0,0 +1,119 @@
+/*!
+This module tests the following properties about the expiry attribute:
+
+1. Documents whose expiry date is in the past are never returned
+2. Documents without an expiry date never expire
+3. The expiry date can be a Unix timestamp in seconds or an RFC 3339 date
+4. The expiry attribute must be filterable to be used in a filter
+*/
+
+use big_s::S;
+use maplit::hashset;
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime};
+
+use crate::index::tests::TempIndex;
+use crate::search::new::tests::collect_field_values;
+use crate::{Filter, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+    let now = OffsetDateTime::now_utc();
+    let rfc3339 = |date: OffsetDateTime| date.format(&Rfc3339).unwrap();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_expiry_field("expiresAt".to_owned());
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "text": "the quick brown fox",
+                "expiresAt": now.unix_timestamp() - 3600,
+            },
+            {
+                "id": 1,
+                "text": "the quick brown fox",
+                "expiresAt": now.unix_timestamp() + 3600,
+            },
+            {
+                "id": 2,
+                "text": "the quick brown fox",
+            },
+            {
+                "id": 3,
+                "text": "the quick brown fox",
+                "expiresAt": 0,
+            },
+            {
+                "id": 4,
+                "text": "the quick brown fox",
+                "expiresAt": rfc3339(now - Duration::hours(1)),
+            },
+            {
+                "id": 5,
+                "text": "the quick brown fox",
+                "expiresAt": rfc3339(now + Duration::hours(1)),
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_expired_documents_are_hidden() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("quick fox");
+    let SearchResult { documents_ids, candidates, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    insta::assert_debug_snapshot!(ids, @r###"
+    [
+        "1",
+        "2",
+        "5",
+    ]
+    "###);
+    assert_eq!(candidates.len(), 3);
+
+    // placeholder search
+    let s = Search::new(&txn, &index);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    let ids = collect_field_values(&index, &txn, "id", &documents_ids);
+    insta::assert_debug_snapshot!(ids, @r###"
+    [
+        "1",
+        "2",
+        "5",
+    ]
+    "###);
+}
+
+#[test]
+fn test_expiry_attribute_must_be_filterable() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let filter = Filter::from_str("expiresAt <= 1").unwrap().unwrap();
+    let error = filter.evaluate(&txn, &index).unwrap_err();
+    assert!(error.to_string().contains("Attribute `expiresAt` is not filterable"), "{error}");
+    drop(txn);
+
+    index.update_settings(|s| s.set_filterable_fields(hashset! { S("expiresAt") })).unwrap();
+    let txn = index.read_txn().unwrap();
+
+    let docids = filter.evaluate(&txn, &index).unwrap();
+    let expired =
+        index.expired_documents_ids(&txn, OffsetDateTime::now_utc().unix_timestamp()).unwrap();
+    assert_eq!(docids.len(), 1);
+    assert!(docids.is_subset(&expired));
+    assert_eq!(expired.len(), 3);
+}
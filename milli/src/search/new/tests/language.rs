@@ -1,3 +1,7 @@
+use std::collections::BTreeMap;
+
+use charabia::{Language, Script};
+
 use crate::index::tests::TempIndex;
 use crate::{Search, SearchResult};
 
@@ -21,3 +25,36 @@ fn test_kanji_language_detection() {
 
     insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
 }
+
+#[test]
+fn test_localized_attributes_locales_are_the_default_query_locales() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_localized_attributes(BTreeMap::from([(
+                "title".to_owned(),
+                vec!["jpn".to_owned()],
+            )]))
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "東京のお寿司。" },
+            { "id": 1, "description": "北京烤鸭" }
+        ]))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+
+    // the query is tokenized like the localized attributes, not with the detected languages
+    let script_language = index.query_script_language(&txn).unwrap();
+    assert_eq!(script_language.get(&Script::Cj), Some(&vec![Language::Jpn]));
+
+    let mut search = Search::new(&txn, &index);
+    search.query("東京");
+    let SearchResult { documents_ids, .. } = search.execute().unwrap();
+
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0]");
+}
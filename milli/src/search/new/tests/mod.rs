@@ -2,14 +2,17 @@ pub mod attribute_fid;
 pub mod attribute_position;
 pub mod distinct;
 pub mod exactness;
+pub mod expiry;
 pub mod geo_sort;
 pub mod integration;
 #[cfg(feature = "all-tokenizations")]
 pub mod language;
 pub mod ngram_split_words;
+pub mod phonetic;
 pub mod proximity;
 pub mod proximity_typo;
 pub mod sort;
+pub mod stemming;
 pub mod stop_words;
 pub mod typo;
 pub mod typo_proximity;
@@ -0,0 +1,84 @@
+/*!
+This module tests the following properties about phonetic matching:
+
+1. Words of the phonetic attributes can be matched by a similar sounding query word
+2. Phonetic matches are ranked below typo matches by the `typo` ranking rule
+*/
+
+use std::collections::HashSet;
+
+use crate::index::tests::TempIndex;
+use crate::search::new::tests::collect_field_values;
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["name".to_owned()]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Typo]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "name": "meyer",
+            },
+            {
+                "id": 1,
+                "name": "maier",
+            },
+            {
+                "id": 2,
+                "name": "meyers",
+            },
+            {
+                "id": 3,
+                "name": "mayer",
+            },
+            {
+                "id": 4,
+                "name": "miller",
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_phonetic_attributes() {
+    let index = create_index();
+
+    // without phonetic attributes, maier cannot be reached from meyer
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("meyer");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2, 3]");
+    drop(txn);
+
+    index
+        .update_settings(|s| s.set_phonetic_attributes(HashSet::from(["name".to_owned()])))
+        .unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("meyer");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 2, 3, 1]");
+    let names = collect_field_values(&index, &txn, "name", &documents_ids);
+    insta::assert_debug_snapshot!(names, @r###"
+    [
+        "\"meyer\"",
+        "\"meyers\"",
+        "\"mayer\"",
+        "\"maier\"",
+    ]
+    "###);
+}
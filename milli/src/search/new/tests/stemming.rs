@@ -0,0 +1,76 @@
+/*!
+This module tests the following properties about stemming:
+
+1. The words sharing a stem with a query word match it
+2. The stems are not indexed as words of the documents
+3. The original word is ranked before the words sharing its stem
+*/
+
+use std::collections::BTreeSet;
+
+use crate::index::tests::TempIndex;
+use crate::{Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            {
+                "id": 0,
+                "text": "she runs every morning",
+            },
+            {
+                "id": 1,
+                "text": "the running shoes",
+            },
+            {
+                "id": 2,
+                "text": "a long run",
+            },
+            {
+                "id": 3,
+                "text": "the runner",
+            },
+        ]))
+        .unwrap();
+    index
+}
+
+#[test]
+fn test_stemming() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("running");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1]");
+    assert!(index.stem_word_docids.is_empty(&txn).unwrap());
+    drop(txn);
+
+    index.update_settings(|s| s.set_stemming(BTreeSet::from(["eng".to_owned()]))).unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    s.query("running");
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids.len(), 3, "{documents_ids:?}");
+    assert_eq!(documents_ids[0], 1);
+    assert!(!documents_ids.contains(&3));
+
+    // the stems are stored in their own database, not among the words of the documents
+    assert_eq!(index.word_docids.get(&txn, "morn").unwrap(), None);
+    assert!(index.word_docids.get(&txn, "morning").unwrap().is_some());
+    let docids = index.stem_word_docids.get(&txn, &("morn", "morning")).unwrap().unwrap();
+    assert_eq!(docids.into_iter().collect::<Vec<_>>(), vec![0]);
+}
@@ -89,6 +89,9 @@ Create a snapshot test of the given database.
     - `exact_word_docids`
     - `word_prefix_docids`
     - `exact_word_prefix_docids`
+    - `ngram_word_docids`
+    - `phonetic_word_docids`
+    - `stem_word_docids`
     - `docid_word_positions`
     - `word_pair_proximity_docids`
     - `word_prefix_pair_proximity_docids`
@@ -97,6 +100,10 @@ Create a snapshot test of the given database.
     - `word_prefix_position_docids`
     - `facet_id_f64_docids`
     - `facet_id_string_docids`
+    - `facet_id_is_string_docids`
+    - `facet_id_size_docids`
+    - `field_id_docid_facet_element_f64s`
+    - `field_id_docid_facet_element_strings`
     - `documents_ids`
     - `stop_words`
     - `soft_deleted_documents_ids`
@@ -217,6 +224,21 @@ pub fn snap_exact_word_prefix_docids(index: &Index) -> String {
         &format!("{s:<16} {}", display_bitmap(&b))
     })
 }
+pub fn snap_ngram_word_docids(index: &Index) -> String {
+    make_db_snap_from_iter!(index, ngram_word_docids, |((ngram, word), b)| {
+        &format!("{ngram:<16} {word:<16} {}", display_bitmap(&b))
+    })
+}
+pub fn snap_phonetic_word_docids(index: &Index) -> String {
+    make_db_snap_from_iter!(index, phonetic_word_docids, |((code, word), b)| {
+        &format!("{code:<16} {word:<16} {}", display_bitmap(&b))
+    })
+}
+pub fn snap_stem_word_docids(index: &Index) -> String {
+    make_db_snap_from_iter!(index, stem_word_docids, |((stem, word), b)| {
+        &format!("{stem:<16} {word:<16} {}", display_bitmap(&b))
+    })
+}
 pub fn snap_docid_word_positions(index: &Index) -> String {
     make_db_snap_from_iter!(index, docid_word_positions, |((idx, s), b)| {
         &format!("{idx:<6} {s:<16} {}", display_bitmap(&b))
@@ -286,6 +308,16 @@ pub fn snap_facet_id_is_empty_docids(index: &Index) -> String {
         &format!("{facet_id:<3} {}", display_bitmap(&docids))
     })
 }
+pub fn snap_facet_id_is_string_docids(index: &Index) -> String {
+    make_db_snap_from_iter!(index, facet_id_is_string_docids, |(facet_id, docids)| {
+        &format!("{facet_id:<3} {}", display_bitmap(&docids))
+    })
+}
+pub fn snap_facet_id_size_docids(index: &Index) -> String {
+    make_db_snap_from_iter!(index, facet_id_size_docids, |((facet_id, size), docids)| {
+        &format!("{facet_id:<3} {size:<6} {}", display_bitmap(&docids))
+    })
+}
 pub fn snap_facet_id_string_docids(index: &Index) -> String {
     make_db_snap_from_iter!(index, facet_id_string_docids, |(
         FacetGroupKey { field_id, level, left_bound },
@@ -302,6 +334,22 @@ pub fn snap_field_id_docid_facet_strings(index: &Index) -> String {
         &format!("{field_id:<3} {doc_id:<4} {string:<12} {other_string}")
     })
 }
+pub fn snap_field_id_docid_facet_element_f64s(index: &Index) -> String {
+    make_db_snap_from_iter!(index, field_id_docid_facet_element_f64s, |(
+        (field_id, doc_id, number),
+        indexes,
+    )| {
+        &format!("{field_id:<3} {doc_id:<4} {number:<6} {}", display_bitmap(&indexes))
+    })
+}
+pub fn snap_field_id_docid_facet_element_strings(index: &Index) -> String {
+    make_db_snap_from_iter!(index, field_id_docid_facet_element_strings, |(
+        (field_id, doc_id, string),
+        indexes,
+    )| {
+        &format!("{field_id:<3} {doc_id:<4} {string:<12} {}", display_bitmap(&indexes))
+    })
+}
 pub fn snap_documents_ids(index: &Index) -> String {
     let rtxn = index.read_txn().unwrap();
     let documents_ids = index.documents_ids(&rtxn).unwrap();
@@ -477,6 +525,15 @@ macro_rules! full_snap_of_db {
     ($index:ident, exact_word_prefix_docids) => {{
         $crate::snapshot_tests::snap_exact_word_prefix_docids(&$index)
     }};
+    ($index:ident, ngram_word_docids) => {{
+        $crate::snapshot_tests::snap_ngram_word_docids(&$index)
+    }};
+    ($index:ident, phonetic_word_docids) => {{
+        $crate::snapshot_tests::snap_phonetic_word_docids(&$index)
+    }};
+    ($index:ident, stem_word_docids) => {{
+        $crate::snapshot_tests::snap_stem_word_docids(&$index)
+    }};
     ($index:ident, docid_word_positions) => {{
         $crate::snapshot_tests::snap_docid_word_positions(&$index)
     }};
@@ -510,6 +567,12 @@ macro_rules! full_snap_of_db {
     ($index:ident, field_id_docid_facet_strings) => {{
         $crate::snapshot_tests::snap_field_id_docid_facet_strings(&$index)
     }};
+    ($index:ident, field_id_docid_facet_element_f64s) => {{
+        $crate::snapshot_tests::snap_field_id_docid_facet_element_f64s(&$index)
+    }};
+    ($index:ident, field_id_docid_facet_element_strings) => {{
+        $crate::snapshot_tests::snap_field_id_docid_facet_element_strings(&$index)
+    }};
     ($index:ident, facet_id_exists_docids) => {{
         $crate::snapshot_tests::snap_facet_id_exists_docids(&$index)
     }};
@@ -519,6 +582,12 @@ macro_rules! full_snap_of_db {
     ($index:ident, facet_id_is_empty_docids) => {{
         $crate::snapshot_tests::snap_facet_id_is_empty_docids(&$index)
     }};
+    ($index:ident, facet_id_is_string_docids) => {{
+        $crate::snapshot_tests::snap_facet_id_is_string_docids(&$index)
+    }};
+    ($index:ident, facet_id_size_docids) => {{
+        $crate::snapshot_tests::snap_facet_id_size_docids(&$index)
+    }};
     ($index:ident, documents_ids) => {{
         $crate::snapshot_tests::snap_documents_ids(&$index)
     }};
@@ -32,14 +32,21 @@ impl<'t, 'u, 'i> ClearDocuments<'t, 'u, 'i> {
             field_id_word_count_docids,
             word_prefix_position_docids,
             word_prefix_fid_docids,
+            ngram_word_docids,
+            phonetic_word_docids,
+            stem_word_docids,
             script_language_docids,
             facet_id_f64_docids,
             facet_id_string_docids,
             facet_id_exists_docids,
             facet_id_is_null_docids,
             facet_id_is_empty_docids,
+            facet_id_is_string_docids,
+            facet_id_size_docids,
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
+            field_id_docid_facet_element_f64s,
+            field_id_docid_facet_element_strings,
             documents,
         } = self.index;
 
@@ -89,14 +96,21 @@ impl<'t, 'u, 'i> ClearDocuments<'t, 'u, 'i> {
         field_id_word_count_docids.clear(self.wtxn)?;
         word_prefix_position_docids.clear(self.wtxn)?;
         word_prefix_fid_docids.clear(self.wtxn)?;
+        ngram_word_docids.clear(self.wtxn)?;
+        phonetic_word_docids.clear(self.wtxn)?;
+        stem_word_docids.clear(self.wtxn)?;
         script_language_docids.clear(self.wtxn)?;
         facet_id_f64_docids.clear(self.wtxn)?;
         facet_id_exists_docids.clear(self.wtxn)?;
         facet_id_is_null_docids.clear(self.wtxn)?;
         facet_id_is_empty_docids.clear(self.wtxn)?;
+        facet_id_is_string_docids.clear(self.wtxn)?;
+        facet_id_size_docids.clear(self.wtxn)?;
         facet_id_string_docids.clear(self.wtxn)?;
         field_id_docid_facet_f64s.clear(self.wtxn)?;
         field_id_docid_facet_strings.clear(self.wtxn)?;
+        field_id_docid_facet_element_f64s.clear(self.wtxn)?;
+        field_id_docid_facet_element_strings.clear(self.wtxn)?;
         documents.clear(self.wtxn)?;
 
         Ok(number_of_documents)
@@ -145,10 +159,17 @@ mod tests {
         assert!(index.word_pair_proximity_docids.is_empty(&rtxn).unwrap());
         assert!(index.field_id_word_count_docids.is_empty(&rtxn).unwrap());
         assert!(index.word_prefix_pair_proximity_docids.is_empty(&rtxn).unwrap());
+        assert!(index.ngram_word_docids.is_empty(&rtxn).unwrap());
+        assert!(index.phonetic_word_docids.is_empty(&rtxn).unwrap());
+        assert!(index.stem_word_docids.is_empty(&rtxn).unwrap());
         assert!(index.facet_id_f64_docids.is_empty(&rtxn).unwrap());
         assert!(index.facet_id_string_docids.is_empty(&rtxn).unwrap());
+        assert!(index.facet_id_is_string_docids.is_empty(&rtxn).unwrap());
+        assert!(index.facet_id_size_docids.is_empty(&rtxn).unwrap());
         assert!(index.field_id_docid_facet_f64s.is_empty(&rtxn).unwrap());
         assert!(index.field_id_docid_facet_strings.is_empty(&rtxn).unwrap());
+        assert!(index.field_id_docid_facet_element_f64s.is_empty(&rtxn).unwrap());
+        assert!(index.field_id_docid_facet_element_strings.is_empty(&rtxn).unwrap());
         assert!(index.documents.is_empty(&rtxn).unwrap());
     }
 }
@@ -13,10 +13,10 @@ use super::ClearDocuments;
 use crate::error::InternalError;
 use crate::facet::FacetType;
 use crate::heed_codec::facet::FieldDocIdFacetCodec;
-use crate::heed_codec::CboRoaringBitmapCodec;
+use crate::heed_codec::{CboRoaringBitmapCodec, StrStrCodec};
 use crate::{
-    ExternalDocumentsIds, FieldId, FieldIdMapMissingEntry, Index, Result, RoaringBitmapCodec,
-    SmallString32, BEU32,
+    infix_ngrams, phonetic_codes, relative_from_absolute_position, ExternalDocumentsIds, FieldId,
+    FieldIdMapMissingEntry, Index, Result, RoaringBitmapCodec, SmallString32, Stemmers, BEU32,
 };
 
 pub struct DeleteDocuments<'t, 'u, 'i> {
@@ -241,19 +241,31 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
             word_prefix_position_docids,
             word_fid_docids,
             word_prefix_fid_docids,
+            ngram_word_docids,
+            phonetic_word_docids,
+            stem_word_docids,
             facet_id_f64_docids: _,
             facet_id_string_docids: _,
             field_id_docid_facet_f64s: _,
             field_id_docid_facet_strings: _,
+            field_id_docid_facet_element_f64s,
+            field_id_docid_facet_element_strings,
             script_language_docids,
             facet_id_exists_docids,
             facet_id_is_null_docids,
             facet_id_is_empty_docids,
+            facet_id_is_string_docids,
+            facet_id_size_docids,
             documents,
         } = self.index;
 
-        // Retrieve the words contained in the documents.
+        // Retrieve the words contained in the documents, and the ones of their infix
+        // and phonetic attributes.
+        let infix_attributes = self.index.infix_attributes_ids(self.wtxn)?;
+        let phonetic_attributes = self.index.phonetic_attributes_ids(self.wtxn)?;
         let mut words = Vec::new();
+        let mut infix_words = Vec::new();
+        let mut phonetic_words = Vec::new();
         for docid in &self.to_delete_docids {
             documents.delete(self.wtxn, &BEU32::new(docid))?;
 
@@ -262,7 +274,19 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
             // It's faster to acquire a cursor to get and delete, as we avoid traversing the LMDB B-Tree two times but only once.
             let mut iter = docid_word_positions.prefix_iter_mut(self.wtxn, &(docid, ""))?;
             while let Some(result) = iter.next() {
-                let ((_docid, word), _positions) = result?;
+                let ((_docid, word), positions) = result?;
+                let in_attributes = |attributes: &HashSet<FieldId>| {
+                    positions.iter().any(|position| {
+                        let (fid, _) = relative_from_absolute_position(position);
+                        attributes.contains(&fid)
+                    })
+                };
+                if in_attributes(&infix_attributes) {
+                    infix_words.push(SmallString32::from(word));
+                }
+                if in_attributes(&phonetic_attributes) {
+                    phonetic_words.push(SmallString32::from(word));
+                }
                 // This boolean will indicate if we must remove this word from the words FST.
                 words.push((SmallString32::from(word), false));
                 // safety: we don't keep references from inside the LMDB database.
@@ -302,6 +326,48 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
             )?;
         }
 
+        // We delete the documents ids from the entries of the stems of the words.
+        let stemmers = Stemmers::new(&self.index.stemming(self.wtxn)?);
+        for (word, _) in &words {
+            for stem in stemmers.stems(word.as_str()) {
+                remove_from_word_derivation_docids(
+                    self.wtxn,
+                    stem_word_docids,
+                    &stem,
+                    word.as_str(),
+                    &self.to_delete_docids,
+                )?;
+            }
+        }
+
+        // We delete the documents ids from the entries of the n-grams of the words
+        // contained in an infix attribute.
+        for word in &infix_words {
+            for ngram in infix_ngrams(word.as_str()) {
+                remove_from_word_derivation_docids(
+                    self.wtxn,
+                    ngram_word_docids,
+                    ngram,
+                    word.as_str(),
+                    &self.to_delete_docids,
+                )?;
+            }
+        }
+
+        // We delete the documents ids from the entries of the phonetic codes of the words
+        // contained in a phonetic attribute.
+        for word in &phonetic_words {
+            for code in phonetic_codes(word.as_str()) {
+                remove_from_word_derivation_docids(
+                    self.wtxn,
+                    phonetic_word_docids,
+                    &code,
+                    word.as_str(),
+                    &self.to_delete_docids,
+                )?;
+            }
+        }
+
         // We construct an FST set that contains the words to delete from the words FST.
         let words_to_delete =
             words.iter().filter_map(
@@ -394,23 +460,7 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
             &self.to_delete_docids,
         )?;
 
-        if let Some(mut rtree) = self.index.geo_rtree(self.wtxn)? {
-            let mut geo_faceted_doc_ids = self.index.geo_faceted_documents_ids(self.wtxn)?;
-
-            let (points_to_remove, docids_to_remove): (Vec<_>, RoaringBitmap) = rtree
-                .iter()
-                .filter(|&point| self.to_delete_docids.contains(point.data.0))
-                .cloned()
-                .map(|point| (point, point.data.0))
-                .unzip();
-            points_to_remove.iter().for_each(|point| {
-                rtree.remove(point);
-            });
-            geo_faceted_doc_ids -= docids_to_remove;
-
-            self.index.put_geo_rtree(self.wtxn, &rtree)?;
-            self.index.put_geo_faceted_documents_ids(self.wtxn, &geo_faceted_doc_ids)?;
-        }
+        remove_docids_from_geo(self.wtxn, self.index, &self.to_delete_docids)?;
 
         for facet_type in [FacetType::Number, FacetType::String] {
             let mut affected_facet_values = HashMap::new();
@@ -441,6 +491,18 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
             .execute(self.wtxn)?;
         }
 
+        // We delete the entries of the deleted documents from the array elements facet databases.
+        remove_docids_from_field_id_docid_facet_elements(
+            self.wtxn,
+            field_id_docid_facet_element_f64s.remap_data_type::<DecodeIgnore>(),
+            &self.to_delete_docids,
+        )?;
+        remove_docids_from_field_id_docid_facet_elements(
+            self.wtxn,
+            field_id_docid_facet_element_strings.remap_data_type::<DecodeIgnore>(),
+            &self.to_delete_docids,
+        )?;
+
         // Remove the documents ids from the script language database.
         Self::delete_from_db(
             script_language_docids.iter_mut(self.wtxn)?.remap_key_type(),
@@ -467,6 +529,19 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
             &self.to_delete_docids,
         )?;
 
+        // We delete the documents ids that are under the facet field id values.
+        remove_docids_from_facet_id_docids(
+            self.wtxn,
+            facet_id_is_string_docids,
+            &self.to_delete_docids,
+        )?;
+
+        // Remove the documents ids from the facet field id size database.
+        Self::delete_from_db(
+            facet_id_size_docids.iter_mut(self.wtxn)?.remap_key_type(),
+            &self.to_delete_docids,
+        )?;
+
         self.index.put_soft_deleted_documents_ids(self.wtxn, &RoaringBitmap::new())?;
 
         Ok(DetailedDocumentDeletionResult {
@@ -501,6 +576,108 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
     }
 }
 
+/// Removes the facet values of the documents for the given fields only, the documents
+/// and the values of their other fields are left untouched.
+///
+/// The geo points of the `geo_docids` documents are removed as well.
+pub(crate) fn remove_docids_from_facet_fields(
+    wtxn: &mut heed::RwTxn,
+    index: &Index,
+    fields_docids: &HashMap<FieldId, RoaringBitmap>,
+    geo_docids: &RoaringBitmap,
+) -> Result<()> {
+    if !geo_docids.is_empty() {
+        remove_docids_from_geo(wtxn, index, geo_docids)?;
+    }
+
+    for (&field_id, to_remove) in fields_docids {
+        for facet_type in [FacetType::Number, FacetType::String] {
+            let mut docids = index.faceted_documents_ids(wtxn, field_id, facet_type)?;
+            if !docids.is_disjoint(to_remove) {
+                docids -= to_remove;
+                index.put_faceted_documents_ids(wtxn, field_id, facet_type, &docids)?;
+            }
+
+            let facet_values = remove_docids_from_field_id_docid_facet_value(
+                index, wtxn, facet_type, field_id, to_remove,
+            )?;
+            if !facet_values.is_empty() {
+                let affected_facet_values = HashMap::from([(field_id, facet_values)]);
+                FacetsDelete::new(index, facet_type, affected_facet_values, to_remove)
+                    .execute(wtxn)?;
+            }
+        }
+
+        for db in [
+            index.field_id_docid_facet_element_f64s.remap_types::<ByteSlice, DecodeIgnore>(),
+            index.field_id_docid_facet_element_strings.remap_types::<ByteSlice, DecodeIgnore>(),
+        ] {
+            let mut iter = db
+                .prefix_iter_mut(wtxn, &field_id.to_be_bytes())?
+                .remap_key_type::<FieldDocIdFacetCodec<ByteSlice>>();
+            while let Some(result) = iter.next() {
+                let ((_, docid, _), _) = result?;
+                if to_remove.contains(docid) {
+                    // safety: we don't keep references from inside the LMDB database.
+                    unsafe { iter.del_current()? };
+                }
+            }
+        }
+
+        for db in [
+            index.facet_id_exists_docids.remap_key_type::<ByteSlice>(),
+            index.facet_id_is_null_docids.remap_key_type::<ByteSlice>(),
+            index.facet_id_is_empty_docids.remap_key_type::<ByteSlice>(),
+            index.facet_id_is_string_docids.remap_key_type::<ByteSlice>(),
+            index.facet_id_size_docids.remap_key_type::<ByteSlice>(),
+        ] {
+            // The size database keys are prefixed by the field id, the other keys are the field id.
+            let mut iter = db.prefix_iter_mut(wtxn, &field_id.to_be_bytes())?;
+            while let Some(result) = iter.next() {
+                let (bytes, mut docids) = result?;
+                let previous_len = docids.len();
+                docids -= to_remove;
+                if docids.is_empty() {
+                    // safety: we don't keep references from inside the LMDB database.
+                    unsafe { iter.del_current()? };
+                } else if docids.len() != previous_len {
+                    let bytes = bytes.to_owned();
+                    // safety: we don't keep references from inside the LMDB database.
+                    unsafe { iter.put_current(&bytes, &docids)? };
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn remove_docids_from_geo(
+    wtxn: &mut heed::RwTxn,
+    index: &Index,
+    to_remove: &RoaringBitmap,
+) -> Result<()> {
+    if let Some(mut rtree) = index.geo_rtree(wtxn)? {
+        let mut geo_faceted_doc_ids = index.geo_faceted_documents_ids(wtxn)?;
+
+        let (points_to_remove, docids_to_remove): (Vec<_>, RoaringBitmap) = rtree
+            .iter()
+            .filter(|&point| to_remove.contains(point.data.0))
+            .cloned()
+            .map(|point| (point, point.data.0))
+            .unzip();
+        points_to_remove.iter().for_each(|point| {
+            rtree.remove(point);
+        });
+        geo_faceted_doc_ids -= docids_to_remove;
+
+        index.put_geo_rtree(wtxn, &rtree)?;
+        index.put_geo_faceted_documents_ids(wtxn, &geo_faceted_doc_ids)?;
+    }
+
+    Ok(())
+}
+
 fn remove_from_word_prefix_docids(
     txn: &mut heed::RwTxn,
     db: &Database<Str, RoaringBitmapCodec>,
@@ -559,6 +736,29 @@ fn remove_from_word_docids(
     Ok(())
 }
 
+/// Removes the documents ids from the entry of a word and one of its derivations,
+/// like its stem, one of its n-grams or phonetic codes, removing the entry when it no longer has any document.
+fn remove_from_word_derivation_docids(
+    txn: &mut heed::RwTxn,
+    db: &heed::Database<StrStrCodec, CboRoaringBitmapCodec>,
+    derivation: &str,
+    word: &str,
+    to_remove: &RoaringBitmap,
+) -> Result<()> {
+    let key = (derivation, word);
+    if let Some(mut docids) = db.get(txn, &key)? {
+        let previous_len = docids.len();
+        docids -= to_remove;
+        if docids.is_empty() {
+            db.delete(txn, &key)?;
+        } else if docids.len() != previous_len {
+            db.put(txn, &key, &docids)?;
+        }
+    }
+
+    Ok(())
+}
+
 fn remove_docids_from_field_id_docid_facet_value(
     index: &Index,
     wtxn: &mut heed::RwTxn,
@@ -593,6 +793,23 @@ fn remove_docids_from_field_id_docid_facet_value(
     Ok(all_affected_facet_values)
 }
 
+fn remove_docids_from_field_id_docid_facet_elements<C>(
+    wtxn: &mut heed::RwTxn,
+    db: heed::Database<FieldDocIdFacetCodec<C>, DecodeIgnore>,
+    to_remove: &RoaringBitmap,
+) -> heed::Result<()> {
+    let mut iter = db.remap_key_type::<FieldDocIdFacetCodec<ByteSlice>>().iter_mut(wtxn)?;
+    while let Some(result) = iter.next() {
+        let ((_, docid, _), _) = result?;
+        if to_remove.contains(docid) {
+            // safety: we don't keep references from inside the LMDB database.
+            unsafe { iter.del_current()? };
+        }
+    }
+
+    Ok(())
+}
+
 fn remove_docids_from_facet_id_docids<'a, C>(
     wtxn: &'a mut heed::RwTxn,
     db: &heed::Database<C, CboRoaringBitmapCodec>,
@@ -621,6 +838,8 @@ where
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
+
     use big_s::S;
     use heed::RwTxn;
     use maplit::hashset;
@@ -1199,4 +1418,46 @@ mod tests {
             DeletionStrategy::AlwaysSoft,
         );
     }
+
+    #[test]
+    fn hard_deletion_removes_the_word_derivations() {
+        let index = TempIndex::new();
+        index
+            .update_settings(|settings| {
+                settings.set_stemming(BTreeSet::from([S("eng")]));
+                settings.set_infix_attributes(hashset! { S("reference") });
+                settings.set_phonetic_attributes(hashset! { S("title") });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "running dogs", "reference": "ABC-45219" },
+                { "id": 1, "title": "running cats", "reference": "XYZ-45219" },
+            ]))
+            .unwrap();
+
+        let mut wtxn = index.write_txn().unwrap();
+        delete_documents(&mut wtxn, &index, &["0"], DeletionStrategy::AlwaysHard);
+        wtxn.commit().unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let remaining = index.external_documents_ids(&rtxn).unwrap().get("1").unwrap();
+        let stem_docids = |key: (&str, &str)| index.stem_word_docids.get(&rtxn, &key).unwrap();
+        assert_eq!(stem_docids(("run", "running")), Some(RoaringBitmap::from_iter([remaining])));
+        assert_eq!(stem_docids(("dog", "dogs")), None);
+
+        let ngram_docids = |key: (&str, &str)| index.ngram_word_docids.get(&rtxn, &key).unwrap();
+        assert_eq!(ngram_docids(("452", "45219")), Some(RoaringBitmap::from_iter([remaining])));
+        assert_eq!(ngram_docids(("abc", "abc")), None);
+
+        let phonetic_docids =
+            |key: (&str, &str)| index.phonetic_word_docids.get(&rtxn, &key).unwrap();
+        for code in phonetic_codes("running") {
+            let docids = phonetic_docids((&code, "running"));
+            assert_eq!(docids, Some(RoaringBitmap::from_iter([remaining])));
+        }
+        for code in phonetic_codes("dogs") {
+            assert_eq!(phonetic_docids((&code, "dogs")), None);
+        }
+    }
 }
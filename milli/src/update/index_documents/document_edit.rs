@@ -0,0 +1,396 @@
+use itertools::merge_join_by;
+use itertools::EitherOrBoth::{Both, Left, Right};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value};
+
+use crate::error::{InternalError, UserError};
+use crate::Result;
+
+/// The names of the operations a field of an edited document can use.
+const OPERATIONS: [&str; 6] = ["_set", "_unset", "_increment", "_decrement", "_append", "_remove"];
+
+/// A single operation to apply on a, possibly nested, field of a document.
+///
+/// The `path` is the dotted path of the field as it was given by the user,
+/// its first segment is the top-level field under which the edit is stored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldEdit {
+    pub path: String,
+    pub operation: EditOperation,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EditOperation {
+    /// Replace the value of the field, creating it if it doesn't exist.
+    Set(Value),
+    /// Remove the field from the document.
+    Unset,
+    /// Add the number to the value of the field, a missing field counts as zero.
+    Increment(Number),
+    /// Subtract the number from the value of the field, a missing field counts as zero.
+    Decrement(Number),
+    /// Push the value, or all the values of an array, at the end of the field array.
+    Append(Value),
+    /// Remove all the occurrences of the value, or of the values of an array, from the field array.
+    Remove(Value),
+}
+
+impl FieldEdit {
+    /// Parses the edit of the field `path` from a JSON object containing exactly one operation.
+    pub fn from_json(path: &str, value: Value) -> Result<Self> {
+        let object = match value {
+            Value::Object(object) if object.len() == 1 => object,
+            _ => {
+                return Err(invalid_edit(
+                    path,
+                    format!(
+                        "expected an object containing exactly one operation among {}",
+                        operation_names()
+                    ),
+                ))
+            }
+        };
+
+        let (name, operand) = object.into_iter().next().unwrap();
+        let operation = match name.as_str() {
+            "_set" => EditOperation::Set(operand),
+            "_unset" => EditOperation::Unset,
+            "_increment" | "_decrement" => {
+                let number = match operand {
+                    Value::Number(number) => number,
+                    operand => {
+                        return Err(invalid_edit(
+                            path,
+                            format!(
+                                "the `{name}` operation expects a number but found `{operand}`"
+                            ),
+                        ))
+                    }
+                };
+                if name == "_increment" {
+                    EditOperation::Increment(number)
+                } else {
+                    EditOperation::Decrement(number)
+                }
+            }
+            "_append" => EditOperation::Append(operand),
+            "_remove" => EditOperation::Remove(operand),
+            _ => {
+                return Err(invalid_edit(
+                    path,
+                    format!("unknown operation `{name}`, expected one of {}", operation_names()),
+                ))
+            }
+        };
+
+        Ok(FieldEdit { path: path.to_string(), operation })
+    }
+
+    /// Applies this edit on the value of its top-level field, `None` meaning the field is missing.
+    pub fn apply(&self, value: &mut Option<Value>) -> Result<()> {
+        let nested: Vec<&str> = self.path.split('.').skip(1).collect();
+        apply_at(value, &nested, &self.operation, &self.path)
+    }
+}
+
+impl EditOperation {
+    /// Whether this operation creates the field when it is missing.
+    fn creates_value(&self) -> bool {
+        !matches!(self, EditOperation::Unset | EditOperation::Remove(_))
+    }
+}
+
+fn apply_at(
+    value: &mut Option<Value>,
+    path: &[&str],
+    operation: &EditOperation,
+    field: &str,
+) -> Result<()> {
+    let (segment, rest) = match path.split_first() {
+        Some(split) => split,
+        None => return apply_operation(value, operation, field),
+    };
+
+    if value.is_none() {
+        if !operation.creates_value() {
+            return Ok(());
+        }
+        *value = Some(Value::Object(Map::new()));
+    }
+
+    let object = match value {
+        Some(Value::Object(object)) => object,
+        _ => {
+            return Err(invalid_edit(
+                field,
+                format!("the `{segment}` key can only be reached through an object"),
+            ))
+        }
+    };
+
+    let mut child = object.get(*segment).cloned();
+    apply_at(&mut child, rest, operation, field)?;
+    match child {
+        Some(child) => {
+            object.insert(segment.to_string(), child);
+        }
+        None => {
+            object.remove(*segment);
+        }
+    }
+
+    Ok(())
+}
+
+fn apply_operation(
+    value: &mut Option<Value>,
+    operation: &EditOperation,
+    field: &str,
+) -> Result<()> {
+    match operation {
+        EditOperation::Set(new) => *value = Some(new.clone()),
+        EditOperation::Unset => *value = None,
+        EditOperation::Increment(number) => {
+            *value = Some(add_numbers(value.as_ref(), number, false, field)?)
+        }
+        EditOperation::Decrement(number) => {
+            *value = Some(add_numbers(value.as_ref(), number, true, field)?)
+        }
+        EditOperation::Append(operand) => match value {
+            Some(Value::Array(array)) => array.extend(operand_values(operand)),
+            Some(_) => {
+                return Err(invalid_edit(field, "the `_append` operation expects an array field"))
+            }
+            None => *value = Some(Value::Array(operand_values(operand).collect())),
+        },
+        EditOperation::Remove(operand) => match value {
+            Some(Value::Array(array)) => {
+                let removed: Vec<_> = operand_values(operand).collect();
+                array.retain(|value| !removed.contains(value));
+            }
+            Some(_) => {
+                return Err(invalid_edit(field, "the `_remove` operation expects an array field"))
+            }
+            None => (),
+        },
+    }
+
+    Ok(())
+}
+
+/// Returns the values of the operand, an array operand is considered as a list of values.
+fn operand_values(operand: &Value) -> impl Iterator<Item = Value> + '_ {
+    let values = match operand {
+        Value::Array(values) => values.as_slice(),
+        operand => std::slice::from_ref(operand),
+    };
+    values.iter().cloned()
+}
+
+fn add_numbers(
+    current: Option<&Value>,
+    operand: &Number,
+    subtract: bool,
+    field: &str,
+) -> Result<Value> {
+    let current = match current {
+        Some(Value::Number(number)) => number.clone(),
+        Some(_) => {
+            return Err(invalid_edit(
+                field,
+                "only numeric fields can be incremented or decremented",
+            ))
+        }
+        None => Number::from(0),
+    };
+
+    // we keep integers as integers as long as the result fits.
+    if let (Some(left), Some(right)) = (current.as_i64(), operand.as_i64()) {
+        let result = if subtract { left.checked_sub(right) } else { left.checked_add(right) };
+        if let Some(result) = result {
+            return Ok(Value::from(result));
+        }
+    }
+
+    let left = current.as_f64().unwrap_or_default();
+    let right = operand.as_f64().unwrap_or_default();
+    let result = if subtract { left - right } else { left + right };
+    Number::from_f64(result)
+        .map(Value::Number)
+        .ok_or_else(|| invalid_edit(field, "the result is not a finite number"))
+}
+
+/// Converts a partial document into the operations of an edited document, the values that
+/// aren't already an operation are set as-is like in a partial document update.
+pub fn patch_to_operations(patch: &Map<String, Value>) -> Map<String, Value> {
+    patch
+        .iter()
+        .map(|(field, value)| {
+            let operation = match value {
+                Value::Object(object) if is_operation(object) => value.clone(),
+                _ => Value::Object(Map::from_iter([("_set".to_string(), value.clone())])),
+            };
+            (field.clone(), operation)
+        })
+        .collect()
+}
+
+/// An object describes an operation when its only key is the name of an operation,
+/// any other object, like `{ "_private": true }`, is a value.
+fn is_operation(object: &Map<String, Value>) -> bool {
+    object.len() == 1 && object.keys().all(|key| OPERATIONS.contains(&key.as_str()))
+}
+
+fn operation_names() -> String {
+    OPERATIONS.iter().map(|op| format!("`{op}`")).collect::<Vec<_>>().join(", ")
+}
+
+fn invalid_edit(field: &str, message: impl Into<String>) -> crate::Error {
+    UserError::InvalidDocumentEditOperation { field: field.to_string(), message: message.into() }
+        .into()
+}
+
+/// Concatenates two obkvs of field edits, the edits of the `update` are applied after
+/// the ones of the `base`.
+pub fn concat_field_edits(
+    base: obkv::KvReaderU16,
+    update: obkv::KvReaderU16,
+    buffer: &mut Vec<u8>,
+) -> Result<()> {
+    buffer.clear();
+
+    let mut writer = obkv::KvWriter::new(buffer);
+    for eob in merge_join_by(base.iter(), update.iter(), |(b, _), (u, _)| b.cmp(u)) {
+        match eob {
+            Both((k, base), (_, update)) => {
+                let mut edits: Vec<FieldEdit> =
+                    serde_json::from_slice(base).map_err(InternalError::SerdeJson)?;
+                let update: Vec<FieldEdit> =
+                    serde_json::from_slice(update).map_err(InternalError::SerdeJson)?;
+                edits.extend(update);
+                let edits = serde_json::to_vec(&edits).map_err(InternalError::SerdeJson)?;
+                writer.insert(k, edits)?;
+            }
+            Left((k, v)) | Right((k, v)) => writer.insert(k, v)?,
+        }
+    }
+
+    writer.finish()?;
+    Ok(())
+}
+
+/// Applies an obkv of field edits on an obkv document and writes the edited document in the buffer.
+pub fn apply_field_edits(
+    document: obkv::KvReaderU16,
+    edits: obkv::KvReaderU16,
+    buffer: &mut Vec<u8>,
+) -> Result<()> {
+    buffer.clear();
+
+    let mut writer = obkv::KvWriter::new(buffer);
+    for eob in merge_join_by(document.iter(), edits.iter(), |(d, _), (e, _)| d.cmp(e)) {
+        let (k, value, edits) = match eob {
+            Left((k, v)) => {
+                writer.insert(k, v)?;
+                continue;
+            }
+            Both((k, value), (_, edits)) => (k, Some(value), edits),
+            Right((k, edits)) => (k, None, edits),
+        };
+
+        let mut value = match value {
+            Some(value) => {
+                Some(serde_json::from_slice::<Value>(value).map_err(InternalError::SerdeJson)?)
+            }
+            None => None,
+        };
+        let edits: Vec<FieldEdit> =
+            serde_json::from_slice(edits).map_err(InternalError::SerdeJson)?;
+        for edit in &edits {
+            edit.apply(&mut value)?;
+        }
+
+        if let Some(value) = value {
+            let value = serde_json::to_vec(&value).map_err(InternalError::SerdeJson)?;
+            writer.insert(k, value)?;
+        }
+    }
+
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn edit(path: &str, operation: Value) -> FieldEdit {
+        FieldEdit::from_json(path, operation).unwrap()
+    }
+
+    #[test]
+    fn parse_field_edits() {
+        assert_eq!(
+            edit("title", json!({ "_set": "Dune" })).operation,
+            EditOperation::Set(json!("Dune"))
+        );
+        assert_eq!(edit("title", json!({ "_unset": null })).operation, EditOperation::Unset);
+        assert_eq!(
+            edit("stock", json!({ "_decrement": 2 })).operation,
+            EditOperation::Decrement(2.into())
+        );
+
+        assert!(FieldEdit::from_json("title", json!("Dune")).is_err());
+        assert!(FieldEdit::from_json("title", json!({ "_set": 1, "_unset": null })).is_err());
+        assert!(FieldEdit::from_json("title", json!({ "_replace": 1 })).is_err());
+        assert!(FieldEdit::from_json("stock", json!({ "_increment": "1" })).is_err());
+    }
+
+    #[test]
+    fn apply_operations() {
+        let mut value = Some(json!(41));
+        edit("stock", json!({ "_increment": 1 })).apply(&mut value).unwrap();
+        assert_eq!(value, Some(json!(42)));
+        edit("stock", json!({ "_decrement": 0.5 })).apply(&mut value).unwrap();
+        assert_eq!(value, Some(json!(41.5)));
+
+        let mut value = None;
+        edit("tags", json!({ "_append": ["scifi", "classic"] })).apply(&mut value).unwrap();
+        edit("tags", json!({ "_append": "space" })).apply(&mut value).unwrap();
+        edit("tags", json!({ "_remove": "classic" })).apply(&mut value).unwrap();
+        assert_eq!(value, Some(json!(["scifi", "space"])));
+
+        let mut value = Some(json!({ "name": "Frank", "born": 1920 }));
+        edit("author.name", json!({ "_set": "Frank Herbert" })).apply(&mut value).unwrap();
+        edit("author.born", json!({ "_unset": null })).apply(&mut value).unwrap();
+        edit("author.books.count", json!({ "_increment": 1 })).apply(&mut value).unwrap();
+        assert_eq!(value, Some(json!({ "name": "Frank Herbert", "books": { "count": 1 } })));
+
+        let mut value = Some(json!("Dune"));
+        assert!(edit("title", json!({ "_increment": 1 })).apply(&mut value).is_err());
+        assert!(edit("title", json!({ "_append": 1 })).apply(&mut value).is_err());
+        assert!(edit("title.name", json!({ "_set": 1 })).apply(&mut value).is_err());
+    }
+
+    #[test]
+    fn convert_patch_to_operations() {
+        let patch = json!({
+            "title": "Dune",
+            "stock": { "_increment": 1 },
+            "author": { "name": "Frank Herbert" },
+            "flags": { "_private": true },
+        });
+        let operations = patch_to_operations(patch.as_object().unwrap());
+        assert_eq!(
+            Value::Object(operations),
+            json!({
+                "title": { "_set": "Dune" },
+                "stock": { "_increment": 1 },
+                "author": { "_set": { "name": "Frank Herbert" } },
+                "flags": { "_set": { "_private": true } },
+            })
+        );
+    }
+}
@@ -1,14 +1,20 @@
+use std::fs::File;
 use std::io::{Read, Seek};
 use std::result::Result as StdResult;
 use std::{fmt, iter};
 
+use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::documents::{DocumentsBatchIndex, DocumentsBatchReader, EnrichedDocumentsBatchReader};
+use crate::documents::{
+    DocumentsBatchBuilder, DocumentsBatchIndex, DocumentsBatchReader, EnrichedDocumentsBatchReader,
+};
 use crate::error::{GeoError, InternalError, UserError};
-use crate::update::index_documents::{obkv_to_object, writer_into_reader};
-use crate::{FieldId, Index, Object, Result};
+use crate::update::index_documents::{obkv_to_object, writer_into_reader, IndexDocumentsMethod};
+use crate::{
+    FieldId, Index, IngestionRules, InvalidDocument, InvalidDocumentPolicy, Object, Result,
+};
 
 /// The symbol used to define levels in a nested primary key.
 const PRIMARY_KEY_SPLIT_SYMBOL: char = '.';
@@ -16,11 +22,43 @@ const PRIMARY_KEY_SPLIT_SYMBOL: char = '.';
 /// The default primary that is used when not specified.
 const DEFAULT_PRIMARY_KEY: &str = "id";
 
+/// Rewrites the documents of the batch by applying the ingestion rules of the index to them.
+///
+/// This must be done before the batch is enriched, so that the primary key
+/// and the `_geo` field are looked for in the transformed documents. The documents of
+/// a partial update or of an edition only go through the rules that don't synthesize fields.
+pub fn apply_ingestion_rules<R: Read + Seek>(
+    rules: &IngestionRules,
+    update_method: IndexDocumentsMethod,
+    reader: DocumentsBatchReader<R>,
+) -> Result<DocumentsBatchReader<File>> {
+    let (mut cursor, documents_batch_index) = reader.into_cursor_and_fields_index();
+
+    let mut builder = DocumentsBatchBuilder::new(tempfile::tempfile()?);
+    while let Some(document) = cursor.next_document()? {
+        let mut object = obkv_to_object(&document, &documents_batch_index)?;
+        rules.apply(&mut object, update_method);
+        builder.append_json_object(&object)?;
+    }
+
+    let mut file = builder.into_inner()?;
+    file.rewind()?;
+    Ok(DocumentsBatchReader::from_reader(file)?)
+}
+
 /// This function validates and enrich the documents by checking that:
 ///  - we can infer a primary key,
 ///  - all the documents id exist and are extracted,
 ///  - the validity of them but also,
-///  - the validity of the `_geo` field depending on the settings.
+///  - the validity of the `_geo` field depending on the settings,
+///  - the documents match the schema of the index, if any.
+///
+/// Depending on the schema, the invalid documents either reject the whole batch
+/// or are only skipped when the enriched documents are read. The documents of a
+/// partial update don't have to contain the required fields of the schema.
+///
+/// The documents of an edition only contain operations, they are neither validated against
+/// the schema nor for their `_geo` field: the transform validates the edited documents.
 ///
 /// # Panics
 ///
@@ -29,6 +67,7 @@ pub fn enrich_documents_batch<R: Read + Seek>(
     rtxn: &heed::RoTxn,
     index: &Index,
     autogenerate_docids: bool,
+    update_method: IndexDocumentsMethod,
     reader: DocumentsBatchReader<R>,
 ) -> Result<StdResult<EnrichedDocumentsBatchReader<R>, UserError>> {
     let (mut cursor, mut documents_batch_index) = reader.into_cursor_and_fields_index();
@@ -97,7 +136,9 @@ pub fn enrich_documents_batch<R: Read + Seek>(
 
     // If the settings specifies that a _geo field must be used therefore we must check the
     // validity of it in all the documents of this batch and this is when we return `Some`.
+    let edition = update_method == IndexDocumentsMethod::EditDocuments;
     let geo_field_id = match documents_batch_index.id("_geo") {
+        Some(_) if edition => None,
         Some(geo_field_id)
             if index.sortable_fields(rtxn)?.contains("_geo")
                 || index.filterable_fields(rtxn)?.contains("_geo") =>
@@ -107,6 +148,11 @@ pub fn enrich_documents_batch<R: Read + Seek>(
         _otherwise => None,
     };
 
+    let document_schema = index.document_schema(rtxn)?;
+    let partial = update_method == IndexDocumentsMethod::UpdateDocuments;
+    let mut skipped_documents = RoaringBitmap::new();
+    let mut invalid_documents = Vec::new();
+
     let mut count = 0;
     while let Some(document) = cursor.next_document()? {
         let document_id = match fetch_or_generate_document_id(
@@ -121,7 +167,27 @@ pub fn enrich_documents_batch<R: Read + Seek>(
             Err(user_error) => return Ok(Err(user_error)),
         };
 
-        if let Some(geo_value) = geo_field_id.and_then(|fid| document.get(fid)) {
+        let mut skipped = false;
+        if !edition && !document_schema.is_empty() {
+            let object = obkv_to_object(&document, &documents_batch_index)?;
+            if let Some(reason) = document_schema.validate(&object, partial) {
+                let document_id = document_id.value().to_string();
+                match document_schema.on_invalid_document {
+                    InvalidDocumentPolicy::Reject => {
+                        return Ok(Err(UserError::InvalidDocumentSchema { document_id, reason }));
+                    }
+                    InvalidDocumentPolicy::Skip => {
+                        skipped_documents.insert(count);
+                        invalid_documents.push(InvalidDocument { document_id, reason });
+                        skipped = true;
+                    }
+                }
+            }
+        }
+
+        // the skipped documents are never indexed, their `_geo` field can be invalid.
+        let geo_value = geo_field_id.filter(|_| !skipped).and_then(|fid| document.get(fid));
+        if let Some(geo_value) = geo_value {
             if let Err(user_error) = validate_geo_from_json(&document_id, geo_value)? {
                 return Ok(Err(UserError::from(user_error)));
             }
@@ -139,6 +205,8 @@ pub fn enrich_documents_batch<R: Read + Seek>(
         DocumentsBatchReader::new(cursor, documents_batch_index),
         primary_key_name,
         external_ids,
+        skipped_documents,
+        invalid_documents,
     )?;
 
     Ok(Ok(reader))
@@ -28,6 +28,9 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
     indexer: GrenadParameters,
     searchable_fields: &Option<HashSet<FieldId>>,
     stop_words: Option<&fst::Set<&[u8]>>,
+    allowed_separators: Option<&[&str]>,
+    dictionary: Option<&[&str]>,
+    localized_attributes: &HashMap<FieldId, HashMap<Script, Vec<Language>>>,
     max_positions_per_attributes: Option<u32>,
 ) -> Result<(RoaringBitmap, grenad::Reader<File>, ScriptLanguageDocidsMap)> {
     let max_positions_per_attributes = max_positions_per_attributes
@@ -46,12 +49,23 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
     );
 
     let mut buffers = Buffers::default();
-    let mut tokenizer_builder = TokenizerBuilder::new();
-    if let Some(stop_words) = stop_words {
-        tokenizer_builder.stop_words(stop_words);
-    }
+    let mut tokenizer_builder = tokenizer_builder(stop_words, allowed_separators, dictionary, None);
     let tokenizer = tokenizer_builder.build();
 
+    // the localized attributes are tokenized with a tokenizer restricted to their languages.
+    let mut localized_tokenizer_builders: HashMap<_, _> = localized_attributes
+        .iter()
+        .map(|(field_id, allow_list)| {
+            let builder =
+                tokenizer_builder(stop_words, allowed_separators, dictionary, Some(allow_list));
+            (*field_id, builder)
+        })
+        .collect();
+    let localized_tokenizers: HashMap<_, _> = localized_tokenizer_builders
+        .iter_mut()
+        .map(|(field_id, builder)| (*field_id, builder.build()))
+        .collect();
+
     let mut cursor = obkv_documents.into_cursor()?;
     while let Some((key, value)) = cursor.move_on_next()? {
         let document_id = key
@@ -70,6 +84,7 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
             &obkv,
             searchable_fields,
             &tokenizer,
+            &localized_tokenizers,
             max_positions_per_attributes,
             &mut buffers,
             &mut script_language_word_count,
@@ -92,11 +107,12 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
             // then we don't rerun the extraction.
             if !script_language.is_empty() {
                 // build a new temporary tokenizer including the allow list.
-                let mut tokenizer_builder = TokenizerBuilder::new();
-                if let Some(stop_words) = stop_words {
-                    tokenizer_builder.stop_words(stop_words);
-                }
-                tokenizer_builder.allow_list(&script_language);
+                let mut tokenizer_builder = tokenizer_builder(
+                    stop_words,
+                    allowed_separators,
+                    dictionary,
+                    Some(&script_language),
+                );
                 let tokenizer = tokenizer_builder.build();
 
                 script_language_word_count.clear();
@@ -106,6 +122,7 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
                     &obkv,
                     searchable_fields,
                     &tokenizer,
+                    &localized_tokenizers,
                     max_positions_per_attributes,
                     &mut buffers,
                     &mut script_language_word_count,
@@ -128,10 +145,34 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
         .map(|reader| (documents_ids, reader, script_language_docids))
 }
 
-fn extract_tokens_from_document<T: AsRef<[u8]>>(
+/// Returns a tokenizer builder configured with the tokenization settings of the index.
+fn tokenizer_builder<'a>(
+    stop_words: Option<&'a fst::Set<&'a [u8]>>,
+    allowed_separators: Option<&'a [&'a str]>,
+    dictionary: Option<&'a [&'a str]>,
+    script_language: Option<&'a HashMap<Script, Vec<Language>>>,
+) -> TokenizerBuilder<'a, &'a [u8]> {
+    let mut tokenizer_builder = TokenizerBuilder::new();
+    if let Some(stop_words) = stop_words {
+        tokenizer_builder.stop_words(stop_words);
+    }
+    if let Some(separators) = allowed_separators {
+        tokenizer_builder.separators(separators);
+    }
+    if let Some(dictionary) = dictionary {
+        tokenizer_builder.words_dict(dictionary);
+    }
+    if let Some(script_language) = script_language {
+        tokenizer_builder.allow_list(script_language);
+    }
+    tokenizer_builder
+}
+
+fn extract_tokens_from_document(
     obkv: &KvReader<FieldId>,
     searchable_fields: &Option<HashSet<FieldId>>,
-    tokenizer: &Tokenizer<T>,
+    tokenizer: &Tokenizer,
+    localized_tokenizers: &HashMap<FieldId, Tokenizer>,
     max_positions_per_attributes: u32,
     buffers: &mut Buffers,
     script_language_word_count: &mut HashMap<Script, Vec<(Language, usize)>>,
@@ -142,6 +183,7 @@ fn extract_tokens_from_document<T: AsRef<[u8]>>(
             let value = serde_json::from_slice(field_bytes).map_err(InternalError::SerdeJson)?;
             buffers.field_buffer.clear();
             if let Some(field) = json_to_string(&value, &mut buffers.field_buffer) {
+                let tokenizer = localized_tokenizers.get(&field_id).unwrap_or(tokenizer);
                 let tokens = process_tokens(tokenizer.tokenize(field))
                     .take_while(|(p, _)| (*p as u32) < max_positions_per_attributes);
 
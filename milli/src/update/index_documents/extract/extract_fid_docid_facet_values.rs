@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::TryInto;
 use std::fs::File;
 use std::io;
@@ -7,12 +7,16 @@ use std::mem::size_of;
 use heed::zerocopy::AsBytes;
 use heed::BytesEncode;
 use roaring::RoaringBitmap;
-use serde_json::{from_slice, Value};
+use serde_json::{from_slice, Map, Value};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 
-use super::helpers::{create_sorter, keep_first, sorter_into_reader, GrenadParameters};
+use super::helpers::{
+    create_sorter, keep_first, merge_cbo_roaring_bitmaps, sorter_into_reader, GrenadParameters,
+};
 use crate::error::InternalError;
 use crate::facet::value_encoding::f64_into_bytes;
-use crate::update::index_documents::{create_writer, writer_into_reader};
+use crate::update::index_documents::{create_writer, writer_into_reader, MergeFn};
 use crate::{CboRoaringBitmapCodec, DocumentId, FieldId, Result, BEU32, MAX_FACET_VALUE_LENGTH};
 
 /// The extracted facet values stored in grenad files by type.
@@ -22,27 +26,62 @@ pub struct ExtractedFacetValues {
     pub fid_facet_is_null_docids_chunk: grenad::Reader<File>,
     pub fid_facet_is_empty_docids_chunk: grenad::Reader<File>,
     pub fid_facet_exists_docids_chunk: grenad::Reader<File>,
+    pub fid_facet_is_string_docids_chunk: grenad::Reader<File>,
+    pub fid_facet_size_docids_chunk: grenad::Reader<File>,
+    pub fid_docid_facet_element_numbers_chunk: grenad::Reader<File>,
+    pub fid_docid_facet_element_strings_chunk: grenad::Reader<File>,
 }
 
 /// Extracts the facet values of each faceted field of each document.
 ///
 /// Returns the generated grenad reader containing the docid the fid and the orginal value as key
 /// and the normalized value as value extracted from the given chunk of documents.
+///
+/// The `element_faceted_fields` associates the fields containing arrays of objects with the
+/// faceted fields nested in them, the facet values of those are also extracted along with
+/// the indexes of the array elements they come from.
+///
+/// The `hierarchical_facets` associates the hierarchical facets with the separator of their
+/// levels, every ancestor path of their string values is extracted as a facet value too.
+///
+/// The RFC 3339 dates of the `expiry_field_id` are also extracted as numbers,
+/// the Unix timestamps in seconds they represent.
 #[logging_timer::time]
 pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
     obkv_documents: grenad::Reader<R>,
     indexer: GrenadParameters,
     faceted_fields: &HashSet<FieldId>,
+    element_faceted_fields: &HashMap<FieldId, Vec<(String, FieldId)>>,
+    hierarchical_facets: &HashMap<FieldId, String>,
+    expiry_field_id: Option<FieldId>,
 ) -> Result<ExtractedFacetValues> {
     let max_memory = indexer.max_memory_by_thread();
 
+    let mut fid_docid_facet_element_numbers_sorter = create_sorter(
+        grenad::SortAlgorithm::Stable,
+        merge_cbo_roaring_bitmaps,
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        indexer.max_nb_chunks,
+        max_memory.map(|m| m / 4),
+    );
+
+    let mut fid_docid_facet_element_strings_sorter = create_sorter(
+        grenad::SortAlgorithm::Stable,
+        merge_cbo_roaring_bitmaps,
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        indexer.max_nb_chunks,
+        max_memory.map(|m| m / 4),
+    );
+
     let mut fid_docid_facet_numbers_sorter = create_sorter(
         grenad::SortAlgorithm::Stable,
         keep_first,
         indexer.chunk_compression_type,
         indexer.chunk_compression_level,
         indexer.max_nb_chunks,
-        max_memory.map(|m| m / 2),
+        max_memory.map(|m| m / 4),
     );
 
     let mut fid_docid_facet_strings_sorter = create_sorter(
@@ -51,12 +90,14 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
         indexer.chunk_compression_type,
         indexer.chunk_compression_level,
         indexer.max_nb_chunks,
-        max_memory.map(|m| m / 2),
+        max_memory.map(|m| m / 4),
     );
 
     let mut facet_exists_docids = BTreeMap::<FieldId, RoaringBitmap>::new();
     let mut facet_is_null_docids = BTreeMap::<FieldId, RoaringBitmap>::new();
     let mut facet_is_empty_docids = BTreeMap::<FieldId, RoaringBitmap>::new();
+    let mut facet_is_string_docids = BTreeMap::<FieldId, RoaringBitmap>::new();
+    let mut facet_size_docids = BTreeMap::<(FieldId, u32), RoaringBitmap>::new();
 
     let mut key_buffer = Vec::new();
     let mut cursor = obkv_documents.into_cursor()?;
@@ -64,6 +105,20 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
         let obkv = obkv::KvReader::new(value);
 
         for (field_id, field_bytes) in obkv.iter() {
+            if let Some(nested_fields) = element_faceted_fields.get(&field_id) {
+                let document: [u8; 4] = docid_bytes[..4].try_into().ok().unwrap();
+                let document = BEU32::from(document).get();
+                let value = from_slice(field_bytes).map_err(InternalError::SerdeJson)?;
+                extract_facet_element_values(
+                    field_id,
+                    document,
+                    &value,
+                    nested_fields,
+                    &mut fid_docid_facet_element_numbers_sorter,
+                    &mut fid_docid_facet_element_strings_sorter,
+                )?;
+            }
+
             if faceted_fields.contains(&field_id) {
                 key_buffer.clear();
 
@@ -82,6 +137,15 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
 
                 let value = from_slice(field_bytes).map_err(InternalError::SerdeJson)?;
 
+                if let Value::Array(values) = &value {
+                    let size = values.len().try_into().unwrap_or(u32::MAX);
+                    facet_size_docids.entry((field_id, size)).or_default().insert(document);
+                }
+
+                if contains_string(&value) {
+                    facet_is_string_docids.entry(field_id).or_default().insert(document);
+                }
+
                 match extract_facet_values(&value) {
                     FilterableValues::Null => {
                         facet_is_null_docids.entry(field_id).or_default().insert(document);
@@ -89,7 +153,14 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                     FilterableValues::Empty => {
                         facet_is_empty_docids.entry(field_id).or_default().insert(document);
                     }
-                    FilterableValues::Values { numbers, strings } => {
+                    FilterableValues::Values { mut numbers, mut strings } => {
+                        if expiry_field_id == Some(field_id) {
+                            numbers.extend(strings.iter().filter_map(|(_, original)| {
+                                let date = OffsetDateTime::parse(original, &Rfc3339).ok()?;
+                                Some(date.unix_timestamp() as f64)
+                            }));
+                        }
+
                         // insert facet numbers in sorter
                         for number in numbers {
                             key_buffer.truncate(size_of::<FieldId>() + size_of::<DocumentId>());
@@ -102,15 +173,15 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                             }
                         }
 
+                        if let Some(separator) = hierarchical_facets.get(&field_id) {
+                            strings = expand_hierarchical_facet_values(strings, separator);
+                        }
+
                         // insert normalized and original facet string in sorter
                         for (normalized, original) in
                             strings.into_iter().filter(|(n, _)| !n.is_empty())
                         {
-                            let normalized_truncated_value: String = normalized
-                                .char_indices()
-                                .take_while(|(idx, _)| idx + 4 < MAX_FACET_VALUE_LENGTH)
-                                .map(|(_, c)| c)
-                                .collect();
+                            let normalized_truncated_value = truncate_facet_string(&normalized);
 
                             key_buffer.truncate(size_of::<FieldId>() + size_of::<DocumentId>());
                             key_buffer.extend_from_slice(normalized_truncated_value.as_bytes());
@@ -156,15 +227,136 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
     }
     let facet_is_empty_docids_reader = writer_into_reader(facet_is_empty_docids_writer)?;
 
+    let mut facet_is_string_docids_writer = create_writer(
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        tempfile::tempfile()?,
+    );
+    for (fid, bitmap) in facet_is_string_docids.into_iter() {
+        let bitmap_bytes = CboRoaringBitmapCodec::bytes_encode(&bitmap).unwrap();
+        facet_is_string_docids_writer.insert(fid.to_be_bytes(), &bitmap_bytes)?;
+    }
+    let facet_is_string_docids_reader = writer_into_reader(facet_is_string_docids_writer)?;
+
+    let mut facet_size_docids_writer = create_writer(
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        tempfile::tempfile()?,
+    );
+    for ((fid, size), bitmap) in facet_size_docids.into_iter() {
+        // Note: this encoding is consistent with FieldIdSizeCodec
+        key_buffer.clear();
+        key_buffer.extend_from_slice(&fid.to_be_bytes());
+        key_buffer.extend_from_slice(&size.to_be_bytes());
+        let bitmap_bytes = CboRoaringBitmapCodec::bytes_encode(&bitmap).unwrap();
+        facet_size_docids_writer.insert(&key_buffer, &bitmap_bytes)?;
+    }
+    let facet_size_docids_reader = writer_into_reader(facet_size_docids_writer)?;
+
     Ok(ExtractedFacetValues {
         docid_fid_facet_numbers_chunk: sorter_into_reader(fid_docid_facet_numbers_sorter, indexer)?,
         docid_fid_facet_strings_chunk: sorter_into_reader(fid_docid_facet_strings_sorter, indexer)?,
         fid_facet_is_null_docids_chunk: facet_is_null_docids_reader,
         fid_facet_is_empty_docids_chunk: facet_is_empty_docids_reader,
         fid_facet_exists_docids_chunk: facet_exists_docids_reader,
+        fid_facet_is_string_docids_chunk: facet_is_string_docids_reader,
+        fid_facet_size_docids_chunk: facet_size_docids_reader,
+        fid_docid_facet_element_numbers_chunk: sorter_into_reader(
+            fid_docid_facet_element_numbers_sorter,
+            indexer,
+        )?,
+        fid_docid_facet_element_strings_chunk: sorter_into_reader(
+            fid_docid_facet_element_strings_sorter,
+            indexer,
+        )?,
     })
 }
 
+/// Extracts the facet values of the fields nested in an array of objects, each value
+/// is associated with the index of the element it was found in.
+///
+/// The array field id itself is associated with an empty string and all the elements indexes,
+/// a single object is considered to be an array of one element.
+fn extract_facet_element_values(
+    field_id: FieldId,
+    document: DocumentId,
+    value: &Value,
+    nested_fields: &[(String, FieldId)],
+    numbers_sorter: &mut grenad::Sorter<MergeFn>,
+    strings_sorter: &mut grenad::Sorter<MergeFn>,
+) -> Result<()> {
+    let elements: Vec<&Map<String, Value>> = match value {
+        Value::Array(values) => values.iter().filter_map(Value::as_object).collect(),
+        Value::Object(object) => vec![object],
+        _ => return Ok(()),
+    };
+
+    let mut key_buffer = Vec::new();
+    let mut elements_indexes = RoaringBitmap::new();
+    for (index, element) in elements.into_iter().enumerate() {
+        let index = index as u32;
+        elements_indexes.insert(index);
+        let element_bitmap: RoaringBitmap = std::iter::once(index).collect();
+        let element_bitmap_bytes = CboRoaringBitmapCodec::bytes_encode(&element_bitmap).unwrap();
+
+        let element = flatten_serde_json::flatten(element);
+        for (relative_name, nested_field_id) in nested_fields {
+            let (numbers, strings) = match element.get(relative_name).map(extract_facet_values) {
+                Some(FilterableValues::Values { numbers, strings }) => (numbers, strings),
+                _ => continue,
+            };
+
+            key_buffer.clear();
+            key_buffer.extend_from_slice(&nested_field_id.to_be_bytes());
+            key_buffer.extend_from_slice(&document.to_be_bytes());
+            let prefix_len = key_buffer.len();
+
+            for number in numbers {
+                key_buffer.truncate(prefix_len);
+                if let Some(value_bytes) = f64_into_bytes(number) {
+                    key_buffer.extend_from_slice(&value_bytes);
+                    key_buffer.extend_from_slice(&number.to_be_bytes());
+                    numbers_sorter.insert(&key_buffer, &element_bitmap_bytes)?;
+                }
+            }
+
+            for (normalized, _original) in strings.into_iter().filter(|(n, _)| !n.is_empty()) {
+                key_buffer.truncate(prefix_len);
+                key_buffer.extend_from_slice(truncate_facet_string(&normalized).as_bytes());
+                strings_sorter.insert(&key_buffer, &element_bitmap_bytes)?;
+            }
+        }
+    }
+
+    if !elements_indexes.is_empty() {
+        key_buffer.clear();
+        key_buffer.extend_from_slice(&field_id.to_be_bytes());
+        key_buffer.extend_from_slice(&document.to_be_bytes());
+        let bitmap_bytes = CboRoaringBitmapCodec::bytes_encode(&elements_indexes).unwrap();
+        strings_sorter.insert(&key_buffer, bitmap_bytes)?;
+    }
+
+    Ok(())
+}
+
+/// Truncates the normalized facet string to make it fit in an LMDB key.
+fn truncate_facet_string(normalized: &str) -> String {
+    normalized
+        .char_indices()
+        .take_while(|(idx, _)| idx + 4 < MAX_FACET_VALUE_LENGTH)
+        .map(|(_, c)| c)
+        .collect()
+}
+
+/// Returns `true` if the value is a string or an array containing a string.
+fn contains_string(value: &Value) -> bool {
+    match value {
+        Value::String(_) => true,
+        Value::Array(values) => values.iter().any(Value::is_string),
+        _ => false,
+    }
+}
+
 /// Represent what a document field contains.
 enum FilterableValues {
     /// Corresponds to the JSON `null` value.
@@ -175,6 +367,28 @@ enum FilterableValues {
     Values { numbers: Vec<f64>, strings: Vec<(String, String)> },
 }
 
+/// Replaces each path of a hierarchical facet by all the paths leading to it,
+/// e.g. `A > B > C` by `A`, `A > B` and `A > B > C`.
+///
+/// The levels are trimmed and the empty ones are ignored.
+fn expand_hierarchical_facet_values(
+    strings: Vec<(String, String)>,
+    separator: &str,
+) -> Vec<(String, String)> {
+    let mut paths = BTreeMap::new();
+    for (_, original) in strings {
+        let mut path = String::new();
+        for level in original.split(separator).map(str::trim).filter(|l| !l.is_empty()) {
+            if !path.is_empty() {
+                path.push_str(separator);
+            }
+            path.push_str(level);
+            paths.entry(crate::normalize_facet(&path)).or_insert_with(|| path.clone());
+        }
+    }
+    paths.into_iter().collect()
+}
+
 fn extract_facet_values(value: &Value) -> FilterableValues {
     fn inner_extract_facet_values(
         value: &Value,
@@ -0,0 +1,63 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io;
+
+use super::helpers::{
+    create_sorter, merge_cbo_roaring_bitmaps, read_u32_ne_bytes, sorter_into_reader,
+    try_split_array_at, GrenadParameters,
+};
+use crate::error::SerializationError;
+use crate::index::db_name::DOCID_WORD_POSITIONS;
+use crate::{infix_ngrams, relative_from_absolute_position, DocumentId, FieldId, Result};
+
+/// Extracts the character n-grams of the words of the infix attributes, the words
+/// containing them and the documents ids where these words appear in an infix attribute.
+#[logging_timer::time]
+pub fn extract_ngram_word_docids<R: io::Read + io::Seek>(
+    docid_word_positions: grenad::Reader<R>,
+    indexer: GrenadParameters,
+    infix_attributes: &HashSet<FieldId>,
+) -> Result<grenad::Reader<File>> {
+    let max_memory = indexer.max_memory_by_thread();
+
+    let mut ngram_word_docids_sorter = create_sorter(
+        grenad::SortAlgorithm::Unstable,
+        merge_cbo_roaring_bitmaps,
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        indexer.max_nb_chunks,
+        max_memory,
+    );
+
+    // If there are no infix attributes, there is nothing to extract.
+    if infix_attributes.is_empty() {
+        return sorter_into_reader(ngram_word_docids_sorter, indexer);
+    }
+
+    let mut key_buffer = Vec::new();
+    let mut cursor = docid_word_positions.into_cursor()?;
+    while let Some((key, positions)) = cursor.move_on_next()? {
+        let (document_id_bytes, word_bytes) = try_split_array_at(key)
+            .ok_or(SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
+        let document_id = DocumentId::from_be_bytes(document_id_bytes);
+
+        let in_infix_attribute = read_u32_ne_bytes(positions).any(|position| {
+            let (fid, _) = relative_from_absolute_position(position);
+            infix_attributes.contains(&fid)
+        });
+        if !in_infix_attribute {
+            continue;
+        }
+
+        let word = std::str::from_utf8(word_bytes)?;
+        for ngram in infix_ngrams(word) {
+            key_buffer.clear();
+            key_buffer.extend_from_slice(ngram.as_bytes());
+            key_buffer.push(0);
+            key_buffer.extend_from_slice(word_bytes);
+            ngram_word_docids_sorter.insert(&key_buffer, document_id.to_ne_bytes())?;
+        }
+    }
+
+    sorter_into_reader(ngram_word_docids_sorter, indexer)
+}
@@ -0,0 +1,54 @@
+use std::fs::File;
+use std::io;
+
+use super::helpers::{
+    create_sorter, merge_cbo_roaring_bitmaps, sorter_into_reader, try_split_array_at,
+    GrenadParameters,
+};
+use crate::error::SerializationError;
+use crate::index::db_name::DOCID_WORD_POSITIONS;
+use crate::{DocumentId, Result, Stemmers};
+
+/// Extracts the stems of the words in the languages for which the stemming is enabled,
+/// the words sharing them and the documents ids where these words appear.
+#[logging_timer::time]
+pub fn extract_stem_word_docids<R: io::Read + io::Seek>(
+    docid_word_positions: grenad::Reader<R>,
+    indexer: GrenadParameters,
+    stemmers: &Stemmers,
+) -> Result<grenad::Reader<File>> {
+    let max_memory = indexer.max_memory_by_thread();
+
+    let mut stem_word_docids_sorter = create_sorter(
+        grenad::SortAlgorithm::Unstable,
+        merge_cbo_roaring_bitmaps,
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        indexer.max_nb_chunks,
+        max_memory,
+    );
+
+    // If the stemming is not enabled, there is nothing to extract.
+    if stemmers.is_empty() {
+        return sorter_into_reader(stem_word_docids_sorter, indexer);
+    }
+
+    let mut key_buffer = Vec::new();
+    let mut cursor = docid_word_positions.into_cursor()?;
+    while let Some((key, _positions)) = cursor.move_on_next()? {
+        let (document_id_bytes, word_bytes) = try_split_array_at(key)
+            .ok_or(SerializationError::Decoding { db_name: Some(DOCID_WORD_POSITIONS) })?;
+        let document_id = DocumentId::from_be_bytes(document_id_bytes);
+
+        let word = std::str::from_utf8(word_bytes)?;
+        for stem in stemmers.stems(word) {
+            key_buffer.clear();
+            key_buffer.extend_from_slice(stem.as_bytes());
+            key_buffer.push(0);
+            key_buffer.extend_from_slice(word_bytes);
+            stem_word_docids_sorter.insert(&key_buffer, document_id.to_ne_bytes())?;
+        }
+    }
+
+    sorter_into_reader(stem_word_docids_sorter, indexer)
+}
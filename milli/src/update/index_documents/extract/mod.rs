@@ -4,14 +4,18 @@ mod extract_facet_string_docids;
 mod extract_fid_docid_facet_values;
 mod extract_fid_word_count_docids;
 mod extract_geo_points;
+mod extract_ngram_word_docids;
+mod extract_phonetic_word_docids;
+mod extract_stem_word_docids;
 mod extract_word_docids;
 mod extract_word_fid_docids;
 mod extract_word_pair_proximity_docids;
 mod extract_word_position_docids;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 
+use charabia::{Language, Script};
 use crossbeam_channel::Sender;
 use log::debug;
 use rayon::prelude::*;
@@ -22,16 +26,52 @@ use self::extract_facet_string_docids::extract_facet_string_docids;
 use self::extract_fid_docid_facet_values::{extract_fid_docid_facet_values, ExtractedFacetValues};
 use self::extract_fid_word_count_docids::extract_fid_word_count_docids;
 use self::extract_geo_points::extract_geo_points;
+use self::extract_ngram_word_docids::extract_ngram_word_docids;
+use self::extract_phonetic_word_docids::extract_phonetic_word_docids;
+use self::extract_stem_word_docids::extract_stem_word_docids;
 use self::extract_word_docids::extract_word_docids;
 use self::extract_word_fid_docids::extract_word_fid_docids;
 use self::extract_word_pair_proximity_docids::extract_word_pair_proximity_docids;
 use self::extract_word_position_docids::extract_word_position_docids;
 use super::helpers::{
-    as_cloneable_grenad, merge_cbo_roaring_bitmaps, merge_roaring_bitmaps, CursorClonableMmap,
-    GrenadParameters, MergeFn, MergeableReader,
+    as_cloneable_grenad, concat_u32s_array, merge_cbo_roaring_bitmaps, merge_roaring_bitmaps,
+    CursorClonableMmap, GrenadParameters, MergeFn, MergeableReader,
 };
 use super::{helpers, TypedChunk};
-use crate::{FieldId, Result};
+use crate::{FieldId, Result, Stemmers};
+
+/// The facet values extracted from a chunk of documents that must be merged with the other chunks:
+/// the docid_fid_facet_numbers, docid_fid_facet_strings, fid_facet_is_null_docids,
+/// fid_facet_is_empty_docids, fid_facet_exists_docids, fid_facet_is_string_docids
+/// and fid_facet_size_docids chunks.
+type ExtractedFacetValuesChunk = (
+    grenad::Reader<CursorClonableMmap>,
+    (
+        grenad::Reader<CursorClonableMmap>,
+        (
+            grenad::Reader<File>,
+            (
+                grenad::Reader<File>,
+                (grenad::Reader<File>, (grenad::Reader<File>, grenad::Reader<File>)),
+            ),
+        ),
+    ),
+);
+
+/// The [`ExtractedFacetValuesChunk`]s of all the chunks of documents, collected by database.
+type ExtractedFacetValuesChunks = (
+    Vec<grenad::Reader<CursorClonableMmap>>,
+    (
+        Vec<grenad::Reader<CursorClonableMmap>>,
+        (
+            Vec<grenad::Reader<File>>,
+            (
+                Vec<grenad::Reader<File>>,
+                (Vec<grenad::Reader<File>>, (Vec<grenad::Reader<File>>, Vec<grenad::Reader<File>>)),
+            ),
+        ),
+    ),
+);
 
 /// Extract data for each databases from obkv documents in parallel.
 /// Send data in grenad file over provided Sender.
@@ -43,11 +83,21 @@ pub(crate) fn data_from_obkv_documents(
     lmdb_writer_sx: Sender<Result<TypedChunk>>,
     searchable_fields: Option<HashSet<FieldId>>,
     faceted_fields: HashSet<FieldId>,
+    element_faceted_fields: HashMap<FieldId, Vec<(String, FieldId)>>,
+    hierarchical_facets: HashMap<FieldId, String>,
+    expiry_field_id: Option<FieldId>,
     primary_key_id: FieldId,
     geo_fields_ids: Option<(FieldId, FieldId)>,
     stop_words: Option<fst::Set<&[u8]>>,
+    allowed_separators: Option<&[&str]>,
+    dictionary: Option<&[&str]>,
+    localized_attributes: HashMap<FieldId, HashMap<Script, Vec<Language>>>,
     max_positions_per_attributes: Option<u32>,
     exact_attributes: HashSet<FieldId>,
+    infix_attributes: HashSet<FieldId>,
+    phonetic_attributes: HashSet<FieldId>,
+    stemmers: Stemmers,
+    in_place_documents: Option<(grenad::Reader<File>, grenad::Reader<File>)>,
 ) -> Result<()> {
     original_obkv_chunks
         .par_bridge()
@@ -56,86 +106,56 @@ pub(crate) fn data_from_obkv_documents(
         })
         .collect::<Result<()>>()?;
 
-    #[allow(clippy::type_complexity)]
-    let result: Result<(Vec<_>, (Vec<_>, (Vec<_>, (Vec<_>, (Vec<_>, Vec<_>)))))> =
-        flattened_obkv_chunks
-            .par_bridge()
-            .map(|flattened_obkv_chunks| {
-                send_and_extract_flattened_documents_data(
-                    flattened_obkv_chunks,
-                    indexer,
-                    lmdb_writer_sx.clone(),
-                    &searchable_fields,
-                    &faceted_fields,
-                    primary_key_id,
-                    geo_fields_ids,
-                    &stop_words,
-                    max_positions_per_attributes,
-                )
-            })
-            .collect();
-
-    let (
-        docid_word_positions_chunks,
-        (
-            docid_fid_facet_numbers_chunks,
-            (
-                docid_fid_facet_strings_chunks,
-                (
-                    facet_is_null_docids_chunks,
-                    (facet_is_empty_docids_chunks, facet_exists_docids_chunks),
-                ),
-            ),
-        ),
-    ) = result?;
-
-    // merge facet_exists_docids and send them as a typed chunk
-    {
-        let lmdb_writer_sx = lmdb_writer_sx.clone();
-        rayon::spawn(move || {
-            debug!("merge {} database", "facet-id-exists-docids");
-            match facet_exists_docids_chunks.merge(merge_cbo_roaring_bitmaps, &indexer) {
-                Ok(reader) => {
-                    let _ = lmdb_writer_sx.send(Ok(TypedChunk::FieldIdFacetExistsDocids(reader)));
-                }
-                Err(e) => {
-                    let _ = lmdb_writer_sx.send(Err(e));
-                }
-            }
-        });
-    }
-
-    // merge facet_is_null_docids and send them as a typed chunk
-    {
-        let lmdb_writer_sx = lmdb_writer_sx.clone();
-        rayon::spawn(move || {
-            debug!("merge {} database", "facet-id-is-null-docids");
-            match facet_is_null_docids_chunks.merge(merge_cbo_roaring_bitmaps, &indexer) {
-                Ok(reader) => {
-                    let _ = lmdb_writer_sx.send(Ok(TypedChunk::FieldIdFacetIsNullDocids(reader)));
-                }
-                Err(e) => {
-                    let _ = lmdb_writer_sx.send(Err(e));
-                }
-            }
-        });
+    let result: Result<(Vec<_>, ExtractedFacetValuesChunks)> = flattened_obkv_chunks
+        .par_bridge()
+        .map(|flattened_obkv_chunks| {
+            send_and_extract_flattened_documents_data(
+                flattened_obkv_chunks,
+                indexer,
+                lmdb_writer_sx.clone(),
+                &searchable_fields,
+                &faceted_fields,
+                &element_faceted_fields,
+                &hierarchical_facets,
+                expiry_field_id,
+                primary_key_id,
+                geo_fields_ids,
+                &stop_words,
+                allowed_separators,
+                dictionary,
+                &localized_attributes,
+                max_positions_per_attributes,
+            )
+        })
+        .collect();
+
+    let (mut docid_word_positions_chunks, facet_values_chunks) = result?;
+
+    // Only the changed searchable fields of the documents updated in place are tokenized, their
+    // words are merged with the stored words of their unchanged fields.
+    if let Some((searchable_documents, kept_word_positions)) = in_place_documents {
+        let (_, changed_word_positions, script_language_pair) = extract_docid_word_positions(
+            searchable_documents,
+            indexer,
+            &searchable_fields,
+            stop_words.as_ref(),
+            allowed_separators,
+            dictionary,
+            &localized_attributes,
+            max_positions_per_attributes,
+        )?;
+        let _ = lmdb_writer_sx.send(Ok(TypedChunk::ScriptLanguageDocids(script_language_pair)));
+
+        let docid_word_positions_chunk =
+            vec![changed_word_positions, kept_word_positions].merge(concat_u32s_array, &indexer)?;
+        let docid_word_positions_chunk =
+            unsafe { as_cloneable_grenad(&docid_word_positions_chunk)? };
+        let _ = lmdb_writer_sx
+            .send(Ok(TypedChunk::DocidWordPositions(docid_word_positions_chunk.clone())));
+        docid_word_positions_chunks.push(docid_word_positions_chunk);
     }
 
-    // merge facet_is_empty_docids and send them as a typed chunk
-    {
-        let lmdb_writer_sx = lmdb_writer_sx.clone();
-        rayon::spawn(move || {
-            debug!("merge {} database", "facet-id-is-empty-docids");
-            match facet_is_empty_docids_chunks.merge(merge_cbo_roaring_bitmaps, &indexer) {
-                Ok(reader) => {
-                    let _ = lmdb_writer_sx.send(Ok(TypedChunk::FieldIdFacetIsEmptyDocids(reader)));
-                }
-                Err(e) => {
-                    let _ = lmdb_writer_sx.send(Err(e));
-                }
-            }
-        });
-    }
+    spawn_facet_merge_tasks(facet_values_chunks, indexer, lmdb_writer_sx.clone());
 
     spawn_extraction_task::<_, _, Vec<grenad::Reader<File>>>(
         docid_word_positions_chunks.clone(),
@@ -180,15 +200,199 @@ pub(crate) fn data_from_obkv_documents(
         "word-position-docids",
     );
     spawn_extraction_task::<_, _, Vec<grenad::Reader<File>>>(
-        docid_word_positions_chunks,
+        docid_word_positions_chunks.clone(),
+        indexer,
+        lmdb_writer_sx.clone(),
+        move |doc_word_pos, indexer| {
+            extract_ngram_word_docids(doc_word_pos, indexer, &infix_attributes)
+        },
+        merge_cbo_roaring_bitmaps,
+        TypedChunk::NgramWordDocids,
+        "ngram-word-docids",
+    );
+    spawn_extraction_task::<_, _, Vec<grenad::Reader<File>>>(
+        docid_word_positions_chunks.clone(),
+        indexer,
+        lmdb_writer_sx.clone(),
+        move |doc_word_pos, indexer| {
+            extract_phonetic_word_docids(doc_word_pos, indexer, &phonetic_attributes)
+        },
+        merge_cbo_roaring_bitmaps,
+        TypedChunk::PhoneticWordDocids,
+        "phonetic-word-docids",
+    );
+    spawn_extraction_task::<_, _, Vec<grenad::Reader<File>>>(
+        docid_word_positions_chunks.clone(),
         indexer,
         lmdb_writer_sx.clone(),
+        move |doc_word_pos, indexer| extract_stem_word_docids(doc_word_pos, indexer, &stemmers),
+        merge_cbo_roaring_bitmaps,
+        TypedChunk::StemWordDocids,
+        "stem-word-docids",
+    );
+    spawn_extraction_task::<_, _, Vec<grenad::Reader<File>>>(
+        docid_word_positions_chunks,
+        indexer,
+        lmdb_writer_sx,
         extract_word_fid_docids,
         merge_cbo_roaring_bitmaps,
         TypedChunk::WordFidDocids,
         "word-fid-docids",
     );
 
+    Ok(())
+}
+
+/// Extract the data of the word databases from a chunk of docid word positions,
+/// the stored words of the documents updated in place that must be removed.
+pub(crate) fn word_data_from_docid_word_positions(
+    docid_word_positions_chunk: grenad::Reader<File>,
+    indexer: GrenadParameters,
+    exact_attributes: &HashSet<FieldId>,
+    infix_attributes: &HashSet<FieldId>,
+    phonetic_attributes: &HashSet<FieldId>,
+    stemmers: &Stemmers,
+) -> Result<Vec<TypedChunk>> {
+    let chunk = unsafe { as_cloneable_grenad(&docid_word_positions_chunk)? };
+
+    let (word_docids_reader, exact_word_docids_reader) =
+        extract_word_docids(chunk.clone(), indexer, exact_attributes)?;
+
+    Ok(vec![
+        TypedChunk::WordDocids { word_docids_reader, exact_word_docids_reader },
+        TypedChunk::WordPairProximityDocids(extract_word_pair_proximity_docids(
+            chunk.clone(),
+            indexer,
+        )?),
+        TypedChunk::FieldIdWordcountDocids(extract_fid_word_count_docids(chunk.clone(), indexer)?),
+        TypedChunk::WordPositionDocids(extract_word_position_docids(chunk.clone(), indexer)?),
+        TypedChunk::WordFidDocids(extract_word_fid_docids(chunk.clone(), indexer)?),
+        TypedChunk::NgramWordDocids(extract_ngram_word_docids(
+            chunk.clone(),
+            indexer,
+            infix_attributes,
+        )?),
+        TypedChunk::PhoneticWordDocids(extract_phonetic_word_docids(
+            chunk.clone(),
+            indexer,
+            phonetic_attributes,
+        )?),
+        TypedChunk::StemWordDocids(extract_stem_word_docids(chunk, indexer, stemmers)?),
+    ])
+}
+
+/// Extract the data of the facet databases only from obkv documents in parallel,
+/// leaving the word databases untouched.
+/// Send data in grenad file over provided Sender.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn facet_data_from_obkv_documents(
+    flattened_obkv_chunks: impl Iterator<Item = Result<grenad::Reader<File>>> + Send,
+    indexer: GrenadParameters,
+    lmdb_writer_sx: Sender<Result<TypedChunk>>,
+    faceted_fields: HashSet<FieldId>,
+    element_faceted_fields: HashMap<FieldId, Vec<(String, FieldId)>>,
+    hierarchical_facets: HashMap<FieldId, String>,
+    expiry_field_id: Option<FieldId>,
+    primary_key_id: FieldId,
+    geo_fields_ids: Option<(FieldId, FieldId)>,
+) -> Result<()> {
+    let result: Result<ExtractedFacetValuesChunks> = flattened_obkv_chunks
+        .par_bridge()
+        .map(|flattened_documents_chunk| {
+            let flattened_documents_chunk =
+                flattened_documents_chunk.and_then(|c| unsafe { as_cloneable_grenad(&c) })?;
+
+            if let Some(geo_fields_ids) = geo_fields_ids {
+                spawn_geo_points_extraction(
+                    flattened_documents_chunk.clone(),
+                    indexer,
+                    lmdb_writer_sx.clone(),
+                    primary_key_id,
+                    geo_fields_ids,
+                );
+            }
+
+            send_and_extract_facet_values(
+                flattened_documents_chunk,
+                indexer,
+                &lmdb_writer_sx,
+                &faceted_fields,
+                &element_faceted_fields,
+                &hierarchical_facets,
+                expiry_field_id,
+            )
+        })
+        .collect();
+
+    spawn_facet_merge_tasks(result?, indexer, lmdb_writer_sx);
+
+    Ok(())
+}
+
+/// Spawn the tasks merging the facet values extracted from every chunk of documents.
+/// The merged databases are sent as TypedChunks into lmdb_writer_sx.
+fn spawn_facet_merge_tasks(
+    facet_values_chunks: ExtractedFacetValuesChunks,
+    indexer: GrenadParameters,
+    lmdb_writer_sx: Sender<Result<TypedChunk>>,
+) {
+    let (
+        docid_fid_facet_numbers_chunks,
+        (
+            docid_fid_facet_strings_chunks,
+            (
+                facet_is_null_docids_chunks,
+                (
+                    facet_is_empty_docids_chunks,
+                    (
+                        facet_exists_docids_chunks,
+                        (facet_is_string_docids_chunks, facet_size_docids_chunks),
+                    ),
+                ),
+            ),
+        ),
+    ) = facet_values_chunks;
+
+    spawn_merge_task(
+        facet_exists_docids_chunks,
+        indexer,
+        lmdb_writer_sx.clone(),
+        TypedChunk::FieldIdFacetExistsDocids,
+        "facet-id-exists-docids",
+    );
+
+    spawn_merge_task(
+        facet_is_null_docids_chunks,
+        indexer,
+        lmdb_writer_sx.clone(),
+        TypedChunk::FieldIdFacetIsNullDocids,
+        "facet-id-is-null-docids",
+    );
+
+    spawn_merge_task(
+        facet_is_string_docids_chunks,
+        indexer,
+        lmdb_writer_sx.clone(),
+        TypedChunk::FieldIdFacetIsStringDocids,
+        "facet-id-is-string-docids",
+    );
+
+    spawn_merge_task(
+        facet_size_docids_chunks,
+        indexer,
+        lmdb_writer_sx.clone(),
+        TypedChunk::FieldIdFacetSizeDocids,
+        "facet-id-size-docids",
+    );
+
+    spawn_merge_task(
+        facet_is_empty_docids_chunks,
+        indexer,
+        lmdb_writer_sx.clone(),
+        TypedChunk::FieldIdFacetIsEmptyDocids,
+        "facet-id-is-empty-docids",
+    );
+
     spawn_extraction_task::<_, _, Vec<grenad::Reader<File>>>(
         docid_fid_facet_strings_chunks,
         indexer,
@@ -208,8 +412,29 @@ pub(crate) fn data_from_obkv_documents(
         TypedChunk::FieldIdFacetNumberDocids,
         "field-id-facet-number-docids",
     );
+}
 
-    Ok(())
+/// Spawn a new task to merge the chunks of a database using the cbo roaring bitmaps merge function.
+/// The merged chunk is serialized as TypedChunk using the serialize_fn
+/// and sent into lmdb_writer_sx.
+fn spawn_merge_task(
+    chunks: Vec<grenad::Reader<File>>,
+    indexer: GrenadParameters,
+    lmdb_writer_sx: Sender<Result<TypedChunk>>,
+    serialize_fn: fn(grenad::Reader<File>) -> TypedChunk,
+    name: &'static str,
+) {
+    rayon::spawn(move || {
+        debug!("merge {} database", name);
+        match chunks.merge(merge_cbo_roaring_bitmaps, &indexer) {
+            Ok(reader) => {
+                let _ = lmdb_writer_sx.send(Ok(serialize_fn(reader)));
+            }
+            Err(e) => {
+                let _ = lmdb_writer_sx.send(Err(e));
+            }
+        }
+    });
 }
 
 /// Spawn a new task to extract data for a specific DB using extract_fn.
@@ -268,43 +493,38 @@ fn send_original_documents_data(
 /// - docid_word_positions
 /// - docid_fid_facet_numbers
 /// - docid_fid_facet_strings
+/// - docid_fid_facet_element_numbers
+/// - docid_fid_facet_element_strings
 /// - docid_fid_facet_exists
 #[allow(clippy::too_many_arguments)]
-#[allow(clippy::type_complexity)]
 fn send_and_extract_flattened_documents_data(
     flattened_documents_chunk: Result<grenad::Reader<File>>,
     indexer: GrenadParameters,
     lmdb_writer_sx: Sender<Result<TypedChunk>>,
     searchable_fields: &Option<HashSet<FieldId>>,
     faceted_fields: &HashSet<FieldId>,
+    element_faceted_fields: &HashMap<FieldId, Vec<(String, FieldId)>>,
+    hierarchical_facets: &HashMap<FieldId, String>,
+    expiry_field_id: Option<FieldId>,
     primary_key_id: FieldId,
     geo_fields_ids: Option<(FieldId, FieldId)>,
     stop_words: &Option<fst::Set<&[u8]>>,
+    allowed_separators: Option<&[&str]>,
+    dictionary: Option<&[&str]>,
+    localized_attributes: &HashMap<FieldId, HashMap<Script, Vec<Language>>>,
     max_positions_per_attributes: Option<u32>,
-) -> Result<(
-    grenad::Reader<CursorClonableMmap>,
-    (
-        grenad::Reader<CursorClonableMmap>,
-        (
-            grenad::Reader<CursorClonableMmap>,
-            (grenad::Reader<File>, (grenad::Reader<File>, grenad::Reader<File>)),
-        ),
-    ),
-)> {
+) -> Result<(grenad::Reader<CursorClonableMmap>, ExtractedFacetValuesChunk)> {
     let flattened_documents_chunk =
         flattened_documents_chunk.and_then(|c| unsafe { as_cloneable_grenad(&c) })?;
 
     if let Some(geo_fields_ids) = geo_fields_ids {
-        let documents_chunk_cloned = flattened_documents_chunk.clone();
-        let lmdb_writer_sx_cloned = lmdb_writer_sx.clone();
-        rayon::spawn(move || {
-            let result =
-                extract_geo_points(documents_chunk_cloned, indexer, primary_key_id, geo_fields_ids);
-            let _ = match result {
-                Ok(geo_points) => lmdb_writer_sx_cloned.send(Ok(TypedChunk::GeoPoints(geo_points))),
-                Err(error) => lmdb_writer_sx_cloned.send(Err(error)),
-            };
-        });
+        spawn_geo_points_extraction(
+            flattened_documents_chunk.clone(),
+            indexer,
+            lmdb_writer_sx.clone(),
+            primary_key_id,
+            geo_fields_ids,
+        );
     }
 
     let (docid_word_positions_chunk, docid_fid_facet_values_chunks): (Result<_>, Result<_>) =
@@ -316,6 +536,9 @@ fn send_and_extract_flattened_documents_data(
                         indexer,
                         searchable_fields,
                         stop_words.as_ref(),
+                        allowed_separators,
+                        dictionary,
+                        localized_attributes,
                         max_positions_per_attributes,
                     )?;
 
@@ -334,46 +557,111 @@ fn send_and_extract_flattened_documents_data(
                 Ok(docid_word_positions_chunk)
             },
             || {
-                let ExtractedFacetValues {
-                    docid_fid_facet_numbers_chunk,
-                    docid_fid_facet_strings_chunk,
-                    fid_facet_is_null_docids_chunk,
-                    fid_facet_is_empty_docids_chunk,
-                    fid_facet_exists_docids_chunk,
-                } = extract_fid_docid_facet_values(
+                send_and_extract_facet_values(
                     flattened_documents_chunk.clone(),
                     indexer,
+                    &lmdb_writer_sx,
                     faceted_fields,
-                )?;
-
-                // send docid_fid_facet_numbers_chunk to DB writer
-                let docid_fid_facet_numbers_chunk =
-                    unsafe { as_cloneable_grenad(&docid_fid_facet_numbers_chunk)? };
-
-                let _ = lmdb_writer_sx.send(Ok(TypedChunk::FieldIdDocidFacetNumbers(
-                    docid_fid_facet_numbers_chunk.clone(),
-                )));
+                    element_faceted_fields,
+                    hierarchical_facets,
+                    expiry_field_id,
+                )
+            },
+        );
 
-                // send docid_fid_facet_strings_chunk to DB writer
-                let docid_fid_facet_strings_chunk =
-                    unsafe { as_cloneable_grenad(&docid_fid_facet_strings_chunk)? };
+    Ok((docid_word_positions_chunk?, docid_fid_facet_values_chunks?))
+}
 
-                let _ = lmdb_writer_sx.send(Ok(TypedChunk::FieldIdDocidFacetStrings(
-                    docid_fid_facet_strings_chunk.clone(),
-                )));
+/// Spawn a new task to extract the geo points of a chunk of documents
+/// and send them into lmdb_writer_sx.
+fn spawn_geo_points_extraction(
+    flattened_documents_chunk: grenad::Reader<CursorClonableMmap>,
+    indexer: GrenadParameters,
+    lmdb_writer_sx: Sender<Result<TypedChunk>>,
+    primary_key_id: FieldId,
+    geo_fields_ids: (FieldId, FieldId),
+) {
+    rayon::spawn(move || {
+        let result =
+            extract_geo_points(flattened_documents_chunk, indexer, primary_key_id, geo_fields_ids);
+        let _ = match result {
+            Ok(geo_points) => lmdb_writer_sx.send(Ok(TypedChunk::GeoPoints(geo_points))),
+            Err(error) => lmdb_writer_sx.send(Err(error)),
+        };
+    });
+}
 
-                Ok((
-                    docid_fid_facet_numbers_chunk,
+/// Extract the facet values of a chunk of documents and send into lmdb_writer_sx:
+/// - docid_fid_facet_numbers
+/// - docid_fid_facet_strings
+/// - docid_fid_facet_element_numbers
+/// - docid_fid_facet_element_strings
+///
+/// The chunks that must be merged with the ones of the other documents are returned.
+fn send_and_extract_facet_values(
+    flattened_documents_chunk: grenad::Reader<CursorClonableMmap>,
+    indexer: GrenadParameters,
+    lmdb_writer_sx: &Sender<Result<TypedChunk>>,
+    faceted_fields: &HashSet<FieldId>,
+    element_faceted_fields: &HashMap<FieldId, Vec<(String, FieldId)>>,
+    hierarchical_facets: &HashMap<FieldId, String>,
+    expiry_field_id: Option<FieldId>,
+) -> Result<ExtractedFacetValuesChunk> {
+    let ExtractedFacetValues {
+        docid_fid_facet_numbers_chunk,
+        docid_fid_facet_strings_chunk,
+        fid_facet_is_null_docids_chunk,
+        fid_facet_is_empty_docids_chunk,
+        fid_facet_exists_docids_chunk,
+        fid_facet_is_string_docids_chunk,
+        fid_facet_size_docids_chunk,
+        fid_docid_facet_element_numbers_chunk,
+        fid_docid_facet_element_strings_chunk,
+    } = extract_fid_docid_facet_values(
+        flattened_documents_chunk,
+        indexer,
+        faceted_fields,
+        element_faceted_fields,
+        hierarchical_facets,
+        expiry_field_id,
+    )?;
+
+    // send the facet values of the arrays elements to DB writer
+    let _ = lmdb_writer_sx.send(Ok(TypedChunk::FieldIdDocidFacetElementNumbers(
+        fid_docid_facet_element_numbers_chunk,
+    )));
+    let _ = lmdb_writer_sx.send(Ok(TypedChunk::FieldIdDocidFacetElementStrings(
+        fid_docid_facet_element_strings_chunk,
+    )));
+
+    // send docid_fid_facet_numbers_chunk to DB writer
+    let docid_fid_facet_numbers_chunk =
+        unsafe { as_cloneable_grenad(&docid_fid_facet_numbers_chunk)? };
+
+    let _ = lmdb_writer_sx
+        .send(Ok(TypedChunk::FieldIdDocidFacetNumbers(docid_fid_facet_numbers_chunk.clone())));
+
+    // send docid_fid_facet_strings_chunk to DB writer
+    let docid_fid_facet_strings_chunk =
+        unsafe { as_cloneable_grenad(&docid_fid_facet_strings_chunk)? };
+
+    let _ = lmdb_writer_sx
+        .send(Ok(TypedChunk::FieldIdDocidFacetStrings(docid_fid_facet_strings_chunk.clone())));
+
+    Ok((
+        docid_fid_facet_numbers_chunk,
+        (
+            docid_fid_facet_strings_chunk,
+            (
+                fid_facet_is_null_docids_chunk,
+                (
+                    fid_facet_is_empty_docids_chunk,
                     (
-                        docid_fid_facet_strings_chunk,
-                        (
-                            fid_facet_is_null_docids_chunk,
-                            (fid_facet_is_empty_docids_chunk, fid_facet_exists_docids_chunk),
-                        ),
+                        fid_facet_exists_docids_chunk,
+                        (fid_facet_is_string_docids_chunk, fid_facet_size_docids_chunk),
                     ),
-                ))
-            },
-        );
-
-    Ok((docid_word_positions_chunk?, docid_fid_facet_values_chunks?))
+                ),
+            ),
+        ),
+    ))
 }
@@ -6,6 +6,7 @@ use roaring::RoaringBitmap;
 
 use super::read_u32_ne_bytes;
 use crate::heed_codec::CboRoaringBitmapCodec;
+use crate::update::index_documents::document_edit::{apply_field_edits, concat_field_edits};
 use crate::update::index_documents::transform::Operation;
 use crate::Result;
 
@@ -109,6 +110,54 @@ pub fn merge_obkvs_and_operations<'a>(
     Ok(Cow::from(ret))
 }
 
+/// Merge all the obkvs in the order we see them, applying the field edits on top
+/// of the latest version of the document.
+///
+/// When the base document isn't part of the values, the field edits are concatenated
+/// and will be applied once merged with the base document.
+pub fn merge_obkvs_and_edit_operations<'a>(
+    _key: &[u8],
+    obkvs: &[Cow<'a, [u8]>],
+) -> Result<Cow<'a, [u8]>> {
+    // [add, edit, delete, add, edit]
+    // we can ignore everything that happened before the last delete.
+    let starting_position =
+        obkvs.iter().rposition(|obkv| obkv[0] == Operation::Deletion as u8).unwrap_or(0);
+
+    // [add, edit, delete]
+    // if the last operation was a deletion then we simply return the deletion
+    if starting_position == obkvs.len() - 1 && obkvs.last().unwrap()[0] == Operation::Deletion as u8
+    {
+        return Ok(obkvs[obkvs.len() - 1].clone());
+    }
+
+    let mut operation = None;
+    let mut acc = Vec::new();
+    let mut buffer = Vec::new();
+    for current in &obkvs[starting_position..] {
+        let (kind, obkv) = (current[0], obkv::KvReader::new(&current[1..]));
+        if kind == Operation::Addition as u8 {
+            acc.clear();
+            acc.extend_from_slice(&current[1..]);
+            operation = Some(Operation::Addition);
+        } else if kind == Operation::Edition as u8 {
+            match operation {
+                Some(Operation::Addition) => {
+                    apply_field_edits(obkv::KvReader::new(&acc), obkv, &mut buffer)?
+                }
+                _ => {
+                    concat_field_edits(obkv::KvReader::new(&acc), obkv, &mut buffer)?;
+                    operation = Some(Operation::Edition);
+                }
+            }
+            std::mem::swap(&mut acc, &mut buffer);
+        }
+    }
+
+    acc.insert(0, operation.unwrap_or(Operation::Deletion) as u8);
+    Ok(Cow::from(acc))
+}
+
 pub fn merge_cbo_roaring_bitmaps<'a>(
     _key: &[u8],
     values: &[Cow<'a, [u8]>],
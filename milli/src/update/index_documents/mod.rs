@@ -1,25 +1,29 @@
+mod document_edit;
 mod enrich;
 mod extract;
 mod helpers;
 mod transform;
 mod typed_chunk;
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
 use std::io::{Cursor, Read, Seek};
 use std::iter::FromIterator;
 use std::num::NonZeroU32;
 use std::result::Result as StdResult;
 
+use charabia::{Language, Script};
 use crossbeam_channel::{Receiver, Sender};
-use heed::types::Str;
+use heed::types::{ByteSlice, Str};
 use heed::Database;
 use log::debug;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 use slice_group_by::GroupBy;
-use typed_chunk::{write_typed_chunk_into_index, TypedChunk};
+use typed_chunk::{remove_typed_chunk_from_index, write_typed_chunk_into_index, TypedChunk};
 
-use self::enrich::enrich_documents_batch;
+pub use self::document_edit::{patch_to_operations, EditOperation, FieldEdit};
+use self::enrich::{apply_ingestion_rules, enrich_documents_batch};
 pub use self::enrich::{
     extract_finite_float_from_value, validate_document_id, validate_document_id_value,
     validate_geo_from_json, DocumentId,
@@ -30,7 +34,7 @@ pub use self::helpers::{
     sorter_into_lmdb_database, valid_lmdb_key, writer_into_reader, ClonableMmap, MergeFn,
 };
 use self::helpers::{grenad_obkv_into_chunks, GrenadParameters};
-pub use self::transform::{Transform, TransformOutput};
+pub use self::transform::{InPlaceUpdates, Transform, TransformOutput};
 use crate::documents::{obkv_to_object, DocumentsBatchReader};
 use crate::error::{Error, InternalError, UserError};
 pub use crate::update::index_documents::helpers::CursorClonableMmap;
@@ -38,11 +42,15 @@ use crate::update::{
     self, DeletionStrategy, IndexerConfig, PrefixWordPairsProximityDocids, UpdateIndexingStep,
     WordPrefixDocids, WordPrefixIntegerDocids, WordsPrefixesFst,
 };
-use crate::{Index, Result, RoaringBitmapCodec};
+use crate::{
+    is_faceted_by, locales_allow_list, parse_locale, relative_from_absolute_position, FieldId,
+    FieldsIdsMap, Index, InvalidDocument, Result, RoaringBitmapCodec, Stemmers,
+};
 
 static MERGED_DATABASE_COUNT: usize = 7;
 static PREFIX_DATABASE_COUNT: usize = 5;
 static TOTAL_POSTING_DATABASE_COUNT: usize = MERGED_DATABASE_COUNT + PREFIX_DATABASE_COUNT;
+static FACET_DATABASE_COUNT: usize = 7;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DocumentAdditionResult {
@@ -50,6 +58,9 @@ pub struct DocumentAdditionResult {
     pub indexed_documents: u64,
     /// The total number of documents in the index after the update
     pub number_of_documents: u64,
+    /// The edited documents that were skipped because they did not match the schema once
+    /// edited, by index of the call to [`IndexDocuments::add_documents`] that last edited them.
+    pub invalid_edited_documents: BTreeMap<usize, Vec<InvalidDocument>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -62,6 +73,10 @@ pub enum IndexDocumentsMethod {
     /// Merge the previous version of the document with the new version,
     /// replacing old attributes values with the new ones and add the new attributes.
     UpdateDocuments,
+
+    /// Apply field-level operations on the previous version of the document,
+    /// the documents that don't exist yet are ignored.
+    EditDocuments,
 }
 
 impl Default for IndexDocumentsMethod {
@@ -80,6 +95,7 @@ pub struct IndexDocuments<'t, 'u, 'i, 'a, FP, FA> {
     should_abort: FA,
     added_documents: u64,
     deleted_documents: u64,
+    added_batches: usize,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -93,6 +109,16 @@ pub struct IndexDocumentsConfig {
     pub autogenerate_docids: bool,
 }
 
+/// The fields the facet values are extracted from, see
+/// [`IndexDocuments::facet_extraction_settings`].
+struct FacetExtractionSettings {
+    faceted_fields: HashSet<FieldId>,
+    element_faceted_fields: HashMap<FieldId, Vec<(String, FieldId)>>,
+    hierarchical_facets: HashMap<FieldId, String>,
+    expiry_field_id: Option<FieldId>,
+    geo_fields_ids: Option<(FieldId, FieldId)>,
+}
+
 impl<'t, 'u, 'i, 'a, FP, FA> IndexDocuments<'t, 'u, 'i, 'a, FP, FA>
 where
     FP: Fn(UpdateIndexingStep) + Sync,
@@ -124,6 +150,7 @@ where
             index,
             added_documents: 0,
             deleted_documents: 0,
+            added_batches: 0,
         })
     }
 
@@ -137,11 +164,27 @@ where
         mut self,
         reader: DocumentsBatchReader<R>,
     ) -> Result<(Self, StdResult<u64, UserError>)> {
+        self.added_batches += 1;
+
         // Early return when there is no document to add
         if reader.is_empty() {
             return Ok((self, Ok(0)));
         }
 
+        let ingestion_rules = self.index.ingestion_rules(self.wtxn)?;
+        if ingestion_rules.is_empty() {
+            self.add_documents_batch(reader)
+        } else {
+            let reader =
+                apply_ingestion_rules(&ingestion_rules, self.config.update_method, reader)?;
+            self.add_documents_batch(reader)
+        }
+    }
+
+    fn add_documents_batch<R: Read + Seek>(
+        mut self,
+        reader: DocumentsBatchReader<R>,
+    ) -> Result<(Self, StdResult<u64, UserError>)> {
         // We check for user errors in this validator and if there is one, we can return
         // the `IndexDocument` struct as it is valid to send more documents into it.
         // However, if there is an internal error we throw it away!
@@ -149,6 +192,7 @@ where
             self.wtxn,
             self.index,
             self.config.autogenerate_docids,
+            self.config.update_method,
             reader,
         )? {
             Ok(reader) => reader,
@@ -158,6 +202,7 @@ where
         let indexed_documents =
             self.transform.as_mut().expect("Invalid document addition state").read_documents(
                 enriched_documents_reader,
+                self.added_batches - 1,
                 self.wtxn,
                 &self.progress,
                 &self.should_abort,
@@ -168,6 +213,18 @@ where
         Ok((self, Ok(indexed_documents)))
     }
 
+    /// Returns the number of documents that were skipped so far because their version
+    /// was lower than the one of the stored documents or they did not match the schema.
+    pub fn skipped_documents(&self) -> u64 {
+        self.transform.as_ref().map_or(0, |transform| transform.skipped_documents as u64)
+    }
+
+    /// Returns the documents that were skipped so far because they did not match the schema,
+    /// along with the reason why they were skipped.
+    pub fn invalid_documents(&self) -> &[InvalidDocument] {
+        self.transform.as_ref().map_or(&[], |transform| &transform.invalid_documents)
+    }
+
     /// Remove a batch of documents from the current builder.
     ///
     /// Returns the number of documents deleted from the builder.
@@ -196,9 +253,13 @@ where
     pub fn execute(mut self) -> Result<DocumentAdditionResult> {
         if self.added_documents == 0 {
             let number_of_documents = self.index.number_of_documents(self.wtxn)?;
-            return Ok(DocumentAdditionResult { indexed_documents: 0, number_of_documents });
+            return Ok(DocumentAdditionResult {
+                indexed_documents: 0,
+                number_of_documents,
+                invalid_edited_documents: BTreeMap::new(),
+            });
         }
-        let output = self
+        let mut output = self
             .transform
             .take()
             .expect("Invalid document addition state")
@@ -220,14 +281,19 @@ where
         }
 
         let indexed_documents = output.documents_count as u64;
+        let invalid_edited_documents = std::mem::take(&mut output.invalid_edited_documents);
         let number_of_documents = self.execute_raw(output)?;
 
-        Ok(DocumentAdditionResult { indexed_documents, number_of_documents })
+        Ok(DocumentAdditionResult {
+            indexed_documents,
+            number_of_documents,
+            invalid_edited_documents,
+        })
     }
 
     /// Returns the total number of documents in the index after the update.
     #[logging_timer::time("IndexDocuments::{}")]
-    pub fn execute_raw(self, output: TransformOutput) -> Result<u64>
+    pub fn execute_raw(mut self, output: TransformOutput) -> Result<u64>
     where
         FP: Fn(UpdateIndexingStep) + Sync,
         FA: Fn() -> bool + Sync,
@@ -242,6 +308,8 @@ where
             documents_count,
             original_documents,
             flattened_documents,
+            mut in_place_updates,
+            invalid_edited_documents: _,
         } = output;
 
         // The fields_ids_map is put back to the store now so the rest of the transaction sees an
@@ -283,30 +351,39 @@ where
         let searchable_fields =
             self.index.searchable_fields_ids(self.wtxn)?.map(HashSet::from_iter);
         // get filterable fields for facet databases
-        let faceted_fields = self.index.faceted_fields_ids(self.wtxn)?;
-        // get the fid of the `_geo.lat` and `_geo.lng` fields.
-        let geo_fields_ids = match self.index.fields_ids_map(self.wtxn)?.id("_geo") {
-            Some(gfid) => {
-                let is_sortable = self.index.sortable_fields_ids(self.wtxn)?.contains(&gfid);
-                let is_filterable = self.index.filterable_fields_ids(self.wtxn)?.contains(&gfid);
-                // if `_geo` is faceted then we get the `lat` and `lng`
-                if is_sortable || is_filterable {
-                    let field_ids = self
-                        .index
-                        .fields_ids_map(self.wtxn)?
-                        .insert("_geo.lat")
-                        .zip(self.index.fields_ids_map(self.wtxn)?.insert("_geo.lng"))
-                        .ok_or(UserError::AttributeLimitReached)?;
-                    Some(field_ids)
-                } else {
-                    None
-                }
-            }
-            None => None,
-        };
-
-        let stop_words = self.index.stop_words(self.wtxn)?;
+        let FacetExtractionSettings {
+            faceted_fields,
+            element_faceted_fields,
+            hierarchical_facets,
+            expiry_field_id,
+            geo_fields_ids,
+        } = self.facet_extraction_settings(&fields_ids_map)?;
+
+        let separators = self.index.allowed_separators(self.wtxn)?;
+        let separators: Option<Vec<_>> =
+            separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        let dictionary = self.index.dictionary(self.wtxn)?;
+        let dictionary: Option<Vec<_>> =
+            dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+        // get the languages the tokenization of the localized attributes is restricted to,
+        // the nested fields of a localized attribute share its languages.
+        let localized_attributes = self.index.localized_attributes(self.wtxn)?;
+        let localized_attributes: HashMap<FieldId, HashMap<Script, Vec<Language>>> =
+            fields_ids_map
+                .iter()
+                .filter_map(|(field_id, name)| {
+                    let languages: Vec<_> = localized_attributes
+                        .iter()
+                        .filter(|(attribute, _)| is_faceted_by(name, attribute))
+                        .flat_map(|(_, locales)| locales.iter().filter_map(|l| parse_locale(l)))
+                        .collect();
+                    (!languages.is_empty()).then(|| (field_id, locales_allow_list(&languages)))
+                })
+                .collect();
+        let stemmers = Stemmers::new(&self.index.stemming(self.wtxn)?);
         let exact_attributes = self.index.exact_attributes_ids(self.wtxn)?;
+        let infix_attributes = self.index.infix_attributes_ids(self.wtxn)?;
+        let phonetic_attributes = self.index.phonetic_attributes_ids(self.wtxn)?;
 
         let pool_params = GrenadParameters {
             chunk_compression_type: self.indexer_config.chunk_compression_type,
@@ -318,6 +395,31 @@ where
             self.indexer_config.documents_chunk_size.unwrap_or(1024 * 1024 * 4); // 4MiB
         let max_positions_per_attributes = self.indexer_config.max_positions_per_attributes;
 
+        // The stored words of the documents updated in place whose searchable fields changed are
+        // removed, only the words of these fields are extracted again.
+        let mut in_place_documents = None;
+        if let Some(in_place_updates) = in_place_updates.as_mut() {
+            if let Some(searchable_documents) = in_place_updates.searchable_documents.take() {
+                let changed_fields_docids = nested_changed_fields_docids(
+                    &in_place_updates.changed_fields_docids,
+                    &fields_ids_map,
+                );
+                let kept_word_positions = self.remove_in_place_words(
+                    &in_place_updates.searchable_documents_ids,
+                    &changed_fields_docids,
+                    &exact_attributes,
+                    &infix_attributes,
+                    &phonetic_attributes,
+                    &stemmers,
+                    pool_params,
+                )?;
+                in_place_documents =
+                    Some((grenad::Reader::new(searchable_documents)?, kept_word_positions));
+            }
+        }
+
+        let stop_words = self.index.stop_words(self.wtxn)?;
+
         // Run extraction pipeline in parallel.
         pool.install(|| {
             // split obkv file into several chunks
@@ -338,11 +440,21 @@ where
                     lmdb_writer_sx.clone(),
                     searchable_fields,
                     faceted_fields,
+                    element_faceted_fields,
+                    hierarchical_facets,
+                    expiry_field_id,
                     primary_key_id,
                     geo_fields_ids,
                     stop_words,
+                    separators.as_deref(),
+                    dictionary.as_deref(),
+                    localized_attributes,
                     max_positions_per_attributes,
                     exact_attributes,
+                    infix_attributes,
+                    phonetic_attributes,
+                    stemmers,
+                    in_place_documents,
                 )
             });
 
@@ -438,6 +550,12 @@ where
             }
         }
 
+        // The documents updated in place are written and the facets of their changed fields are
+        // indexed again, the words of these fields are extracted along with the other documents.
+        if let Some(in_place_updates) = in_place_updates {
+            self.execute_in_place_updates(in_place_updates, &fields_ids_map, primary_key_id)?;
+        }
+
         // We write the field distribution into the main database
         self.index.put_field_distribution(self.wtxn, &field_distribution)?;
 
@@ -461,6 +579,321 @@ where
         Ok(all_documents_ids.len())
     }
 
+    /// Returns the faceted fields along with the faceted fields nested in arrays of objects,
+    /// the separators of the hierarchical facets and the fids of the `_geo` coordinates.
+    fn facet_extraction_settings(
+        &self,
+        fields_ids_map: &FieldsIdsMap,
+    ) -> Result<FacetExtractionSettings> {
+        let faceted_fields = self.index.faceted_fields_ids(self.wtxn)?;
+        // get the faceted fields nested in objects, grouped by the field containing them,
+        // to be able to filter on the elements of the arrays of objects.
+        let mut element_faceted_fields = HashMap::<FieldId, Vec<(String, FieldId)>>::new();
+        for &field_id in &faceted_fields {
+            if let Some(name) = fields_ids_map.name(field_id) {
+                for (dot_index, _) in name.match_indices('.') {
+                    if let Some(parent_id) = fields_ids_map.id(&name[..dot_index]) {
+                        let relative_name = name[dot_index + 1..].to_string();
+                        element_faceted_fields
+                            .entry(parent_id)
+                            .or_default()
+                            .push((relative_name, field_id));
+                    }
+                }
+            }
+        }
+        // get the separator of the levels of the hierarchical facets.
+        let hierarchical_facets: HashMap<FieldId, String> = self
+            .index
+            .hierarchical_facets(self.wtxn)?
+            .into_iter()
+            .filter_map(|(name, separator)| Some((fields_ids_map.id(&name)?, separator)))
+            .collect();
+        // get the fid of the expiry field, its RFC 3339 dates are indexed as timestamps.
+        let expiry_field_id = match self.index.expiry_field(self.wtxn)? {
+            Some(name) => fields_ids_map.id(name),
+            None => None,
+        };
+        // get the fid of the `_geo.lat` and `_geo.lng` fields.
+        let geo_fields_ids = match self.index.fields_ids_map(self.wtxn)?.id("_geo") {
+            Some(gfid) => {
+                let is_sortable = self.index.sortable_fields_ids(self.wtxn)?.contains(&gfid);
+                let is_filterable = self.index.filterable_fields_ids(self.wtxn)?.contains(&gfid);
+                // if `_geo` is faceted then we get the `lat` and `lng`
+                if is_sortable || is_filterable {
+                    let field_ids = self
+                        .index
+                        .fields_ids_map(self.wtxn)?
+                        .insert("_geo.lat")
+                        .zip(self.index.fields_ids_map(self.wtxn)?.insert("_geo.lng"))
+                        .ok_or(UserError::AttributeLimitReached)?;
+                    Some(field_ids)
+                } else {
+                    None
+                }
+            }
+            None => None,
+        };
+
+        Ok(FacetExtractionSettings {
+            faceted_fields,
+            element_faceted_fields,
+            hierarchical_facets,
+            expiry_field_id,
+            geo_fields_ids,
+        })
+    }
+
+    /// Only fills the facet databases with the flattened documents of the `TransformOutput`,
+    /// the documents and the word databases are left untouched.
+    ///
+    /// The facet databases must have been cleared beforehand,
+    /// see [`Transform::prepare_for_facets_reindexing`].
+    #[logging_timer::time("IndexDocuments::{}")]
+    pub(crate) fn execute_facets_raw(mut self, output: TransformOutput) -> Result<()>
+    where
+        FP: Fn(UpdateIndexingStep) + Sync,
+        FA: Fn() -> bool + Sync,
+    {
+        let TransformOutput { primary_key, fields_ids_map, flattened_documents, .. } = output;
+
+        self.index.put_fields_ids_map(self.wtxn, &fields_ids_map)?;
+
+        let primary_key_id = fields_ids_map.id(&primary_key).unwrap();
+        self.index_facet_values(flattened_documents, &fields_ids_map, primary_key_id, true)
+    }
+
+    /// Removes the stored words of the documents updated in place from the word databases,
+    /// along with the entries of their prefixes.
+    ///
+    /// Returns the stored word positions of their unchanged fields, they are merged with the word
+    /// positions of their changed fields instead of being extracted again.
+    #[allow(clippy::too_many_arguments)]
+    fn remove_in_place_words(
+        &mut self,
+        documents_ids: &RoaringBitmap,
+        changed_fields_docids: &HashMap<FieldId, RoaringBitmap>,
+        exact_attributes: &HashSet<FieldId>,
+        infix_attributes: &HashSet<FieldId>,
+        phonetic_attributes: &HashSet<FieldId>,
+        stemmers: &Stemmers,
+        pool_params: GrenadParameters,
+    ) -> Result<grenad::Reader<File>>
+    where
+        FP: Fn(UpdateIndexingStep) + Sync,
+        FA: Fn() -> bool + Sync,
+    {
+        let mut stored_word_positions = create_writer(
+            self.indexer_config.chunk_compression_type,
+            self.indexer_config.chunk_compression_level,
+            tempfile::tempfile()?,
+        );
+        let mut kept_word_positions = create_writer(
+            self.indexer_config.chunk_compression_type,
+            self.indexer_config.chunk_compression_level,
+            tempfile::tempfile()?,
+        );
+
+        let mut key_buffer = Vec::new();
+        let mut positions_buffer = Vec::new();
+        for docid in documents_ids {
+            let mut iter =
+                self.index.docid_word_positions.prefix_iter_mut(self.wtxn, &(docid, ""))?;
+            while let Some(result) = iter.next() {
+                let ((_docid, word), positions) = result?;
+                key_buffer.clear();
+                key_buffer.extend_from_slice(&docid.to_be_bytes());
+                key_buffer.extend_from_slice(word.as_bytes());
+
+                positions_buffer.clear();
+                positions_buffer.extend(positions.iter().flat_map(u32::to_ne_bytes));
+                stored_word_positions.insert(&key_buffer, &positions_buffer)?;
+
+                // The positions in the changed fields are replaced by the extracted ones.
+                positions_buffer.clear();
+                positions_buffer.extend(
+                    positions
+                        .iter()
+                        .filter(|&position| {
+                            let (field_id, _) = relative_from_absolute_position(position);
+                            changed_fields_docids
+                                .get(&field_id)
+                                .map_or(true, |docids| !docids.contains(docid))
+                        })
+                        .flat_map(u32::to_ne_bytes),
+                );
+                if !positions_buffer.is_empty() {
+                    kept_word_positions.insert(&key_buffer, &positions_buffer)?;
+                }
+
+                // safety: we don't keep references from inside the LMDB database.
+                unsafe { iter.del_current()? };
+            }
+        }
+
+        let words_prefixes_fst =
+            self.index.words_prefixes_fst(self.wtxn)?.map_data(|cow| cow.into_owned())?;
+        let stored_word_positions = writer_into_reader(stored_word_positions)?;
+        for typed_chunk in extract::word_data_from_docid_word_positions(
+            stored_word_positions,
+            pool_params,
+            exact_attributes,
+            infix_attributes,
+            phonetic_attributes,
+            stemmers,
+        )? {
+            remove_typed_chunk_from_index(typed_chunk, self.index, self.wtxn, &words_prefixes_fst)?;
+        }
+
+        writer_into_reader(kept_word_positions)
+    }
+
+    /// Writes the documents updated in place and indexes the facet values of their changed
+    /// fields again.
+    fn execute_in_place_updates(
+        &mut self,
+        in_place_updates: InPlaceUpdates,
+        fields_ids_map: &FieldsIdsMap,
+        primary_key_id: FieldId,
+    ) -> Result<()>
+    where
+        FP: Fn(UpdateIndexingStep) + Sync,
+        FA: Fn() -> bool + Sync,
+    {
+        let InPlaceUpdates {
+            changed_fields_docids, original_documents, flattened_documents, ..
+        } = in_place_updates;
+
+        let mut cursor = grenad::Reader::new(original_documents)?.into_cursor()?;
+        while let Some((key, value)) = cursor.move_on_next()? {
+            self.index
+                .documents
+                .remap_types::<ByteSlice, ByteSlice>()
+                .put(self.wtxn, key, value)?;
+        }
+
+        // The values of all the fields nested in the changed fields must be removed.
+        let fields_docids = nested_changed_fields_docids(&changed_fields_docids, fields_ids_map);
+        let geo_docids = fields_ids_map
+            .id("_geo")
+            .and_then(|geo_field_id| changed_fields_docids.get(&geo_field_id))
+            .cloned()
+            .unwrap_or_default();
+        update::remove_docids_from_facet_fields(
+            self.wtxn,
+            self.index,
+            &fields_docids,
+            &geo_docids,
+        )?;
+
+        self.index_facet_values(flattened_documents, fields_ids_map, primary_key_id, false)
+    }
+
+    /// Extracts the facet values of the flattened documents and merges them into the facet
+    /// databases, reporting the merged databases when `report_progress` is set.
+    fn index_facet_values(
+        &mut self,
+        flattened_documents: File,
+        fields_ids_map: &FieldsIdsMap,
+        primary_key_id: FieldId,
+        report_progress: bool,
+    ) -> Result<()>
+    where
+        FP: Fn(UpdateIndexingStep) + Sync,
+        FA: Fn() -> bool + Sync,
+    {
+        let backup_pool;
+        let pool = match self.indexer_config.thread_pool {
+            Some(ref pool) => pool,
+            #[cfg(not(test))]
+            None => {
+                backup_pool = rayon::ThreadPoolBuilder::new().build()?;
+                &backup_pool
+            }
+            #[cfg(test)]
+            None => {
+                backup_pool = rayon::ThreadPoolBuilder::new().num_threads(1).build()?;
+                &backup_pool
+            }
+        };
+
+        let flattened_documents = grenad::Reader::new(flattened_documents)?;
+
+        let (lmdb_writer_sx, lmdb_writer_rx): (
+            Sender<Result<TypedChunk>>,
+            Receiver<Result<TypedChunk>>,
+        ) = crossbeam_channel::unbounded();
+
+        let FacetExtractionSettings {
+            faceted_fields,
+            element_faceted_fields,
+            hierarchical_facets,
+            expiry_field_id,
+            geo_fields_ids,
+        } = self.facet_extraction_settings(fields_ids_map)?;
+
+        let pool_params = GrenadParameters {
+            chunk_compression_type: self.indexer_config.chunk_compression_type,
+            chunk_compression_level: self.indexer_config.chunk_compression_level,
+            max_memory: self.indexer_config.max_memory,
+            max_nb_chunks: self.indexer_config.max_nb_chunks,
+        };
+        let documents_chunk_size =
+            self.indexer_config.documents_chunk_size.unwrap_or(1024 * 1024 * 4); // 4MiB
+
+        pool.install(|| {
+            let result =
+                grenad_obkv_into_chunks(flattened_documents, pool_params, documents_chunk_size)
+                    .and_then(|flattened_chunk| {
+                        extract::facet_data_from_obkv_documents(
+                            flattened_chunk,
+                            pool_params,
+                            lmdb_writer_sx.clone(),
+                            faceted_fields,
+                            element_faceted_fields,
+                            hierarchical_facets,
+                            expiry_field_id,
+                            primary_key_id,
+                            geo_fields_ids,
+                        )
+                    });
+
+            if let Err(e) = result {
+                let _ = lmdb_writer_sx.send(Err(e));
+            }
+
+            // needs to be droped to avoid channel waiting lock.
+            drop(lmdb_writer_sx)
+        });
+
+        let mut databases_seen = 0;
+        if report_progress {
+            (self.progress)(UpdateIndexingStep::MergeDataIntoFinalDatabase {
+                databases_seen,
+                total_databases: FACET_DATABASE_COUNT,
+            });
+        }
+
+        for result in lmdb_writer_rx {
+            if (self.should_abort)() {
+                return Err(Error::InternalError(InternalError::AbortedIndexation));
+            }
+
+            // The documents are already in the index, the facet databases are merged into.
+            let (_, is_merged_database) =
+                write_typed_chunk_into_index(result?, self.index, self.wtxn, false)?;
+            if is_merged_database && report_progress {
+                databases_seen += 1;
+                (self.progress)(UpdateIndexingStep::MergeDataIntoFinalDatabase {
+                    databases_seen,
+                    total_databases: FACET_DATABASE_COUNT,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     #[logging_timer::time("IndexDocuments::{}")]
     pub fn execute_prefix_databases(
         self,
@@ -653,6 +1086,25 @@ where
     }
 }
 
+/// Returns the documents ids of the changed fields along with all the fields nested in them,
+/// the changed fields are top-level fields.
+fn nested_changed_fields_docids(
+    changed_fields_docids: &HashMap<FieldId, RoaringBitmap>,
+    fields_ids_map: &FieldsIdsMap,
+) -> HashMap<FieldId, RoaringBitmap> {
+    let mut fields_docids = HashMap::<FieldId, RoaringBitmap>::new();
+    for (changed_field_id, docids) in changed_fields_docids {
+        if let Some(changed_name) = fields_ids_map.name(*changed_field_id) {
+            for (field_id, name) in fields_ids_map.iter() {
+                if is_faceted_by(name, changed_name) {
+                    *fields_docids.entry(field_id).or_default() |= docids;
+                }
+            }
+        }
+    }
+    fields_docids
+}
+
 /// Run the word prefix docids update operation.
 #[allow(clippy::too_many_arguments)]
 fn execute_word_prefix_docids(
@@ -776,11 +1228,12 @@ mod tests {
         assert_eq!(count, 1);
 
         // Check that we get only one document from the database.
-        // Since the document has been deleted and re-inserted, its internal docid has been incremented to 1
-        let docs = index.documents(&rtxn, Some(1)).unwrap();
+        // Since the name of the document did not change, it has been updated in place and
+        // keeps its internal docid.
+        let docs = index.documents(&rtxn, Some(0)).unwrap();
         assert_eq!(docs.len(), 1);
         let (id, doc) = docs[0];
-        assert_eq!(id, 1);
+        assert_eq!(id, 0);
 
         // Check that this document is equal to the last one sent.
         let mut doc_iter = doc.iter();
@@ -987,9 +1440,630 @@ mod tests {
         assert_eq!(count, 6);
 
         db_snap!(index, word_docids, "updated");
-        db_snap!(index, soft_deleted_documents_ids, "updated", @"[0, 1, 4, ]");
+        db_snap!(index, soft_deleted_documents_ids, "updated", @"[]");
+
+        drop(rtxn);
+    }
+
+    #[test]
+    fn update_of_non_searchable_fields_is_done_in_place() {
+        let mut index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_searchable_fields(vec![S("title")]);
+                settings.set_filterable_fields(hashset! { S("price"), S("_geo") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "title": "Dune", "price": 10, "stock": 3 },
+                { "id": 2, "title": "Emma", "price": 20, "stock": 1, "_geo": { "lat": 35, "lng": 23 } }
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let docid = index.external_documents_ids(&rtxn).unwrap().get("2").unwrap();
+        drop(rtxn);
+
+        index.index_documents_config.update_method = IndexDocumentsMethod::UpdateDocuments;
+        index
+            .add_documents(documents!([
+                { "id": 2, "price": 15, "stock": 0, "_geo": { "lat": 12, "lng": 21 } }
+            ]))
+            .unwrap();
+
+        // The document keeps its internal id and its words.
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.external_documents_ids(&rtxn).unwrap().get("2"), Some(docid));
+        assert!(index.soft_deleted_documents_ids(&rtxn).unwrap().is_empty());
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 2);
+        let result = index.search(&rtxn).query("emma").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![docid]);
+
+        // Only the new facet values of the document match.
+        let filter = |filter: &str| {
+            crate::Filter::from_str(filter).unwrap().unwrap().evaluate(&rtxn, &index).unwrap()
+        };
+        assert_eq!(filter("price = 15"), RoaringBitmap::from_iter([docid]));
+        assert!(filter("price = 20").is_empty());
+        assert_eq!(filter("_geoRadius(12, 21, 10)"), RoaringBitmap::from_iter([docid]));
+        assert!(filter("_geoRadius(35, 23, 10)").is_empty());
+        drop(rtxn);
+
+        // Updating all the searchable fields replaces the document.
+        index.add_documents(documents!([{ "id": 2, "title": "Persuasion" }])).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let new_docid = index.external_documents_ids(&rtxn).unwrap().get("2").unwrap();
+        assert_ne!(new_docid, docid);
+        let result = index.search(&rtxn).query("persuasion").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![new_docid]);
+        let filter = crate::Filter::from_str("price = 15").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap(), RoaringBitmap::from_iter([new_docid]));
+    }
+
+    #[test]
+    fn update_in_place_removes_the_facets_of_the_changed_fields() {
+        let mut index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_searchable_fields(vec![S("title")]);
+                settings.set_filterable_fields(
+                    hashset! { S("variants"), S("tags"), S("code"), S("_geo") },
+                );
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                {
+                    "id": 1,
+                    "title": "Dune",
+                    "variants": [{ "color": "red" }, { "color": "green" }],
+                    "tags": ["a", "b", "c"],
+                    "code": "x1",
+                    "_geo": { "lat": 35, "lng": 23 }
+                }
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let docid = index.external_documents_ids(&rtxn).unwrap().get("1").unwrap();
+        drop(rtxn);
+
+        index.index_documents_config.update_method = IndexDocumentsMethod::UpdateDocuments;
+        index
+            .add_documents(documents!([
+                {
+                    "id": 1,
+                    "variants": [{ "color": "blue" }],
+                    "tags": ["a"],
+                    "code": 12,
+                    "_geo": { "lat": 12, "lng": 21 }
+                }
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.external_documents_ids(&rtxn).unwrap().get("1"), Some(docid));
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let filter = |filter: &str| {
+            crate::Filter::from_str(filter).unwrap().unwrap().evaluate(&rtxn, &index).unwrap()
+        };
+
+        // the elements of the arrays of objects
+        let color_fid = fields_ids_map.id("variants.color").unwrap();
+        let element = |color: &str| {
+            index.field_id_docid_facet_element_strings.get(&rtxn, &(color_fid, docid, color))
+        };
+        assert!(element("red").unwrap().is_none());
+        assert!(element("green").unwrap().is_none());
+        assert!(element("blue").unwrap().is_some());
+        assert!(filter("variants MATCHES (color = red)").is_empty());
+        assert_eq!(filter("variants MATCHES (color = blue)"), RoaringBitmap::from_iter([docid]));
+
+        // the type of the values
+        let code_fid = fields_ids_map.id("code").unwrap();
+        assert!(index.string_typed_documents_ids(&rtxn, code_fid).unwrap().is_empty());
+        assert!(filter("code IS STRING").is_empty());
+        assert_eq!(filter("code IS NUMBER"), RoaringBitmap::from_iter([docid]));
+
+        // the size of the arrays
+        let tags_fid = fields_ids_map.id("tags").unwrap();
+        assert!(index.facet_id_size_docids.get(&rtxn, &(tags_fid, 3)).unwrap().is_none());
+        assert_eq!(
+            index.facet_id_size_docids.get(&rtxn, &(tags_fid, 1)).unwrap(),
+            Some(RoaringBitmap::from_iter([docid]))
+        );
+        assert!(filter("_size.tags = 3").is_empty());
+
+        // the geo points
+        let rtree = index.geo_rtree(&rtxn).unwrap().unwrap();
+        assert_eq!(rtree.size(), 1);
+        assert_eq!(
+            index.geo_faceted_documents_ids(&rtxn).unwrap(),
+            RoaringBitmap::from_iter([docid])
+        );
+        assert!(filter("_geoRadius(35, 23, 10)").is_empty());
+        assert_eq!(filter("_geoRadius(12, 21, 10)"), RoaringBitmap::from_iter([docid]));
+    }
+
+    #[test]
+    fn update_in_place_only_extracts_the_words_of_the_changed_fields() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.update_method = IndexDocumentsMethod::UpdateDocuments;
+
+        index
+            .update_settings(|settings| settings.set_filterable_fields(hashset! { S("price") }))
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 1, "title": "Dune", "author": "Frank Herbert", "price": 10 },
+                { "id": 2, "title": "Emma", "author": "Jane Austen", "price": 20 }
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let docid = index.external_documents_ids(&rtxn).unwrap().get("1").unwrap();
+        let title_positions = index.docid_word_positions.get(&rtxn, &(docid, "dune")).unwrap();
+        drop(rtxn);
+
+        // All the fields are searchable, the words of the title are kept.
+        index
+            .add_documents(documents!([{ "id": 1, "author": "F. Herbert", "price": 15 }]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.external_documents_ids(&rtxn).unwrap().get("1"), Some(docid));
+        assert!(index.soft_deleted_documents_ids(&rtxn).unwrap().is_empty());
+        assert_eq!(
+            index.docid_word_positions.get(&rtxn, &(docid, "dune")).unwrap(),
+            title_positions
+        );
+        assert!(index.docid_word_positions.get(&rtxn, &(docid, "frank")).unwrap().is_none());
+        assert!(index.word_docids.get(&rtxn, "frank").unwrap().is_none());
+        assert!(!index.words_fst(&rtxn).unwrap().contains("frank"));
+
+        let search = |query: &str| index.search(&rtxn).query(query).execute().unwrap();
+        assert_eq!(search("dune").documents_ids, vec![docid]);
+        assert_eq!(search("herbert").documents_ids, vec![docid]);
+        assert_eq!(search("15").documents_ids, vec![docid]);
+        assert!(search("frank").documents_ids.is_empty());
+        assert!(search("10").documents_ids.is_empty());
+        let filter = crate::Filter::from_str("price = 15").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap(), RoaringBitmap::from_iter([docid]));
+        drop(rtxn);
+
+        // When all its searchable fields but the primary key change, the document is replaced.
+        let rtxn = index.read_txn().unwrap();
+        let docid = index.external_documents_ids(&rtxn).unwrap().get("2").unwrap();
+        drop(rtxn);
+
+        index
+            .add_documents(documents!([
+                { "id": 2, "title": "Persuasion", "author": "J. Austen", "price": 25 }
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let new_docid = index.external_documents_ids(&rtxn).unwrap().get("2").unwrap();
+        assert_ne!(new_docid, docid);
+        let result = index.search(&rtxn).query("persuasion").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![new_docid]);
+        let result = index.search(&rtxn).query("emma").execute().unwrap();
+        assert!(result.documents_ids.is_empty());
+    }
+
+    #[test]
+    fn simple_documents_edition() {
+        let mut index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "title": "Dune", "stock": 3, "tags": ["scifi"], "author": { "name": "Frank", "born": 1920 } },
+                { "id": 2, "title": "Emma", "stock": 1 }
+            ]))
+            .unwrap();
+
+        index.index_documents_config.update_method = IndexDocumentsMethod::EditDocuments;
+        index
+            .add_documents(documents!([
+                {
+                    "id": 1,
+                    "stock": { "_increment": 2 },
+                    "tags": { "_append": "classic" },
+                    "author.name": { "_set": "Frank Herbert" },
+                    "author.born": { "_unset": null }
+                },
+                { "id": 2, "title": { "_unset": null }, "stock": { "_decrement": 1 } },
+                { "id": 3, "title": { "_set": "Ghost" } }
+            ]))
+            .unwrap();
+
+        // The edition of the unknown document is ignored.
+        let rtxn = index.read_txn().unwrap();
+        let count = index.number_of_documents(&rtxn).unwrap();
+        assert_eq!(count, 2);
+
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let external_documents_ids = index.external_documents_ids(&rtxn).unwrap();
+        let document = |external_id: &str| {
+            let docid = external_documents_ids.get(external_id).unwrap();
+            let (_, obkv) = index.documents(&rtxn, Some(docid)).unwrap()[0];
+            serde_json::Value::Object(crate::all_obkv_to_json(obkv, &fields_ids_map).unwrap())
+        };
+        assert_eq!(
+            document("1"),
+            serde_json::json!({ "id": 1, "title": "Dune", "stock": 5, "tags": ["scifi", "classic"], "author": { "name": "Frank Herbert" } })
+        );
+        assert_eq!(document("2"), serde_json::json!({ "id": 2, "stock": 0 }));
+
+        // The edited nested fields are searchable.
+        let result = index.search(&rtxn).query("herbert").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![external_documents_ids.get("1").unwrap()]);
+        drop(rtxn);
+
+        // An invalid operation fails the whole batch.
+        let error = index
+            .add_documents(documents!([{ "id": 1, "title": { "_increment": 1 } }]))
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::UserError(UserError::InvalidDocumentEditOperation { ref field, .. }) if field == "title"
+        ));
+    }
+
+    #[test]
+    fn ingestion_rules_are_applied_before_indexing() {
+        let index = TempIndex::new();
+
+        let rules = serde_json::json!([
+            { "type": "rename", "from": "ref", "to": "id" },
+            { "type": "concat", "fields": ["first", "last"], "separator": " ", "into": "name" },
+            { "type": "lowercase", "field": "name" },
+            { "type": "drop", "field": "first" },
+            { "type": "default", "field": "kind", "value": "person" },
+            { "type": "geo", "lat": "latitude", "lng": "longitude" },
+            { "type": "split", "field": "tags", "separator": "," },
+        ]);
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("_geo"), S("tags") });
+                settings.set_ingestion_rules(serde_json::from_value(rules.clone()).unwrap());
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "ref": 1, "first": "Jane", "last": "DOE", "latitude": 45.3, "longitude": 2.5, "tags": "red, blue" },
+                { "ref": 2, "first": "John", "kind": "robot", "tags": "green" }
+            ]))
+            .unwrap();
+
+        // The primary key and the `_geo` field are taken from the transformed documents.
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.primary_key(&rtxn).unwrap(), Some("id"));
+        assert_eq!(index.ingestion_rules(&rtxn).unwrap().version, 1);
+
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let external_documents_ids = index.external_documents_ids(&rtxn).unwrap();
+        let document = |external_id: &str| {
+            let docid = external_documents_ids.get(external_id).unwrap();
+            let (_, obkv) = index.documents(&rtxn, Some(docid)).unwrap()[0];
+            serde_json::Value::Object(crate::all_obkv_to_json(obkv, &fields_ids_map).unwrap())
+        };
+        assert_eq!(
+            document("1"),
+            serde_json::json!({
+                "id": 1,
+                "last": "DOE",
+                "latitude": 45.3,
+                "longitude": 2.5,
+                "tags": ["red", "blue"],
+                "name": "jane doe",
+                "kind": "person",
+                "_geo": { "lat": 45.3, "lng": 2.5 }
+            })
+        );
+        assert_eq!(
+            document("2"),
+            serde_json::json!({ "id": 2, "kind": "robot", "tags": ["green"], "name": "john" })
+        );
+
+        let filter = |filter: &str| {
+            crate::Filter::from_str(filter).unwrap().unwrap().evaluate(&rtxn, &index).unwrap()
+        };
+        assert_eq!(filter("tags = blue").len(), 1);
+        assert_eq!(filter("_geoRadius(45.3, 2.5, 10)").len(), 1);
+        drop(rtxn);
+
+        // Updating the rules bumps their version, setting the same rules again does not.
+        index
+            .update_settings(|settings| {
+                settings.set_ingestion_rules(serde_json::from_value(rules.clone()).unwrap());
+            })
+            .unwrap();
+        index.update_settings(|settings| settings.set_ingestion_rules(Vec::new())).unwrap();
+        let rtxn = index.read_txn().unwrap();
+        let expected = crate::IngestionRules { version: 2, rules: Vec::new() };
+        assert_eq!(index.ingestion_rules(&rtxn).unwrap(), expected);
+    }
+
+    #[test]
+    fn synthesizing_ingestion_rules_are_not_applied_to_partial_updates() {
+        let mut index = TempIndex::new();
+
+        let rules = serde_json::json!([
+            { "type": "concat", "fields": ["first", "last"], "separator": " ", "into": "name" },
+            { "type": "default", "field": "kind", "value": "person" },
+            { "type": "lowercase", "field": "last" },
+        ]);
+        index
+            .update_settings(|settings| {
+                settings.set_ingestion_rules(serde_json::from_value(rules.clone()).unwrap());
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 1, "first": "Jane", "last": "Doe", "kind": "robot" }
+            ]))
+            .unwrap();
 
+        index.index_documents_config.update_method = IndexDocumentsMethod::UpdateDocuments;
+        index.add_documents(documents!([{ "id": 1, "last": "SMITH" }])).unwrap();
+
+        // The name and the kind are not rebuilt from the partial document.
+        let rtxn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let docid = index.external_documents_ids(&rtxn).unwrap().get("1").unwrap();
+        let (_, obkv) = index.documents(&rtxn, Some(docid)).unwrap()[0];
+        assert_eq!(
+            serde_json::Value::Object(crate::all_obkv_to_json(obkv, &fields_ids_map).unwrap()),
+            serde_json::json!({
+                "id": 1,
+                "first": "Jane",
+                "last": "smith",
+                "kind": "robot",
+                "name": "Jane Doe"
+            })
+        );
+    }
+
+    #[test]
+    fn documents_not_matching_the_schema() {
+        let mut index = TempIndex::new();
+
+        let schema: crate::DocumentSchema = serde_json::from_value(serde_json::json!({
+            "fields": { "price": "number", "_geo": "geo" },
+            "required": ["price"],
+        }))
+        .unwrap();
+        index.update_settings(|settings| settings.set_document_schema(schema.clone())).unwrap();
+
+        let error = index
+            .add_documents(documents!([
+                { "id": 1, "price": 10 },
+                { "id": 2, "price": null }
+            ]))
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::UserError(UserError::InvalidDocumentSchema { ref document_id, .. }) if document_id == "2"
+        ));
+
+        let schema = crate::DocumentSchema {
+            on_invalid_document: crate::InvalidDocumentPolicy::Skip,
+            ..schema
+        };
+        index
+            .update_settings(|settings| {
+                settings.set_document_schema(schema.clone());
+                settings.set_filterable_fields(hashset! { S("_geo") });
+            })
+            .unwrap();
+
+        // The invalid `_geo` of a skipped document doesn't make the batch fail.
+        let mut wtxn = index.write_txn().unwrap();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+        let (builder, user_result) = builder
+            .add_documents(documents!([
+                { "id": 1, "price": 10 },
+                { "id": 2, "price": "12,50" },
+                { "id": 3, "price": 20, "_geo": { "lat": "north" } }
+            ]))
+            .unwrap();
+        assert_eq!(user_result.unwrap(), 1);
+        assert_eq!(builder.skipped_documents(), 2);
+        let invalid_documents: Vec<_> =
+            builder.invalid_documents().iter().map(|d| d.document_id.as_str()).collect();
+        assert_eq!(invalid_documents, ["2", "3"]);
+        assert_eq!(
+            builder.invalid_documents()[0].reason,
+            "the field `price` must be of type `number`, found `\"12,50\"`"
+        );
+        builder.execute().unwrap();
+        wtxn.commit().unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let external_documents_ids = index.external_documents_ids(&rtxn).unwrap();
+        assert!(external_documents_ids.get("1").is_some());
+        assert!(external_documents_ids.get("2").is_none());
+        assert!(external_documents_ids.get("3").is_none());
         drop(rtxn);
+
+        // The partial updates don't have to contain the required fields.
+        index.index_documents_config.update_method = IndexDocumentsMethod::UpdateDocuments;
+        index.add_documents(documents!([{ "id": 1, "title": "Dune" }])).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let docid = index.external_documents_ids(&rtxn).unwrap().get("1").unwrap();
+        let (_, obkv) = index.documents(&rtxn, Some(docid)).unwrap()[0];
+        assert_eq!(
+            serde_json::Value::Object(crate::all_obkv_to_json(obkv, &fields_ids_map).unwrap()),
+            serde_json::json!({ "id": 1, "price": 10, "title": "Dune" })
+        );
+    }
+
+    #[test]
+    fn edited_documents_not_matching_the_schema() {
+        let mut index = TempIndex::new();
+
+        let schema: crate::DocumentSchema = serde_json::from_value(serde_json::json!({
+            "fields": { "price": "number" },
+            "required": ["price"],
+        }))
+        .unwrap();
+        index.update_settings(|settings| settings.set_document_schema(schema.clone())).unwrap();
+        index
+            .add_documents(documents!([{ "id": 1, "price": 10 }, { "id": 2, "price": 5 }]))
+            .unwrap();
+
+        // The operations are not validated, only the edited documents are.
+        index.index_documents_config.update_method = IndexDocumentsMethod::EditDocuments;
+        index.add_documents(documents!([{ "id": 1, "price": { "_increment": 2 } }])).unwrap();
+
+        let error = index
+            .add_documents(documents!([{ "id": 1, "price": { "_unset": null } }]))
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            Error::UserError(UserError::InvalidDocumentSchema { ref document_id, .. }) if document_id == "1"
+        ));
+
+        let schema = crate::DocumentSchema {
+            on_invalid_document: crate::InvalidDocumentPolicy::Skip,
+            ..schema
+        };
+        index.update_settings(|settings| settings.set_document_schema(schema)).unwrap();
+
+        let mut wtxn = index.write_txn().unwrap();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+        let (builder, user_result) =
+            builder.add_documents(documents!([{ "id": 1, "price": { "_set": "free" } }])).unwrap();
+        user_result.unwrap();
+        let (builder, user_result) =
+            builder.add_documents(documents!([{ "id": 2, "price": { "_increment": 1 } }])).unwrap();
+        user_result.unwrap();
+        let result = builder.execute().unwrap();
+        wtxn.commit().unwrap();
+
+        // The skipped edited document keeps its stored version.
+        let invalid_documents: Vec<_> = result.invalid_edited_documents.into_iter().collect();
+        assert_eq!(
+            invalid_documents,
+            [(
+                0,
+                vec![crate::InvalidDocument {
+                    document_id: S("1"),
+                    reason: S("the field `price` must be of type `number`, found `\"free\"`"),
+                }]
+            )]
+        );
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 2);
+        assert_eq!(index.field_distribution(&rtxn).unwrap().get("price"), Some(&2));
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let external_documents_ids = index.external_documents_ids(&rtxn).unwrap();
+        let document = |external_id: &str| {
+            let docid = external_documents_ids.get(external_id).unwrap();
+            let (_, obkv) = index.documents(&rtxn, Some(docid)).unwrap()[0];
+            serde_json::Value::Object(crate::all_obkv_to_json(obkv, &fields_ids_map).unwrap())
+        };
+        assert_eq!(document("1"), serde_json::json!({ "id": 1, "price": 12 }));
+        assert_eq!(document("2"), serde_json::json!({ "id": 2, "price": 6 }));
+    }
+
+    #[test]
+    fn skip_documents_with_older_version() {
+        let index = TempIndex::new();
+
+        index.update_settings(|settings| settings.set_version_field(S("version"))).unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 1, "title": "Dune", "version": 2 },
+                { "id": 2, "title": "Emma", "version": 1 }
+            ]))
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "title": "Dune Messiah", "version": 1 },
+                { "id": 2, "title": "Persuasion", "version": 3 },
+                { "id": 3, "title": "Ghost", "version": 0 }
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let count = index.number_of_documents(&rtxn).unwrap();
+        assert_eq!(count, 3);
+
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let external_documents_ids = index.external_documents_ids(&rtxn).unwrap();
+        let document = |external_id: &str| {
+            let docid = external_documents_ids.get(external_id).unwrap();
+            let (_, obkv) = index.documents(&rtxn, Some(docid)).unwrap()[0];
+            serde_json::Value::Object(crate::all_obkv_to_json(obkv, &fields_ids_map).unwrap())
+        };
+        // The older version of the document 1 is skipped.
+        assert_eq!(document("1"), serde_json::json!({ "id": 1, "title": "Dune", "version": 2 }));
+        assert_eq!(
+            document("2"),
+            serde_json::json!({ "id": 2, "title": "Persuasion", "version": 3 })
+        );
+        assert_eq!(document("3"), serde_json::json!({ "id": 3, "title": "Ghost", "version": 0 }));
+    }
+
+    #[test]
+    fn skip_documents_with_older_version_in_the_same_batch() {
+        let index = TempIndex::new();
+
+        index.update_settings(|settings| settings.set_version_field(S("version"))).unwrap();
+        index.add_documents(documents!([{ "id": 1, "title": "Dune", "version": 1 }])).unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 1, "title": "Children of Dune", "version": 3 },
+                { "id": 1, "title": "Dune Messiah", "version": 2 },
+                { "id": 2, "title": "Emma", "version": 2 },
+                { "id": 2, "title": "Persuasion", "version": 1 }
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        let external_documents_ids = index.external_documents_ids(&rtxn).unwrap();
+        let document = |external_id: &str| {
+            let docid = external_documents_ids.get(external_id).unwrap();
+            let (_, obkv) = index.documents(&rtxn, Some(docid)).unwrap()[0];
+            serde_json::Value::Object(crate::all_obkv_to_json(obkv, &fields_ids_map).unwrap())
+        };
+        // The version 2 is newer than the stored one but older than the version 3 of the batch.
+        assert_eq!(
+            document("1"),
+            serde_json::json!({ "id": 1, "title": "Children of Dune", "version": 3 })
+        );
+        assert_eq!(document("2"), serde_json::json!({ "id": 2, "title": "Emma", "version": 2 }));
     }
 
     #[test]
@@ -2167,6 +3241,7 @@ mod tests {
         DocumentAdditionResult {
             indexed_documents: 3,
             number_of_documents: 2,
+            invalid_edited_documents: {},
         }
         "###);
         wtxn.commit().unwrap();
@@ -2216,6 +3291,7 @@ mod tests {
         DocumentAdditionResult {
             indexed_documents: 5,
             number_of_documents: 1,
+            invalid_edited_documents: {},
         }
         "###);
         wtxn.commit().unwrap();
@@ -2254,6 +3330,7 @@ mod tests {
         DocumentAdditionResult {
             indexed_documents: 3,
             number_of_documents: 3,
+            invalid_edited_documents: {},
         }
         "###);
         wtxn.commit().unwrap();
@@ -2292,6 +3369,7 @@ mod tests {
         DocumentAdditionResult {
             indexed_documents: 2,
             number_of_documents: 1,
+            invalid_edited_documents: {},
         }
         "###);
         wtxn.commit().unwrap();
@@ -2332,6 +3410,7 @@ mod tests {
         DocumentAdditionResult {
             indexed_documents: 2,
             number_of_documents: 2,
+            invalid_edited_documents: {},
         }
         "###);
         wtxn.commit().unwrap();
@@ -2379,6 +3458,7 @@ mod tests {
         DocumentAdditionResult {
             indexed_documents: 3,
             number_of_documents: 1,
+            invalid_edited_documents: {},
         }
         "###);
         wtxn.commit().unwrap();
@@ -2415,6 +3495,7 @@ mod tests {
         DocumentAdditionResult {
             indexed_documents: 1,
             number_of_documents: 1,
+            invalid_edited_documents: {},
         }
         "###);
         wtxn.commit().unwrap();
@@ -2450,6 +3531,7 @@ mod tests {
         DocumentAdditionResult {
             indexed_documents: 1,
             number_of_documents: 1,
+            invalid_edited_documents: {},
         }
         "###);
         wtxn.commit().unwrap();
@@ -1,28 +1,32 @@
 use std::borrow::Cow;
 use std::collections::hash_map::Entry;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
 use std::io::{Read, Seek};
 
 use fxhash::FxHashMap;
 use heed::RoTxn;
-use itertools::Itertools;
+use itertools::{EitherOrBoth, Itertools};
 use obkv::{KvReader, KvWriter};
 use roaring::RoaringBitmap;
 use serde_json::Value;
 use smartstring::SmartString;
 
+use super::document_edit::FieldEdit;
 use super::helpers::{
-    create_sorter, create_writer, keep_latest_obkv, merge_obkvs_and_operations, MergeFn,
+    create_sorter, create_writer, keep_first, keep_latest_obkv, merge_obkvs_and_edit_operations,
+    merge_obkvs_and_operations, MergeFn,
 };
 use super::{IndexDocumentsMethod, IndexerConfig};
 use crate::documents::{DocumentsBatchIndex, EnrichedDocument, EnrichedDocumentsBatchReader};
 use crate::error::{Error, InternalError, UserError};
+use crate::facet::FacetType;
 use crate::index::{db_name, main_key};
 use crate::update::{AvailableDocumentsIds, ClearDocuments, UpdateIndexingStep};
 use crate::{
-    ExternalDocumentsIds, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldsIdsMap, Index,
-    Result, BEU32,
+    all_obkv_to_json, is_faceted_by, DocumentId, ExternalDocumentsIds, FieldDistribution, FieldId,
+    FieldIdMapMissingEntry, FieldsIdsMap, Index, InvalidDocument, InvalidDocumentPolicy, Result,
+    BEU32,
 };
 
 pub struct TransformOutput {
@@ -35,6 +39,24 @@ pub struct TransformOutput {
     pub documents_count: usize,
     pub original_documents: File,
     pub flattened_documents: File,
+    pub in_place_updates: Option<InPlaceUpdates>,
+    /// The edited documents that did not match the schema once edited, by index of the
+    /// batch of documents that last edited them. They keep their stored version.
+    pub invalid_edited_documents: BTreeMap<usize, Vec<InvalidDocument>>,
+}
+
+/// The replaced documents that keep some of their searchable fields. They keep their internal ids
+/// and only the facet values and the words of their changed fields must be indexed again.
+pub struct InPlaceUpdates {
+    /// The internal ids of the updated documents, grouped by the fields that changed.
+    pub changed_fields_docids: HashMap<FieldId, RoaringBitmap>,
+    pub original_documents: File,
+    /// The flattened documents, only containing the fields that changed.
+    pub flattened_documents: File,
+    /// The internal ids of the updated documents whose searchable fields changed.
+    pub searchable_documents_ids: RoaringBitmap,
+    /// The flattened documents whose searchable fields changed, only containing these fields.
+    pub searchable_documents: Option<File>,
 }
 
 /// Extract the external ids, deduplicate and compute the new internal documents ids
@@ -59,18 +81,40 @@ pub struct Transform<'a, 'i> {
     flattened_sorter: grenad::Sorter<MergeFn>,
 
     replaced_documents_ids: RoaringBitmap,
+    // The internal ids of the documents replaced by the new internal ids.
+    original_documents_ids: FxHashMap<DocumentId, DocumentId>,
+    // The documents edited by this update, by the new internal ids of their edited versions.
+    edited_documents: FxHashMap<DocumentId, EditedDocument>,
     new_documents_ids: RoaringBitmap,
     // To increase the cache locality and decrease the heap usage we use compact smartstring.
     new_external_documents_ids_builder: FxHashMap<SmartString<smartstring::Compact>, u64>,
+    // The versions of the documents added in this update, they replace the stored versions.
+    pending_versions: FxHashMap<SmartString<smartstring::Compact>, f64>,
     documents_count: usize,
+    /// The number of documents that were not indexed because their version was lower
+    /// than the one of the stored documents, or because they did not match the schema.
+    pub skipped_documents: usize,
+    /// The documents that were skipped because they did not match the schema, with the reason.
+    pub invalid_documents: Vec<InvalidDocument>,
+}
+
+/// A document of the database that is edited, its edited version is only known once the
+/// edits are merged and must then be validated against the schema of the index.
+struct EditedDocument {
+    original_docid: DocumentId,
+    external_id: SmartString<smartstring::Compact>,
+    /// The index of the last batch of documents that edited it.
+    documents_batch: usize,
 }
 
 /// This enum is specific to the grenad sorter stored in the transform.
-/// It's used as the first byte of the grenads and tells you if the document id was an addition or a deletion.
+/// It's used as the first byte of the grenads and tells you if the document id was an addition, a deletion
+/// or an edition, in which case the obkv contains the field edits to apply to the document.
 #[repr(u8)]
 pub enum Operation {
     Addition,
     Deletion,
+    Edition,
 }
 
 /// Create a mapping between the field ids found in the document batch and the one that were
@@ -109,6 +153,7 @@ impl<'a, 'i> Transform<'a, 'i> {
         let merge_function = match index_documents_method {
             IndexDocumentsMethod::ReplaceDocuments => keep_latest_obkv,
             IndexDocumentsMethod::UpdateDocuments => merge_obkvs_and_operations,
+            IndexDocumentsMethod::EditDocuments => merge_obkvs_and_edit_operations,
         };
 
         // We initialize the sorter with the user indexing settings.
@@ -146,15 +191,23 @@ impl<'a, 'i> Transform<'a, 'i> {
             flattened_sorter,
             index_documents_method,
             replaced_documents_ids: RoaringBitmap::new(),
+            original_documents_ids: FxHashMap::default(),
+            edited_documents: FxHashMap::default(),
             new_documents_ids: RoaringBitmap::new(),
             new_external_documents_ids_builder: FxHashMap::default(),
+            pending_versions: FxHashMap::default(),
             documents_count: 0,
+            skipped_documents: 0,
+            invalid_documents: Vec::new(),
         })
     }
 
+    /// Reads the documents of the batch, the `documents_batch` index identifies the batch
+    /// in the report of the edited documents skipped because they don't match the schema.
     pub fn read_documents<R, FP, FA>(
         &mut self,
         reader: EnrichedDocumentsBatchReader<R>,
+        documents_batch: usize,
         wtxn: &mut heed::RwTxn,
         progress_callback: FP,
         should_abort: FA,
@@ -164,6 +217,20 @@ impl<'a, 'i> Transform<'a, 'i> {
         FP: Fn(UpdateIndexingStep) + Sync,
         FA: Fn() -> bool + Sync,
     {
+        // the documents not matching the schema of the index are skipped by the cursor.
+        self.skipped_documents += reader.skipped_documents_count() as usize;
+        self.invalid_documents.extend_from_slice(reader.invalid_documents());
+
+        if self.index_documents_method == IndexDocumentsMethod::EditDocuments {
+            return self.read_document_edits(
+                reader,
+                documents_batch,
+                wtxn,
+                progress_callback,
+                should_abort,
+            );
+        }
+
         let (mut cursor, fields_index) = reader.into_cursor_and_fields_index();
         let external_documents_ids = self.index.external_documents_ids(wtxn)?;
         let mapping = create_fields_mapping(&mut self.fields_ids_map, &fields_index)?;
@@ -172,6 +239,12 @@ impl<'a, 'i> Transform<'a, 'i> {
         let primary_key_id =
             self.fields_ids_map.insert(&primary_key).ok_or(UserError::AttributeLimitReached)?;
 
+        // when the field isn't known yet, none of the stored documents can have a version.
+        let version_field_id = match self.index.version_field(wtxn)? {
+            Some(version_field) => self.fields_ids_map.id(version_field),
+            None => None,
+        };
+
         let mut obkv_buffer = Vec::new();
         let mut document_sorter_buffer = Vec::new();
         let mut documents_count = 0;
@@ -219,6 +292,26 @@ impl<'a, 'i> Transform<'a, 'i> {
                 writer.insert(*k, v)?;
             }
 
+            if let Some(version_field_id) = version_field_id {
+                let version = document_version(KvReader::new(&obkv_buffer), version_field_id)?;
+                if let Some(version) = version {
+                    if self.is_older_than_indexed(
+                        wtxn,
+                        &external_documents_ids,
+                        external_id,
+                        version_field_id,
+                        version,
+                    )? {
+                        self.skipped_documents += 1;
+                        field_buffer = drop_and_reuse(field_buffer_cache);
+                        docid_buffer.clear();
+                        obkv_buffer.clear();
+                        continue;
+                    }
+                    self.pending_versions.insert(external_id.into(), version);
+                }
+            }
+
             let mut original_docid = None;
 
             let docid = match self.new_external_documents_ids_builder.entry((*external_id).into()) {
@@ -264,11 +357,15 @@ impl<'a, 'i> Transform<'a, 'i> {
                     skip_insertion = true;
                 } else {
                     // we associate the base document with the new key, everything will get merged later.
+                    self.original_documents_ids.insert(docid, original_docid);
                     document_sorter_buffer.clear();
                     document_sorter_buffer.push(Operation::Addition as u8);
                     document_sorter_buffer.extend_from_slice(base_obkv);
                     self.original_sorter.insert(docid.to_be_bytes(), &document_sorter_buffer)?;
-                    match self.flatten_from_fields_ids_map(KvReader::new(base_obkv))? {
+                    match Self::flatten_from_fields_ids_map(
+                        &mut self.fields_ids_map,
+                        KvReader::new(base_obkv),
+                    )? {
                         Some(flattened_obkv) => {
                             // we recreate our buffer with the flattened documents
                             document_sorter_buffer.clear();
@@ -293,7 +390,10 @@ impl<'a, 'i> Transform<'a, 'i> {
                 // We use the extracted/generated user id as the key for this document.
                 self.original_sorter.insert(docid.to_be_bytes(), &document_sorter_buffer)?;
 
-                match self.flatten_from_fields_ids_map(KvReader::new(&obkv_buffer))? {
+                match Self::flatten_from_fields_ids_map(
+                    &mut self.fields_ids_map,
+                    KvReader::new(&obkv_buffer),
+                )? {
                     Some(flattened_obkv) => {
                         document_sorter_buffer.clear();
                         document_sorter_buffer.push(Operation::Addition as u8);
@@ -329,6 +429,181 @@ impl<'a, 'i> Transform<'a, 'i> {
         Ok(documents_count)
     }
 
+    /// Returns `true` when the document with the same external id, either added earlier in this
+    /// update or stored in the database, has a greater version than the new document. The
+    /// versions are compared as numbers, the documents with a missing or non-numeric version
+    /// are always indexed.
+    fn is_older_than_indexed(
+        &self,
+        rtxn: &RoTxn,
+        external_documents_ids: &ExternalDocumentsIds,
+        external_id: &str,
+        version_field_id: FieldId,
+        version: f64,
+    ) -> Result<bool> {
+        // the document added earlier in this update replaces the stored one.
+        if self.new_external_documents_ids_builder.contains_key(external_id) {
+            return Ok(self.pending_versions.get(external_id).map_or(false, |p| version < *p));
+        }
+
+        let docid = match external_documents_ids.get(external_id) {
+            Some(docid) => docid,
+            None => return Ok(false),
+        };
+
+        // the document was removed earlier in this update, it's as if it never existed.
+        if self.replaced_documents_ids.contains(docid) {
+            return Ok(false);
+        }
+
+        let stored = self.index.documents.get(rtxn, &BEU32::new(docid))?.ok_or(
+            InternalError::DatabaseMissingEntry { db_name: db_name::DOCUMENTS, key: None },
+        )?;
+
+        match document_version(stored, version_field_id)? {
+            Some(stored) => Ok(version < stored),
+            None => Ok(false),
+        }
+    }
+
+    /// The `EditDocuments` version of `read_documents`, the documents of the batch contain
+    /// the field edits to apply on the documents of the database.
+    ///
+    /// The documents that don't exist in the database, or that were removed before, are ignored.
+    /// The base version of an edited document is pushed in the sorter the first time we see it,
+    /// the field edits are then applied on it when the sorter merges its entries.
+    fn read_document_edits<R, FP, FA>(
+        &mut self,
+        reader: EnrichedDocumentsBatchReader<R>,
+        documents_batch: usize,
+        wtxn: &mut heed::RwTxn,
+        progress_callback: FP,
+        should_abort: FA,
+    ) -> Result<usize>
+    where
+        R: Read + Seek,
+        FP: Fn(UpdateIndexingStep) + Sync,
+        FA: Fn() -> bool + Sync,
+    {
+        let (mut cursor, fields_index) = reader.into_cursor_and_fields_index();
+        let external_documents_ids = self.index.external_documents_ids(wtxn)?;
+        let primary_key = cursor.primary_key().to_string();
+
+        let mut obkv_buffer = Vec::new();
+        let mut document_sorter_buffer = Vec::new();
+        let mut documents_count = 0;
+        let mut field_edits: BTreeMap<FieldId, Vec<FieldEdit>> = BTreeMap::new();
+        while let Some(enriched_document) = cursor.next_enriched_document()? {
+            let EnrichedDocument { document, document_id } = enriched_document;
+
+            if should_abort() {
+                return Err(Error::InternalError(InternalError::AbortedIndexation));
+            }
+
+            if self.indexer_settings.log_every_n.map_or(false, |len| documents_count % len == 0) {
+                progress_callback(UpdateIndexingStep::RemapDocumentAddition {
+                    documents_seen: documents_count,
+                });
+            }
+
+            // The edits are grouped by top-level field, a nested path like `author.name`
+            // is stored under the `author` field.
+            field_edits.clear();
+            for (k, v) in document.iter() {
+                let name = fields_index
+                    .name(k)
+                    .ok_or(InternalError::FieldIdMappingMissingEntry { key: k })?;
+                if name == primary_key {
+                    continue;
+                }
+
+                let value = serde_json::from_slice(v).map_err(InternalError::SerdeJson)?;
+                let edit = FieldEdit::from_json(name, value)?;
+                let top_level_name = name.split('.').next().unwrap_or(name);
+                let fid = self
+                    .fields_ids_map
+                    .insert(top_level_name)
+                    .ok_or(UserError::AttributeLimitReached)?;
+                field_edits.entry(fid).or_default().push(edit);
+            }
+
+            let external_id = document_id.value();
+            let docid = match self.new_external_documents_ids_builder.entry((*external_id).into()) {
+                Entry::Occupied(entry) => {
+                    let docid = *entry.get() as u32;
+                    if let Some(edited) = self.edited_documents.get_mut(&docid) {
+                        edited.documents_batch = documents_batch;
+                    }
+                    docid
+                }
+                Entry::Vacant(entry) => {
+                    // An edition never creates a document, it must already be in the db
+                    // and must not have been removed by the remove_documents method.
+                    let original_docid = match external_documents_ids.get(entry.key()) {
+                        Some(docid) if !self.replaced_documents_ids.contains(docid) => docid,
+                        _ => continue,
+                    };
+
+                    let original_key = BEU32::new(original_docid);
+                    let base_obkv = self
+                        .index
+                        .documents
+                        .remap_data_type::<heed::types::ByteSlice>()
+                        .get(wtxn, &original_key)?
+                        .ok_or(InternalError::DatabaseMissingEntry {
+                            db_name: db_name::DOCUMENTS,
+                            key: None,
+                        })?;
+
+                    let docid = self
+                        .available_documents_ids
+                        .next()
+                        .ok_or(UserError::DocumentLimitReached)?;
+                    let external_id = entry.key().clone();
+                    entry.insert(docid as u64);
+                    self.replaced_documents_ids.insert(original_docid);
+                    self.new_documents_ids.insert(docid);
+                    self.edited_documents.insert(
+                        docid,
+                        EditedDocument { original_docid, external_id, documents_batch },
+                    );
+
+                    // we associate the base document with the new key, the edits will be applied on it later.
+                    document_sorter_buffer.clear();
+                    document_sorter_buffer.push(Operation::Addition as u8);
+                    document_sorter_buffer.extend_from_slice(base_obkv);
+                    self.original_sorter.insert(docid.to_be_bytes(), &document_sorter_buffer)?;
+                    docid
+                }
+            };
+
+            obkv_buffer.clear();
+            let mut writer = obkv::KvWriter::new(&mut obkv_buffer);
+            for (fid, edits) in &field_edits {
+                let edits = serde_json::to_vec(edits).map_err(InternalError::SerdeJson)?;
+                writer.insert(*fid, edits)?;
+            }
+
+            document_sorter_buffer.clear();
+            document_sorter_buffer.push(Operation::Edition as u8);
+            document_sorter_buffer.extend_from_slice(&obkv_buffer);
+            self.original_sorter.insert(docid.to_be_bytes(), &document_sorter_buffer)?;
+            documents_count += 1;
+
+            progress_callback(UpdateIndexingStep::RemapDocumentAddition {
+                documents_seen: documents_count,
+            });
+        }
+
+        progress_callback(UpdateIndexingStep::RemapDocumentAddition {
+            documents_seen: documents_count,
+        });
+
+        self.index.put_fields_ids_map(wtxn, &self.fields_ids_map)?;
+        self.documents_count += documents_count;
+        Ok(documents_count)
+    }
+
     /// The counter part of `read_documents` that removes documents either from the transform or the database.
     /// It can be called before, after or in between two calls of the `read_documents`.
     ///
@@ -375,6 +650,7 @@ impl<'a, 'i> Transform<'a, 'i> {
                     // 2. It wasn't in it because the document was created by a previous batch and since
                     //    we're removing it there is nothing to do.
                     self.new_documents_ids.remove(doc_id);
+                    self.pending_versions.remove(entry.key());
                     entry.remove_entry();
                 }
                 Entry::Vacant(entry) => {
@@ -396,9 +672,12 @@ impl<'a, 'i> Transform<'a, 'i> {
         Ok(documents_deleted)
     }
 
-    // Flatten a document from the fields ids map contained in self and insert the new
+    // Flatten a document from the given fields ids map and insert the new
     // created fields. Returns `None` if the document doesn't need to be flattened.
-    fn flatten_from_fields_ids_map(&mut self, obkv: KvReader<FieldId>) -> Result<Option<Vec<u8>>> {
+    fn flatten_from_fields_ids_map(
+        fields_ids_map: &mut FieldsIdsMap,
+        obkv: KvReader<FieldId>,
+    ) -> Result<Option<Vec<u8>>> {
         if obkv
             .iter()
             .all(|(_, value)| !json_depth_checker::should_flatten_from_unchecked_slice(value))
@@ -419,7 +698,7 @@ impl<'a, 'i> Transform<'a, 'i> {
         // all the raw values get inserted directly in the `key_value` vec.
         for (key, value) in obkv.iter() {
             if json_depth_checker::should_flatten_from_unchecked_slice(value) {
-                let key = self.fields_ids_map.name(key).ok_or(FieldIdMapMissingEntry::FieldId {
+                let key = fields_ids_map.name(key).ok_or(FieldIdMapMissingEntry::FieldId {
                     field_id: key,
                     process: "Flatten from fields ids map.",
                 })?;
@@ -437,7 +716,7 @@ impl<'a, 'i> Transform<'a, 'i> {
         // Once we have the flattened version we insert all the new generated fields_ids
         // (if any) in the fields ids map and serialize the value.
         for (key, value) in flattened.into_iter() {
-            let fid = self.fields_ids_map.insert(&key).ok_or(UserError::AttributeLimitReached)?;
+            let fid = fields_ids_map.insert(&key).ok_or(UserError::AttributeLimitReached)?;
             let value = serde_json::to_vec(&value).map_err(InternalError::SerdeJson)?;
             key_value.push((fid, value.into()));
         }
@@ -552,7 +831,7 @@ impl<'a, 'i> Transform<'a, 'i> {
     /// format like CSV, JSON or JSON stream. This sorter must contain a key that is the document
     /// id for the user side and the value must be an obkv where keys are valid fields ids.
     pub(crate) fn output_from_sorter<F>(
-        self,
+        mut self,
         wtxn: &mut heed::RwTxn,
         progress_callback: F,
     ) -> Result<TransformOutput>
@@ -584,13 +863,71 @@ impl<'a, 'i> Transform<'a, 'i> {
 
         self.remove_deleted_documents_from_field_distribution(wtxn, &mut field_distribution)?;
 
+        // We create a final writer to write the flattened documents in order. When editing the
+        // documents, the flattened versions can only be computed once the edits have been applied.
+        let mut flattened_writer = create_writer(
+            self.indexer_settings.chunk_compression_type,
+            self.indexer_settings.chunk_compression_level,
+            tempfile::tempfile()?,
+        );
+        let flatten_merged_documents =
+            self.index_documents_method == IndexDocumentsMethod::EditDocuments;
+
+        // The replaced documents are updated in place, only their changed fields are indexed
+        // again. When all their searchable fields but the primary key changed there are no
+        // words to keep: they are replaced and all their fields are indexed again.
+        let searchable_fields: Option<Vec<String>> = self
+            .index
+            .searchable_fields(wtxn)?
+            .map(|fields| fields.into_iter().map(String::from).collect());
+        let is_searchable = |name: &str| {
+            searchable_fields.as_ref().map_or(true, |searchable_fields| {
+                searchable_fields.iter().any(|searchable| {
+                    is_faceted_by(name, searchable) || is_faceted_by(searchable, name)
+                })
+            })
+        };
+        let mut in_place_original_sorter = create_sorter(
+            grenad::SortAlgorithm::Stable,
+            keep_first,
+            self.indexer_settings.chunk_compression_type,
+            self.indexer_settings.chunk_compression_level,
+            self.indexer_settings.max_nb_chunks,
+            self.indexer_settings.max_memory.map(|mem| mem / 2),
+        );
+        let mut in_place_flattened_sorter = create_sorter(
+            grenad::SortAlgorithm::Stable,
+            keep_first,
+            self.indexer_settings.chunk_compression_type,
+            self.indexer_settings.chunk_compression_level,
+            self.indexer_settings.max_nb_chunks,
+            self.indexer_settings.max_memory.map(|mem| mem / 2),
+        );
+        let mut in_place_searchable_sorter = create_sorter(
+            grenad::SortAlgorithm::Stable,
+            keep_first,
+            self.indexer_settings.chunk_compression_type,
+            self.indexer_settings.chunk_compression_level,
+            self.indexer_settings.max_nb_chunks,
+            self.indexer_settings.max_memory.map(|mem| mem / 2),
+        );
+        let mut in_place_documents_ids = RoaringBitmap::new();
+        let mut in_place_searchable_documents_ids = RoaringBitmap::new();
+        let mut changed_fields_docids = HashMap::<FieldId, RoaringBitmap>::new();
+
+        // The edited documents are validated once their edits are applied.
+        let document_schema = self.index.document_schema(wtxn)?;
+        let mut skipped_documents_ids = RoaringBitmap::new();
+        let mut invalid_edited_documents = BTreeMap::<usize, Vec<InvalidDocument>>::new();
+
         // Here we are going to do the document count + field distribution + `write_into_stream_writer`
         let mut iter = self.original_sorter.into_stream_merger_iter()?;
         // used only for the callback
         let mut documents_count = 0;
 
         while let Some((key, val)) = iter.next()? {
-            if val[0] == Operation::Deletion as u8 {
+            // We skip the deleted documents and the edits that were never merged with a document.
+            if val[0] != Operation::Addition as u8 {
                 continue;
             }
             let val = &val[1..];
@@ -602,6 +939,54 @@ impl<'a, 'i> Transform<'a, 'i> {
                 total_documents: self.documents_count,
             });
 
+            let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
+            if let Some(edited) =
+                self.edited_documents.get(&docid).filter(|_| !document_schema.is_empty())
+            {
+                let object = all_obkv_to_json(KvReader::new(val), &self.fields_ids_map)?;
+                if let Some(reason) = document_schema.validate(&object, false) {
+                    let document_id = edited.external_id.to_string();
+                    match document_schema.on_invalid_document {
+                        InvalidDocumentPolicy::Reject => {
+                            return Err(UserError::InvalidDocumentSchema { document_id, reason })?;
+                        }
+                        InvalidDocumentPolicy::Skip => {
+                            // The document keeps its stored version, the new id is left unused.
+                            let original_docid = edited.original_docid;
+                            skipped_documents_ids.insert(docid);
+                            self.replaced_documents_ids.remove(original_docid);
+                            self.new_documents_ids.remove(docid);
+
+                            // The stored version was removed from the field distribution
+                            // along with the replaced documents, we count it back.
+                            let original = self
+                                .index
+                                .documents
+                                .get(wtxn, &BEU32::new(original_docid))?
+                                .ok_or(InternalError::DatabaseMissingEntry {
+                                    db_name: db_name::DOCUMENTS,
+                                    key: None,
+                                })?;
+                            for (key, _) in original.iter() {
+                                let name = self.fields_ids_map.name(key).ok_or(
+                                    FieldIdMapMissingEntry::FieldId {
+                                        field_id: key,
+                                        process: "Computing field distribution in transform.",
+                                    },
+                                )?;
+                                *field_distribution.entry(name.to_string()).or_insert(0) += 1;
+                            }
+
+                            invalid_edited_documents
+                                .entry(edited.documents_batch)
+                                .or_default()
+                                .push(InvalidDocument { document_id, reason });
+                            continue;
+                        }
+                    }
+                }
+            }
+
             // We increment all the field of the current document in the field distribution.
             let obkv = KvReader::new(val);
 
@@ -613,36 +998,157 @@ impl<'a, 'i> Transform<'a, 'i> {
                     })?;
                 *field_distribution.entry(name.to_string()).or_insert(0) += 1;
             }
+
+            if let Some(&original_docid) = self.original_documents_ids.get(&docid) {
+                let changed_fields =
+                    self.changed_fields(wtxn, original_docid, KvReader::new(val))?;
+                let mut changed_names = Vec::with_capacity(changed_fields.len());
+                for &field_id in &changed_fields {
+                    let name = self.fields_ids_map.name(field_id).ok_or(
+                        FieldIdMapMissingEntry::FieldId {
+                            field_id,
+                            process: "Computing the changed fields in transform.",
+                        },
+                    )?;
+                    changed_names.push(name.to_string());
+                }
+
+                let is_searchable_change =
+                    changed_names.iter().any(|changed| is_searchable(changed.as_str()));
+                let keeps_searchable_fields = obkv.iter().any(|(field_id, _)| {
+                    !changed_fields.contains(&field_id)
+                        && self.fields_ids_map.name(field_id).map_or(false, |name| {
+                            !is_faceted_by(&primary_key, name) && is_searchable(name)
+                        })
+                });
+
+                if !is_searchable_change || keeps_searchable_fields {
+                    // The document keeps its internal id, the new one is left unused.
+                    in_place_documents_ids.insert(docid);
+                    self.replaced_documents_ids.remove(original_docid);
+                    self.new_documents_ids.remove(docid);
+
+                    let original_key = original_docid.to_be_bytes();
+                    in_place_original_sorter.insert(original_key, val)?;
+
+                    // Only the changed fields are kept, they are the only ones to index again.
+                    let flattened = match Self::flatten_from_fields_ids_map(
+                        &mut self.fields_ids_map,
+                        KvReader::new(val),
+                    )? {
+                        Some(flattened_obkv) => Cow::Owned(flattened_obkv),
+                        None => Cow::Borrowed(val),
+                    };
+                    let mut changed_fields_writer = obkv::KvWriterU16::memory();
+                    let mut searchable_fields_writer = obkv::KvWriterU16::memory();
+                    for (field_id, value) in KvReader::<FieldId>::new(&flattened).iter() {
+                        let name = self.fields_ids_map.name(field_id).unwrap_or_default();
+                        if changed_names.iter().any(|changed| is_faceted_by(name, changed)) {
+                            changed_fields_writer.insert(field_id, value)?;
+                            if is_searchable(name) {
+                                searchable_fields_writer.insert(field_id, value)?;
+                            }
+                        }
+                    }
+                    in_place_flattened_sorter
+                        .insert(original_key, changed_fields_writer.into_inner()?)?;
+                    // The words of the changed searchable fields are extracted again, even
+                    // when they were removed from the document.
+                    if is_searchable_change {
+                        in_place_searchable_documents_ids.insert(original_docid);
+                        in_place_searchable_sorter
+                            .insert(original_key, searchable_fields_writer.into_inner()?)?;
+                    }
+
+                    for field_id in changed_fields {
+                        changed_fields_docids.entry(field_id).or_default().insert(original_docid);
+                    }
+                    continue;
+                }
+            }
+
             writer.insert(key, val)?;
+
+            if flatten_merged_documents {
+                match Self::flatten_from_fields_ids_map(&mut self.fields_ids_map, obkv)? {
+                    Some(flattened_obkv) => flattened_writer.insert(key, flattened_obkv)?,
+                    None => flattened_writer.insert(key, val)?,
+                }
+            }
         }
 
         let mut original_documents = writer.into_inner()?;
         // We then extract the file and reset the seek to be able to read it again.
         original_documents.rewind()?;
 
-        // We create a final writer to write the new documents in order from the sorter.
-        let mut writer = create_writer(
-            self.indexer_settings.chunk_compression_type,
-            self.indexer_settings.chunk_compression_level,
-            tempfile::tempfile()?,
-        );
-
         // Once we have written all the documents into the final sorter, we write the nested documents
-        // into this writer.
+        // into the flattened writer, the flattened sorter is empty when editing the documents.
         // We get rids of the `Operation` byte and skip the deleted documents as well.
         let mut iter = self.flattened_sorter.into_stream_merger_iter()?;
         while let Some((key, val)) = iter.next()? {
-            if val[0] == Operation::Deletion as u8 {
+            let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
+            if val[0] == Operation::Deletion as u8 || in_place_documents_ids.contains(docid) {
                 continue;
             }
             let val = &val[1..];
-            writer.insert(key, val)?;
+            flattened_writer.insert(key, val)?;
         }
-        let mut flattened_documents = writer.into_inner()?;
+        let mut flattened_documents = flattened_writer.into_inner()?;
         flattened_documents.rewind()?;
 
-        let mut new_external_documents_ids_builder: Vec<_> =
-            self.new_external_documents_ids_builder.into_iter().collect();
+        let in_place_updates = if in_place_documents_ids.is_empty() {
+            None
+        } else {
+            let mut original_documents = create_writer(
+                self.indexer_settings.chunk_compression_type,
+                self.indexer_settings.chunk_compression_level,
+                tempfile::tempfile()?,
+            );
+            in_place_original_sorter.write_into_stream_writer(&mut original_documents)?;
+            let mut original_documents = original_documents.into_inner()?;
+            original_documents.rewind()?;
+
+            let mut flattened_documents = create_writer(
+                self.indexer_settings.chunk_compression_type,
+                self.indexer_settings.chunk_compression_level,
+                tempfile::tempfile()?,
+            );
+            in_place_flattened_sorter.write_into_stream_writer(&mut flattened_documents)?;
+            let mut flattened_documents = flattened_documents.into_inner()?;
+            flattened_documents.rewind()?;
+
+            let searchable_documents = if in_place_searchable_documents_ids.is_empty() {
+                None
+            } else {
+                let mut searchable_documents = create_writer(
+                    self.indexer_settings.chunk_compression_type,
+                    self.indexer_settings.chunk_compression_level,
+                    tempfile::tempfile()?,
+                );
+                in_place_searchable_sorter.write_into_stream_writer(&mut searchable_documents)?;
+                let mut searchable_documents = searchable_documents.into_inner()?;
+                searchable_documents.rewind()?;
+                Some(searchable_documents)
+            };
+
+            Some(InPlaceUpdates {
+                changed_fields_docids,
+                original_documents,
+                flattened_documents,
+                searchable_documents_ids: in_place_searchable_documents_ids,
+                searchable_documents,
+            })
+        };
+
+        // The documents updated in place and the skipped edited documents keep their external ids.
+        let mut new_external_documents_ids_builder: Vec<_> = self
+            .new_external_documents_ids_builder
+            .into_iter()
+            .filter(|(_, docid)| {
+                !in_place_documents_ids.contains(*docid as u32)
+                    && !skipped_documents_ids.contains(*docid as u32)
+            })
+            .collect();
 
         new_external_documents_ids_builder
             .sort_unstable_by(|(left, _), (right, _)| left.cmp(right));
@@ -663,9 +1169,37 @@ impl<'a, 'i> Transform<'a, 'i> {
             documents_count: self.documents_count,
             original_documents,
             flattened_documents,
+            in_place_updates,
+            invalid_edited_documents,
         })
     }
 
+    /// Returns the fields whose values differ between the stored version of the document
+    /// and the given one, including the fields only one of them contains.
+    fn changed_fields(
+        &self,
+        rtxn: &RoTxn,
+        docid: DocumentId,
+        document: KvReader<FieldId>,
+    ) -> Result<Vec<FieldId>> {
+        let stored = self.index.documents.get(rtxn, &BEU32::new(docid))?.ok_or(
+            InternalError::DatabaseMissingEntry { db_name: db_name::DOCUMENTS, key: None },
+        )?;
+
+        Ok(stored
+            .iter()
+            .merge_join_by(document.iter(), |(left, _), (right, _)| left.cmp(right))
+            .filter_map(|entry| match entry {
+                EitherOrBoth::Both((field_id, left), (_, right)) => {
+                    (left != right).then_some(field_id)
+                }
+                EitherOrBoth::Left((field_id, _)) | EitherOrBoth::Right((field_id, _)) => {
+                    Some(field_id)
+                }
+            })
+            .collect())
+    }
+
     /// Clear all databases. Returns a `TransformOutput` with a file that contains the documents
     /// of the index with the attributes reordered accordingly to the `FieldsIdsMap` given as argument.
     ///
@@ -782,6 +1316,8 @@ impl<'a, 'i> Transform<'a, 'i> {
             documents_count,
             original_documents,
             flattened_documents,
+            in_place_updates: None,
+            invalid_edited_documents: BTreeMap::new(),
         };
 
         let new_facets = output.compute_real_facets(wtxn, self.index)?;
@@ -792,6 +1328,126 @@ impl<'a, 'i> Transform<'a, 'i> {
 
         Ok(output)
     }
+
+    /// Returns a `TransformOutput` with the flattened version of the documents of the index and
+    /// clears the facet databases, for the facet values to be extracted again.
+    ///
+    /// The documents and the word databases are left untouched, the original documents file
+    /// is empty as nothing else than the facet databases must be reindexed.
+    pub fn prepare_for_facets_reindexing(
+        self,
+        wtxn: &mut heed::RwTxn<'i, '_>,
+    ) -> Result<TransformOutput> {
+        // There already has been a document addition, the primary key should be set by now.
+        let primary_key = self
+            .index
+            .primary_key(wtxn)?
+            .ok_or(InternalError::DatabaseMissingEntry {
+                db_name: db_name::MAIN,
+                key: Some(main_key::PRIMARY_KEY_KEY),
+            })?
+            .to_string();
+        let field_distribution = self.index.field_distribution(wtxn)?;
+        let external_documents_ids = self.index.external_documents_ids(wtxn)?.into_static();
+        let documents_ids = self.index.documents_ids(wtxn)?;
+        let documents_count = documents_ids.len() as usize;
+        let mut fields_ids_map = self.fields_ids_map;
+
+        let original_writer = create_writer(
+            self.indexer_settings.chunk_compression_type,
+            self.indexer_settings.chunk_compression_level,
+            tempfile::tempfile()?,
+        );
+
+        let mut flattened_writer = create_writer(
+            self.indexer_settings.chunk_compression_type,
+            self.indexer_settings.chunk_compression_level,
+            tempfile::tempfile()?,
+        );
+
+        for result in self.index.all_documents(wtxn)? {
+            let (docid, obkv) = result?;
+            let flattened_obkv = match Self::flatten_from_fields_ids_map(&mut fields_ids_map, obkv)?
+            {
+                Some(flattened_obkv) => flattened_obkv,
+                None => {
+                    let mut writer = obkv::KvWriterU16::memory();
+                    for (fid, value) in obkv.iter() {
+                        writer.insert(fid, value)?;
+                    }
+                    writer.into_inner()?
+                }
+            };
+            flattened_writer.insert(docid.to_be_bytes(), &flattened_obkv)?;
+        }
+
+        let mut original_documents = original_writer.into_inner()?;
+        original_documents.rewind()?;
+
+        let mut flattened_documents = flattened_writer.into_inner()?;
+        flattened_documents.rewind()?;
+
+        let output = TransformOutput {
+            primary_key,
+            fields_ids_map,
+            field_distribution,
+            external_documents_ids,
+            new_documents_ids: RoaringBitmap::default(),
+            replaced_documents_ids: RoaringBitmap::default(),
+            documents_count,
+            original_documents,
+            flattened_documents,
+            in_place_updates: None,
+            invalid_edited_documents: BTreeMap::new(),
+        };
+
+        // We clear the facet databases before changing the faceted fields,
+        // the faceted documents ids are stored by field.
+        clear_facet_databases(wtxn, self.index)?;
+
+        let new_facets = output.compute_real_facets(wtxn, self.index)?;
+        self.index.put_faceted_fields(wtxn, &new_facets)?;
+
+        Ok(output)
+    }
+}
+
+/// Clears the facet databases and the geo data along with the faceted documents ids
+/// of the currently faceted fields.
+fn clear_facet_databases(wtxn: &mut heed::RwTxn, index: &Index) -> Result<()> {
+    let empty_roaring = RoaringBitmap::default();
+
+    index.delete_geo_rtree(wtxn)?;
+    index.delete_geo_faceted_documents_ids(wtxn)?;
+    for field_id in index.faceted_fields_ids(wtxn)? {
+        index.put_faceted_documents_ids(wtxn, field_id, FacetType::Number, &empty_roaring)?;
+        index.put_faceted_documents_ids(wtxn, field_id, FacetType::String, &empty_roaring)?;
+    }
+
+    index.facet_id_f64_docids.clear(wtxn)?;
+    index.facet_id_string_docids.clear(wtxn)?;
+    index.facet_id_exists_docids.clear(wtxn)?;
+    index.facet_id_is_null_docids.clear(wtxn)?;
+    index.facet_id_is_empty_docids.clear(wtxn)?;
+    index.facet_id_is_string_docids.clear(wtxn)?;
+    index.facet_id_size_docids.clear(wtxn)?;
+    index.field_id_docid_facet_f64s.clear(wtxn)?;
+    index.field_id_docid_facet_strings.clear(wtxn)?;
+    index.field_id_docid_facet_element_f64s.clear(wtxn)?;
+    index.field_id_docid_facet_element_strings.clear(wtxn)?;
+
+    Ok(())
+}
+
+/// Returns the numeric version of the document, if any.
+fn document_version(document: KvReader<FieldId>, version_field_id: FieldId) -> Result<Option<f64>> {
+    match document.get(version_field_id) {
+        Some(value) => {
+            let value: Value = serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+            Ok(value.as_f64())
+        }
+        None => Ok(None),
+    }
 }
 
 /// Drops all the value of type `U` in vec, and reuses the allocation to create a `Vec<T>`.
@@ -1,5 +1,5 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::convert::TryInto;
 use std::fs::File;
 use std::io;
@@ -7,7 +7,7 @@ use std::io;
 use charabia::{Language, Script};
 use grenad::MergerBuilder;
 use heed::types::ByteSlice;
-use heed::{BytesDecode, RwTxn};
+use heed::{BytesDecode, BytesEncode, RwTxn};
 use roaring::RoaringBitmap;
 
 use super::helpers::{
@@ -20,13 +20,15 @@ use crate::update::facet::FacetsUpdate;
 use crate::update::index_documents::helpers::as_cloneable_grenad;
 use crate::{
     lat_lng_to_xyz, BoRoaringBitmapCodec, CboRoaringBitmapCodec, DocumentId, GeoPoint, Index,
-    Result,
+    Result, UncheckedU8StrStrCodec,
 };
 
 pub(crate) enum TypedChunk {
     DocidWordPositions(grenad::Reader<CursorClonableMmap>),
     FieldIdDocidFacetStrings(grenad::Reader<CursorClonableMmap>),
     FieldIdDocidFacetNumbers(grenad::Reader<CursorClonableMmap>),
+    FieldIdDocidFacetElementStrings(grenad::Reader<File>),
+    FieldIdDocidFacetElementNumbers(grenad::Reader<File>),
     Documents(grenad::Reader<CursorClonableMmap>),
     FieldIdWordcountDocids(grenad::Reader<File>),
     NewDocumentsIds(RoaringBitmap),
@@ -36,12 +38,17 @@ pub(crate) enum TypedChunk {
     },
     WordPositionDocids(grenad::Reader<File>),
     WordFidDocids(grenad::Reader<File>),
+    NgramWordDocids(grenad::Reader<File>),
+    PhoneticWordDocids(grenad::Reader<File>),
+    StemWordDocids(grenad::Reader<File>),
     WordPairProximityDocids(grenad::Reader<File>),
     FieldIdFacetStringDocids(grenad::Reader<File>),
     FieldIdFacetNumberDocids(grenad::Reader<File>),
     FieldIdFacetExistsDocids(grenad::Reader<File>),
     FieldIdFacetIsNullDocids(grenad::Reader<File>),
     FieldIdFacetIsEmptyDocids(grenad::Reader<File>),
+    FieldIdFacetIsStringDocids(grenad::Reader<File>),
+    FieldIdFacetSizeDocids(grenad::Reader<File>),
     GeoPoints(grenad::Reader<File>),
     ScriptLanguageDocids(HashMap<(Script, Language), RoaringBitmap>),
 }
@@ -154,6 +161,39 @@ pub(crate) fn write_typed_chunk_into_index(
             )?;
             is_merged_database = true;
         }
+        TypedChunk::NgramWordDocids(ngram_word_docids_iter) => {
+            append_entries_into_database(
+                ngram_word_docids_iter,
+                &index.ngram_word_docids,
+                wtxn,
+                index_is_empty,
+                |value, _buffer| Ok(value),
+                merge_cbo_roaring_bitmaps,
+            )?;
+            is_merged_database = true;
+        }
+        TypedChunk::PhoneticWordDocids(phonetic_word_docids_iter) => {
+            append_entries_into_database(
+                phonetic_word_docids_iter,
+                &index.phonetic_word_docids,
+                wtxn,
+                index_is_empty,
+                |value, _buffer| Ok(value),
+                merge_cbo_roaring_bitmaps,
+            )?;
+            is_merged_database = true;
+        }
+        TypedChunk::StemWordDocids(stem_word_docids_iter) => {
+            append_entries_into_database(
+                stem_word_docids_iter,
+                &index.stem_word_docids,
+                wtxn,
+                index_is_empty,
+                |value, _buffer| Ok(value),
+                merge_cbo_roaring_bitmaps,
+            )?;
+            is_merged_database = true;
+        }
         TypedChunk::FieldIdFacetNumberDocids(facet_id_number_docids_iter) => {
             let indexer = FacetsUpdate::new(index, FacetType::Number, facet_id_number_docids_iter);
             indexer.execute(wtxn)?;
@@ -197,6 +237,28 @@ pub(crate) fn write_typed_chunk_into_index(
             )?;
             is_merged_database = true;
         }
+        TypedChunk::FieldIdFacetIsStringDocids(facet_id_is_string_docids) => {
+            append_entries_into_database(
+                facet_id_is_string_docids,
+                &index.facet_id_is_string_docids,
+                wtxn,
+                index_is_empty,
+                |value, _buffer| Ok(value),
+                merge_cbo_roaring_bitmaps,
+            )?;
+            is_merged_database = true;
+        }
+        TypedChunk::FieldIdFacetSizeDocids(facet_id_size_docids) => {
+            append_entries_into_database(
+                facet_id_size_docids,
+                &index.facet_id_size_docids,
+                wtxn,
+                index_is_empty,
+                |value, _buffer| Ok(value),
+                merge_cbo_roaring_bitmaps,
+            )?;
+            is_merged_database = true;
+        }
         TypedChunk::WordPairProximityDocids(word_pair_proximity_docids_iter) => {
             append_entries_into_database(
                 word_pair_proximity_docids_iter,
@@ -228,6 +290,26 @@ pub(crate) fn write_typed_chunk_into_index(
                 }
             }
         }
+        TypedChunk::FieldIdDocidFacetElementNumbers(fid_docid_facet_element_number) => {
+            let index_fid_docid_facet_element_numbers =
+                index.field_id_docid_facet_element_f64s.remap_types::<ByteSlice, ByteSlice>();
+            let mut cursor = fid_docid_facet_element_number.into_cursor()?;
+            while let Some((key, value)) = cursor.move_on_next()? {
+                if valid_lmdb_key(key) {
+                    index_fid_docid_facet_element_numbers.put(wtxn, key, value)?;
+                }
+            }
+        }
+        TypedChunk::FieldIdDocidFacetElementStrings(fid_docid_facet_element_string) => {
+            let index_fid_docid_facet_element_strings =
+                index.field_id_docid_facet_element_strings.remap_types::<ByteSlice, ByteSlice>();
+            let mut cursor = fid_docid_facet_element_string.into_cursor()?;
+            while let Some((key, value)) = cursor.move_on_next()? {
+                if valid_lmdb_key(key) {
+                    index_fid_docid_facet_element_strings.put(wtxn, key, value)?;
+                }
+            }
+        }
         TypedChunk::GeoPoints(geo_points) => {
             let mut rtree = index.geo_rtree(wtxn)?.unwrap_or_default();
             let mut geo_faceted_docids = index.geo_faceted_documents_ids(wtxn)?;
@@ -272,6 +354,196 @@ pub(crate) fn write_typed_chunk_into_index(
     Ok((RoaringBitmap::new(), is_merged_database))
 }
 
+/// Remove the documents ids of the typed chunk from the corresponding LMDB databases of the
+/// provided index, along with the entries of the prefixes of its words.
+///
+/// Only the typed chunks of the word databases are supported,
+/// the words no document contains anymore are removed from the words fst.
+pub(crate) fn remove_typed_chunk_from_index(
+    typed_chunk: TypedChunk,
+    index: &Index,
+    wtxn: &mut RwTxn,
+    words_prefixes_fst: &fst::Set<Vec<u8>>,
+) -> Result<()> {
+    match typed_chunk {
+        TypedChunk::WordDocids { word_docids_reader, exact_word_docids_reader } => {
+            let mut words = BTreeSet::new();
+            for (reader, database, prefix_database) in [
+                (word_docids_reader, index.word_docids, index.word_prefix_docids),
+                (exact_word_docids_reader, index.exact_word_docids, index.exact_word_prefix_docids),
+            ] {
+                let database = database.remap_key_type::<ByteSlice>();
+                let prefix_database = prefix_database.remap_key_type::<ByteSlice>();
+                let mut cursor = reader.into_cursor()?;
+                while let Some((word, value)) = cursor.move_on_next()? {
+                    let docids = RoaringBitmap::deserialize_from(value)?;
+                    remove_docids_from_entry(wtxn, database, word, &docids)?;
+                    for prefix in word_prefixes(word, words_prefixes_fst) {
+                        remove_docids_from_entry(wtxn, prefix_database, prefix, &docids)?;
+                    }
+                    words.insert(word.to_vec());
+                }
+            }
+
+            let word_docids = index.word_docids.remap_key_type::<ByteSlice>();
+            let exact_word_docids = index.exact_word_docids.remap_key_type::<ByteSlice>();
+            let mut removed_words = fst::SetBuilder::memory();
+            for word in words {
+                if word_docids.get(wtxn, &word)?.is_none()
+                    && exact_word_docids.get(wtxn, &word)?.is_none()
+                {
+                    removed_words.insert(word)?;
+                }
+            }
+            let removed_words = removed_words.into_set();
+
+            if !removed_words.is_empty() {
+                let words_fst = index.words_fst(wtxn)?;
+                let difference = words_fst.op().add(&removed_words).difference();
+                let mut builder = fst::SetBuilder::memory();
+                builder.extend_stream(difference.into_stream())?;
+                let words_fst = builder.into_set();
+                index.put_words_fst(wtxn, &words_fst)?;
+            }
+        }
+        TypedChunk::WordPairProximityDocids(reader) => {
+            let database = index.word_pair_proximity_docids.remap_key_type::<ByteSlice>();
+            let word_prefix_database =
+                index.word_prefix_pair_proximity_docids.remap_key_type::<ByteSlice>();
+            let prefix_word_database =
+                index.prefix_word_pair_proximity_docids.remap_key_type::<ByteSlice>();
+            let mut cursor = reader.into_cursor()?;
+            while let Some((key, value)) = cursor.move_on_next()? {
+                let docids = CboRoaringBitmapCodec::deserialize_from(value)?;
+                remove_docids_from_entry(wtxn, database, key, &docids)?;
+
+                let (proximity, word1, word2) =
+                    UncheckedU8StrStrCodec::bytes_decode(key).ok_or(heed::Error::Decoding)?;
+                for prefix in word_prefixes(word2, words_prefixes_fst) {
+                    let key = UncheckedU8StrStrCodec::bytes_encode(&(proximity, word1, prefix))
+                        .ok_or(heed::Error::Encoding)?;
+                    remove_docids_from_entry(wtxn, word_prefix_database, &key, &docids)?;
+                }
+                for prefix in word_prefixes(word1, words_prefixes_fst) {
+                    let key = UncheckedU8StrStrCodec::bytes_encode(&(proximity, prefix, word2))
+                        .ok_or(heed::Error::Encoding)?;
+                    remove_docids_from_entry(wtxn, prefix_word_database, &key, &docids)?;
+                }
+            }
+        }
+        TypedChunk::WordPositionDocids(reader) => {
+            remove_word_integer_entries(
+                reader,
+                index.word_position_docids.remap_key_type(),
+                index.word_prefix_position_docids.remap_key_type(),
+                wtxn,
+                words_prefixes_fst,
+            )?;
+        }
+        TypedChunk::WordFidDocids(reader) => {
+            remove_word_integer_entries(
+                reader,
+                index.word_fid_docids.remap_key_type(),
+                index.word_prefix_fid_docids.remap_key_type(),
+                wtxn,
+                words_prefixes_fst,
+            )?;
+        }
+        TypedChunk::FieldIdWordcountDocids(reader) => {
+            remove_entries(reader, index.field_id_word_count_docids.remap_key_type(), wtxn)?;
+        }
+        TypedChunk::NgramWordDocids(reader) => {
+            remove_entries(reader, index.ngram_word_docids.remap_key_type(), wtxn)?;
+        }
+        TypedChunk::PhoneticWordDocids(reader) => {
+            remove_entries(reader, index.phonetic_word_docids.remap_key_type(), wtxn)?;
+        }
+        TypedChunk::StemWordDocids(reader) => {
+            remove_entries(reader, index.stem_word_docids.remap_key_type(), wtxn)?;
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
+/// Remove the documents ids of the provided entries from the database.
+fn remove_entries(
+    data: grenad::Reader<File>,
+    database: heed::Database<ByteSlice, CboRoaringBitmapCodec>,
+    wtxn: &mut RwTxn,
+) -> Result<()> {
+    let mut cursor = data.into_cursor()?;
+    while let Some((key, value)) = cursor.move_on_next()? {
+        let docids = CboRoaringBitmapCodec::deserialize_from(value)?;
+        remove_docids_from_entry(wtxn, database, key, &docids)?;
+    }
+
+    Ok(())
+}
+
+/// Remove the documents ids of the provided word and integer entries from the database,
+/// and from the entries of the prefixes of the words in the prefix database.
+fn remove_word_integer_entries(
+    data: grenad::Reader<File>,
+    database: heed::Database<ByteSlice, CboRoaringBitmapCodec>,
+    prefix_database: heed::Database<ByteSlice, CboRoaringBitmapCodec>,
+    wtxn: &mut RwTxn,
+    words_prefixes_fst: &fst::Set<Vec<u8>>,
+) -> Result<()> {
+    let mut key_buffer = Vec::new();
+    let mut cursor = data.into_cursor()?;
+    while let Some((key, value)) = cursor.move_on_next()? {
+        let docids = CboRoaringBitmapCodec::deserialize_from(value)?;
+        remove_docids_from_entry(wtxn, database, key, &docids)?;
+
+        // The keys are made of the word, a nul byte and the big endian u16.
+        let (word, integer) = key.split_at(key.len().saturating_sub(3));
+        for prefix in word_prefixes(word, words_prefixes_fst) {
+            key_buffer.clear();
+            key_buffer.extend_from_slice(prefix);
+            key_buffer.extend_from_slice(integer);
+            remove_docids_from_entry(wtxn, prefix_database, &key_buffer, &docids)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove the documents ids from the entry of the database,
+/// the entry is deleted when no documents ids are left.
+fn remove_docids_from_entry<C>(
+    wtxn: &mut RwTxn,
+    database: heed::Database<ByteSlice, C>,
+    key: &[u8],
+    docids: &RoaringBitmap,
+) -> Result<()>
+where
+    C: for<'a> BytesDecode<'a, DItem = RoaringBitmap>
+        + for<'a> BytesEncode<'a, EItem = RoaringBitmap>,
+{
+    if let Some(mut entry_docids) = database.get(wtxn, key)? {
+        entry_docids -= docids;
+        if entry_docids.is_empty() {
+            database.delete(wtxn, key)?;
+        } else {
+            database.put(wtxn, key, &entry_docids)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the prefixes of the word that are part of the words prefixes fst.
+fn word_prefixes<'a>(
+    word: &'a [u8],
+    words_prefixes_fst: &'a fst::Set<Vec<u8>>,
+) -> impl Iterator<Item = &'a [u8]> + 'a {
+    (1..=word.len())
+        .map(move |len| &word[..len])
+        .filter(move |prefix| words_prefixes_fst.contains(prefix))
+}
+
 fn merge_word_docids_reader_into_fst(
     word_docids_iter: grenad::Reader<io::Cursor<ClonableMmap>>,
     exact_word_docids_iter: grenad::Reader<io::Cursor<ClonableMmap>>,
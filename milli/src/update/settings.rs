@@ -1,4 +1,4 @@
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::result::Result as StdResult;
 
 use charabia::{Tokenizer, TokenizerBuilder};
@@ -14,7 +14,10 @@ use crate::error::UserError;
 use crate::index::{DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS};
 use crate::update::index_documents::IndexDocumentsMethod;
 use crate::update::{IndexDocuments, UpdateIndexingStep};
-use crate::{FieldsIdsMap, Index, Result};
+use crate::{
+    parse_locale, stemming_algorithm, DocumentSchema, FieldsIdsMap, Index, IngestionRule,
+    IngestionRules, OrderBy, Result,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Setting<T> {
@@ -112,16 +115,45 @@ pub struct Settings<'a, 't, 'u, 'i> {
     sortable_fields: Setting<HashSet<String>>,
     criteria: Setting<Vec<Criterion>>,
     stop_words: Setting<BTreeSet<String>>,
+    /// Tokens considered as separators in addition to the default ones.
+    separator_tokens: Setting<BTreeSet<String>>,
+    /// Default separators that must not be considered as separators.
+    non_separator_tokens: Setting<BTreeSet<String>>,
+    /// Words that must be kept as a single token.
+    dictionary: Setting<BTreeSet<String>>,
+    /// The locales the language detection of the tokenizer is restricted to, by attribute name.
+    localized_attributes: Setting<BTreeMap<String, Vec<String>>>,
+    /// The locales of the languages in which the words are stemmed.
+    stemming: Setting<BTreeSet<String>>,
     distinct_field: Setting<String>,
+    /// The field compared to discard the documents older than the stored ones.
+    version_field: Setting<String>,
+    /// The field holding the date after which the documents are removed.
+    expiry_field: Setting<String>,
+    /// The rules applied to the documents before they are indexed.
+    ingestion_rules: Setting<Vec<IngestionRule>>,
+    /// The version given to the ingestion rules, bumped automatically when not specified.
+    ingestion_rules_version: Option<u32>,
+    /// The types and the required fields the documents must match to be indexed.
+    document_schema: Setting<DocumentSchema>,
     synonyms: Setting<HashMap<String, Vec<String>>>,
     primary_key: Setting<String>,
     authorize_typos: Setting<bool>,
     min_word_len_two_typos: Setting<u8>,
     min_word_len_one_typo: Setting<u8>,
+    /// Disable the typo tolerance on the words only made of digits.
+    disable_typos_on_numbers: Setting<bool>,
     exact_words: Setting<BTreeSet<String>>,
     /// Attributes on which typo tolerance is disabled.
     exact_attributes: Setting<HashSet<String>>,
+    /// Attributes whose words can be matched by a part of them.
+    infix_attributes: Setting<HashSet<String>>,
+    /// Attributes whose words can be matched by similar sounding words.
+    phonetic_attributes: Setting<HashSet<String>>,
     max_values_per_facet: Setting<usize>,
+    sort_facet_values_by: Setting<HashMap<String, OrderBy>>,
+    /// The separator of the levels of the hierarchical facets, by facet name.
+    hierarchical_facets: Setting<BTreeMap<String, String>>,
     pagination_max_total_hits: Setting<usize>,
 }
 
@@ -140,15 +172,30 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             sortable_fields: Setting::NotSet,
             criteria: Setting::NotSet,
             stop_words: Setting::NotSet,
+            separator_tokens: Setting::NotSet,
+            non_separator_tokens: Setting::NotSet,
+            dictionary: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
+            stemming: Setting::NotSet,
             distinct_field: Setting::NotSet,
+            version_field: Setting::NotSet,
+            expiry_field: Setting::NotSet,
+            ingestion_rules: Setting::NotSet,
+            ingestion_rules_version: None,
+            document_schema: Setting::NotSet,
             synonyms: Setting::NotSet,
             primary_key: Setting::NotSet,
             authorize_typos: Setting::NotSet,
             exact_words: Setting::NotSet,
             min_word_len_two_typos: Setting::NotSet,
             min_word_len_one_typo: Setting::NotSet,
+            disable_typos_on_numbers: Setting::NotSet,
             exact_attributes: Setting::NotSet,
+            infix_attributes: Setting::NotSet,
+            phonetic_attributes: Setting::NotSet,
             max_values_per_facet: Setting::NotSet,
+            sort_facet_values_by: Setting::NotSet,
+            hierarchical_facets: Setting::NotSet,
             pagination_max_total_hits: Setting::NotSet,
             indexer_config,
         }
@@ -198,6 +245,30 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.stop_words = Setting::Reset;
     }
 
+    pub fn set_separator_tokens(&mut self, separator_tokens: BTreeSet<String>) {
+        self.separator_tokens = Setting::Set(separator_tokens);
+    }
+
+    pub fn reset_separator_tokens(&mut self) {
+        self.separator_tokens = Setting::Reset;
+    }
+
+    pub fn set_non_separator_tokens(&mut self, non_separator_tokens: BTreeSet<String>) {
+        self.non_separator_tokens = Setting::Set(non_separator_tokens);
+    }
+
+    pub fn reset_non_separator_tokens(&mut self) {
+        self.non_separator_tokens = Setting::Reset;
+    }
+
+    pub fn set_dictionary(&mut self, dictionary: BTreeSet<String>) {
+        self.dictionary = Setting::Set(dictionary);
+    }
+
+    pub fn reset_dictionary(&mut self) {
+        self.dictionary = Setting::Reset;
+    }
+
     pub fn set_stop_words(&mut self, stop_words: BTreeSet<String>) {
         self.stop_words =
             if stop_words.is_empty() { Setting::Reset } else { Setting::Set(stop_words) }
@@ -211,6 +282,43 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.distinct_field = Setting::Set(distinct_field);
     }
 
+    pub fn reset_version_field(&mut self) {
+        self.version_field = Setting::Reset;
+    }
+
+    pub fn set_version_field(&mut self, version_field: String) {
+        self.version_field = Setting::Set(version_field);
+    }
+
+    pub fn reset_expiry_field(&mut self) {
+        self.expiry_field = Setting::Reset;
+    }
+
+    pub fn set_expiry_field(&mut self, expiry_field: String) {
+        self.expiry_field = Setting::Set(expiry_field);
+    }
+
+    pub fn reset_ingestion_rules(&mut self) {
+        self.ingestion_rules = Setting::Reset;
+    }
+
+    pub fn set_ingestion_rules(&mut self, rules: Vec<IngestionRule>) {
+        self.ingestion_rules = Setting::Set(rules);
+    }
+
+    /// Forces the version of the ingestion rules, e.g. when importing a dump.
+    pub fn set_ingestion_rules_version(&mut self, version: u32) {
+        self.ingestion_rules_version = Some(version);
+    }
+
+    pub fn reset_document_schema(&mut self) {
+        self.document_schema = Setting::Reset;
+    }
+
+    pub fn set_document_schema(&mut self, schema: DocumentSchema) {
+        self.document_schema = Setting::Set(schema);
+    }
+
     pub fn reset_synonyms(&mut self) {
         self.synonyms = Setting::Reset;
     }
@@ -251,6 +359,14 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.min_word_len_one_typo = Setting::Reset;
     }
 
+    pub fn set_disable_typos_on_numbers(&mut self, val: bool) {
+        self.disable_typos_on_numbers = Setting::Set(val);
+    }
+
+    pub fn reset_disable_typos_on_numbers(&mut self) {
+        self.disable_typos_on_numbers = Setting::Reset;
+    }
+
     pub fn set_exact_words(&mut self, words: BTreeSet<String>) {
         self.exact_words = Setting::Set(words);
     }
@@ -267,6 +383,22 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.exact_attributes = Setting::Reset;
     }
 
+    pub fn set_infix_attributes(&mut self, attrs: HashSet<String>) {
+        self.infix_attributes = Setting::Set(attrs);
+    }
+
+    pub fn reset_infix_attributes(&mut self) {
+        self.infix_attributes = Setting::Reset;
+    }
+
+    pub fn set_phonetic_attributes(&mut self, attrs: HashSet<String>) {
+        self.phonetic_attributes = Setting::Set(attrs);
+    }
+
+    pub fn reset_phonetic_attributes(&mut self) {
+        self.phonetic_attributes = Setting::Reset;
+    }
+
     pub fn set_max_values_per_facet(&mut self, value: usize) {
         self.max_values_per_facet = Setting::Set(value);
     }
@@ -275,6 +407,38 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.max_values_per_facet = Setting::Reset;
     }
 
+    pub fn set_sort_facet_values_by(&mut self, value: HashMap<String, OrderBy>) {
+        self.sort_facet_values_by = Setting::Set(value);
+    }
+
+    pub fn reset_sort_facet_values_by(&mut self) {
+        self.sort_facet_values_by = Setting::Reset;
+    }
+
+    pub fn set_localized_attributes(&mut self, value: BTreeMap<String, Vec<String>>) {
+        self.localized_attributes = Setting::Set(value);
+    }
+
+    pub fn reset_localized_attributes(&mut self) {
+        self.localized_attributes = Setting::Reset;
+    }
+
+    pub fn set_stemming(&mut self, locales: BTreeSet<String>) {
+        self.stemming = Setting::Set(locales);
+    }
+
+    pub fn reset_stemming(&mut self) {
+        self.stemming = Setting::Reset;
+    }
+
+    pub fn set_hierarchical_facets(&mut self, value: BTreeMap<String, String>) {
+        self.hierarchical_facets = Setting::Set(value);
+    }
+
+    pub fn reset_hierarchical_facets(&mut self) {
+        self.hierarchical_facets = Setting::Reset;
+    }
+
     pub fn set_pagination_max_total_hits(&mut self, value: usize) {
         self.pagination_max_total_hits = Setting::Set(value);
     }
@@ -330,6 +494,40 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(())
     }
 
+    fn reindex_facets<FP, FA>(&mut self, progress_callback: &FP, should_abort: &FA) -> Result<()>
+    where
+        FP: Fn(UpdateIndexingStep) + Sync,
+        FA: Fn() -> bool + Sync,
+    {
+        // if the settings are set before any document update, we don't need to do anything.
+        if self.index.number_of_documents(self.wtxn)? == 0 {
+            return Ok(());
+        }
+
+        let transform = Transform::new(
+            self.wtxn,
+            self.index,
+            self.indexer_config,
+            IndexDocumentsMethod::ReplaceDocuments,
+            false,
+        )?;
+
+        // We only clear the facet databases, the documents and the words are left untouched.
+        let output = transform.prepare_for_facets_reindexing(self.wtxn)?;
+
+        let indexing_builder = IndexDocuments::new(
+            self.wtxn,
+            self.index,
+            self.indexer_config,
+            IndexDocumentsConfig::default(),
+            &progress_callback,
+            &should_abort,
+        )?;
+        indexing_builder.execute_facets_raw(output)?;
+
+        Ok(())
+    }
+
     fn update_displayed(&mut self) -> Result<bool> {
         match self.displayed_fields {
             Setting::Set(ref fields) => {
@@ -358,6 +556,77 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(true)
     }
 
+    /// The version is only compared when documents are added, updating it never
+    /// requires to reindex the stored documents.
+    fn update_version_field(&mut self) -> Result<bool> {
+        match self.version_field {
+            Setting::Set(ref attr) => {
+                self.index.put_version_field(self.wtxn, attr)?;
+            }
+            Setting::Reset => {
+                self.index.delete_version_field(self.wtxn)?;
+            }
+            Setting::NotSet => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// The expiry field is faceted and its RFC 3339 dates are indexed as numbers,
+    /// changing it reindexes the facets of the documents.
+    fn update_expiry_field(&mut self) -> Result<bool> {
+        match self.expiry_field {
+            Setting::Set(ref attr) => {
+                if self.index.expiry_field(self.wtxn)? == Some(attr.as_str()) {
+                    return Ok(false);
+                }
+                self.index.put_expiry_field(self.wtxn, attr)?;
+                Ok(true)
+            }
+            Setting::Reset => Ok(self.index.delete_expiry_field(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    /// The rules are only applied to the documents indexed afterwards, no reindexing
+    /// is needed. The version is bumped every time the rules change, unless forced.
+    fn update_ingestion_rules(&mut self) -> Result<()> {
+        let rules = match self.ingestion_rules {
+            Setting::Set(ref rules) => rules.clone(),
+            Setting::Reset => Vec::new(),
+            Setting::NotSet => return Ok(()),
+        };
+
+        let current = self.index.ingestion_rules(self.wtxn)?;
+        let version = match self.ingestion_rules_version {
+            Some(version) => version,
+            None if current.rules == rules => return Ok(()),
+            None => current.version + 1,
+        };
+
+        if rules.is_empty() && version == 0 {
+            self.index.delete_ingestion_rules(self.wtxn)?;
+        } else {
+            self.index.put_ingestion_rules(self.wtxn, &IngestionRules { version, rules })?;
+        }
+
+        Ok(())
+    }
+
+    /// The schema is only checked against the documents indexed afterwards.
+    fn update_document_schema(&mut self) -> Result<()> {
+        match self.document_schema {
+            Setting::Set(ref schema) => {
+                self.index.put_document_schema(self.wtxn, schema)?;
+            }
+            Setting::Reset => {
+                self.index.delete_document_schema(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
     /// Updates the index's searchable attributes. This causes the field map to be recomputed to
     /// reflect the order of the searchable attributes.
     fn update_searchable(&mut self) -> Result<bool> {
@@ -433,10 +702,58 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         }
     }
 
+    fn update_separator_tokens(&mut self) -> Result<bool> {
+        match self.separator_tokens {
+            Setting::Set(ref separator_tokens) => {
+                let current = self.index.separator_tokens(self.wtxn)?;
+                if current.as_ref() != Some(separator_tokens) {
+                    self.index.put_separator_tokens(self.wtxn, separator_tokens)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_separator_tokens(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_non_separator_tokens(&mut self) -> Result<bool> {
+        match self.non_separator_tokens {
+            Setting::Set(ref non_separator_tokens) => {
+                let current = self.index.non_separator_tokens(self.wtxn)?;
+                if current.as_ref() != Some(non_separator_tokens) {
+                    self.index.put_non_separator_tokens(self.wtxn, non_separator_tokens)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_non_separator_tokens(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_dictionary(&mut self) -> Result<bool> {
+        match self.dictionary {
+            Setting::Set(ref dictionary) => {
+                let current = self.index.dictionary(self.wtxn)?;
+                if current.as_ref() != Some(dictionary) {
+                    self.index.put_dictionary(self.wtxn, dictionary)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_dictionary(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
     fn update_synonyms(&mut self) -> Result<bool> {
         match self.synonyms {
             Setting::Set(ref synonyms) => {
-                fn normalize(tokenizer: &Tokenizer<&[u8]>, text: &str) -> Vec<String> {
+                fn normalize(tokenizer: &Tokenizer, text: &str) -> Vec<String> {
                     tokenizer
                         .tokenize(text)
                         .filter_map(|token| {
@@ -454,6 +771,21 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
                 if let Some(ref stop_words) = stop_words {
                     builder.stop_words(stop_words);
                 }
+
+                let separators = self.index.allowed_separators(self.wtxn)?;
+                let separators: Option<Vec<_>> =
+                    separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+                if let Some(ref separators) = separators {
+                    builder.separators(separators);
+                }
+
+                let dictionary = self.index.dictionary(self.wtxn)?;
+                let dictionary: Option<Vec<_>> =
+                    dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+                if let Some(ref dictionary) = dictionary {
+                    builder.words_dict(dictionary);
+                }
+
                 let tokenizer = builder.build();
 
                 let mut new_synonyms = HashMap::new();
@@ -508,6 +840,44 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         }
     }
 
+    fn update_infix_attributes(&mut self) -> Result<bool> {
+        match self.infix_attributes {
+            Setting::Set(ref attrs) => {
+                let old_attrs = self.index.infix_attributes(self.wtxn)?;
+                let old_attrs = old_attrs.into_iter().map(String::from).collect::<HashSet<_>>();
+
+                if attrs != &old_attrs {
+                    let attrs = attrs.iter().map(String::as_str).collect::<Vec<_>>();
+                    self.index.put_infix_attributes(self.wtxn, &attrs)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_infix_attributes(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_phonetic_attributes(&mut self) -> Result<bool> {
+        match self.phonetic_attributes {
+            Setting::Set(ref attrs) => {
+                let old_attrs = self.index.phonetic_attributes(self.wtxn)?;
+                let old_attrs = old_attrs.into_iter().map(String::from).collect::<HashSet<_>>();
+
+                if attrs != &old_attrs {
+                    let attrs = attrs.iter().map(String::as_str).collect::<Vec<_>>();
+                    self.index.put_phonetic_attributes(self.wtxn, &attrs)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_phonetic_attributes(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
     fn update_filterable(&mut self) -> Result<()> {
         match self.filterable_fields {
             Setting::Set(ref fields) => {
@@ -600,6 +970,18 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         }
     }
 
+    fn update_disable_typos_on_numbers(&mut self) -> Result<()> {
+        match self.disable_typos_on_numbers {
+            Setting::Set(flag) => self.index.put_disable_typos_on_numbers(self.wtxn, flag)?,
+            Setting::Reset => {
+                self.index.delete_disable_typos_on_numbers(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
     fn update_min_typo_word_len(&mut self) -> Result<()> {
         let one = self.min_word_len_one_typo.or_reset(DEFAULT_MIN_WORD_LEN_ONE_TYPO);
         let two = self.min_word_len_two_typos.or_reset(DEFAULT_MIN_WORD_LEN_TWO_TYPOS);
@@ -637,7 +1019,7 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
     fn update_exact_words(&mut self) -> Result<()> {
         match self.exact_words {
             Setting::Set(ref mut words) => {
-                fn normalize(tokenizer: &Tokenizer<&[u8]>, text: &str) -> String {
+                fn normalize(tokenizer: &Tokenizer, text: &str) -> String {
                     tokenizer.tokenize(text).map(|token| token.lemma().to_string()).collect()
                 }
 
@@ -646,6 +1028,21 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
                 if let Some(ref stop_words) = stop_words {
                     builder.stop_words(stop_words);
                 }
+
+                let separators = self.index.allowed_separators(self.wtxn)?;
+                let separators: Option<Vec<_>> =
+                    separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+                if let Some(ref separators) = separators {
+                    builder.separators(separators);
+                }
+
+                let dictionary = self.index.dictionary(self.wtxn)?;
+                let dictionary: Option<Vec<_>> =
+                    dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+                if let Some(ref dictionary) = dictionary {
+                    builder.words_dict(dictionary);
+                }
+
                 let tokenizer = builder.build();
 
                 let mut words: Vec<_> =
@@ -680,6 +1077,90 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(())
     }
 
+    fn update_sort_facet_values_by(&mut self) -> Result<()> {
+        match self.sort_facet_values_by.as_ref() {
+            Setting::Set(value) => {
+                self.index.put_sort_facet_values_by(self.wtxn, value)?;
+            }
+            Setting::Reset => {
+                self.index.delete_sort_facet_values_by(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_hierarchical_facets(&mut self) -> Result<bool> {
+        match self.hierarchical_facets {
+            Setting::Set(ref hierarchical_facets) => {
+                if let Some((name, _)) =
+                    hierarchical_facets.iter().find(|(_, separator)| separator.is_empty())
+                {
+                    return Err(UserError::InvalidHierarchicalFacetSeparator {
+                        facet_name: name.clone(),
+                    }
+                    .into());
+                }
+
+                if hierarchical_facets != &self.index.hierarchical_facets(self.wtxn)? {
+                    self.index.put_hierarchical_facets(self.wtxn, hierarchical_facets)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_hierarchical_facets(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_localized_attributes(&mut self) -> Result<bool> {
+        match self.localized_attributes {
+            Setting::Set(ref localized_attributes) => {
+                for (attribute, locales) in localized_attributes {
+                    if let Some(locale) = locales.iter().find(|l| parse_locale(l).is_none()) {
+                        return Err(UserError::InvalidLocalizedAttributeLocale {
+                            attribute_name: attribute.clone(),
+                            locale: locale.clone(),
+                        }
+                        .into());
+                    }
+                }
+
+                if localized_attributes != &self.index.localized_attributes(self.wtxn)? {
+                    self.index.put_localized_attributes(self.wtxn, localized_attributes)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_localized_attributes(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_stemming(&mut self) -> Result<bool> {
+        match self.stemming {
+            Setting::Set(ref locales) => {
+                if let Some(locale) =
+                    locales.iter().find(|l| parse_locale(l).and_then(stemming_algorithm).is_none())
+                {
+                    return Err(UserError::InvalidStemmingLocale { locale: locale.clone() }.into());
+                }
+
+                if locales != &self.index.stemming(self.wtxn)? {
+                    self.index.put_stemming(self.wtxn, locales)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_stemming(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
     fn update_pagination_max_total_hits(&mut self) -> Result<()> {
         match self.pagination_max_total_hits {
             Setting::Set(max) => {
@@ -704,16 +1185,30 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         let old_faceted_fields = self.index.user_defined_faceted_fields(self.wtxn)?;
         let old_fields_ids_map = self.index.fields_ids_map(self.wtxn)?;
 
+        // The tokenization settings must be updated before the synonyms and the exact
+        // words are, as they are normalized with the new tokenizer.
+        let separator_tokens_updated = self.update_separator_tokens()?;
+        let non_separator_tokens_updated = self.update_non_separator_tokens()?;
+        let dictionary_updated = self.update_dictionary()?;
+        let localized_attributes_updated = self.update_localized_attributes()?;
+        let stemming_updated = self.update_stemming()?;
+
         self.update_displayed()?;
         self.update_filterable()?;
         self.update_sortable()?;
         self.update_distinct_field()?;
+        self.update_version_field()?;
+        let expiry_field_updated = self.update_expiry_field()?;
+        self.update_ingestion_rules()?;
+        self.update_document_schema()?;
         self.update_criteria()?;
         self.update_primary_key()?;
         self.update_authorize_typos()?;
         self.update_min_typo_word_len()?;
+        self.update_disable_typos_on_numbers()?;
         self.update_exact_words()?;
         self.update_max_values_per_facet()?;
+        self.update_sort_facet_values_by()?;
         self.update_pagination_max_total_hits()?;
 
         // If there is new faceted fields we indicate that we must reindex as we must
@@ -726,14 +1221,27 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         let synonyms_updated = self.update_synonyms()?;
         let searchable_updated = self.update_searchable()?;
         let exact_attributes_updated = self.update_exact_attributes()?;
+        let infix_attributes_updated = self.update_infix_attributes()?;
+        let phonetic_attributes_updated = self.update_phonetic_attributes()?;
+        let hierarchical_facets_updated = self.update_hierarchical_facets()?;
 
         if stop_words_updated
-            || faceted_updated
+            || separator_tokens_updated
+            || non_separator_tokens_updated
+            || dictionary_updated
+            || localized_attributes_updated
+            || stemming_updated
             || synonyms_updated
             || searchable_updated
             || exact_attributes_updated
+            || infix_attributes_updated
+            || phonetic_attributes_updated
         {
             self.reindex(&progress_callback, &should_abort, old_fields_ids_map)?;
+        } else if faceted_updated || hierarchical_facets_updated || expiry_field_updated {
+            // The words are not impacted by the faceted fields,
+            // only the facet databases must be reindexed.
+            self.reindex_facets(&progress_callback, &should_abort)?;
         }
 
         Ok(())
@@ -1305,6 +1813,60 @@ mod tests {
         let _ = filter.evaluate(&rtxn, &index).unwrap_err();
     }
 
+    #[test]
+    fn setting_filterable_only_reindexes_facets() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "name": "kevin", "age": 23 },
+                { "id": 1, "name": "kevina", "age": 21 },
+                { "id": 2, "name": "benoit", "age": 34 }
+            ]))
+            .unwrap();
+
+        let steps = std::sync::Mutex::new(Vec::new());
+        let mut wtxn = index.write_txn().unwrap();
+        let mut builder = Settings::new(&mut wtxn, &index.inner, &index.indexer_config);
+        builder.set_filterable_fields(hashset! { S("age") });
+        builder.execute(|step| steps.lock().unwrap().push(step), || false).unwrap();
+        wtxn.commit().unwrap();
+
+        // Only the facet databases have been reindexed.
+        let steps = steps.into_inner().unwrap();
+        assert!(!steps.is_empty());
+        assert!(steps
+            .iter()
+            .all(|step| matches!(step, UpdateIndexingStep::MergeDataIntoFinalDatabase { .. })));
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("age > 22").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap().len(), 2);
+        // The word databases are left untouched.
+        let result = index.search(&rtxn).query(r#""benoit""#).execute().unwrap();
+        assert_eq!(result.documents_ids.len(), 1);
+        drop(rtxn);
+
+        // Changing the displayed fields doesn't reindex anything.
+        let steps = std::sync::Mutex::new(Vec::new());
+        let mut wtxn = index.write_txn().unwrap();
+        let mut builder = Settings::new(&mut wtxn, &index.inner, &index.indexer_config);
+        builder.set_displayed_fields(vec![S("name")]);
+        builder.execute(|step| steps.lock().unwrap().push(step), || false).unwrap();
+        wtxn.commit().unwrap();
+        assert!(steps.into_inner().unwrap().is_empty());
+
+        // Removing the filterable fields empties the facet databases.
+        index
+            .update_settings(|settings| {
+                settings.reset_filterable_fields();
+            })
+            .unwrap();
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.facet_id_f64_docids.is_empty(&rtxn).unwrap());
+        assert!(index.field_id_docid_facet_f64s.is_empty(&rtxn).unwrap());
+    }
+
     #[test]
     fn setting_primary_key() {
         let mut index = TempIndex::new();
@@ -1429,6 +1991,28 @@ mod tests {
         assert!(!index.authorize_typos(&txn).unwrap());
     }
 
+    #[test]
+    fn test_disable_typos_on_numbers() {
+        let index = TempIndex::new();
+
+        let mut txn = index.write_txn().unwrap();
+        assert!(!index.disable_typos_on_numbers(&txn).unwrap());
+
+        index
+            .update_settings_using_wtxn(&mut txn, |settings| {
+                settings.set_disable_typos_on_numbers(true);
+            })
+            .unwrap();
+        assert!(index.disable_typos_on_numbers(&txn).unwrap());
+
+        index
+            .update_settings_using_wtxn(&mut txn, |settings| {
+                settings.reset_disable_typos_on_numbers();
+            })
+            .unwrap();
+        assert!(!index.disable_typos_on_numbers(&txn).unwrap());
+    }
+
     #[test]
     fn update_min_word_len_for_typo() {
         let index = TempIndex::new();
@@ -1506,15 +2090,30 @@ mod tests {
                     sortable_fields,
                     criteria,
                     stop_words,
+                    separator_tokens,
+                    non_separator_tokens,
+                    dictionary,
+                    localized_attributes,
+                    stemming,
                     distinct_field,
+                    version_field,
+                    expiry_field,
+                    ingestion_rules,
+                    ingestion_rules_version,
+                    document_schema,
                     synonyms,
                     primary_key,
                     authorize_typos,
                     min_word_len_two_typos,
                     min_word_len_one_typo,
+                    disable_typos_on_numbers,
                     exact_words,
                     exact_attributes,
+                    infix_attributes,
+                    phonetic_attributes,
                     max_values_per_facet,
+                    sort_facet_values_by,
+                    hierarchical_facets,
                     pagination_max_total_hits,
                 } = settings;
                 assert!(matches!(searchable_fields, Setting::NotSet));
@@ -1523,15 +2122,30 @@ mod tests {
                 assert!(matches!(sortable_fields, Setting::NotSet));
                 assert!(matches!(criteria, Setting::NotSet));
                 assert!(matches!(stop_words, Setting::NotSet));
+                assert!(matches!(separator_tokens, Setting::NotSet));
+                assert!(matches!(non_separator_tokens, Setting::NotSet));
+                assert!(matches!(dictionary, Setting::NotSet));
+                assert!(matches!(localized_attributes, Setting::NotSet));
+                assert!(matches!(stemming, Setting::NotSet));
                 assert!(matches!(distinct_field, Setting::NotSet));
+                assert!(matches!(version_field, Setting::NotSet));
+                assert!(matches!(expiry_field, Setting::NotSet));
+                assert!(matches!(ingestion_rules, Setting::NotSet));
+                assert!(ingestion_rules_version.is_none());
+                assert!(matches!(document_schema, Setting::NotSet));
                 assert!(matches!(synonyms, Setting::NotSet));
                 assert!(matches!(primary_key, Setting::NotSet));
                 assert!(matches!(authorize_typos, Setting::NotSet));
                 assert!(matches!(min_word_len_two_typos, Setting::NotSet));
                 assert!(matches!(min_word_len_one_typo, Setting::NotSet));
+                assert!(matches!(disable_typos_on_numbers, Setting::NotSet));
                 assert!(matches!(exact_words, Setting::NotSet));
                 assert!(matches!(exact_attributes, Setting::NotSet));
+                assert!(matches!(infix_attributes, Setting::NotSet));
+                assert!(matches!(phonetic_attributes, Setting::NotSet));
                 assert!(matches!(max_values_per_facet, Setting::NotSet));
+                assert!(matches!(sort_facet_values_by, Setting::NotSet));
+                assert!(matches!(hierarchical_facets, Setting::NotSet));
                 assert!(matches!(pagination_max_total_hits, Setting::NotSet));
             })
             .unwrap();